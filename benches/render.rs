@@ -0,0 +1,57 @@
+//! Parsing and rendering of a month of programming, run with `cargo bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use wod::create_workout;
+use wod::render::Format;
+
+const WORKOUTS: &[&str] = &[
+    "wl 3x(2+1) clean, split jerk @85%",
+    "wl 4x2 front squat @85%",
+    "wl 3x4 push press @75%",
+    "ft 21 pull up, 42 du, 21 thruster @43kg, 18 chest to bar, 36 du, 18 thruster @51kg, 15 bar mu, 30 du, 15 thruster @61kg",
+    "ft 21-15-9 pull up, thruster @43/30kg",
+    "5rd 20 double under, 30cal row, 15 wall ball @9/6kg",
+    "3rd 5 pull up, 10 push up, 15 air squat",
+    "emom-12 3 power clean @70/47.5kg, 6 burpee over the bar",
+    "wl [5x3 back squat @80%; 3x5 romanian deadlift @60kg]",
+    "tabata-8 echo bike, sit up",
+];
+
+/// Thirty days of the workouts above.
+fn month() -> impl Iterator<Item = &'static str> {
+    WORKOUTS.iter().cycle().take(30 * 3).copied()
+}
+
+fn parse(c: &mut Criterion) {
+    c.bench_function("parse a month", |b| {
+        b.iter(|| {
+            for workout in month() {
+                black_box(create_workout(black_box(workout), None, None).unwrap());
+            }
+        })
+    });
+}
+
+fn render(c: &mut Criterion) {
+    let workouts: Vec<_> = month()
+        .map(|workout| create_workout(workout, None, None).unwrap())
+        .collect();
+    c.bench_function("render a month to markdown", |b| {
+        b.iter(|| {
+            for workout in &workouts {
+                black_box(workout.to_string());
+            }
+        })
+    });
+    let renderer = Format::Html.renderer();
+    c.bench_function("render a month to html", |b| {
+        b.iter(|| {
+            for workout in &workouts {
+                black_box(renderer.render(workout));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, parse, render);
+criterion_main!(benches);
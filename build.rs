@@ -0,0 +1,20 @@
+//! Writes the C header of the `ffi` feature to `$OUT_DIR/wod.h`, `make ffi` copies it
+//! next to the library.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi")]
+    {
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        let crate_dir = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+        let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+        cbindgen::Builder::new()
+            .with_src(crate_dir.join("src/ffi.rs"))
+            .with_language(cbindgen::Language::C)
+            .with_include_guard("WOD_H")
+            .with_header("/* Generated by build.rs with the `ffi` feature, don't edit it. */")
+            .generate()
+            .expect("Unable to generate the C header")
+            .write_to_file(out_dir.join("wod.h"));
+    }
+}
@@ -0,0 +1,77 @@
+//! Library of benchmark workouts, so they can be added by their name, i.e.
+//! `wod add "Fran"`, instead of writing their shorthand.
+
+/// A benchmark workout known by its name.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Benchmark {
+    /// Name of the workout, i.e. "Fran".
+    pub name: &'static str,
+    /// The workout in shorthand, with the Rx loads.
+    pub shorthand: &'static str,
+}
+
+const fn benchmark(name: &'static str, shorthand: &'static str) -> Benchmark {
+    Benchmark { name, shorthand }
+}
+
+/// The benchmark workouts known, the "Girls" and some of the "Heroes".
+pub const BENCHMARKS: &[Benchmark] = &[
+    benchmark("Amanda", "ft 9-7-5 ring mu, snatch @61/43kg"),
+    benchmark("Annie", "ft 50-40-30-20-10 du, sit up"),
+    benchmark("Chelsea", "emom-30 5 pull up, 10 push up, 15 air squat"),
+    benchmark("Cindy", "amrap-20 5 pull up, 10 push up, 15 air squat"),
+    benchmark("Diane", "ft 21-15-9 deadlift @102/70kg, hspu"),
+    benchmark(
+        "DT",
+        "5rd 12 deadlift @70/47.5kg, 9 hang power clean, 6 push jerk",
+    ),
+    benchmark("Fran", "ft 21-15-9 pull up, thruster @43/30kg"),
+    benchmark("Grace", "ft 30 clean and jerk @61/43kg"),
+    benchmark(
+        "Helen",
+        "3rd 400m run, 21 kettlebell swing @24/16kg, 12 pull up",
+    ),
+    benchmark("Isabel", "ft 30 snatch @61/43kg"),
+    benchmark("Jackie", "ft 1000m row, 50 thruster @20/15kg, 30 pull up"),
+    benchmark("Karen", "ft 150 wall ball @9/6kg"),
+    benchmark("Kelly", "5rd 400m run, 30 box jump, 30 wall ball @9/6kg"),
+    benchmark("Mary", "amrap-20 5 hspu, 10 pistol squat, 15 pull up"),
+    benchmark(
+        "Murph",
+        "ft 1600m run, 100 pull up, 200 push up, 300 air squat, 1600m run",
+    ),
+    benchmark("Nancy", "5rd 400m run, 15 ohs @43/30kg"),
+];
+
+/// Finds a benchmark by its name, ignoring the case and the surrounding spaces.
+///
+/// # Examples
+///
+/// ```
+/// use wod::benchmarks::find;
+///
+/// let fran = find(" fran ").unwrap();
+/// assert_eq!(fran.name, "Fran");
+/// assert_eq!(fran.shorthand, "ft 21-15-9 pull up, thruster @43/30kg");
+/// assert!(find("ft 21-15-9 pull up, thruster").is_none());
+/// ```
+pub fn find(name: &str) -> Option<&'static Benchmark> {
+    let name = name.trim();
+    BENCHMARKS
+        .iter()
+        .find(|b| b.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    #[test]
+    fn test_benchmarks_parse() {
+        for b in BENCHMARKS {
+            let workout = create_workout(b.shorthand, None, Some(b.name.to_string()));
+            assert!(workout.is_ok(), "{}: {}", b.name, b.shorthand);
+        }
+    }
+}
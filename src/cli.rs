@@ -1,13 +1,27 @@
-use clap::{Parser, Subcommand};
+use std::io::{self, Write};
+use std::path::PathBuf;
 
-use wod::{default_filename, today};
+use chrono::{NaiveDate, Weekday};
+use clap::{Args, CommandFactory, Parser, Subcommand};
+
+use wod::cycles::Cycle;
+use wod::date::parse_date;
+use wod::frequency::ReportFormat;
+use wod::generator::Stimulus;
+use wod::highlight::HighlightFormat;
+use wod::movement::{Equipment, Movement};
+use wod::render::Format;
+use wod::results::SeriesFormat;
+use wod::rm::Formula;
+use wod::scaling::{Level, Restriction};
+use wod::{grammar, today, WodError};
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(author, version, about, long_about = None, after_long_help = grammar::reference())]
 pub struct Cli {
-    /// Optional name to work with.
-    #[arg(default_value_t = default_filename())]
-    pub filename: String,
+    /// Optional name to work with. By default it's generated from the `filename_template`
+    /// of the configuration, "wod-<date>" unless changed.
+    pub filename: Option<String>,
 
     /// A path pointing to a file with a list of workouts to add.
     /// Each line in the file should be a workout as you would pass
@@ -16,9 +30,10 @@ pub struct Cli {
     pub wodfile: Option<String>,
 
     /// A date that will be used in the metadata of the generated file.
-    /// It must be in format: "YYYY-MM-DD"
-    #[arg(long, default_value_t = today())]
-    pub file_date: String,
+    /// It must be in format "YYYY-MM-DD", or relative to the current day:
+    /// "today", "tomorrow", "yesterday", "friday", "next monday", "last monday"...
+    #[arg(long, default_value = "today", value_parser = parse_file_date)]
+    pub file_date: NaiveDate,
 
     /// Languages for the files, as expected by Hugo.
     /// It must be a comma separated list of [ISO code](https://en.wikipedia.org/wiki/List_of_ISO_639_language_codes).
@@ -34,11 +49,44 @@ pub struct Cli {
     #[arg(short, long, default_value = "false")]
     pub force: bool,
 
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Create the files as drafts, Hugo won't show them until `wod publish` is run.
+    /// By default the `draft` of the configuration.
+    #[arg(long)]
+    pub draft: bool,
+
+    /// Write every workout of a wod file to its own file, named after the workout
+    /// (or its position in the file). By default the `per_workout` of the configuration.
+    #[arg(long)]
+    pub per_workout: bool,
+
+    /// Disable colors in the error messages. Colors are also disabled
+    /// if the `NO_COLOR` environment variable is set.
+    #[arg(long, global = true, default_value = "false")]
+    pub no_color: bool,
+
     /// Subcommands
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+// Where the workout files are, for the commands that write them. Not a doc comment,
+// clap would take it as the about text of the commands that flatten it.
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Section of the site for the files, i.e. "competition" writes them to `content/competition`
+    /// instead of `content/wod`. By default the `section` of the configuration.
+    #[arg(long)]
+    pub section: Option<String>,
+
+    /// Directory where the workout files are written. By default the `output_dir`
+    /// of the configuration, or `content/wod` when run from the root of a Hugo site.
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Command to add a new workout to a file.
@@ -47,15 +95,89 @@ pub enum Commands {
     List(ListCommand),
     /// Command to create the workout and return it to the console.
     Check(CheckCommand),
+    /// Command to show the movements, machines and total load lifted of a workout.
+    Stats(StatsCommand),
+    /// Command to export the load lifted per week and movement pattern of the files of
+    /// the output directory, to plot the training load over a cycle.
+    Volume(VolumeCommand),
+    /// Command to export the number and kinds of workouts per day, to render a
+    /// training calendar on the blog.
+    Heatmap(HeatmapCommand),
+    /// Command to show the differences between two workouts, i.e. the Rx and scaled versions.
+    Diff(DiffCommand),
+    /// Command to generate a random workout, for when inspiration is missing.
+    Random(RandomCommand),
+    /// Command to run a timer in the terminal following the workout structure.
+    Timer(TimerCommand),
+    /// Command to record the result of a workout, or review the history with `wod log show`.
+    Log(LogCommand),
+    /// Command to add the leaderboard of the athletes for a workout to the file of the day.
+    Leaderboard(LeaderboardCommand),
+    /// Command to add a warm-up for the movements of the day before the first workout.
+    Warmup(WarmupCommand),
+    /// Command to render the workouts of the day for an athlete with restrictions.
+    Personalize(PersonalizeCommand),
+    /// Command to manage the personal records per movement.
+    Pr(PrCommand),
+    /// Command to compute the working weight for a percentage of the 1RM.
+    Percent(PercentCommand),
+    /// Command to program a strength cycle from the personal records, i.e. 5/3/1.
+    Cycle(CycleCommand),
+    /// Command to generate the skeleton `.wod` files of the days of a training plan.
+    Plan(PlanCommand),
+    /// Command to import a workout published in a page to the shorthand.
+    ImportUrl(ImportUrlCommand),
+    /// Command to export an interval workout to a structured format for a sports watch,
+    /// or to the script of the cues to call out during the workout.
+    Export(ExportCommand),
+    /// Command to remove the last workout added to a file.
+    Undo(UndoCommand),
+    /// Command to publish a file created as a draft.
+    Publish(PublishCommand),
+    /// Command to generate the QR code (SVG) of the link to the published page of the day.
+    Qr(QrCommand),
+    /// Command to generate the files for every `.wod` file in a directory.
+    Build(BuildCommand),
+    /// Command to regenerate the markdown files of a directory from the source of their workouts.
+    Regen(RegenCommand),
+    /// Command to split a wod file with the workouts of several days, separated by `---`
+    /// lines, into a file per day.
+    Split(SplitCommand),
+    /// Command to merge generated files into one page, i.e. the AM and PM sessions of a day.
+    Merge(MergeCommand),
+    /// Command to preview a wod file, rendering it again every time it changes.
+    Watch(WatchCommand),
+    /// Command to check the configuration and environment, reporting the problems found.
+    Doctor,
+    /// Command to create the file of the day from a template, i.e. "strength+metcon".
+    New(NewCommand),
+    /// Command to create a Hugo site to publish the workouts, with a sample wod file.
+    Init(InitCommand),
+    /// Command to add the archetype, the list partial and the timer shortcode for the
+    /// workouts to an existing Hugo site.
+    InitHugo(InitHugoCommand),
+    /// Command to serve the parser over HTTP: POST /parse, POST /render and GET /movements.
+    Serve(ServeCommand),
+    /// Command to rewrite wod files in canonical form: spacing, movement names and
+    /// directives at the top.
+    Fmt(FmtCommand),
+    /// Command to run a language server for wod files over stdin and stdout, for the
+    /// diagnostics, completion and hover of the editors.
+    Lsp,
+    /// Command to generate the syntax highlighting of wod files for an editor.
+    Grammar(GrammarCommand),
+    /// Command to generate the man page, including the reference of the workout shorthand.
+    Manpage(ManpageCommand),
 }
 
 #[derive(Parser, Debug)]
 pub struct AddCommand {
     /// The filename to add the workout to. By default will use the same used with the `wod` command.
-    #[arg(short, long, default_value_t = default_filename())]
-    pub filename: String,
+    #[arg(short, long)]
+    pub filename: Option<String>,
 
-    /// The workout to add, i.e. "4rd 21 box jump over, 15 bar mu".
+    /// The workout to add, i.e. "4rd 21 box jump over, 15 bar mu", or the name of a
+    /// benchmark, i.e. "Fran".
     #[arg(required = true)]
     pub workout: String,
 
@@ -66,6 +188,43 @@ pub struct AddCommand {
     /// Name for the workout, i.e. "Fran", or "Open 25.2" if any.
     #[arg(short, long, default_value = None)]
     pub name: Option<String>,
+
+    /// URL of a video shown under the workout, i.e. the demo of the complex of the day.
+    /// The YouTube videos are embedded with the `youtube` shortcode of Hugo, from the time
+    /// they start at (`?t=90`). Must be an http(s) URL. Can be repeated.
+    #[arg(long)]
+    pub video: Vec<String>,
+
+    /// URL of an image shown under the workout, with the `figure` shortcode of Hugo. Must
+    /// be an http(s) URL. Can be repeated.
+    #[arg(long)]
+    pub image: Vec<String>,
+
+    /// Position of the workout in the file, i.e. 1 to add it as the first workout.
+    /// By default it's appended at the end.
+    #[arg(long, conflicts_with = "before")]
+    pub at: Option<usize>,
+
+    /// Insert the workout before the first one containing this text, i.e. "for time".
+    #[arg(long)]
+    pub before: Option<String>,
+
+    /// Add the workout even if the file already contains it.
+    #[arg(long, default_value = "false")]
+    pub force: bool,
+
+    /// Render a scaled version of the workout under it: intermediate or beginner.
+    /// Can be repeated, and adds to the levels of the configuration file.
+    #[arg(long)]
+    pub scaled: Vec<Level>,
+
+    /// Output format: md, json, text, html or checklist. Only markdown is added to the file,
+    /// the other formats are printed to share the workout or use it in scripts.
+    #[arg(long, default_value = "md")]
+    pub format: Format,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
 }
 
 #[derive(Parser, Debug)]
@@ -77,6 +236,593 @@ pub struct ListCommand {
 
 #[derive(Parser, Debug)]
 pub struct CheckCommand {
-    /// Whether to list the workouts or generate a markdown page for them.
+    /// The workout to check, i.e. "ft 21-15-9 pull up, thruster @43/30kg", or a wod
+    /// file, i.e. "plan.wod", to check every workout in it.
+    #[arg(required_unless_present = "files")]
+    pub wod: Option<String>,
+
+    /// Show the absolute loads of percentage based lines, using the personal records.
+    #[arg(long, default_value = "false")]
+    pub loads: bool,
+
+    /// Show the target time and split of the row, run, bike and ski lines, using the
+    /// baselines of the personal records.
+    #[arg(long, default_value = "false")]
+    pub paces: bool,
+
+    /// Check the quality of the programming too: heavy hinges on consecutive days,
+    /// too many grip intensive movements, EMOM intervals that can't be done in time.
+    #[arg(long, default_value = "false")]
+    pub lint: bool,
+
+    /// Markdown files generated by wod to lint instead of a workout, a day each in
+    /// order, i.e. the files of a week.
+    #[arg(long, num_args = 1.., requires = "lint", conflicts_with = "wod")]
+    pub files: Vec<PathBuf>,
+
+    /// Report the problems as text or as JSON (line, column, code, message and
+    /// suggestion of each one) for editors and CI jobs.
+    #[arg(long, value_enum, default_value = "text", conflicts_with = "files")]
+    pub format: CheckFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum CheckFormat {
+    /// The rendered workout, or a problem per line for a wod file.
+    Text,
+    /// A JSON array with the problems found.
+    Json,
+}
+
+#[derive(Parser, Debug)]
+pub struct StatsCommand {
+    /// The workout to summarize, i.e. "wl 5x5 back squat @70%".
+    #[arg(required_unless_present = "frequency")]
+    pub wod: Option<String>,
+
+    /// Report how often the movements and their patterns were programmed in the files
+    /// of the output directory instead, with the ones left out for too long or repeated
+    /// too often, see the `[frequency]` of the configuration.
+    #[arg(long, conflicts_with = "wod")]
+    pub frequency: bool,
+
+    /// Format of the frequency report: markdown or json.
+    #[arg(long, default_value = "markdown", requires = "frequency")]
+    pub format: ReportFormat,
+
+    /// The day of the frequency report, the files after it are left out. In format
+    /// "YYYY-MM-DD" or relative to the current day, as the `--file-date`.
+    #[arg(long, default_value = "today", value_parser = parse_file_date)]
+    pub date: NaiveDate,
+}
+
+#[derive(Parser, Debug)]
+pub struct VolumeCommand {
+    /// Format of the export: json or csv.
+    #[arg(long, default_value = "json")]
+    pub format: SeriesFormat,
+}
+
+#[derive(Parser, Debug)]
+pub struct HeatmapCommand {
+    /// Directory with the generated files, i.e. "content/wod". By default the output
+    /// directory.
+    pub dir: Option<PathBuf>,
+
+    /// Format of the export: json or csv.
+    #[arg(long, default_value = "json")]
+    pub format: SeriesFormat,
+}
+
+#[derive(Parser, Debug)]
+pub struct DiffCommand {
+    /// The workout to compare from, i.e. "ft 21-15-9 pull up, thruster @43/30kg".
+    pub first: String,
+    /// The workout to compare to, i.e. "ft 21-15-9 strict pull up, thruster @30/20kg".
+    pub second: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct RandomCommand {
+    /// Length of the workout in minutes.
+    #[arg(short, long, default_value = "12")]
+    pub minutes: u32,
+
+    /// Equipment available, i.e. "barbell,box,rower". Everything by default.
+    #[arg(long, value_delimiter = ',', conflicts_with = "bodyweight")]
+    pub equipment: Vec<Equipment>,
+
+    /// Use only bodyweight movements.
+    #[arg(long, default_value = "false")]
+    pub bodyweight: bool,
+
+    /// Movements to choose from, i.e. "pull up,thruster,row". Every movement by default.
+    #[arg(long, value_delimiter = ',')]
+    pub movements: Vec<Movement>,
+
+    /// The kind of effort: mixed, cardio, gymnastics or weightlifting.
+    #[arg(long, default_value = "mixed")]
+    pub stimulus: Stimulus,
+
+    /// Seed to generate the same workout again.
+    #[arg(long)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Parser, Debug)]
+pub struct TimerCommand {
+    /// The workout to time, i.e. "emom-12 10 pull up", "amrap-15 ..." or "tabata 20 air squat".
     pub wod: String,
 }
+
+#[derive(Parser, Debug)]
+pub struct UndoCommand {
+    /// The file to remove the workout from. By default will use the same used with the `wod` command.
+    pub file: Option<String>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct PublishCommand {
+    /// The file to publish. By default will use the same used with the `wod` command.
+    pub file: Option<String>,
+
+    /// Set the file back as a draft instead.
+    #[arg(long)]
+    pub unpublish: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct RegenCommand {
+    /// Content directory with the markdown files, i.e. "content/wod". The workouts are
+    /// read from the source block embedded in each file, or the `.wod` file next to it.
+    pub dir: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+pub struct SplitCommand {
+    /// The wod file with the days, separated by `---` lines, i.e. "april.wod".
+    pub wodfile: PathBuf,
+
+    /// The date of the first day, in format "YYYY-MM-DD" or relative to the current
+    /// day, as the `--file-date`.
+    #[arg(long, default_value = "today", value_parser = parse_file_date)]
+    pub start: NaiveDate,
+
+    /// Languages for the files, as a comma separated list, i.e. "en,es".
+    #[arg(short, long)]
+    pub languages: Option<String>,
+
+    /// Whether to overwrite the files if they exist, defaults to false.
+    #[arg(short, long, default_value = "false")]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Create the files as drafts, see `wod --draft`.
+    #[arg(long)]
+    pub draft: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct MergeCommand {
+    /// The markdown files generated by wod, ordered by their date in the page.
+    #[arg(required = true, num_args = 2..)]
+    pub files: Vec<PathBuf>,
+
+    /// The file to write the page to, it can be one of the files merged.
+    #[arg(short, long)]
+    pub output: PathBuf,
+
+    /// Whether to overwrite the output if it exists, defaults to false.
+    #[arg(short, long, default_value = "false")]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct BuildCommand {
+    /// Directory with the `.wod` files, the date of each workout is read from the
+    /// name of the file, i.e. "2025-03-21.wod".
+    pub dir: PathBuf,
+
+    /// Languages for the files, as a comma separated list, i.e. "en,es".
+    #[arg(short, long)]
+    pub languages: Option<String>,
+
+    /// Whether to overwrite the files if they exist, defaults to false.
+    #[arg(short, long, default_value = "false")]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Create the files as drafts, see `wod --draft`.
+    #[arg(long)]
+    pub draft: bool,
+
+    /// Write every workout to its own file, see `wod --per-workout`.
+    #[arg(long)]
+    pub per_workout: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct WatchCommand {
+    /// The wod file to watch, with a workout per line as in `wod --wodfile`.
+    pub wodfile: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+pub struct NewCommand {
+    /// The file to create. By default will use the same used with the `wod` command.
+    pub filename: Option<String>,
+
+    /// The template to use. The shipped templates can be replaced, or new ones added,
+    /// as `<name>.wod` files in `~/.wod/templates`.
+    #[arg(short, long, required_unless_present = "list")]
+    pub template: Option<String>,
+
+    /// List the templates available.
+    #[arg(long, default_value = "false")]
+    pub list: bool,
+
+    /// A date that will be used in the metadata of the generated file, "YYYY-MM-DD"
+    /// or relative to the current day, i.e. "tomorrow" or "next monday".
+    #[arg(long, default_value = "today", value_parser = parse_file_date)]
+    pub file_date: NaiveDate,
+
+    /// Languages for the files, as a comma separated list, i.e. "en,es".
+    #[arg(short, long)]
+    pub languages: Option<String>,
+
+    /// Whether to overwrite the file if it exists, defaults to false.
+    #[arg(short, long, default_value = "false")]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Create the files as drafts, see `wod --draft`.
+    #[arg(long)]
+    pub draft: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ManpageCommand {
+    /// File to write the man page to, printed to stdout if not given.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct FmtCommand {
+    /// The wod files to format, i.e. "plan.wod".
+    #[arg(required = true)]
+    pub files: Vec<PathBuf>,
+
+    /// Don't write the files, fail if any of them isn't formatted, for CI jobs.
+    #[arg(long, default_value = "false")]
+    pub check: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct GrammarCommand {
+    /// Format of the definition: tmLanguage (VS Code, Sublime Text) or vim.
+    #[arg(long, default_value = "tmLanguage")]
+    pub format: HighlightFormat,
+
+    /// File to write the definition to, printed to stdout if not given.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+pub enum ExportFormat {
+    /// Garmin Connect workout JSON.
+    Garmin,
+    /// Cue script as JSON, the announcements with their time for timer apps.
+    Cues,
+    /// Cue script as SubRip subtitles, for text-to-speech or video players.
+    Srt,
+}
+
+#[derive(Parser, Debug)]
+pub struct ExportCommand {
+    /// The workout to export, i.e. "emom-12 10 pull up" or "tabata 20 air squat".
+    pub wod: String,
+
+    /// Format of the exported workout.
+    #[arg(long, value_enum, default_value = "garmin")]
+    pub format: ExportFormat,
+
+    /// Name of the workout shown in the watch, by default the workout itself.
+    #[arg(short, long)]
+    pub name: Option<String>,
+
+    /// File to write the workout to, printed to stdout if not given.
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct LogCommand {
+    /// The file with the workout of the day. By default will use the same used with the `wod` command.
+    #[arg(short, long)]
+    pub file: Option<String>,
+
+    /// The score for the workout, i.e. "12:34" or "5 rounds + 3".
+    #[arg(short, long)]
+    pub score: Option<String>,
+
+    /// Notes for the result, i.e. "rx" or "scaled".
+    #[arg(short, long)]
+    pub notes: Option<String>,
+
+    /// Name of the workout, i.e. "Fran". By default the title of the file is used.
+    #[arg(short, long)]
+    pub workout: Option<String>,
+
+    #[command(subcommand)]
+    pub action: Option<LogAction>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LogAction {
+    /// Show the history of results.
+    Show,
+    /// Export the results as a time series to plot them, with the scores as numbers
+    /// and the estimated 1RMs, i.e. `wod log export --movement "back squat" --format csv`.
+    Export {
+        /// Only the results for a movement, the workouts named after it.
+        #[arg(short, long)]
+        movement: Option<String>,
+        /// Output format: json or csv.
+        #[arg(long, default_value = "json")]
+        format: SeriesFormat,
+        /// The formula to estimate the 1RM: epley or brzycki.
+        #[arg(long, default_value = "epley")]
+        formula: Formula,
+    },
+}
+
+#[derive(Parser, Debug)]
+pub struct LeaderboardCommand {
+    /// Name of the workout, i.e. "Fran".
+    pub workout: String,
+
+    /// The file with the workout of the day. By default will use the same used with the `wod` command.
+    #[arg(short, long)]
+    pub file: Option<String>,
+
+    /// CSV file with the scores, `athlete,score[,division]` per line where the division
+    /// is rx or scaled. The scores are read from the standard input if not given.
+    #[arg(short, long)]
+    pub scores: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct WarmupCommand {
+    /// The file with the workouts of the day. By default will use the same used with the `wod` command.
+    #[arg(short, long)]
+    pub from: Option<String>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct PersonalizeCommand {
+    /// The file with the workouts of the day. By default will use the same used with the `wod` command.
+    #[arg(short, long)]
+    pub file: Option<String>,
+
+    /// Restrictions of the athlete, i.e. "no overhead,no impact". The ones of the
+    /// `[athlete]` table of the configuration by default.
+    #[arg(long, value_delimiter = ',')]
+    pub restrictions: Vec<Restriction>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct PrCommand {
+    #[command(subcommand)]
+    pub action: PrAction,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PrAction {
+    /// Set the personal record for a movement, i.e. `wod pr set "back squat" 140kg`.
+    Set {
+        /// The movement, i.e. "back squat".
+        movement: String,
+        /// The weight, i.e. "140kg".
+        weight: String,
+        /// The repetitions done with the weight, the 1RM is estimated from them.
+        #[arg(long, default_value = "1")]
+        reps: u32,
+        /// The formula to estimate the 1RM: epley or brzycki.
+        #[arg(long, default_value = "epley")]
+        formula: Formula,
+    },
+    /// Set the baseline score of a monostructural movement, used for the target paces,
+    /// i.e. `wod pr baseline row "2k 7:30"`.
+    Baseline {
+        /// The movement: row, run, bike, echo bike or ski.
+        movement: String,
+        /// The distance and the time it was done in, i.e. "2k 7:30" or "5k 22:00".
+        baseline: String,
+    },
+    /// List the personal records.
+    List,
+}
+
+#[derive(Parser, Debug)]
+pub struct PercentCommand {
+    /// The percentage of the 1RM, i.e. "82%".
+    pub percent: String,
+
+    /// The movement to look up in the personal records, i.e. "clean".
+    #[arg(required_unless_present = "one_rm")]
+    pub movement: Option<String>,
+
+    /// An explicit 1RM to use instead of the personal record, i.e. "100kg".
+    #[arg(long)]
+    pub one_rm: Option<String>,
+
+    /// The repetitions done with `--one-rm`, to estimate the 1RM from a rep max.
+    #[arg(long, default_value = "1", requires = "one_rm")]
+    pub reps: u32,
+
+    /// The formula to estimate the 1RM: epley or brzycki.
+    #[arg(long, default_value = "epley")]
+    pub formula: Formula,
+}
+
+#[derive(Parser, Debug)]
+pub struct CycleCommand {
+    /// The cycle: 531 (or 5/3/1) or linear (or 3x5).
+    pub cycle: Cycle,
+
+    /// The lifts of the cycle, as a comma separated list, i.e. "back squat,deadlift".
+    #[arg(required = true, value_delimiter = ',')]
+    pub movements: Vec<String>,
+
+    /// The first day of the cycle, "YYYY-MM-DD" or relative to the current day,
+    /// i.e. "next monday".
+    #[arg(long, default_value = "today", value_parser = parse_file_date)]
+    pub start: NaiveDate,
+
+    /// The number of weeks of the cycle.
+    #[arg(long, default_value = "4")]
+    pub weeks: usize,
+
+    /// The days of the week to train, i.e. "mon,thu". The lifts are done on them in
+    /// turns, by default all of them on the weekday of `--start`.
+    #[arg(long, value_delimiter = ',')]
+    pub days: Vec<Weekday>,
+
+    /// Write the file of every day of training instead of printing the wod file.
+    #[arg(long, default_value = "false")]
+    pub write: bool,
+
+    /// Languages for the files written, as a comma separated list, i.e. "en,es".
+    #[arg(short, long, requires = "write")]
+    pub languages: Option<String>,
+
+    /// Whether to overwrite the files if they exist, defaults to false.
+    #[arg(short, long, default_value = "false", requires = "write")]
+    pub force: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Create the files as drafts, see `wod --draft`.
+    #[arg(long, requires = "write")]
+    pub draft: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct PlanCommand {
+    /// The TOML file with the plan: the start, the days of training and the phases.
+    pub plan: PathBuf,
+
+    /// Directory where the `.wod` files are written, the current one by default.
+    #[arg(short, long, default_value = ".")]
+    pub dir: PathBuf,
+
+    /// Whether to overwrite the files if they exist, defaults to false.
+    #[arg(short, long, default_value = "false")]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ImportUrlCommand {
+    /// The link of the page with the workout, i.e. the workout of the day of a gym.
+    pub link: String,
+}
+
+#[derive(Parser, Debug)]
+pub struct QrCommand {
+    /// The file of the page. By default will use the same used with the `wod` command.
+    #[arg(short, long)]
+    pub file: Option<String>,
+
+    /// File to write the SVG image to, printed to stdout if not given.
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct InitCommand {
+    /// The directory of the site, i.e. "myblog". It must not exist or be empty.
+    pub root: PathBuf,
+}
+
+#[derive(Parser, Debug)]
+pub struct InitHugoCommand {
+    /// The root of the Hugo site, the current directory by default.
+    #[arg(default_value = ".")]
+    pub root: PathBuf,
+
+    /// Whether to overwrite the files if they exist, defaults to false.
+    #[arg(short, long, default_value = "false")]
+    pub force: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ServeCommand {
+    /// The port to listen on.
+    #[arg(short, long, default_value = "8080")]
+    pub port: u16,
+
+    /// The address to listen on, use "0.0.0.0" to accept connections from other machines.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+}
+
+/// Parses the `--file-date`, relative to today, see [`wod::date::parse_date`].
+fn parse_file_date(s: &str) -> Result<NaiveDate, WodError> {
+    parse_date(s, today())
+}
+
+/// Writes the man page of `wod` in roff format.
+///
+/// The reference of the shorthand is added as its own section, instead of the
+/// generic section used by clap for the text after the help.
+pub fn manpage(out: &mut dyn Write) -> io::Result<()> {
+    let cmd = Cli::command().after_long_help(None::<&str>);
+    let mut page = Vec::new();
+    clap_mangen::Man::new(cmd).render(&mut page)?;
+    let page = String::from_utf8_lossy(&page);
+
+    let mut shorthand = String::from(".SH SHORTHAND\n.nf\n");
+    for line in grammar::reference().lines() {
+        // Avoid lines being read as roff requests
+        let line = line.replace('\\', "\\e");
+        if line.starts_with(['.', '\'']) {
+            shorthand.push_str("\\&");
+        }
+        shorthand.push_str(&line);
+        shorthand.push('\n');
+    }
+    shorthand.push_str(".fi\n");
+
+    match page.find(".SH VERSION") {
+        Some(index) => write!(out, "{}{}{}", &page[..index], shorthand, &page[index..]),
+        None => write!(out, "{}{}", page, shorthand),
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::error::WodError;
+use crate::frequency::Frequency;
+use crate::front_matter::Profile;
+use crate::i18n::{self, Catalog};
+use crate::render::{CommentStyle, Layout, Markdown};
+use crate::scaling::{AthleteProfile, Scaling};
+use crate::Kind;
+
+/// User configuration, read from a TOML file.
+///
+/// Every field is optional, the defaults are used for the missing ones.
+///
+/// # Examples
+///
+/// ```
+/// use wod::config::Config;
+///
+/// let config: Config = toml::from_str("plate_increment = 2.5").unwrap();
+/// assert_eq!(config.plate_increment, 2.5);
+///
+/// let config: Config = toml::from_str("").unwrap();
+/// assert_eq!(config, Config::default());
+/// ```
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Smallest jump in load that can be done with the plates available, the working
+    /// weights computed from percentages are rounded to a multiple of it.
+    pub plate_increment: f64,
+    /// Directory where the workout files are written, relative filenames are
+    /// placed inside it.
+    pub output_dir: Option<PathBuf>,
+    /// Section of the site where every file is written instead of `wod`, see
+    /// [`crate::hugo::section_dir`].
+    pub section: Option<String>,
+    /// Section per kind of workout (see [`crate::WorkoutType::kind`]), i.e.
+    /// `weightlifting = "weightlifting"`, the wod files whose workouts are all
+    /// routed to the same section are written there.
+    pub sections: BTreeMap<String, String>,
+    /// Layout of the movements per kind of workout, i.e. `weightlifting = "table"`,
+    /// see [`Layout`]. The kinds missing keep the lines.
+    pub layouts: BTreeMap<Kind, Layout>,
+    /// How the comments are written, see [`CommentStyle`]. In italics by default,
+    /// `"quote"` or `"markdown"` keep the links, lists and emphasis of the comments.
+    pub comments: CommentStyle,
+    /// Template for the names of the generated files, see [`crate::expand_filename`]
+    /// for the placeholders available.
+    pub filename_template: String,
+    /// Format of the title of the files, see [`crate::expand_title`] for the placeholders
+    /// available. By default the name of the file without the "wod-" prefix.
+    pub title_format: Option<String>,
+    /// Format of the `slug` of the pages, with the placeholders of the title format,
+    /// i.e. "wod-{date}". The field is not written if not given.
+    pub slug: Option<String>,
+    /// Format of the `url` of the pages, with the placeholders of the title format,
+    /// i.e. "/wod/{year}/{month}/{day}/". The field is not written if not given.
+    pub url: Option<String>,
+    /// Address where the site is published, i.e. "https://wod.example.com", for the
+    /// links to the pages, see [`crate::page_url`].
+    pub base_url: Option<String>,
+    /// Whether the files are created as drafts, hidden by Hugo until they are
+    /// published with `wod publish`.
+    pub draft: bool,
+    /// Whether the pages start with the equipment needed for the workouts of the day,
+    /// i.e. "Equipment: barbell, box". The line is written by `wod base`, and kept up
+    /// to date as the workouts are added, see [`crate::Workout::equipment`].
+    pub equipment: bool,
+    /// Whether every workout of a wod file is written to its own file instead of
+    /// all of them to the page of the day, see [`crate::run_add_wod_from_file`].
+    pub per_workout: bool,
+    /// The static site generator the files are written for, Hugo by default,
+    /// see [`Profile`].
+    pub profile: Profile,
+    /// File with the template of the front matter of the generated files, see
+    /// [`crate::front_matter::expand`]. The one of the profile is used if not given.
+    pub front_matter_template: Option<PathBuf>,
+    /// Loads and levels of the scaled versions of the workouts, see [`Scaling`].
+    pub scaling: Scaling,
+    /// Restrictions of the athlete the workouts are personalized for, see
+    /// [`crate::Workout::substitute`].
+    pub athlete: AthleteProfile,
+    /// Translations of the rendered phrases, keyed by language, i.e. `[messages.es]`.
+    /// See [`crate::i18n`] for the messages available.
+    pub messages: BTreeMap<String, Catalog>,
+    /// Obsidian vault where `wod add` writes the workouts, to the daily notes,
+    /// instead of the files of the site.
+    pub obsidian: Option<Obsidian>,
+    /// How often the movements are expected to be programmed, for the report of
+    /// `wod stats --frequency`, see [`Frequency`].
+    pub frequency: Frequency,
+}
+
+/// The daily notes of an Obsidian vault, the `[obsidian]` table of the configuration.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use std::path::PathBuf;
+/// use wod::config::Obsidian;
+///
+/// let obsidian: Obsidian = toml::from_str("vault = \"/home/me/notes\"").unwrap();
+/// let date = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+/// assert_eq!(obsidian.note(date).unwrap(), PathBuf::from("/home/me/notes/2025-03-21.md"));
+/// assert_eq!(obsidian.heading, "## Workout");
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Obsidian {
+    /// The directory of the vault.
+    pub vault: PathBuf,
+    /// Path of the daily notes in the vault, with the placeholders of
+    /// [`crate::expand_filename`], "{date}" like the daily notes of Obsidian.
+    pub daily_note: String,
+    /// Heading of the note the workouts are added under, created if missing.
+    pub heading: String,
+}
+
+impl Default for Obsidian {
+    fn default() -> Self {
+        Obsidian {
+            vault: PathBuf::new(),
+            daily_note: "{date}".to_string(),
+            heading: "## Workout".to_string(),
+        }
+    }
+}
+
+impl Obsidian {
+    /// The daily note of a day.
+    pub fn note(&self, date: NaiveDate) -> Result<PathBuf, WodError> {
+        let name = crate::expand_filename(&self.daily_note, date)?;
+        Ok(self.vault.join(format!("{}.md", name)))
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            plate_increment: 1.0,
+            output_dir: None,
+            section: None,
+            sections: BTreeMap::new(),
+            layouts: BTreeMap::new(),
+            comments: CommentStyle::default(),
+            filename_template: crate::DEFAULT_FILENAME_TEMPLATE.to_string(),
+            title_format: None,
+            slug: None,
+            url: None,
+            base_url: None,
+            draft: false,
+            equipment: false,
+            per_workout: false,
+            profile: Profile::default(),
+            front_matter_template: None,
+            scaling: Scaling::default(),
+            athlete: AthleteProfile::default(),
+            messages: BTreeMap::new(),
+            obsidian: None,
+            frequency: Frequency::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Default location of the configuration, `<wod home>/config.toml`.
+    pub fn default_path() -> PathBuf {
+        crate::wod_home().join("config.toml")
+    }
+
+    /// Loads the configuration from a file, the default one is returned if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self, WodError> {
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(toml::from_str(&content)?)
+    }
+
+    /// Registers the catalogs of `messages`, so the workouts are rendered with them.
+    pub fn register_messages(&self) {
+        for (language, catalog) in &self.messages {
+            i18n::register(language, catalog.clone());
+        }
+    }
+
+    /// The markdown the workouts are written to the files with, in the `layouts` and
+    /// with the style of the `comments`. The media use the shortcodes of Hugo with its
+    /// `profile`.
+    pub fn markdown(&self) -> Markdown {
+        Markdown {
+            layouts: self.layouts.clone(),
+            comments: self.comments,
+            shortcodes: self.profile == Profile::Hugo,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+
+        let mut file = fs::File::create(&path).unwrap();
+        writeln!(file, "plate_increment = 2.5").unwrap();
+        assert_eq!(Config::load(&path).unwrap().plate_increment, 2.5);
+    }
+
+    #[test]
+    fn test_filename_template() {
+        assert_eq!(Config::default().filename_template, "wod-{date}");
+        let config: Config = toml::from_str(r#"filename_template = "{date}-wod""#).unwrap();
+        assert_eq!(config.filename_template, "{date}-wod");
+        assert_eq!(config.plate_increment, 1.0);
+    }
+
+    #[test]
+    fn test_messages() {
+        let config: Config = toml::from_str(
+            r#"
+            [messages.es]
+            for_time = "Por tiempo"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.messages["es"].get(i18n::Message::ForTime),
+            Some("Por tiempo")
+        );
+        assert!(toml::from_str::<Config>("[messages.es]\nfor_tim = \"Por tiempo\"").is_err());
+    }
+
+    #[test]
+    fn test_layouts() {
+        let config: Config = toml::from_str("[layouts]\nweightlifting = \"table\"").unwrap();
+        assert_eq!(config.layouts[&Kind::Weightlifting], Layout::Table);
+        assert_eq!(config.markdown().layout(Kind::Weightlifting), Layout::Table);
+        assert_eq!(config.markdown().layout(Kind::Emom), Layout::Lines);
+        assert!(toml::from_str::<Config>("[layouts]\nweightlifting = \"grid\"").is_err());
+        assert!(toml::from_str::<Config>("[layouts]\nweighlifting = \"table\"").is_err());
+        let config: Config = toml::from_str("comments = \"quote\"").unwrap();
+        assert_eq!(config.comments, CommentStyle::Quote);
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        assert!(toml::from_str::<Config>("plate_incremnt = 2.5").is_err());
+    }
+}
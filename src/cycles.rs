@@ -0,0 +1,291 @@
+//! Strength cycles: progressions of percentages of the 1RM over several weeks, like
+//! 5/3/1 or a linear 3x5, written as lines of a wod file for the days of training.
+//!
+//! The loads are computed from the personal records of the lifts (see
+//! [`crate::pr::PrStore`]) and rounded to the plates available. A lift without a
+//! record keeps its percentages, i.e. "@70%", so the athlete can do the math.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+use crate::date::relative_weekday;
+use crate::movement::Movement;
+use crate::units::LoadUnit;
+use crate::weight::{format_load, round_to_increment, Weight};
+
+/// A strength cycle, the sets and percentages of every week.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cycle {
+    /// The 5/3/1 of Jim Wendler: waves of 4 weeks (5s, 3s, 5/3/1 and a deload) on a
+    /// training max of the 90% of the 1RM, that grows a 2.5% of the 1RM every wave.
+    FiveThreeOne,
+    /// 3 sets of 5 from the 70% of the 1RM, adding a 2.5% every week.
+    Linear,
+}
+
+impl FromStr for Cycle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "531" | "5/3/1" => Ok(Cycle::FiveThreeOne),
+            "linear" | "3x5" => Ok(Cycle::Linear),
+            _ => Err(format!(
+                "Invalid cycle: `{}`, expected one of: 531, 5/3/1, linear, 3x5",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cycle::FiveThreeOne => write!(f, "5/3/1"),
+            Cycle::Linear => write!(f, "linear 3x5"),
+        }
+    }
+}
+
+impl Cycle {
+    /// The sets of a week (from 0) as (reps, percentage of the 1RM), and the
+    /// comments for the athlete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::cycles::Cycle;
+    ///
+    /// let (sets, comments) = Cycle::FiveThreeOne.week(0);
+    /// assert_eq!(sets, [(5, 58.5), (5, 67.5), (5, 76.5)]);
+    /// assert!(comments.is_some());
+    /// assert_eq!(Cycle::Linear.week(2), (vec![(5, 75.0); 3], None));
+    /// ```
+    pub fn week(self, week: usize) -> (Vec<(u32, f64)>, Option<&'static str>) {
+        match self {
+            Cycle::FiveThreeOne => {
+                let training_max = 90.0 + 2.5 * (week / 4) as f64;
+                let (sets, comments): (&[(u32, f64)], _) = match week % 4 {
+                    0 => (&[(5, 65.0), (5, 75.0), (5, 85.0)], Some(AMRAP)),
+                    1 => (&[(3, 70.0), (3, 80.0), (3, 90.0)], Some(AMRAP)),
+                    2 => (&[(5, 75.0), (3, 85.0), (1, 95.0)], Some(AMRAP)),
+                    _ => (&[(5, 40.0), (5, 50.0), (5, 60.0)], Some("Deload week")),
+                };
+                let sets = sets
+                    .iter()
+                    .map(|(reps, percent)| {
+                        (
+                            *reps,
+                            round_to_increment(training_max * percent / 100.0, 0.5),
+                        )
+                    })
+                    .collect();
+                (sets, comments)
+            }
+            Cycle::Linear => (vec![(5, 70.0 + 2.5 * week as f64); 3], None),
+        }
+    }
+}
+
+/// The comments of the weeks of 5/3/1 with the last set to failure.
+const AMRAP: &str = "Last set for as many reps as possible";
+
+/// The lines of a wod file for a day of training.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    /// The day of the session.
+    pub date: NaiveDate,
+    /// The lines of the lifts of the day, `workout|comments|name`.
+    pub lines: Vec<String>,
+}
+
+/// The sessions of a cycle for the lifts, with their 1RM if known.
+///
+/// The lifts are spread over the `days` of the week in turns, starting with the
+/// first one after `start` (included). Without days, every lift is done on the
+/// weekday of `start`. The loads are rounded to the `increment` of the plates.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{NaiveDate, Weekday};
+/// use wod::cycles::{sessions, Cycle};
+/// use wod::Movement;
+///
+/// let start = NaiveDate::from_ymd_opt(2025, 3, 24).unwrap();
+/// let lifts = [
+///     (Movement::BackSquat, Some("140kg".parse().unwrap())),
+///     (Movement::BenchPress, None),
+/// ];
+/// let sessions = sessions(Cycle::Linear, &lifts, start, 2, &[Weekday::Mon, Weekday::Thu], 2.5);
+/// assert_eq!(sessions.len(), 4);
+/// assert_eq!(sessions[0].lines, ["wl 3x5 back squat @97.5kg||Back Squat linear 3x5, week 1"]);
+/// assert_eq!(sessions[1].date, NaiveDate::from_ymd_opt(2025, 3, 27).unwrap());
+/// assert_eq!(sessions[3].lines, ["wl 3x5 bench press @72.5%||Bench Press linear 3x5, week 2"]);
+/// ```
+pub fn sessions(
+    cycle: Cycle,
+    lifts: &[(Movement, Option<Weight>)],
+    start: NaiveDate,
+    weeks: usize,
+    days: &[Weekday],
+    increment: f64,
+) -> Vec<Session> {
+    let days = if days.is_empty() {
+        vec![start.weekday()]
+    } else {
+        days.to_vec()
+    };
+    let mut program: BTreeMap<NaiveDate, Vec<String>> = BTreeMap::new();
+    for week in 0..weeks {
+        let (sets, comments) = cycle.week(week);
+        for (i, (movement, one_rm)) in lifts.iter().enumerate() {
+            let date =
+                relative_weekday(start, days[i % days.len()], None) + Duration::weeks(week as i64);
+            let name = format!("{} {}, week {}", movement, cycle, week + 1);
+            let line = format!(
+                "{}|{}|{}",
+                lift(movement, one_rm.as_ref(), &sets, increment),
+                comments.unwrap_or_default(),
+                name
+            );
+            program.entry(date).or_default().push(line);
+        }
+    }
+    program
+        .into_iter()
+        .map(|(date, lines)| Session { date, lines })
+        .collect()
+}
+
+/// The weightlifting workout of a lift, the equal sets in a row are grouped, i.e.
+/// "wl 3x5 back squat @100kg" or "wl [1x5 deadlift @60%; 1x3 deadlift @70%]".
+fn lift(
+    movement: &Movement,
+    one_rm: Option<&Weight>,
+    sets: &[(u32, f64)],
+    increment: f64,
+) -> String {
+    let one_rm = one_rm.filter(|w| w.unit != LoadUnit::Percent);
+    let mut groups: Vec<(u32, u32, String)> = Vec::new();
+    for (reps, percent) in sets {
+        let load = match one_rm {
+            Some(one_rm) => format_load(
+                round_to_increment(one_rm.weight_man * percent / 100.0, increment),
+                &one_rm.unit.to_string(),
+            ),
+            None => format_load(*percent, "%"),
+        };
+        match groups.last_mut() {
+            Some((n, r, l)) if *r == *reps && *l == load => *n += 1,
+            _ => groups.push((1, *reps, load)),
+        }
+    }
+    let sets: Vec<String> = groups
+        .iter()
+        .map(|(n, reps, load)| format!("{}x{} {} @{}", n, reps, movement.shorthand(), load))
+        .collect();
+    match sets.as_slice() {
+        [set] => format!("wl {}", set),
+        _ => format!("wl [{}]", sets.join("; ")),
+    }
+}
+
+/// The sessions as the content of a wod file, the lines of every day after a
+/// comment with the date.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wod::cycles::{wodfile, Session};
+///
+/// let session = Session {
+///     date: NaiveDate::from_ymd_opt(2025, 3, 24).unwrap(),
+///     lines: vec!["wl 3x5 deadlift @70%".to_string()],
+/// };
+/// assert_eq!(wodfile(&[session]), "# 2025-03-24\nwl 3x5 deadlift @70%\n");
+/// ```
+pub fn wodfile(sessions: &[Session]) -> String {
+    let mut content = String::new();
+    for (i, session) in sessions.iter().enumerate() {
+        if i > 0 {
+            content.push('\n');
+        }
+        let _ = writeln!(content, "# {}", session.date);
+        for line in &session.lines {
+            let _ = writeln!(content, "{}", line);
+        }
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_workout;
+
+    #[test]
+    fn test_five_three_one() {
+        let start = NaiveDate::from_ymd_opt(2025, 3, 24).unwrap();
+        let lifts = [(Movement::Deadlift, Some("200kg".parse().unwrap()))];
+        let sessions = sessions(Cycle::FiveThreeOne, &lifts, start, 8, &[], 2.5);
+        assert_eq!(sessions.len(), 8);
+        assert_eq!(
+            sessions[2].lines,
+            ["wl [1x5 deadlift @135kg; 1x3 deadlift @152.5kg; 1x1 deadlift @170kg]|Last set for as many reps as possible|Deadlift 5/3/1, week 3"]
+        );
+        assert_eq!(
+            sessions[3].lines[0],
+            "wl [1x5 deadlift @72.5kg; 1x5 deadlift @90kg; 1x5 deadlift @107.5kg]|Deload week|Deadlift 5/3/1, week 4"
+        );
+        // The training max grows on the second wave
+        assert_eq!(
+            sessions[4].date,
+            NaiveDate::from_ymd_opt(2025, 4, 21).unwrap()
+        );
+        assert!(sessions[4].lines[0].starts_with("wl [1x5 deadlift @120kg;"));
+    }
+
+    #[test]
+    fn test_sessions_are_workouts() {
+        let start = NaiveDate::from_ymd_opt(2025, 3, 26).unwrap();
+        let lifts = [
+            (Movement::BackSquat, None),
+            (Movement::ShoulderPress, Some("60kg".parse().unwrap())),
+            (Movement::Deadlift, None),
+        ];
+        let sessions = sessions(
+            Cycle::FiveThreeOne,
+            &lifts,
+            start,
+            4,
+            &[Weekday::Mon, Weekday::Wed],
+            2.5,
+        );
+        // The monday of the first week is before the start, the cycle begins on wednesday
+        assert_eq!(sessions[0].date, start);
+        assert_eq!(sessions[0].lines.len(), 1);
+        assert_eq!(
+            sessions[1].date,
+            NaiveDate::from_ymd_opt(2025, 3, 31).unwrap()
+        );
+        assert_eq!(sessions[1].lines.len(), 2);
+        for line in sessions.iter().flat_map(|s| &s.lines) {
+            let (workout, _) = line.split_once('|').unwrap();
+            assert!(create_workout(workout, None, None).is_ok(), "{}", workout);
+        }
+    }
+
+    #[test]
+    fn test_cycle_from_str() {
+        assert_eq!("5/3/1".parse::<Cycle>().unwrap(), Cycle::FiveThreeOne);
+        assert_eq!("3X5".parse::<Cycle>().unwrap(), Cycle::Linear);
+        assert!("smolov".parse::<Cycle>().is_err());
+        assert_eq!(Cycle::FiveThreeOne.to_string(), "5/3/1");
+    }
+}
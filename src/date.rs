@@ -0,0 +1,126 @@
+//! Dates of the files, the day the workouts are programmed for.
+//!
+//! They are written as "YYYY-MM-DD" in the front matter and the filenames, and can
+//! be given relative to the current day: "today", "tomorrow", "yesterday", a weekday
+//! ("friday", the next one, today included) or "next monday" / "last monday".
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+use crate::error::WodError;
+
+/// Parses a date in "YYYY-MM-DD" format, or relative to `today`.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wod::date::parse_date;
+///
+/// // A friday
+/// let today = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+/// let date = |s| parse_date(s, today).unwrap().to_string();
+/// assert_eq!(date("2025-03-01"), "2025-03-01");
+/// assert_eq!(date("tomorrow"), "2025-03-22");
+/// assert_eq!(date("friday"), "2025-03-21");
+/// assert_eq!(date("next friday"), "2025-03-28");
+/// assert_eq!(date("next Monday"), "2025-03-24");
+/// assert_eq!(date("last monday"), "2025-03-17");
+/// assert!(parse_date("2025-02-29", today).is_err());
+/// ```
+pub fn parse_date(s: &str, today: NaiveDate) -> Result<NaiveDate, WodError> {
+    let invalid = || {
+        WodError::Invalid(format!(
+            "Invalid date: `{}`, expected one of: YYYY-MM-DD, today, tomorrow, yesterday, [next|last] <weekday>",
+            s
+        ))
+    };
+    let day = Days::new(1);
+    let lowercase = s.trim().to_lowercase();
+    let (direction, day_name) = match lowercase.split_once(' ') {
+        Some((direction @ ("next" | "last"), weekday)) => (Some(direction), weekday.trim()),
+        Some(_) => return Err(invalid()),
+        None => (None, lowercase.as_str()),
+    };
+    let relative = match day_name {
+        "today" if direction.is_none() => Some(today),
+        "tomorrow" if direction.is_none() => today.checked_add_days(day),
+        "yesterday" if direction.is_none() => today.checked_sub_days(day),
+        _ => match day_name.parse::<Weekday>() {
+            Ok(weekday) => Some(relative_weekday(today, weekday, direction)),
+            Err(_) if direction.is_none() => None,
+            Err(_) => return Err(invalid()),
+        },
+    };
+    relative.or_else(|| parse_iso(s.trim())).ok_or_else(invalid)
+}
+
+/// Parses a date strictly in "YYYY-MM-DD" format, padded with zeros.
+pub(crate) fn parse_iso(s: &str) -> Option<NaiveDate> {
+    let well_formed = s.len() == 10
+        && s.char_indices().all(|(i, c)| {
+            if i == 4 || i == 7 {
+                c == '-'
+            } else {
+                c.is_ascii_digit()
+            }
+        });
+    well_formed
+        .then(|| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        .flatten()
+}
+
+/// The closest `weekday` from `today`: the next one (today included) without
+/// direction, strictly after it with "next" and strictly before it with "last".
+pub(crate) fn relative_weekday(
+    today: NaiveDate,
+    weekday: Weekday,
+    direction: Option<&str>,
+) -> NaiveDate {
+    let (from, to) = (
+        today.weekday().num_days_from_monday(),
+        weekday.num_days_from_monday(),
+    );
+    let (ahead, behind) = ((7 + to - from) % 7, (7 + from - to) % 7);
+    match direction {
+        Some("last") if behind == 0 => today - Days::new(7),
+        Some("last") => today - Days::new(u64::from(behind)),
+        Some(_) if ahead == 0 => today + Days::new(7),
+        _ => today + Days::new(u64::from(ahead)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn friday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap()
+    }
+
+    #[test]
+    fn test_parse_date() {
+        let date = |s| parse_date(s, friday()).map(|d| d.to_string());
+        assert_eq!(date("2024-02-29").unwrap(), "2024-02-29");
+        assert_eq!(date("today").unwrap(), "2025-03-21");
+        assert_eq!(date("Yesterday").unwrap(), "2025-03-20");
+        assert_eq!(date("sunday").unwrap(), "2025-03-23");
+        assert_eq!(date("last friday").unwrap(), "2025-03-14");
+        assert_eq!(date("last saturday").unwrap(), "2025-03-15");
+    }
+
+    #[test]
+    fn test_parse_date_invalid() {
+        for date in [
+            "2025-02-29",
+            "2025-04-31",
+            "2025-00-10",
+            "2025-3-21",
+            "next week",
+            "next today",
+            "someday",
+            "",
+        ] {
+            assert!(parse_date(date, friday()).is_err(), "{}", date);
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use std::error::Error;
+use std::fmt;
+use std::io::IsTerminal;
+use std::ops::Range;
+
+use crate::error::WodError;
+use crate::lexer::{Lexer, LexerError};
+use crate::movement::suggest_closest_movements;
+use crate::{is_day_separator, is_wodfile_comment, lint, parse_wodfile_line, parts};
+
+const RED: &str = "\x1b[31m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// An error found while parsing a workout, along with the location of the
+/// offending token in the workout string.
+///
+/// # Examples
+///
+/// ```
+/// use wod::diagnostics::Diagnostic;
+///
+/// let workout = "ft 21-15-9 pulup, thruster";
+/// let diagnostic = Diagnostic::from_source(workout).unwrap();
+/// assert_eq!(&workout[diagnostic.span.clone()], "pulup");
+/// assert_eq!(
+///     diagnostic.render(false),
+///     "error: Invalid Movement: Invalid movement: `pulup`, did you mean: `pull up`, `push up` or `v up`?\n  | ft 21-15-9 pulup, thruster\n  |            ^^^^^\n"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// The workout string that failed to parse.
+    pub source: String,
+    /// Byte range of the offending token in the source.
+    pub span: Range<usize>,
+    /// The error found by the lexer.
+    pub error: LexerError,
+}
+
+impl Diagnostic {
+    /// Tokenizes the workout and returns the diagnostic for the first error found,
+    /// or `None` if the workout is valid. The parts of a workout (see [`parts`]) are
+    /// tokenized one by one, with the span relative to the whole source.
+    pub fn from_source(source: &str) -> Option<Self> {
+        let Some(parts) = parts::split(source) else {
+            return Self::from_part(source, source);
+        };
+        parts
+            .iter()
+            .find_map(|part| Self::from_part(source, part.workout))
+    }
+
+    /// The diagnostic of a part of the source, a slice of it.
+    fn from_part(source: &str, part: &str) -> Option<Self> {
+        let offset = part.as_ptr() as usize - source.as_ptr() as usize;
+        let mut lexer = Lexer::new(part);
+        let error = lexer.tokenize().err()?;
+        let span = lexer.span();
+        Some(Diagnostic {
+            source: source.to_string(),
+            span: span.start + offset..span.end + offset,
+            error,
+        })
+    }
+
+    /// Renders the error with the workout below it and the offending token underlined.
+    /// When `color` is true, ANSI escape codes are used to highlight the error.
+    pub fn render(&self, color: bool) -> String {
+        let (red, bold, reset) = if color {
+            (RED, BOLD, RESET)
+        } else {
+            ("", "", "")
+        };
+        let column = self.source[..self.span.start].chars().count();
+        let width = self.source[self.span.clone()].chars().count().max(1);
+        format!(
+            "{bold}{red}error{reset}{bold}: {}{reset}\n  | {}\n  | {}{red}{}{reset}\n",
+            self.error,
+            self.source,
+            " ".repeat(column),
+            "^".repeat(width),
+        )
+    }
+}
+
+impl Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+/// How serious a problem of an [`Annotation`] is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Severity {
+    /// The line can't be parsed.
+    Error,
+    /// A problem of the programming found by a lint rule, see [`lint`].
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a line of a wod file, located to be annotated by an editor or
+/// a CI job, see [`check_wodfile`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Annotation {
+    /// Line of the problem, from 1.
+    pub line: usize,
+    /// Column where the problem starts, from 1, counted in characters.
+    pub column: usize,
+    /// Column right after the end of the problem.
+    pub end_column: usize,
+    pub severity: Severity,
+    /// Kind of problem, i.e. "invalid-movement", or the name of the lint rule.
+    pub code: String,
+    pub message: String,
+    /// Text to replace the offending one with, i.e. the closest movement.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}[{}]: {}",
+            self.line, self.column, self.severity, self.code, self.message
+        )
+    }
+}
+
+impl Annotation {
+    /// The annotation of a lexer error in a line.
+    fn from_diagnostic(line: usize, diagnostic: &Diagnostic) -> Self {
+        let token = &diagnostic.source[diagnostic.span.clone()];
+        let column = diagnostic.source[..diagnostic.span.start].chars().count() + 1;
+        let suggestion = match diagnostic.error {
+            LexerError::InvalidMovement(_) => suggest_closest_movements(token, 1)
+                .first()
+                .map(|s| s.to_string()),
+            _ => None,
+        };
+        Annotation {
+            line,
+            column,
+            end_column: column + token.chars().count(),
+            severity: Severity::Error,
+            code: diagnostic.error.code().to_string(),
+            message: diagnostic.error.to_string(),
+            suggestion,
+        }
+    }
+
+    /// An annotation covering the whole workout of a line, for the problems that
+    /// aren't in a single token.
+    fn whole(line: usize, workout: &str, severity: Severity, code: &str, message: String) -> Self {
+        Annotation {
+            line,
+            column: 1,
+            end_column: workout.chars().count() + 1,
+            severity,
+            code: code.to_string(),
+            message,
+            suggestion: None,
+        }
+    }
+}
+
+/// Checks every workout of a wod file, or a single workout, returning the problems
+/// found in order. The comments and empty lines are skipped, and with `lint` the
+/// rules of [`lint`] are run over the workouts that are valid.
+///
+/// # Examples
+///
+/// ```
+/// use wod::diagnostics::{check_wodfile, Severity};
+///
+/// let annotations = check_wodfile("# Week 1\nft 21-15-9 pull up, thruster\nft 10 pulup", false);
+/// assert_eq!(annotations.len(), 1);
+/// let annotation = &annotations[0];
+/// assert_eq!((annotation.line, annotation.column, annotation.end_column), (3, 7, 12));
+/// assert_eq!(annotation.severity, Severity::Error);
+/// assert_eq!(annotation.code, "invalid-movement");
+/// assert_eq!(annotation.suggestion.as_deref(), Some("pull up"));
+/// ```
+pub fn check_wodfile(content: &str, lint: bool) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let number = i + 1;
+        if line.trim().is_empty() || is_wodfile_comment(line) || is_day_separator(line) {
+            continue;
+        }
+        let workout = match parse_wodfile_line(line) {
+            Ok((workout, _, _)) => workout,
+            Err(e) => {
+                let code = "invalid-line";
+                annotations.push(Annotation::whole(
+                    number,
+                    line,
+                    Severity::Error,
+                    code,
+                    e.to_string(),
+                ));
+                continue;
+            }
+        };
+        if let Some(diagnostic) = Diagnostic::from_source(workout) {
+            annotations.push(Annotation::from_diagnostic(number, &diagnostic));
+            continue;
+        }
+        let workouts = match parts::create_workouts(workout, None, None) {
+            Ok(workouts) => workouts,
+            Err(e) => {
+                let code = match &e {
+                    WodError::Movement(_) => "invalid-movement",
+                    _ => "invalid-workout",
+                };
+                annotations.push(Annotation::whole(
+                    number,
+                    workout,
+                    Severity::Error,
+                    code,
+                    e.to_string(),
+                ));
+                continue;
+            }
+        };
+        if lint {
+            for found in workouts.iter().flat_map(lint::lint_workout) {
+                annotations.push(Annotation::whole(
+                    number,
+                    workout,
+                    Severity::Warning,
+                    found.rule,
+                    found.message,
+                ));
+            }
+        }
+    }
+    annotations
+}
+
+/// Decides whether the diagnostics printed to stderr should be colored.
+///
+/// Colors are disabled by the `--no-color` flag, when the `NO_COLOR` environment
+/// variable is set (see <https://no-color.org>), or when stderr is not a terminal.
+pub fn use_color(no_color: bool) -> bool {
+    let no_color_env = std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty());
+    !no_color && !no_color_env && std::io::stderr().is_terminal()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_source_valid() {
+        assert!(Diagnostic::from_source("ft 21-15-9 pull up, thruster").is_none());
+    }
+
+    #[test]
+    fn test_from_source_workout_type() {
+        let diagnostic = Diagnostic::from_source("fr 21-15-9 pull up").unwrap();
+        assert_eq!(diagnostic.span, 0..2);
+    }
+
+    #[test]
+    fn test_from_source_parts() {
+        let source = "A) wl 5x3 back squat @80%; B) amrap-12 10 pulup";
+        let diagnostic = Diagnostic::from_source(source).unwrap();
+        assert_eq!(&source[diagnostic.span], "pulup");
+        assert!(Diagnostic::from_source("A) wl 5x3 back squat; B) ft 21 pull up").is_none());
+    }
+
+    #[test]
+    fn test_render_color() {
+        let diagnostic = Diagnostic::from_source("ft 10 pulup").unwrap();
+        let rendered = diagnostic.render(true);
+        assert!(rendered.starts_with("\x1b[1m\x1b[31merror\x1b[0m"));
+        assert!(rendered.ends_with("  |       \x1b[31m^^^^^\x1b[0m\n"));
+    }
+
+    #[test]
+    fn test_check_wodfile() {
+        let content = "wl 5x5 back squat @75%\n\nwl 5x5 deadlift @60kg|a|b|c\n\
+                       A) ft 21 pull up; B) wl 3x3 snacth\n\
+                       amrap-10 [1rm snatch]";
+        let annotations = check_wodfile(content, false);
+        let found: Vec<(usize, &str)> = annotations
+            .iter()
+            .map(|a| (a.line, a.code.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (3, "invalid-line"),
+                (4, "invalid-movement"),
+                (5, "invalid-workout")
+            ]
+        );
+        assert!(annotations.iter().all(|a| a.severity == Severity::Error));
+        // The column is in the whole line, not the part
+        assert_eq!(annotations[1].column, 29);
+        assert_eq!(annotations[1].suggestion.as_deref(), Some("snatch"));
+        assert_eq!(annotations[2].column, 1);
+        assert_eq!(annotations[2].end_column, 22);
+    }
+
+    #[test]
+    fn test_check_wodfile_lint() {
+        let content = "ft 21 deadlift @1000kg";
+        assert!(check_wodfile(content, false).is_empty());
+        // The day separators of the files split with `wod split` aren't workouts
+        assert!(check_wodfile("wl 5x5 back squat\n---\nft 21 pull up", true).is_empty());
+        let annotations = check_wodfile(content, true);
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].severity, Severity::Warning);
+        assert_eq!(annotations[0].code, "implausible-load");
+        assert_eq!(
+            annotations[0].to_string(),
+            format!("1:1: warning[implausible-load]: {}", annotations[0].message)
+        );
+        #[cfg(feature = "serde")]
+        {
+            let value = serde_json::to_value(&annotations[0]).unwrap();
+            assert_eq!(value["severity"], "warning");
+            assert!(value["suggestion"].is_null());
+        }
+    }
+
+    #[test]
+    fn test_use_color_flag() {
+        assert!(!use_color(true));
+    }
+}
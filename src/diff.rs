@@ -0,0 +1,250 @@
+//! Differences between two workouts, see [`Workout::diff`].
+//!
+//! The lines of the workouts are compared by their position, so a line inserted in
+//! the middle shows as every following line changed. That is enough to compare the Rx
+//! and scaled versions of a workout, or this year's programming against last year's.
+
+use std::fmt;
+
+use crate::rep_types::rep_type::RepType;
+use crate::weight;
+use crate::workout::{Prescription, Workout, WorkoutStructure};
+
+/// A difference between two workouts, lines are numbered from 1.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Change {
+    /// The type of workout, as in the shorthand: "ft" to "3rd".
+    WorkoutType { from: String, to: String },
+    /// The repetitions per round: "21-15-9" to "15-12-9".
+    Rounds { from: String, to: String },
+    /// The movements of a line.
+    Movements {
+        line: usize,
+        from: String,
+        to: String,
+    },
+    /// The sets and repetitions of a line.
+    Reps {
+        line: usize,
+        from: String,
+        to: String,
+    },
+    /// The load of a line, `None` for a line without load.
+    Load {
+        line: usize,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// A line only found in the second workout.
+    Added { line: usize, text: String },
+    /// A line only found in the first workout.
+    Removed { line: usize, text: String },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let load = |load: &Option<String>| load.clone().unwrap_or_else(|| "-".to_string());
+        match self {
+            Change::WorkoutType { from, to } => write!(f, "Type: {} -> {}", from, to),
+            Change::Rounds { from, to } => write!(f, "Rounds: {} -> {}", from, to),
+            Change::Movements { line, from, to } => {
+                write!(f, "Line {} movements: {} -> {}", line, from, to)
+            }
+            Change::Reps { line, from, to } => write!(f, "Line {} reps: {} -> {}", line, from, to),
+            Change::Load { line, from, to } => {
+                write!(f, "Line {} load: {} -> {}", line, load(from), load(to))
+            }
+            Change::Added { line, text } => write!(f, "Line {} added: {}", line, text),
+            Change::Removed { line, text } => write!(f, "Line {} removed: {}", line, text),
+        }
+    }
+}
+
+/// The repetitions of a line as written in the markdown: "21", "5x5", "3x(2+1)",
+/// "3x(3-2-1)" or "1rm".
+fn reps(line: &Prescription) -> String {
+    if let Some(rm) = &line.rm {
+        return rm.to_string();
+    }
+    let (reps, separator) = if line.wave.is_empty() {
+        (&line.reps, "+")
+    } else {
+        (&line.wave, "-")
+    };
+    let reps = reps
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(separator);
+    match &line.sets {
+        Some(sets) if line.reps.len() > 1 || !line.wave.is_empty() => {
+            format!("{}x({})", sets, reps)
+        }
+        Some(sets) => format!("{}x{}", sets, reps),
+        None => reps,
+    }
+}
+
+fn movements(line: &Prescription) -> String {
+    line.movements
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// The load of a line, "43/30kg", the loads of every set, "60,65,70kg", or the RPE.
+fn load(line: &Prescription) -> Option<String> {
+    if let Some(rpe) = &line.rpe {
+        return Some(rpe.to_string());
+    }
+    if !line.loads.is_empty() {
+        return Some(weight::format_loads(&line.loads, ","));
+    }
+    line.weight.as_ref().map(|w| w.to_string())
+}
+
+/// The whole line, "21 Thruster @ 43/30kg".
+fn text(line: &Prescription) -> String {
+    let mut parts = vec![reps(line), movements(line)];
+    if let Some(load) = load(line) {
+        parts.push(format!("@ {}", load));
+    }
+    parts.retain(|p| !p.is_empty());
+    parts.join(" ")
+}
+
+fn rounds(workout: &Workout) -> String {
+    let rounds = |reps: &[RepType]| {
+        reps.iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>()
+            .join("-")
+    };
+    match &workout.structure {
+        WorkoutStructure::Simple(simple) => rounds(&simple.rounds),
+        WorkoutStructure::Block(sub_workouts) => sub_workouts
+            .iter()
+            .map(|s| rounds(&s.rounds))
+            .collect::<Vec<_>>()
+            .join("; "),
+    }
+}
+
+/// Compares two workouts, see [`Workout::diff`].
+pub(crate) fn changes(first: &Workout, second: &Workout) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let (from, to) = (
+        first.workout_type.shorthand(),
+        second.workout_type.shorthand(),
+    );
+    if from != to {
+        changes.push(Change::WorkoutType { from, to });
+    }
+    let (from, to) = (rounds(first), rounds(second));
+    if from != to {
+        changes.push(Change::Rounds { from, to });
+    }
+
+    let mut lines = first.iter_prescriptions();
+    let mut other_lines = second.iter_prescriptions();
+    for line in 1.. {
+        match (lines.next(), other_lines.next()) {
+            (Some(a), Some(b)) => {
+                if a.movements != b.movements {
+                    changes.push(Change::Movements {
+                        line,
+                        from: movements(a),
+                        to: movements(b),
+                    });
+                }
+                if (&a.sets, &a.reps, &a.wave, &a.rm) != (&b.sets, &b.reps, &b.wave, &b.rm) {
+                    changes.push(Change::Reps {
+                        line,
+                        from: reps(a),
+                        to: reps(b),
+                    });
+                }
+                let (from, to) = (load(a), load(b));
+                if from != to {
+                    changes.push(Change::Load { line, from, to });
+                }
+            }
+            (Some(a), None) => changes.push(Change::Removed {
+                line,
+                text: text(a),
+            }),
+            (None, Some(b)) => changes.push(Change::Added {
+                line,
+                text: text(b),
+            }),
+            (None, None) => break,
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::workout::create_workout;
+
+    fn diff(first: &str, second: &str) -> Vec<String> {
+        let first = create_workout(first, None, None).unwrap();
+        let second = create_workout(second, None, None).unwrap();
+        first.diff(&second).iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        assert!(diff(
+            "ft 21-15-9 c2b, thruster @43/30kg",
+            "ft 21-15-9 chest to bar, thruster @43/30kg"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_changes() {
+        assert_eq!(
+            diff(
+                "ft 21-15-9 pull up, thruster @43/30kg",
+                "3rd 15-12-9 strict pull up, thruster @30/20kg, 10 burpee"
+            ),
+            vec![
+                "Type: ft -> 3rd",
+                "Rounds: 21-15-9 -> 15-12-9",
+                "Line 1 movements: Pull Up -> Strict Pull Up",
+                "Line 2 load: 43/30kg -> 30/20kg",
+                "Line 3 added: 10 Burpee",
+            ]
+        );
+        assert_eq!(
+            diff(
+                "wl [5x5 back squat @70%; 3x(2+1) clean, split jerk @80%]",
+                "wl [5x3 back squat; 1rm clean]"
+            ),
+            vec![
+                "Line 1 reps: 5x5 -> 5x3",
+                "Line 1 load: 70% -> -",
+                "Line 2 movements: Clean + Split Jerk -> Clean",
+                "Line 2 reps: 3x(2+1) -> 1rm",
+                "Line 2 load: 80% -> -",
+            ]
+        );
+        assert_eq!(
+            diff(
+                "wl 3x5 deadlift @100,110,120kg",
+                "wl 3x5 deadlift @100,110,125kg"
+            ),
+            vec!["Line 1 load: 100,110,120kg -> 100,110,125kg"]
+        );
+        assert_eq!(
+            diff("wl 3x(3-2-1) power clean", "wl 3x(5-3-1) power clean"),
+            vec!["Line 1 reps: 3x(3-2-1) -> 3x(5-3-1)"]
+        );
+        assert_eq!(
+            diff("wl 5x5 back squat, 1rm snatch", "wl 5x5 back squat"),
+            vec!["Line 2 removed: 1rm Snatch"]
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::hugo;
+use crate::movement::{registry_problems, Movement};
+
+/// Outcome of a check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Warning => write!(f, "warning"),
+            Status::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Result of one of the checks done by `wod doctor`.
+///
+/// # Examples
+///
+/// ```
+/// use wod::doctor::{Check, Status};
+///
+/// let check = Check { name: "config", status: Status::Ok, message: "valid".to_string() };
+/// assert_eq!(check.to_string(), "[ok] config: valid");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: Status,
+    pub message: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, message: impl Into<String>) -> Self {
+        Check {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.status, self.name, self.message)
+    }
+}
+
+/// Checks the configuration file can be read and is valid.
+pub fn check_config(path: &Path) -> Check {
+    if !path.exists() {
+        return Check::new(
+            "config",
+            Status::Ok,
+            format!("no file found at {}, using the defaults", path.display()),
+        );
+    }
+    match Config::load(path) {
+        Ok(_) => Check::new("config", Status::Ok, format!("{} is valid", path.display())),
+        Err(e) => Check::new(
+            "config",
+            Status::Error,
+            format!("{} is not valid, fix or remove it: {}", path.display(), e),
+        ),
+    }
+}
+
+/// Checks the movements known by the parser and their videos are consistent.
+pub fn check_movements() -> Check {
+    let problems = registry_problems();
+    if problems.is_empty() {
+        let missing = Movement::all().filter(|m| m.video().is_none()).count();
+        Check::new(
+            "movements",
+            Status::Ok,
+            format!(
+                "the registry is consistent, {} movements without video",
+                missing
+            ),
+        )
+    } else {
+        Check::new("movements", Status::Error, problems.join("; "))
+    }
+}
+
+/// Reports where the files will be written, detecting if `cwd` is a Hugo site.
+pub fn check_hugo(cwd: &Path, output_dir: &Path) -> Check {
+    if hugo::is_site(cwd) {
+        Check::new(
+            "hugo",
+            Status::Ok,
+            format!(
+                "Hugo site found, files are written to {}",
+                output_dir.display()
+            ),
+        )
+    } else if output_dir.as_os_str().is_empty() {
+        Check::new(
+            "hugo",
+            Status::Warning,
+            "not in a Hugo site, files are written to the current directory, \
+             set `output_dir` in the config or run from the root of the site",
+        )
+    } else {
+        Check::new(
+            "hugo",
+            Status::Ok,
+            format!(
+                "not in a Hugo site, files are written to {}",
+                output_dir.display()
+            ),
+        )
+    }
+}
+
+/// Checks a file can be created in `dir`, or in its closest existing parent if
+/// the directory will be created later.
+pub fn check_writable(name: &'static str, dir: &Path) -> Check {
+    let existing = dir
+        .ancestors()
+        .map(|d| {
+            if d.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                d
+            }
+        })
+        .find(|d| d.is_dir())
+        .unwrap_or(Path::new("."));
+    let probe = existing.join(".wod-doctor");
+    match fs::write(&probe, "").and_then(|_| fs::remove_file(&probe)) {
+        Ok(()) => Check::new(
+            name,
+            Status::Ok,
+            format!("{} is writable", existing.display()),
+        ),
+        Err(e) => Check::new(
+            name,
+            Status::Error,
+            format!("can't write to {}: {}", existing.display(), e),
+        ),
+    }
+}
+
+/// Runs all the checks of the environment.
+///
+/// # Arguments
+///
+/// * `config_path` - Path to the configuration file.
+/// * `cwd` - The working directory, to detect a Hugo site.
+/// * `output_dir` - Directory where the workouts are written.
+/// * `home` - Directory with the local data (see [`crate::wod_home`]).
+pub fn run_checks(config_path: &Path, cwd: &Path, output_dir: &Path, home: &Path) -> Vec<Check> {
+    vec![
+        check_config(config_path),
+        check_movements(),
+        check_hugo(cwd, output_dir),
+        check_writable("output", output_dir),
+        check_writable("data", home),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_check_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.toml");
+        assert_eq!(check_config(&path).status, Status::Ok);
+
+        fs::write(&path, "plate_increment = 2.5").unwrap();
+        assert_eq!(check_config(&path).status, Status::Ok);
+
+        fs::write(&path, "plate_incremnt = 2.5").unwrap();
+        let check = check_config(&path);
+        assert_eq!(check.status, Status::Error);
+        assert!(check.message.contains("plate_incremnt"));
+    }
+
+    #[test]
+    fn test_check_movements() {
+        assert_eq!(check_movements().status, Status::Ok);
+    }
+
+    #[test]
+    fn test_check_hugo() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        assert_eq!(check_hugo(root, Path::new("")).status, Status::Warning);
+        assert_eq!(check_hugo(root, Path::new("wods")).status, Status::Ok);
+
+        fs::write(root.join("hugo.toml"), "").unwrap();
+        fs::create_dir(root.join("content")).unwrap();
+        let check = check_hugo(root, &root.join("content/wod"));
+        assert_eq!(check.status, Status::Ok);
+        assert!(check.message.starts_with("Hugo site found"));
+    }
+
+    #[test]
+    fn test_check_writable() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("not").join("yet");
+        let check = check_writable("output", &missing);
+        assert_eq!(check.status, Status::Ok);
+        assert_eq!(
+            check.message,
+            format!("{} is writable", temp_dir.path().display())
+        );
+        assert!(!missing.exists());
+    }
+}
@@ -0,0 +1,272 @@
+//! Splits the generated markdown files into their workout blocks.
+//!
+//! A file is made of a preamble (the front matter and the introduction) followed by
+//! the workouts, each one starting with a `---` separator line as written by
+//! the `Display` implementation of [`Workout`](crate::workout::Workout):
+//!
+//! ```text
+//! ---
+//! title: "2025-03-21"
+//! ---
+//!
+//! Workout for the day, 2025-03-21.
+//!
+//! ---
+//!
+//! **Weightlifting**
+//! ...
+//! ```
+
+use std::fmt;
+
+use crate::error::WodError;
+
+/// Where a new workout is placed in a file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Position {
+    /// After the last workout.
+    #[default]
+    End,
+    /// As the n-th workout of the file (starting from 1).
+    At(usize),
+    /// Before the first workout containing the text (case insensitive), i.e. "for time".
+    Before(String),
+}
+
+/// A markdown file split into the preamble and the workout blocks.
+///
+/// # Examples
+///
+/// ```
+/// use wod::document::Document;
+///
+/// let content = "---\ntitle: \"wod\"\n---\n\nIntro\n\n---\n\n**For Time**\n\n---\n\n**Weightlifting**\n\n";
+/// let document = Document::parse(content);
+/// assert_eq!(document.blocks().len(), 2);
+/// assert_eq!(document.to_string(), content);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Document {
+    preamble: String,
+    blocks: Vec<String>,
+}
+
+impl Document {
+    /// Splits the content of a file, the front matter is kept as part of the preamble.
+    pub fn parse(content: &str) -> Self {
+        let mut document = Document::default();
+        let mut lines = content.split_inclusive('\n');
+
+        // The front matter is delimited by `---` lines too (or `+++` in TOML), so it's
+        // read separately. Unlike a workout block, the separator is followed by a field
+        // instead of a blank line.
+        let mut first_lines = content.lines();
+        let delimiter = first_lines
+            .next()
+            .map(str::trim_end)
+            .filter(|l| *l == "---" || *l == "+++")
+            .filter(|_| first_lines.next().is_some_and(|l| !l.trim().is_empty()));
+        if let Some(delimiter) = delimiter {
+            document.preamble.push_str(lines.next().unwrap());
+            for line in lines.by_ref() {
+                document.preamble.push_str(line);
+                if line.trim_end() == delimiter {
+                    break;
+                }
+            }
+        }
+
+        for line in lines {
+            if line.trim_end() == "---" {
+                document.blocks.push(String::new());
+            }
+            match document.blocks.last_mut() {
+                Some(block) => block.push_str(line),
+                None => document.preamble.push_str(line),
+            }
+        }
+        document
+    }
+
+    /// The front matter and the introduction, everything before the first workout.
+    pub fn preamble(&self) -> &str {
+        &self.preamble
+    }
+
+    /// The workout blocks of the file, in order.
+    pub fn blocks(&self) -> &[String] {
+        &self.blocks
+    }
+
+    /// Replaces the workout blocks of the file, keeping the preamble.
+    pub fn set_blocks(&mut self, blocks: Vec<String>) {
+        self.blocks = blocks;
+    }
+
+    /// Inserts a workout block in the given position.
+    ///
+    /// Returns an error if the position is out of range, or no workout matches
+    /// the text of [`Position::Before`].
+    pub fn insert(&mut self, position: &Position, block: String) -> Result<(), WodError> {
+        let index = match position {
+            Position::End => self.blocks.len(),
+            Position::At(n) if (1..=self.blocks.len() + 1).contains(n) => n - 1,
+            Position::At(n) => {
+                return Err(WodError::Invalid(format!(
+                    "Invalid position {}, the file has {} workouts",
+                    n,
+                    self.blocks.len()
+                )))
+            }
+            Position::Before(text) => {
+                let text = text.to_lowercase();
+                self.blocks
+                    .iter()
+                    .position(|b| b.to_lowercase().contains(&text))
+                    .ok_or_else(|| {
+                        WodError::Invalid(format!("No workout found containing `{}`", text))
+                    })?
+            }
+        };
+        self.blocks.insert(index, block);
+        Ok(())
+    }
+
+    /// Whether the file already contains a workout equal to `block`. A block with
+    /// several workouts, i.e. the parts of an entry, must be found as consecutive
+    /// workouts.
+    pub fn contains(&self, block: &str) -> bool {
+        self.find(block).is_some()
+    }
+
+    /// Removes the last workout equal to `block`, or the last run of consecutive
+    /// workouts if it has several of them, returns whether it was found.
+    pub fn remove(&mut self, block: &str) -> bool {
+        match self.find(block) {
+            Some(range) => {
+                self.blocks.drain(range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The position of the last run of workouts equal to the ones of `block`.
+    fn find(&self, block: &str) -> Option<std::ops::Range<usize>> {
+        let blocks = Document::parse(block).blocks;
+        if blocks.is_empty() || blocks.len() > self.blocks.len() {
+            return None;
+        }
+        (0..=self.blocks.len() - blocks.len())
+            .rev()
+            .find(|&i| self.blocks[i..i + blocks.len()] == blocks[..])
+            .map(|i| i..i + blocks.len())
+    }
+
+    /// Removes the last workout of the file and returns it.
+    pub fn pop(&mut self) -> Option<String> {
+        self.blocks.pop()
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.preamble)?;
+        for block in &self.blocks {
+            write!(f, "{}", block)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = "---\ntitle: \"wod\"\ndraft: false\n---\n\nWorkout for the day, wod.\n\n---\n\n**For Time**\n\n21-15-9\n\n---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n";
+
+    #[test]
+    fn test_parse() {
+        let document = Document::parse(CONTENT);
+        assert_eq!(
+            document.preamble,
+            "---\ntitle: \"wod\"\ndraft: false\n---\n\nWorkout for the day, wod.\n\n"
+        );
+        assert_eq!(
+            document.blocks(),
+            [
+                "---\n\n**For Time**\n\n21-15-9\n\n",
+                "---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n"
+            ]
+        );
+        assert_eq!(document.to_string(), CONTENT);
+    }
+
+    #[test]
+    fn test_parse_without_front_matter() {
+        let document = Document::parse("---\n\n**For Time**\n\n");
+        assert_eq!(document.preamble, "");
+        assert_eq!(document.blocks().len(), 1);
+    }
+
+    #[test]
+    fn test_contains() {
+        let document = Document::parse(CONTENT);
+        assert!(document.contains("---\n\n**For Time**\n\n21-15-9\n\n"));
+        assert!(!document.contains("---\n\n**For Time**\n\n"));
+        // Several workouts must be consecutive
+        assert!(document.contains(
+            "---\n\n**For Time**\n\n21-15-9\n\n---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n"
+        ));
+        assert!(!document.contains(
+            "---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n---\n\n**For Time**\n\n21-15-9\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut document = Document::parse(CONTENT);
+        assert!(!document.remove("---\n\n**AMRAP**\n\n"));
+        assert!(document.remove("---\n\n**For Time**\n\n21-15-9\n\n"));
+        assert_eq!(document.blocks().len(), 1);
+        assert_eq!(
+            document.pop(),
+            Some("---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n".to_string())
+        );
+        assert_eq!(document.pop(), None);
+
+        let mut document = Document::parse(CONTENT);
+        assert!(document.remove(
+            "---\n\n**For Time**\n\n21-15-9\n\n---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n"
+        ));
+        assert!(document.blocks().is_empty());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut document = Document::parse(CONTENT);
+        document
+            .insert(&Position::At(1), "---\n\nfirst\n\n".to_string())
+            .unwrap();
+        assert_eq!(document.blocks()[0], "---\n\nfirst\n\n");
+
+        document
+            .insert(
+                &Position::Before("weightLIFTING".to_string()),
+                "---\n\nbefore\n\n".to_string(),
+            )
+            .unwrap();
+        assert_eq!(document.blocks()[2], "---\n\nbefore\n\n");
+
+        document
+            .insert(&Position::End, "---\n\nlast\n\n".to_string())
+            .unwrap();
+        assert_eq!(document.blocks()[4], "---\n\nlast\n\n");
+
+        assert!(document.insert(&Position::At(0), String::new()).is_err());
+        assert!(document.insert(&Position::At(7), String::new()).is_err());
+        assert!(document
+            .insert(&Position::Before("amrap".to_string()), String::new())
+            .is_err());
+    }
+}
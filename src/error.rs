@@ -0,0 +1,102 @@
+use std::io;
+
+use thiserror::Error;
+
+use crate::diagnostics::Diagnostic;
+use crate::lexer::LexerError;
+use crate::movement::MovementParseError;
+
+/// Errors returned by the functions of the crate.
+///
+/// The parsers of the individual types (i.e. [`crate::Weight`]) keep returning a
+/// `String` from `FromStr`, which is reported as [`WodError::Invalid`] when it
+/// reaches the public API.
+///
+/// # Examples
+///
+/// ```
+/// use wod::{create_workout, WodError};
+///
+/// let err = create_workout("ft 21-15-9 pulup", None, None).unwrap_err();
+/// assert!(matches!(err, WodError::Lexer(_)));
+/// assert!(err.is_parse_error());
+/// ```
+#[derive(Debug, Error)]
+pub enum WodError {
+    /// The workout can't be tokenized.
+    #[error(transparent)]
+    Lexer(#[from] LexerError),
+    /// A movement that doesn't exist.
+    #[error(transparent)]
+    Movement(#[from] MovementParseError),
+    /// A lexer error along with the position where it was found.
+    #[error(transparent)]
+    Diagnostic(#[from] Diagnostic),
+    /// The workout is tokenized, but its structure is not supported.
+    #[error("Invalid workout: {0}")]
+    InvalidWorkout(String),
+    /// A line of a wod file without the `workout|comments|name` format.
+    #[error("Invalid wodfile: {0}")]
+    WodFile(String),
+    /// The configuration file is not valid.
+    #[cfg(feature = "cli")]
+    #[error("Invalid config: {0}")]
+    Config(#[from] toml::de::Error),
+    /// One of the local stores (results, personal records, journal) is not valid.
+    #[cfg(feature = "serde")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// `wod fmt --check` found files that aren't in canonical form.
+    #[error("{0} not formatted, run `wod fmt` without --check")]
+    Unformatted(String),
+    /// Any other invalid input, i.e. a date or a template that doesn't exist.
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl WodError {
+    /// Whether the error comes from parsing a workout.
+    pub fn is_parse_error(&self) -> bool {
+        matches!(
+            self,
+            WodError::Lexer(_)
+                | WodError::Movement(_)
+                | WodError::Diagnostic(_)
+                | WodError::InvalidWorkout(_)
+        )
+    }
+}
+
+impl From<String> for WodError {
+    fn from(s: String) -> Self {
+        WodError::Invalid(s)
+    }
+}
+
+impl From<&str> for WodError {
+    fn from(s: &str) -> Self {
+        WodError::Invalid(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_string() {
+        let err: WodError = "Invalid date".into();
+        assert!(matches!(err, WodError::Invalid(_)));
+        assert!(!err.is_parse_error());
+        assert_eq!(err.to_string(), "Invalid date");
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_config_error() {
+        let err: WodError = toml::from_str::<toml::Value>("a = ").unwrap_err().into();
+        assert!(err.to_string().starts_with("Invalid config: "));
+    }
+}
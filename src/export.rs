@@ -0,0 +1,249 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::error::WodError;
+use crate::timer::{schedule, Interval, IntervalKind};
+use crate::workout::Workout;
+
+/// An announcement of a timed workout, for the timer apps or a text-to-speech
+/// engine to call out at its time.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Cue {
+    /// Seconds from the start of the workout.
+    pub seconds: u32,
+    /// The text announced, i.e. "Round 1/12: 10 Pull Up".
+    pub text: String,
+}
+
+/// Formats of the cue scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CueFormat {
+    /// A JSON array of the cues, with their time in seconds and as "MM:SS".
+    #[default]
+    Json,
+    /// SubRip subtitles, each cue shown until the next one.
+    Srt,
+}
+
+/// Builds a Garmin Connect structured workout (the JSON accepted by the workout
+/// import of Garmin Connect) from an interval based workout (EMOM, AMRAP, Tabata).
+///
+/// Every interval of the workout becomes a timed step, the rest periods are
+/// exported as rest steps so the watch shows them differently.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::export::garmin_workout;
+///
+/// let workout = create_workout("tabata 20 air squat", None, None).unwrap();
+/// let garmin = garmin_workout(&workout, "Tabata squats").unwrap();
+/// assert_eq!(garmin["workoutName"], "Tabata squats");
+/// assert_eq!(garmin["workoutSegments"][0]["workoutSteps"].as_array().unwrap().len(), 16);
+/// ```
+pub fn garmin_workout(workout: &Workout, name: &str) -> Result<Value, WodError> {
+    let steps = schedule(workout)?
+        .iter()
+        .enumerate()
+        .map(|(i, interval)| garmin_step(i + 1, interval, workout))
+        .collect::<Vec<_>>();
+
+    Ok(json!({
+        "workoutName": name,
+        "sportType": sport_type(),
+        "workoutSegments": [{
+            "segmentOrder": 1,
+            "sportType": sport_type(),
+            "workoutSteps": steps,
+        }],
+    }))
+}
+
+/// Builds the cues of an interval based workout (EMOM, AMRAP, Tabata) from the
+/// intervals of its timer: the start of every interval with the movements to do (the
+/// line of the round in the alternating EMOMs), the rest periods, and the end.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::export::{cues, Cue};
+///
+/// let workout = create_workout("emom-4-2m 10 pull up, 5 thruster @43/30kg", None, None).unwrap();
+/// let text = |seconds, text: &str| Cue { seconds, text: text.to_string() };
+/// assert_eq!(
+///     cues(&workout).unwrap(),
+///     vec![
+///         text(0, "Round 1/2: 10 Pull Up, 5 Thruster @ 43/30kg"),
+///         text(120, "Round 2/2: 10 Pull Up, 5 Thruster @ 43/30kg"),
+///         text(240, "Time"),
+///     ]
+/// );
+/// ```
+pub fn cues(workout: &Workout) -> Result<Vec<Cue>, WodError> {
+    let intervals = schedule(workout)?;
+    let lines: Vec<String> = workout
+        .iter_prescriptions()
+        .map(|line| workout.line_text(line))
+        .collect();
+
+    let mut cues = Vec::new();
+    let mut seconds = 0;
+    for interval in &intervals {
+        let text = match interval.kind {
+            IntervalKind::Rest => interval.label.clone(),
+            IntervalKind::Work(Some(line)) => format!("{}: {}", interval.label, lines[line]),
+            IntervalKind::Work(None) => format!("{}: {}", interval.label, lines.join(", ")),
+        };
+        cues.push(Cue { seconds, text });
+        seconds += interval.seconds;
+    }
+    cues.push(Cue {
+        seconds,
+        text: "Time".to_string(),
+    });
+    Ok(cues)
+}
+
+/// Writes the cues as a JSON array or as SubRip subtitles.
+///
+/// # Examples
+///
+/// ```
+/// use wod::export::{cue_script, Cue, CueFormat};
+///
+/// let cues = vec![
+///     Cue { seconds: 0, text: "Work 1/8: 20 Air Squat".to_string() },
+///     Cue { seconds: 20, text: "Rest".to_string() },
+/// ];
+/// assert_eq!(
+///     cue_script(&cues, CueFormat::Srt),
+///     "1\n00:00:00,000 --> 00:00:20,000\nWork 1/8: 20 Air Squat\n\n\
+///      2\n00:00:20,000 --> 00:00:23,000\nRest\n"
+/// );
+/// ```
+pub fn cue_script(cues: &[Cue], format: CueFormat) -> String {
+    match format {
+        CueFormat::Json => {
+            let cues: Vec<Value> = cues
+                .iter()
+                .map(|cue| {
+                    json!({
+                        "seconds": cue.seconds,
+                        "time": format!("{:02}:{:02}", cue.seconds / 60, cue.seconds % 60),
+                        "text": cue.text,
+                    })
+                })
+                .collect();
+            serde_json::to_string_pretty(&cues).unwrap_or_default()
+        }
+        CueFormat::Srt => {
+            let mut srt = Vec::new();
+            for (i, cue) in cues.iter().enumerate() {
+                // The last cue is shown for a few seconds
+                let end = cues.get(i + 1).map_or(cue.seconds + 3, |next| next.seconds);
+                srt.push(format!(
+                    "{}\n{} --> {}\n{}\n",
+                    i + 1,
+                    srt_time(cue.seconds),
+                    srt_time(end),
+                    cue.text
+                ));
+            }
+            srt.join("\n")
+        }
+    }
+}
+
+/// A time of the SubRip subtitles, "HH:MM:SS,mmm".
+fn srt_time(seconds: u32) -> String {
+    format!(
+        "{:02}:{:02}:{:02},000",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn sport_type() -> Value {
+    json!({"sportTypeId": 6, "sportTypeKey": "cardio_training"})
+}
+
+fn garmin_step(order: usize, interval: &Interval, workout: &Workout) -> Value {
+    let step_type = match interval.kind {
+        IntervalKind::Rest => json!({"stepTypeId": 5, "stepTypeKey": "rest"}),
+        IntervalKind::Work(_) => json!({"stepTypeId": 3, "stepTypeKey": "interval"}),
+    };
+    json!({
+        "type": "ExecutableStepDTO",
+        "stepOrder": order,
+        "stepType": step_type,
+        "description": interval.title(workout),
+        "endCondition": {"conditionTypeId": 2, "conditionTypeKey": "time"},
+        "endConditionValue": interval.seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    #[test]
+    fn test_garmin_emom() {
+        let workout = create_workout("emom-12-3m-r1m 15cal row", None, None).unwrap();
+        let garmin = garmin_workout(&workout, "EMOM").unwrap();
+        let steps = garmin["workoutSegments"][0]["workoutSteps"]
+            .as_array()
+            .unwrap();
+        assert_eq!(steps.len(), 6);
+        assert_eq!(steps[0]["stepOrder"], 1);
+        assert_eq!(steps[0]["stepType"]["stepTypeKey"], "interval");
+        assert_eq!(steps[0]["endConditionValue"], 180);
+        assert_eq!(steps[1]["stepType"]["stepTypeKey"], "rest");
+        assert_eq!(steps[1]["endConditionValue"], 60);
+    }
+
+    #[test]
+    fn test_cues() {
+        let workout = create_workout("emom-9-3m-r1m-alt 15cal row, 10 burpee", None, None).unwrap();
+        let texts: Vec<(u32, String)> = cues(&workout)
+            .unwrap()
+            .into_iter()
+            .map(|cue| (cue.seconds, cue.text))
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                (0, "Round 1/2: 15 calories Row".to_string()),
+                (180, "Rest".to_string()),
+                (240, "Round 2/2: 10 Burpee".to_string()),
+                (420, "Rest".to_string()),
+                (480, "Time".to_string()),
+            ]
+        );
+
+        let workout = create_workout("amrap-12 5 pull up, 10 push up", None, None).unwrap();
+        assert_eq!(
+            cues(&workout).unwrap()[0].text,
+            "AMRAP: 5 Pull Up, 10 Push Up"
+        );
+
+        let workout = create_workout("tabata 20 air squat", None, None).unwrap();
+        let script = cue_script(&cues(&workout).unwrap(), CueFormat::Json);
+        let value: Value = serde_json::from_str(&script).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 17);
+        assert_eq!(value[2]["time"], "00:30");
+        assert_eq!(value[16]["text"], "Time");
+        assert!(
+            cues(&create_workout("ft 21-15-9 pull up, thruster", None, None).unwrap()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_garmin_unsupported() {
+        let workout = create_workout("ft 21-15-9 pull up, thruster", None, None).unwrap();
+        assert!(garmin_workout(&workout, "Fran").is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! C API to use the parser from other languages, i.e. from a mobile app that can't
+//! link Rust directly.
+//!
+//! Built as a shared library with `make ffi`, which also copies the header generated
+//! by the build script to `target/release/wod.h`.
+//! Every string returned is owned by the caller and has to be released with
+//! [`wod_free`].
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use crate::render::Format;
+use crate::workout::create_workout;
+
+/// Reads a string given by the caller, `None` if it's null or not valid UTF-8.
+///
+/// # Safety
+///
+/// `s` must be null or point to a nul terminated string.
+unsafe fn read(s: *const c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok().map(str::to_string)
+}
+
+/// Hands a string over to the caller, the nul bytes inside it are removed.
+fn give(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', ""))
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Parses a workout and returns it as JSON: `{"workout": {...}}`, or
+/// `{"error": {"message": ..., "start": ..., "end": ...}}` if it isn't valid.
+///
+/// Returns null if `source` is null or not valid UTF-8.
+///
+/// # Safety
+///
+/// `source` must be null or point to a nul terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wod_parse(source: *const c_char) -> *mut c_char {
+    match read(source) {
+        Some(source) => give(crate::parse_workout_json(&source)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Renders a workout in a format: "md", "text", "html" or "json" (null for "md").
+///
+/// Returns null if the workout can't be rendered, and then, if `error` isn't null,
+/// it's set to the message of the error (to be released with `wod_free` as well).
+///
+/// # Safety
+///
+/// `source` and `format` must be null or point to nul terminated strings, and
+/// `error` must be null or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn wod_render(
+    source: *const c_char,
+    format: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    let rendered = (|| {
+        let source = read(source).ok_or("The workout is not a valid UTF-8 string")?;
+        let format: Format = match read(format) {
+            Some(format) => format.parse()?,
+            None => Format::default(),
+        };
+        let workout = create_workout(&source, None, None).map_err(|e| e.to_string())?;
+        Ok::<_, String>(format.renderer().render(&workout))
+    })();
+    match rendered {
+        Ok(rendered) => give(rendered),
+        Err(e) => {
+            if !error.is_null() {
+                *error = give(e);
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Releases a string returned by `wod_parse` or `wod_render`, null is ignored.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library, not released before.
+#[no_mangle]
+pub unsafe extern "C" fn wod_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn take(s: *mut c_char) -> String {
+        assert!(!s.is_null());
+        let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { wod_free(s) };
+        owned
+    }
+
+    #[test]
+    fn test_wod_parse() {
+        let source = CString::new("ft 21-15-9 pull up, thruster @43/30kg").unwrap();
+        let json = take(unsafe { wod_parse(source.as_ptr()) });
+        assert!(json.starts_with("{\"workout\":"));
+        assert!(unsafe { wod_parse(ptr::null()) }.is_null());
+    }
+
+    #[test]
+    fn test_wod_render() {
+        let source = CString::new("ft 21-15-9 pull up, thruster @43/30kg").unwrap();
+        let format = CString::new("text").unwrap();
+        let text = take(unsafe { wod_render(source.as_ptr(), format.as_ptr(), ptr::null_mut()) });
+        assert!(text.ends_with("- Thruster @ 43/30kg"));
+
+        let source = CString::new("ft 21-15-9 pulup").unwrap();
+        let mut error = ptr::null_mut();
+        assert!(unsafe { wod_render(source.as_ptr(), ptr::null(), &mut error) }.is_null());
+        assert!(take(error).contains("pulup"));
+    }
+}
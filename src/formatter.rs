@@ -0,0 +1,194 @@
+//! Formatter of wod files, rewrites every workout in its canonical shorthand (see
+//! [`Workout::to_shorthand`](crate::Workout::to_shorthand)), see `wod fmt`:
+//!
+//! - The spacing is normalized and the movements written with their canonical name,
+//!   "du" is "double under".
+//! - The parts of a class get their labels, "A) ...; B) ...".
+//! - The comments and the name of a workout lose the spaces around them, and the
+//!   empty sections at the end are removed, "ft 21 pull up||" is "ft 21 pull up".
+//! - The directives go at the top of the file, in the same order.
+//! - The day separators are written as `---`.
+//! - Consecutive empty lines are merged.
+
+use crate::error::WodError;
+use crate::parts;
+use crate::workout::create_workout;
+use crate::{
+    is_day_separator, is_wodfile_comment, parse_wodfile_line, wodfile_directive, WODFILE_DIRECTIVES,
+};
+
+/// The canonical form of an entry of a wod file, a workout or the parts of a class.
+///
+/// # Examples
+///
+/// ```
+/// use wod::formatter::format_entry;
+///
+/// assert_eq!(
+///     format_entry("ft  21-15-9 pull up,thruster @ 43/30kg").unwrap(),
+///     "ft 21-15-9 pull up, thruster @43/30kg"
+/// );
+/// assert_eq!(
+///     format_entry("wl 5x3 back squat;amrap-12 10 du").unwrap(),
+///     "A) wl 5x3 back squat; B) amrap-12 10 double under"
+/// );
+/// assert!(format_entry("ft 21 pulup").is_err());
+/// ```
+pub fn format_entry(entry: &str) -> Result<String, WodError> {
+    let Some(parts) = parts::split(entry) else {
+        return Ok(create_workout(entry.trim(), None, None)?.to_shorthand());
+    };
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let workout = create_workout(part.workout, None, None)?;
+            Ok(format!("{}) {}", part.label, workout.to_shorthand()))
+        })
+        .collect::<Result<Vec<_>, WodError>>()?;
+    Ok(parts.join("; "))
+}
+
+/// The canonical form of a line with a workout, `workout|comments|name`.
+fn format_line(line: &str) -> Result<String, WodError> {
+    let (workout, comments, name) = parse_wodfile_line(line)?;
+    let mut formatted = format_entry(workout)?;
+    let section = |s: Option<String>| s.map(|s| s.trim().to_string()).unwrap_or_default();
+    let (comments, name) = (section(comments), section(name));
+    if !comments.is_empty() || !name.is_empty() {
+        formatted.push('|');
+        formatted.push_str(&comments);
+    }
+    if !name.is_empty() {
+        formatted.push('|');
+        formatted.push_str(&name);
+    }
+    Ok(formatted)
+}
+
+/// Formats the lines of a day of a wod file, all of them if it has no day separators.
+fn format_day(lines: &[(usize, &str)]) -> Result<Vec<String>, WodError> {
+    let mut directives = Vec::new();
+    let mut formatted: Vec<String> = Vec::new();
+    for (number, line) in lines {
+        let line = line.trim();
+        if is_wodfile_comment(line) {
+            let directive = WODFILE_DIRECTIVES.iter().find_map(|name| {
+                wodfile_directive(std::iter::once(line), name).map(|value| (*name, value))
+            });
+            match directive {
+                Some(directive) => directives.push(directive),
+                None => formatted.push(line.to_string()),
+            }
+        } else if line.is_empty() {
+            if formatted.last().is_some_and(|last| !last.is_empty()) {
+                formatted.push(String::new());
+            }
+        } else {
+            let line = format_line(line)
+                .map_err(|e| WodError::WodFile(format!("line {}: {}", number, e)))?;
+            formatted.push(line);
+        }
+    }
+    while formatted.last().is_some_and(|last| last.is_empty()) {
+        formatted.pop();
+    }
+    directives.sort_by_key(|(name, _)| WODFILE_DIRECTIVES.iter().position(|d| d == name));
+    let mut day: Vec<String> = directives
+        .into_iter()
+        .map(|(name, value)| format!("# {}: {}", name, value))
+        .collect();
+    day.extend(formatted);
+    Ok(day)
+}
+
+/// Formats the content of a wod file, see the [module documentation](self).
+///
+/// The directives go at the top of their day when the file has day separators
+/// (`---`, see [`run_split`](crate::run_split)), which are written as `---`.
+///
+/// # Examples
+///
+/// ```
+/// use wod::formatter::format_wodfile;
+///
+/// let content = "# Week 1\nft 21 pull up , 15 du ||Monday \n\n\n#level:rx\n";
+/// assert_eq!(
+///     format_wodfile(content).unwrap(),
+///     "# level: rx\n# Week 1\nft 21 pull up, 15 double under||Monday\n"
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns the error of the first line that can't be parsed, along with its number.
+pub fn format_wodfile(content: &str) -> Result<String, WodError> {
+    let mut days = vec![Vec::new()];
+    for (i, line) in content.lines().enumerate() {
+        if is_day_separator(line) {
+            days.push(Vec::new());
+        } else if let Some(day) = days.last_mut() {
+            day.push((i + 1, line));
+        }
+    }
+    let mut formatted = String::new();
+    for (i, day) in days.iter().enumerate() {
+        if i > 0 {
+            formatted.push_str("---\n");
+        }
+        for line in format_day(day)? {
+            formatted.push_str(&line);
+            formatted.push('\n');
+        }
+    }
+    Ok(formatted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line() {
+        assert_eq!(
+            format_line("wl 5x5  back squat @ 75%").unwrap(),
+            "wl 5x5 back squat @75%"
+        );
+        assert_eq!(
+            format_line("ft 21 pull up| T.C. 10' |").unwrap(),
+            "ft 21 pull up|T.C. 10'"
+        );
+        assert_eq!(
+            format_line("ft 21 pull up||Fran").unwrap(),
+            "ft 21 pull up||Fran"
+        );
+        assert!(format_line("ft 21 pull up|a|b|c").is_err());
+    }
+
+    #[test]
+    fn test_format_wodfile() {
+        let content = "\n# section: competition\n\nA) wl 5x3 snatch ; b) emom-10 5 c2b\n\n\n# level: rx\n# Metcon\nwl [1rm snatch;1rm clean]\n\n";
+        let formatted = format_wodfile(content).unwrap();
+        assert_eq!(
+            formatted,
+            "# section: competition\n# level: rx\nA) wl 5x3 snatch; B) emom-10 5 chest to bar\n\n# Metcon\nwl [1rm snatch; 1rm clean]\n"
+        );
+        // Formatting is idempotent
+        assert_eq!(format_wodfile(&formatted).unwrap(), formatted);
+        assert_eq!(format_wodfile("\n\n").unwrap(), "");
+    }
+
+    #[test]
+    fn test_format_wodfile_days() {
+        let content = "# Week 1\nwl 5x5 back squat\n# level: rx\n-----\n\nft 21 pull up\n---\n---\nft 10  burpee\n";
+        assert_eq!(
+            format_wodfile(content).unwrap(),
+            "# level: rx\n# Week 1\nwl 5x5 back squat\n---\nft 21 pull up\n---\n---\nft 10 burpee\n"
+        );
+    }
+
+    #[test]
+    fn test_format_wodfile_error() {
+        let err = format_wodfile("# Monday\nft 21 pull up\nft 10 pulup").unwrap_err();
+        assert!(err.to_string().starts_with("Invalid wodfile: line 3: "));
+    }
+}
@@ -0,0 +1,440 @@
+//! How often the movements and their patterns (see [`Pattern`]) are programmed, with
+//! recommendations for the ones left out for too long, "no olympic lifting in 16
+//! days", or repeated too often, "Double Under 5× this week", see `wod stats --frequency`.
+//!
+//! The windows are set in the `[frequency]` table of the configuration:
+//!
+//! ```toml
+//! [frequency]
+//! window = 14
+//! max_per_week = 3
+//!
+//! [frequency.windows]
+//! "olympic lifting" = 10
+//! "double under" = 7
+//!
+//! [frequency.limits]
+//! "double under" = 4
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::WodError;
+use crate::movement::{Movement, Pattern};
+use crate::workout::Workout;
+
+/// Days of the week the limits of [`Frequency`] are counted in, the last 7 days.
+const WEEK: i64 = 7;
+
+/// How often the movements are expected to be programmed, the `[frequency]` table
+/// of the configuration.
+///
+/// # Examples
+///
+/// ```
+/// use wod::frequency::Frequency;
+///
+/// let frequency: Frequency = toml::from_str("window = 10\n[limits]\n\"double under\" = 4").unwrap();
+/// assert_eq!(frequency.window, 10);
+/// assert_eq!(frequency.max_per_week, 3);
+/// assert!(frequency.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Frequency {
+    /// Days a pattern can go without being programmed before it's flagged.
+    pub window: i64,
+    /// Times a movement can be programmed in the last 7 days before it's flagged.
+    pub max_per_week: usize,
+    /// Days for the patterns or movements with their own window, by name, i.e.
+    /// `"olympic lifting" = 10`. The movements are only flagged when they have one.
+    pub windows: BTreeMap<String, i64>,
+    /// Times in the last 7 days for the movements with their own limit, by name, i.e.
+    /// `"double under" = 4`.
+    pub limits: BTreeMap<String, usize>,
+}
+
+impl Default for Frequency {
+    fn default() -> Self {
+        Frequency {
+            window: 14,
+            max_per_week: 3,
+            windows: BTreeMap::new(),
+            limits: BTreeMap::new(),
+        }
+    }
+}
+
+impl Frequency {
+    /// Checks the names of the windows and limits, patterns or movements.
+    pub fn validate(&self) -> Result<(), WodError> {
+        for name in self.windows.keys() {
+            if name.parse::<Pattern>().is_err() && name.parse::<Movement>().is_err() {
+                return Err(WodError::Invalid(format!(
+                    "Invalid frequency window: `{}`, it's not a pattern or a movement",
+                    name
+                )));
+            }
+        }
+        for name in self.limits.keys() {
+            if name.parse::<Movement>().is_err() {
+                return Err(WodError::Invalid(format!(
+                    "Invalid frequency limit: `{}`, it's not a movement",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The window of a pattern.
+    fn pattern_window(&self, pattern: Pattern) -> i64 {
+        self.windows
+            .get(&pattern.to_string())
+            .copied()
+            .unwrap_or(self.window)
+    }
+
+    /// The window of a movement, if it has one, by any of its names.
+    fn movement_window(&self, movement: &Movement) -> Option<i64> {
+        movement
+            .aliases()
+            .find_map(|alias| self.windows.get(alias).copied())
+    }
+
+    /// The limit of a movement in the last 7 days.
+    fn movement_limit(&self, movement: &Movement) -> usize {
+        movement
+            .aliases()
+            .find_map(|alias| self.limits.get(alias).copied())
+            .unwrap_or(self.max_per_week)
+    }
+}
+
+/// Formats of the report.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReportFormat {
+    #[default]
+    Markdown,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(format!(
+                "Invalid format: `{}`, expected one of: markdown, json",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportFormat::Markdown => write!(f, "markdown"),
+            ReportFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// How often a pattern or a movement was programmed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Entry {
+    /// The pattern, i.e. "olympic lifting", or the movement, i.e. "Double Under".
+    pub name: String,
+    /// The last day it was programmed, "YYYY-MM-DD".
+    pub last: Option<String>,
+    /// Days since the last time it was programmed.
+    pub days_ago: Option<i64>,
+    /// Workouts it was programmed in, in the last 7 days.
+    pub this_week: usize,
+    /// Workouts it was programmed in, in the whole period.
+    pub total: usize,
+}
+
+/// The report of the frequency of the workouts of a period, see [`report`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// First day with workouts, "YYYY-MM-DD".
+    pub from: Option<String>,
+    /// Day of the report, the days after it are left out.
+    pub to: String,
+    /// Every pattern, in the order of [`Pattern::ALL`].
+    pub patterns: Vec<Entry>,
+    /// The movements programmed, the most frequent first.
+    pub movements: Vec<Entry>,
+    /// The patterns or movements left out for too long, or repeated too often.
+    pub recommendations: Vec<String>,
+}
+
+/// The last day and the number of workouts of the days that match.
+fn entry(
+    name: String,
+    days: &[&(NaiveDate, Vec<Workout>)],
+    today: NaiveDate,
+    matches: impl Fn(&Workout) -> bool,
+) -> Entry {
+    let mut entry = Entry {
+        name,
+        last: None,
+        days_ago: None,
+        this_week: 0,
+        total: 0,
+    };
+    let mut last = None;
+    for (date, workouts) in days {
+        let count = workouts.iter().filter(|w| matches(w)).count();
+        if count == 0 {
+            continue;
+        }
+        entry.total += count;
+        if (today - *date).num_days() < WEEK {
+            entry.this_week += count;
+        }
+        last = last.max(Some(*date));
+    }
+    entry.last = last.map(|date| date.to_string());
+    entry.days_ago = last.map(|date| (today - date).num_days());
+    entry
+}
+
+/// Builds the report of the workouts of some days, as of `today`.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wod::create_workout;
+/// use wod::frequency::{report, Frequency};
+///
+/// let isabel = create_workout("ft 30 snatch @61/43kg", None, None).unwrap();
+/// let days = [(NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(), vec![isabel])];
+/// let today = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+/// let report = report(&days, today, &Frequency::default());
+/// assert_eq!(report.patterns[4].days_ago, Some(20));
+/// assert!(report
+///     .recommendations
+///     .contains(&"No olympic lifting in 20 days".to_string()));
+/// ```
+pub fn report(days: &[(NaiveDate, Vec<Workout>)], today: NaiveDate, rules: &Frequency) -> Report {
+    let days: Vec<&(NaiveDate, Vec<Workout>)> =
+        days.iter().filter(|(date, _)| *date <= today).collect();
+    let from = days.iter().map(|(date, _)| *date).min();
+    let span = from.map_or(0, |from| (today - from).num_days());
+    let mut recommendations = Vec::new();
+
+    let patterns: Vec<Entry> = Pattern::ALL
+        .iter()
+        .map(|pattern| {
+            let entry = entry(pattern.to_string(), &days, today, |w| {
+                w.movements().any(|m| m.pattern() == Some(*pattern))
+            });
+            let window = rules.pattern_window(*pattern);
+            match entry.days_ago {
+                Some(days_ago) if days_ago > window => {
+                    recommendations.push(format!("No {} in {} days", pattern, days_ago))
+                }
+                None if span >= window => {
+                    recommendations.push(format!("No {} in {} days", pattern, span))
+                }
+                _ => {}
+            }
+            entry
+        })
+        .collect();
+
+    let mut programmed: Vec<Movement> = Vec::new();
+    for movement in days.iter().flat_map(|(_, w)| w).flat_map(|w| w.movements()) {
+        if *movement != Movement::Rest && !programmed.contains(movement) {
+            programmed.push(movement.clone());
+        }
+    }
+    let mut movements: Vec<Entry> = programmed
+        .iter()
+        .map(|movement| {
+            entry(movement.name().to_string(), &days, today, |w| {
+                w.movements().any(|m| m == movement)
+            })
+        })
+        .collect();
+    for (movement, entry) in programmed.iter().zip(&movements) {
+        if entry.this_week > rules.movement_limit(movement) {
+            recommendations.push(format!("{} {}× this week", entry.name, entry.this_week));
+        }
+        match (rules.movement_window(movement), entry.days_ago) {
+            (Some(window), Some(days_ago)) if days_ago > window => {
+                recommendations.push(format!("No {} in {} days", entry.name, days_ago))
+            }
+            _ => {}
+        }
+    }
+    // The movements with a window that weren't programmed at all
+    for name in rules.windows.keys() {
+        let Ok(movement) = name.parse::<Movement>() else {
+            continue;
+        };
+        if !programmed.contains(&movement) && span >= rules.windows[name] {
+            recommendations.push(format!("No {} in {} days", movement.name(), span));
+        }
+    }
+    movements.sort_by(|a, b| b.total.cmp(&a.total).then(a.name.cmp(&b.name)));
+
+    Report {
+        from: from.map(|date| date.to_string()),
+        to: today.to_string(),
+        patterns,
+        movements,
+        recommendations,
+    }
+}
+
+/// A row of a table of the markdown report.
+fn row(entry: &Entry) -> String {
+    format!(
+        "| {} | {} | {} | {} | {} |\n",
+        entry.name,
+        entry.last.as_deref().unwrap_or("-"),
+        entry.days_ago.map_or("-".to_string(), |d| d.to_string()),
+        entry.this_week,
+        entry.total
+    )
+}
+
+impl Report {
+    /// The report as markdown, a table for the patterns and another one for the
+    /// movements, followed by the recommendations.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from("# Movement frequency\n\n");
+        match &self.from {
+            Some(from) => markdown.push_str(&format!("From {} to {}.\n\n", from, self.to)),
+            None => markdown.push_str(&format!("No workouts up to {}.\n\n", self.to)),
+        }
+        let header = "| Last programmed | Days ago | This week | Total |\n\
+                      | --- | --- | --- | --- | --- |\n";
+        markdown.push_str(&format!("| Pattern {}", header));
+        for entry in &self.patterns {
+            markdown.push_str(&row(entry));
+        }
+        if !self.movements.is_empty() {
+            markdown.push_str(&format!("\n| Movement {}", header));
+            for entry in &self.movements {
+                markdown.push_str(&row(entry));
+            }
+        }
+        markdown.push_str("\n## Recommendations\n\n");
+        if self.recommendations.is_empty() {
+            markdown.push_str("Nothing to change.\n");
+        }
+        for recommendation in &self.recommendations {
+            markdown.push_str(&format!("- {}\n", recommendation));
+        }
+        markdown
+    }
+
+    /// The report in a format.
+    pub fn format(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Markdown => self.to_markdown(),
+            ReportFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, d).unwrap()
+    }
+
+    fn days(workouts: &[(u32, &str)]) -> Vec<(NaiveDate, Vec<Workout>)> {
+        workouts
+            .iter()
+            .map(|(d, w)| (day(*d), vec![create_workout(w, None, None).unwrap()]))
+            .collect()
+    }
+
+    #[test]
+    fn test_report() {
+        let days = days(&[
+            (1, "wl 5x3 snatch @70%"),
+            (17, "ft 50 du, 21 pull up"),
+            (18, "amrap-10 30 du, 10 burpee"),
+            (19, "emom-10 20 du"),
+            (20, "ft 50 du, 30 box jump"),
+            // After the day of the report
+            (22, "wl 5x3 clean @70%"),
+        ]);
+        let report = report(&days, day(21), &Frequency::default());
+        assert_eq!(report.from.as_deref(), Some("2025-03-01"));
+        assert_eq!(report.movements[0].name, "Double Under");
+        assert_eq!(report.movements[0].this_week, 4);
+        let olympic = report
+            .patterns
+            .iter()
+            .find(|e| e.name == "olympic lifting")
+            .unwrap();
+        assert_eq!(olympic.days_ago, Some(20));
+        assert_eq!(
+            report.recommendations,
+            vec![
+                "No squat in 20 days",
+                "No hinge in 20 days",
+                "No press in 20 days",
+                "No olympic lifting in 20 days",
+                "No inverted in 20 days",
+                "No midline in 20 days",
+                "No monostructural in 20 days",
+                "Double Under 4× this week",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report_windows() {
+        let days = days(&[(15, "wl 5x3 snatch @70%"), (20, "ft 50 du")]);
+        let rules: Frequency = toml::from_str(
+            "window = 30\nmax_per_week = 5\n[windows]\n\"olympic lifting\" = 3\n\"ohs\" = 5",
+        )
+        .unwrap();
+        assert!(rules.validate().is_ok());
+        let report = report(&days, day(21), &rules);
+        assert_eq!(
+            report.recommendations,
+            vec![
+                "No olympic lifting in 6 days",
+                "No Overhead Squat in 6 days"
+            ]
+        );
+
+        let rules: Frequency = toml::from_str("[limits]\nsquat = 2").unwrap();
+        assert!(rules.validate().is_err());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let report = report(&days(&[(20, "ft 50 du")]), day(21), &Frequency::default());
+        let markdown = report.to_markdown();
+        assert!(markdown.starts_with("# Movement frequency\n\nFrom 2025-03-20 to 2025-03-21.\n\n"));
+        assert!(markdown.contains("| Double Under | 2025-03-20 | 1 | 1 | 1 |\n"));
+        assert!(markdown.contains("| squat | - | - | 0 | 0 |\n"));
+        assert!(markdown.ends_with("## Recommendations\n\nNothing to change.\n"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.format(ReportFormat::Json)).unwrap();
+        assert_eq!(json["movements"][0]["last"], "2025-03-20");
+    }
+}
@@ -0,0 +1,492 @@
+//! Helpers to read the metadata block at the top of the generated markdown files.
+//!
+//! The files generated by `wod` start with a YAML front matter block delimited
+//! by `---` lines, as expected by Hugo:
+//!
+//! ```text
+//! ---
+//! title: "2025-03-21"
+//! date: 2025-03-21
+//! draft: false
+//! ---
+//! ```
+//!
+//! The block is written from a template, the one of the [`Profile`] of the configuration
+//! ([`DEFAULT_TEMPLATE`] for Hugo) unless it points to another one with
+//! `front_matter_template`. Zola writes the front matter in TOML instead, delimited by
+//! `+++` lines, the fields of both kinds of blocks can be read and written with the
+//! functions of this module. In TOML the fields of a table are given as `table.key`,
+//! i.e. `taxonomies.tags`.
+
+use std::fmt;
+
+use crate::error::WodError;
+
+/// Template of the beginning of the files, with the placeholders expanded by [`expand`].
+pub const DEFAULT_TEMPLATE: &str = r#"---
+title: "{{title}}"
+date: {{date}}
+draft: {{draft}}
+---
+
+Workout for the day, {{title}}.
+"#;
+
+const ZOLA_TEMPLATE: &str = r#"+++
+title = "{{title}}"
+date = {{date}}
+draft = {{draft}}
++++
+
+Workout for the day, {{title}}.
+"#;
+
+const JEKYLL_TEMPLATE: &str = r#"---
+layout: post
+title: "{{title}}"
+date: {{date}}
+published: {{published}}
+---
+
+Workout for the day, {{title}}.
+"#;
+
+/// The static site generator the files are written for, the `profile` of the
+/// configuration. It decides the template of the front matter and the keys of the
+/// fields added to it.
+///
+/// # Examples
+///
+/// ```
+/// use wod::front_matter::Profile;
+///
+/// assert_eq!(Profile::Zola.key("lastmod"), "updated");
+/// assert_eq!(Profile::Jekyll.key("url"), "permalink");
+/// assert_eq!(Profile::Hugo.key("tags"), "tags");
+/// assert_eq!(Profile::detect("+++\ntitle = \"wod\"\n+++\n"), Profile::Zola);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Profile {
+    #[default]
+    Hugo,
+    Zola,
+    Jekyll,
+}
+
+impl Profile {
+    /// The profile of a file already written, from its front matter: Zola writes it
+    /// in TOML, and Jekyll hides the drafts with `published` instead of `draft`.
+    pub fn detect(content: &str) -> Profile {
+        let lines: Vec<&str> = content.lines().collect();
+        match Syntax::of(&lines) {
+            Some(Syntax::Toml) => Profile::Zola,
+            _ if get_field(content, "published").is_some() => Profile::Jekyll,
+            _ => Profile::Hugo,
+        }
+    }
+
+    /// The template of the front matter of the files.
+    pub fn template(self) -> &'static str {
+        match self {
+            Profile::Hugo => DEFAULT_TEMPLATE,
+            Profile::Zola => ZOLA_TEMPLATE,
+            Profile::Jekyll => JEKYLL_TEMPLATE,
+        }
+    }
+
+    /// The key of a field written by `wod` (named as in Hugo) in the front matter
+    /// of the profile. Zola keeps the tags in its `taxonomies` and the fields it
+    /// doesn't know in `extra`.
+    pub fn key(self, field: &'static str) -> &'static str {
+        match (self, field) {
+            (Profile::Zola, "tags") => "taxonomies.tags",
+            (Profile::Zola, "level") => "extra.level",
+            (Profile::Zola, "lastmod") => "updated",
+            (Profile::Zola, "url") => "path",
+            (Profile::Jekyll, "lastmod") => "last_modified_at",
+            (Profile::Jekyll, "url") => "permalink",
+            _ => field,
+        }
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Profile::Hugo => write!(f, "hugo"),
+            Profile::Zola => write!(f, "zola"),
+            Profile::Jekyll => write!(f, "jekyll"),
+        }
+    }
+}
+
+/// The language of a front matter block, given by its delimiter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Syntax {
+    Yaml,
+    Toml,
+}
+
+impl Syntax {
+    /// The syntax of the front matter the lines start with, if any.
+    fn of(lines: &[&str]) -> Option<Syntax> {
+        match lines.first()?.trim() {
+            "---" => Some(Syntax::Yaml),
+            "+++" => Some(Syntax::Toml),
+            _ => None,
+        }
+    }
+
+    fn delimiter(self) -> &'static str {
+        match self {
+            Syntax::Yaml => "---",
+            Syntax::Toml => "+++",
+        }
+    }
+
+    /// Splits a line in the key and the value.
+    fn split(self, line: &str) -> Option<(&str, &str)> {
+        let (key, value) = match self {
+            Syntax::Yaml => line.split_once(':')?,
+            Syntax::Toml => line.split_once('=')?,
+        };
+        Some((key.trim(), value))
+    }
+
+    /// The line of a field.
+    fn field(self, key: &str, value: &str) -> String {
+        match self {
+            Syntax::Yaml => format!("{}: {}\n", key, value),
+            Syntax::Toml => format!("{} = {}\n", key, value),
+        }
+    }
+
+    /// The table and the name of a key, only TOML has tables.
+    fn table_key(self, key: &str) -> (Option<&str>, &str) {
+        match (self, key.split_once('.')) {
+            (Syntax::Toml, Some((table, name))) => (Some(table), name),
+            _ => (None, key),
+        }
+    }
+
+    /// The name of the table a line opens, i.e. `[taxonomies]`.
+    fn table_header(self, line: &str) -> Option<&str> {
+        match self {
+            Syntax::Yaml => None,
+            Syntax::Toml => line.trim().strip_prefix('[')?.strip_suffix(']'),
+        }
+    }
+}
+
+/// The index of the line closing the front matter block, or `None` if the content
+/// doesn't start with one. The separator of the workouts is also a `---` line, but
+/// it's followed by an empty line.
+fn closing_line(lines: &[&str]) -> Option<usize> {
+    let syntax = Syntax::of(lines)?;
+    if lines.get(1)?.trim().is_empty() {
+        return None;
+    }
+    lines
+        .iter()
+        .skip(1)
+        .position(|line| line.trim() == syntax.delimiter())
+        .map(|i| i + 1)
+}
+
+/// The index of the line of a field among the front matter lines.
+fn field_index(lines: &[&str], syntax: Syntax, key: &str) -> Option<usize> {
+    let (table, name) = syntax.table_key(key);
+    let mut current = None;
+    lines.iter().position(|line| {
+        if let Some(header) = syntax.table_header(line) {
+            current = Some(header);
+            return false;
+        }
+        current == table && syntax.split(line).is_some_and(|(k, _)| k == name)
+    })
+}
+
+/// Returns the front matter lines (without the delimiters), or `None` if the
+/// content doesn't start with a front matter block.
+fn front_matter_lines(content: &str) -> Option<Vec<&str>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let end = closing_line(&lines)?;
+    Some(lines[1..end].to_vec())
+}
+
+/// Gets the value of a field from the front matter, with surrounding quotes removed.
+///
+/// # Examples
+///
+/// ```
+/// use wod::front_matter::get_field;
+///
+/// let content = "---\ntitle: \"2025-03-21\"\ndate: 2025-03-21\ndraft: false\n---\n";
+/// assert_eq!(get_field(content, "title"), Some("2025-03-21".to_string()));
+/// assert_eq!(get_field(content, "date"), Some("2025-03-21".to_string()));
+/// assert_eq!(get_field(content, "tags"), None);
+///
+/// let content = "+++\ntitle = \"wod\"\n[extra]\nlevel = \"rx\"\n+++\n";
+/// assert_eq!(get_field(content, "extra.level"), Some("rx".to_string()));
+/// ```
+pub fn get_field(content: &str, key: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let syntax = Syntax::of(&lines)?;
+    let end = closing_line(&lines)?;
+    let index = field_index(&lines[1..end], syntax, key)?;
+    let (_, value) = syntax.split(lines[index + 1])?;
+    Some(value.trim().trim_matches('"').to_string())
+}
+
+/// Gets the values of a list field from the front matter, written inline as `["a", "b"]`.
+///
+/// # Examples
+///
+/// ```
+/// use wod::front_matter::get_list;
+///
+/// let content = "---\ntags: [\"emom\", \"snatch\"]\n---\n";
+/// assert_eq!(get_list(content, "tags"), vec!["emom", "snatch"]);
+/// assert!(get_list(content, "categories").is_empty());
+/// ```
+pub fn get_list(content: &str, key: &str) -> Vec<String> {
+    let Some(value) = get_field(content, key) else {
+        return Vec::new();
+    };
+    value
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|item| item.trim().trim_matches('"').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Sets the value of a field in the front matter, replacing the line of the field
+/// or adding it at the end of the block. The content is returned unchanged if it
+/// doesn't start with a front matter block.
+///
+/// # Examples
+///
+/// ```
+/// use wod::front_matter::set_field;
+///
+/// let content = "---\ntitle: \"wod\"\n---\n\nWorkout\n";
+/// let content = set_field(content, "draft", "true");
+/// assert_eq!(content, "---\ntitle: \"wod\"\ndraft: true\n---\n\nWorkout\n");
+/// assert_eq!(
+///     set_field(&content, "draft", "false"),
+///     "---\ntitle: \"wod\"\ndraft: false\n---\n\nWorkout\n"
+/// );
+/// ```
+pub fn set_field(content: &str, key: &str, value: &str) -> String {
+    let mut lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let (Some(syntax), Some(end)) = (Syntax::of(&lines), closing_line(&lines)) else {
+        return content.to_string();
+    };
+
+    let (table, name) = syntax.table_key(key);
+    let field = syntax.field(name, value);
+    if let Some(i) = field_index(&lines[1..end], syntax, key) {
+        lines[i + 1] = &field;
+        return lines.concat();
+    }
+
+    // The fields of a table go after its last field, and the ones without a table
+    // before the first table
+    let headers: Vec<(usize, &str)> = (1..end)
+        .filter_map(|i| Some((i, syntax.table_header(lines[i])?)))
+        .collect();
+    let next_header = |after: usize| {
+        headers
+            .iter()
+            .find(|(i, _)| *i > after)
+            .map_or(end, |(i, _)| *i)
+    };
+    let header = table.map(|table| format!("[{}]\n", table));
+    match table {
+        None => lines.insert(next_header(0), &field),
+        Some(table) => match headers.iter().find(|(_, name)| *name == table) {
+            Some((i, _)) => lines.insert(next_header(*i), &field),
+            None => {
+                lines.insert(end, &field);
+                lines.insert(end, header.as_deref().unwrap_or_default());
+            }
+        },
+    }
+    lines.concat()
+}
+
+/// Removes a field from the front matter, the content is returned unchanged if
+/// it doesn't have it.
+///
+/// # Examples
+///
+/// ```
+/// use wod::front_matter::remove_field;
+///
+/// let content = "---\ntitle: \"wod\"\nlastmod: 2025-03-22\n---\n";
+/// assert_eq!(remove_field(content, "lastmod"), "---\ntitle: \"wod\"\n---\n");
+/// ```
+pub fn remove_field(content: &str, key: &str) -> String {
+    let mut lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let (Some(syntax), Some(end)) = (Syntax::of(&lines), closing_line(&lines)) else {
+        return content.to_string();
+    };
+    if let Some(i) = field_index(&lines[1..end], syntax, key) {
+        lines.remove(i + 1);
+    }
+    lines.concat()
+}
+
+/// Whether the content starts with a front matter block.
+pub fn has_front_matter(content: &str) -> bool {
+    front_matter_lines(content).is_some()
+}
+
+/// Formats a list of values to be written with [`set_field`], i.e. `["a", "b"]`.
+pub fn format_list(values: &[String]) -> String {
+    let quoted: Vec<String> = values.iter().map(|v| format!("\"{}\"", v)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+/// Expands the `{{name}}` placeholders of a front matter template.
+///
+/// # Arguments
+///
+/// * `template` - The template, i.e. the content of a Hugo archetype.
+/// * `values` - The value of each placeholder, by name.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The expanded template, or `WodError::Invalid` if
+///   it contains an unknown or unclosed placeholder.
+///
+/// # Examples
+///
+/// ```
+/// use wod::front_matter::expand;
+///
+/// let values = [("title", "2025-03-21"), ("languages", "en,es")];
+/// assert_eq!(
+///     expand("title: \"{{title}}\"\nlanguages: [{{languages}}]", &values).unwrap(),
+///     "title: \"2025-03-21\"\nlanguages: [en,es]"
+/// );
+/// assert!(expand("author: {{author}}", &values).is_err());
+/// ```
+pub fn expand(template: &str, values: &[(&str, &str)]) -> Result<String, WodError> {
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        expanded.push_str(&rest[..start]);
+        let end = rest[start..].find("}}").ok_or_else(|| {
+            WodError::Invalid(format!(
+                "Unclosed placeholder in front matter template: `{}`",
+                &rest[start..]
+            ))
+        })?;
+        let name = rest[start + 2..start + end].trim();
+        let value = values
+            .iter()
+            .find_map(|(key, value)| (*key == name).then_some(*value))
+            .ok_or_else(|| {
+                WodError::Invalid(format!(
+                    "Unknown placeholder in front matter template: `{{{{{}}}}}`, expected one of: {}",
+                    name,
+                    values.iter().map(|(key, _)| *key).collect::<Vec<_>>().join(", ")
+                ))
+            })?;
+        expanded.push_str(value);
+        rest = &rest[start + end + 2..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_front_matter_lines() {
+        let content = "---\ntitle: \"wod\"\ndate: 2025-03-21\n---\n\nWorkout for the day\n";
+        assert_eq!(
+            front_matter_lines(content),
+            Some(vec!["title: \"wod\"", "date: 2025-03-21"])
+        );
+        assert_eq!(front_matter_lines("no front matter"), None);
+        assert_eq!(front_matter_lines("---\nunterminated: true\n"), None);
+        assert_eq!(front_matter_lines("---\n\n**For Time**\n\n---\n"), None);
+    }
+
+    #[test]
+    fn test_expand() {
+        let values = [("title", "wod"), ("date", "2025-03-21"), ("draft", "false")];
+        assert_eq!(
+            expand(DEFAULT_TEMPLATE, &values).unwrap(),
+            "---\ntitle: \"wod\"\ndate: 2025-03-21\ndraft: false\n---\n\nWorkout for the day, wod.\n"
+        );
+        assert_eq!(expand("{{ date }}", &values).unwrap(), "2025-03-21");
+        assert_eq!(
+            expand("{{week}}", &values).unwrap_err().to_string(),
+            "Unknown placeholder in front matter template: `{{week}}`, expected one of: title, date, draft"
+        );
+        assert!(expand("title: {{title", &values).is_err());
+    }
+
+    #[test]
+    fn test_set_field() {
+        let content = "---\ntitle: \"wod\"\ntags: []\n---\n\n---\n\n**For Time**\n";
+        let tags = format_list(&["snatch".to_string(), "emom".to_string()]);
+        let content = set_field(content, "tags", &tags);
+        assert_eq!(
+            content,
+            "---\ntitle: \"wod\"\ntags: [\"snatch\", \"emom\"]\n---\n\n---\n\n**For Time**\n"
+        );
+        assert_eq!(get_list(&content, "tags"), vec!["snatch", "emom"]);
+        assert_eq!(
+            set_field("no front matter", "tags", "[]"),
+            "no front matter"
+        );
+        assert_eq!(
+            set_field("---\nunterminated", "tags", "[]"),
+            "---\nunterminated"
+        );
+    }
+
+    #[test]
+    fn test_toml() {
+        let content = "+++\ntitle = \"wod\"\ndraft = false\n+++\n\nIntro\n";
+        let content = set_field(content, "taxonomies.tags", "[\"snatch\"]");
+        let content = set_field(&content, "extra.level", "\"rx\"");
+        let content = set_field(&content, "updated", "2025-03-22");
+        let content = set_field(&content, "taxonomies.tags", "[\"snatch\", \"emom\"]");
+        assert_eq!(
+            content,
+            "+++\ntitle = \"wod\"\ndraft = false\nupdated = 2025-03-22\n\
+             [taxonomies]\ntags = [\"snatch\", \"emom\"]\n[extra]\nlevel = \"rx\"\n+++\n\nIntro\n"
+        );
+        assert_eq!(
+            get_list(&content, "taxonomies.tags"),
+            vec!["snatch", "emom"]
+        );
+        assert_eq!(get_field(&content, "level"), None);
+        assert_eq!(
+            remove_field(&content, "updated"),
+            content.replace("updated = 2025-03-22\n", "")
+        );
+        assert_eq!(Profile::detect(&content), Profile::Zola);
+    }
+
+    #[test]
+    fn test_get_field() {
+        let content = "---\ntitle: \"wod\"\ndate: 2025-03-21\n---\n";
+        assert_eq!(get_field(content, "title"), Some("wod".to_string()));
+        assert_eq!(get_field(content, "missing"), None);
+    }
+}
@@ -0,0 +1,424 @@
+//! Random workouts, for bots and websites that need a workout of the day.
+//!
+//! The workouts are built from the movements that fit the [`Constraints`]: the length
+//! decides the structure (a couplet for sprints, rounds or an EMOM in the middle, a
+//! chipper for the longer ones) and the stimulus which movements are preferred. The
+//! workout is written in the shorthand and parsed, so it's always a valid one.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::WodError;
+use crate::movement::{Equipment, Movement};
+use crate::workout::{create_workout, Workout};
+
+/// The kind of effort a workout is after.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Stimulus {
+    /// A bit of everything, the movements are taken from every modality in turns.
+    #[default]
+    Mixed,
+    /// Machines, running and jumping rope.
+    Cardio,
+    /// Bodyweight movements.
+    Gymnastics,
+    /// Movements with a load.
+    Weightlifting,
+}
+
+impl FromStr for Stimulus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mixed" => Ok(Stimulus::Mixed),
+            "cardio" => Ok(Stimulus::Cardio),
+            "gymnastics" => Ok(Stimulus::Gymnastics),
+            "weightlifting" => Ok(Stimulus::Weightlifting),
+            _ => Err(format!(
+                "Invalid stimulus: `{}`, expected one of: mixed, cardio, gymnastics, weightlifting",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Stimulus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stimulus::Mixed => write!(f, "mixed"),
+            Stimulus::Cardio => write!(f, "cardio"),
+            Stimulus::Gymnastics => write!(f, "gymnastics"),
+            Stimulus::Weightlifting => write!(f, "weightlifting"),
+        }
+    }
+}
+
+/// What the random workout has to fit in.
+///
+/// # Examples
+///
+/// ```
+/// use wod::generator::{random_workout, Constraints, Stimulus};
+/// use wod::movement::Equipment;
+///
+/// let constraints = Constraints {
+///     minutes: 20,
+///     equipment: Some(vec![Equipment::Barbell, Equipment::PullUpBar]),
+///     stimulus: Stimulus::Weightlifting,
+///     seed: Some(7),
+///     ..Default::default()
+/// };
+/// let workout = random_workout(&constraints).unwrap();
+/// assert!(workout
+///     .movements()
+///     .all(|m| matches!(m.equipment(), None | Some(Equipment::Barbell | Equipment::PullUpBar))));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraints {
+    /// Length of the workout in minutes, the time domain.
+    pub minutes: u32,
+    /// Equipment available, `None` for a fully equipped box. The bodyweight movements
+    /// are always available, `Some(vec![])` gives a bodyweight workout.
+    pub equipment: Option<Vec<Equipment>>,
+    /// Movements to choose from, `None` for every movement that fits a workout.
+    pub movements: Option<Vec<Movement>>,
+    /// The kind of effort the workout is after.
+    pub stimulus: Stimulus,
+    /// Seed of the random numbers, the same seed gives the same workout. `None` takes
+    /// one from the clock.
+    pub seed: Option<u64>,
+}
+
+impl Default for Constraints {
+    fn default() -> Self {
+        Constraints {
+            minutes: 12,
+            equipment: None,
+            movements: None,
+            stimulus: Stimulus::default(),
+            seed: None,
+        }
+    }
+}
+
+/// Small pseudo random generator (splitmix64), enough to pick movements.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default()
+        });
+        Rng(seed)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, `n` must be greater than 0.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// The modality of a movement, one of the stimulus other than mixed.
+pub(crate) fn modality(movement: &Movement) -> Stimulus {
+    match movement.equipment() {
+        Some(Equipment::Rower | Equipment::Bike | Equipment::SkiErg | Equipment::JumpRope) => {
+            Stimulus::Cardio
+        }
+        None if *movement == Movement::Run => Stimulus::Cardio,
+        Some(equipment) if load(equipment).is_some() => Stimulus::Weightlifting,
+        _ => Stimulus::Gymnastics,
+    }
+}
+
+/// The Rx load used for the movements done with a piece of equipment.
+pub(crate) fn load(equipment: Equipment) -> Option<&'static str> {
+    match equipment {
+        Equipment::Barbell => Some("43/30kg"),
+        Equipment::Dumbbell => Some("22/15kg"),
+        Equipment::Kettlebell => Some("24/16kg"),
+        Equipment::WallBall => Some("9/6kg"),
+        Equipment::Sandbag | Equipment::DBall => Some("45/30kg"),
+        _ => None,
+    }
+}
+
+/// Whether the movement fits a conditioning workout: the holds, carries and the
+/// accessories of the lifts don't.
+fn is_suitable(movement: &Movement) -> bool {
+    use Movement::*;
+
+    !matches!(
+        movement,
+        Rest | LSit
+            | HandstandHold
+            | HandstandWalk
+            | FarmersCarry
+            | DBallCarry
+            | DBallHold
+            | SledPush
+            | SledPull
+            | SledDrag
+            | TurkishGetUp
+            | BenchPress
+            | CleanPull
+            | CleanDeadlift
+            | SnatchPull
+            | SnatchDeadlift
+            | SnatchBalance
+            | MuscleSnatch
+    )
+}
+
+/// The repetitions of a line, scaled by `percent` from those of a round of a
+/// medium length workout.
+fn reps(movement: &Movement, percent: u32) -> String {
+    let scale = |reps: u32| (reps * percent / 100).max(1);
+    match movement {
+        m if m.is_machine() => format!("{}cal", scale(15)),
+        Movement::Run => format!("{}m", scale(4) * 100),
+        Movement::DoubleUnder => scale(40).to_string(),
+        Movement::RopeClimb | Movement::LeglessRopeClimb | Movement::WallWalk => {
+            scale(3).to_string()
+        }
+        Movement::MuscleUp | Movement::RingMuscleUp | Movement::BarMuscleUp => scale(5).to_string(),
+        m if modality(m) == Stimulus::Weightlifting => scale(10).to_string(),
+        _ => scale(12).to_string(),
+    }
+}
+
+/// A movement with its load if it's done with one, "thruster @43/30kg".
+fn with_load(movement: &Movement) -> String {
+    match movement.equipment().and_then(load) {
+        Some(load) => format!("{} @{}", movement.shorthand(), load),
+        None => movement.shorthand().to_string(),
+    }
+}
+
+/// Picks `count` movements from the pool, preferring the modality of the stimulus.
+fn pick(pool: &[Movement], count: usize, stimulus: Stimulus, rng: &mut Rng) -> Vec<Movement> {
+    let mut groups: Vec<Vec<Movement>> = [
+        Stimulus::Cardio,
+        Stimulus::Gymnastics,
+        Stimulus::Weightlifting,
+    ]
+    .into_iter()
+    .map(|kind| {
+        let mut group: Vec<Movement> = pool
+            .iter()
+            .filter(|m| modality(m) == kind)
+            .cloned()
+            .collect();
+        rng.shuffle(&mut group);
+        group
+    })
+    .collect();
+    rng.shuffle(&mut groups);
+    if stimulus != Stimulus::Mixed {
+        // The preferred modality goes first, the rest fill the missing movements
+        groups.sort_by_key(|group| group.first().map(modality) != Some(stimulus));
+        let all: Vec<Movement> = groups.concat();
+        return all.into_iter().take(count).collect();
+    }
+
+    let mut picked = Vec::new();
+    while picked.len() < count && groups.iter().any(|g| !g.is_empty()) {
+        for group in groups.iter_mut() {
+            if let Some(movement) = group.pop() {
+                if picked.len() < count {
+                    picked.push(movement);
+                }
+            }
+        }
+    }
+    picked
+}
+
+/// Generates a random workout that fits the constraints.
+///
+/// # Examples
+///
+/// ```
+/// use wod::generator::{random_workout, Constraints};
+///
+/// let constraints = Constraints { seed: Some(42), ..Default::default() };
+/// let workout = random_workout(&constraints).unwrap();
+/// // The same seed gives the same workout
+/// assert_eq!(workout, random_workout(&constraints).unwrap());
+/// ```
+///
+/// # Errors
+///
+/// Returns `WodError::Invalid` if no movement fits the constraints, or the length
+/// is 0 minutes.
+pub fn random_workout(constraints: &Constraints) -> Result<Workout, WodError> {
+    if constraints.minutes == 0 {
+        return Err("The workout must last at least a minute".into());
+    }
+    let pool: Vec<Movement> = match &constraints.movements {
+        Some(movements) => movements.clone(),
+        None => Movement::all().filter(is_suitable).collect(),
+    };
+    let pool: Vec<Movement> = pool
+        .into_iter()
+        .filter(|m| match (&constraints.equipment, m.equipment()) {
+            (Some(available), Some(needed)) => available.contains(&needed),
+            _ => true,
+        })
+        .collect();
+    if pool.is_empty() {
+        return Err("No movement fits the constraints of the workout".into());
+    }
+
+    let mut rng = Rng::new(constraints.seed);
+    let minutes = constraints.minutes;
+    let emom = rng.below(2) == 0;
+    let shorthand = match minutes {
+        // A sprint, a couplet of 21-15-9 without machines nor runs, or short rounds
+        0..=10 => {
+            let couplet: Vec<Movement> = pool
+                .iter()
+                .filter(|m| modality(m) != Stimulus::Cardio)
+                .cloned()
+                .collect();
+            let movements = pick(&couplet, 2, constraints.stimulus, &mut rng);
+            if constraints.stimulus != Stimulus::Cardio && movements.len() == 2 {
+                let lines: Vec<String> = movements.iter().map(with_load).collect();
+                format!("ft 21-15-9 {}", lines.join(", "))
+            } else {
+                let movements = pick(&pool, 2, constraints.stimulus, &mut rng);
+                rounds_for_time(3, &movements, 50)
+            }
+        }
+        // Every line takes a minute of the EMOM
+        11..=20 if emom => {
+            let movements = pick(&pool, 3, constraints.stimulus, &mut rng);
+            let minutes = minutes - minutes % movements.len() as u32;
+            let lines: Vec<String> = movements.iter().map(|m| line(m, 50)).collect();
+            match movements.len() {
+                1 => format!("emom-{} {}", minutes, lines.join(", ")),
+                _ => format!("emom-{}-alt {}", minutes, lines.join(", ")),
+            }
+        }
+        11..=20 => {
+            let movements = pick(&pool, 3, constraints.stimulus, &mut rng);
+            rounds_for_time((minutes / 4).clamp(3, 5), &movements, 100)
+        }
+        // A chipper, every movement once with lots of reps
+        _ => {
+            let movements = pick(&pool, 5, constraints.stimulus, &mut rng);
+            let percent = 100 + 100 * (minutes - 20) / 10;
+            let lines: Vec<String> = movements.iter().map(|m| line(m, percent)).collect();
+            format!("ft {}", lines.join(", "))
+        }
+    };
+    create_workout(&shorthand, None, None)
+}
+
+/// A line of the workout, "10 thruster @43/30kg".
+fn line(movement: &Movement, percent: u32) -> String {
+    format!("{} {}", reps(movement, percent), with_load(movement))
+}
+
+fn rounds_for_time(rounds: u32, movements: &[Movement], percent: u32) -> String {
+    let lines: Vec<String> = movements.iter().map(|m| line(m, percent)).collect();
+    format!("{}rd {}", rounds, lines.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constraints(minutes: u32, seed: u64) -> Constraints {
+        Constraints {
+            minutes,
+            seed: Some(seed),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_time_domains() {
+        for seed in 0..20 {
+            let workout = random_workout(&constraints(8, seed)).unwrap();
+            assert!(workout.to_shorthand().starts_with("ft 21-15-9 "));
+
+            let shorthand = random_workout(&constraints(16, seed))
+                .unwrap()
+                .to_shorthand();
+            assert!(
+                shorthand.starts_with("emom-15-alt ") || shorthand.starts_with("4rd "),
+                "{}",
+                shorthand
+            );
+
+            let workout = random_workout(&constraints(30, seed)).unwrap();
+            assert_eq!(workout.movements().count(), 5);
+        }
+    }
+
+    #[test]
+    fn test_stimulus() {
+        for seed in 0..20 {
+            let constraints = Constraints {
+                stimulus: Stimulus::Gymnastics,
+                ..constraints(30, seed)
+            };
+            let workout = random_workout(&constraints).unwrap();
+            assert!(workout
+                .movements()
+                .all(|m| modality(m) == Stimulus::Gymnastics));
+        }
+    }
+
+    #[test]
+    fn test_constraints() {
+        let bodyweight = Constraints {
+            equipment: Some(vec![]),
+            ..constraints(12, 1)
+        };
+        let workout = random_workout(&bodyweight).unwrap();
+        assert!(workout.movements().all(|m| m.equipment().is_none()));
+
+        let pool = Constraints {
+            movements: Some(vec![Movement::Burpee]),
+            ..constraints(30, 1)
+        };
+        assert_eq!(
+            random_workout(&pool).unwrap().to_shorthand(),
+            "ft 24 burpee"
+        );
+
+        let impossible = Constraints {
+            movements: Some(vec![Movement::Row]),
+            equipment: Some(vec![Equipment::Barbell]),
+            ..constraints(12, 1)
+        };
+        assert!(random_workout(&impossible).is_err());
+        assert!(random_workout(&constraints(0, 1)).is_err());
+    }
+
+    #[test]
+    fn test_stimulus_from_str() {
+        assert_eq!("Cardio".parse::<Stimulus>().unwrap(), Stimulus::Cardio);
+        assert!("strength".parse::<Stimulus>().is_err());
+    }
+}
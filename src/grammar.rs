@@ -0,0 +1,200 @@
+//! Reference of the shorthand used to write the workouts.
+//!
+//! The examples are checked against the parser in the tests, so the help of the
+//! command line and the man page stay in sync with the language accepted.
+
+/// A piece of the shorthand along with what it means.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Example {
+    pub syntax: &'static str,
+    pub description: &'static str,
+}
+
+const fn example(syntax: &'static str, description: &'static str) -> Example {
+    Example {
+        syntax,
+        description,
+    }
+}
+
+/// Prefixes for the workout types, the first word of every workout.
+pub const WORKOUT_TYPES: &[Example] = &[
+    example("ft", "For time"),
+    example("5rd", "5 rounds for time"),
+    example("amrap-15", "As many rounds as possible in 15 minutes"),
+    example(
+        "amrap-90s",
+        "AMRAP in seconds, or in minutes with decimals amrap-7.5",
+    ),
+    example("emom-12", "Every minute on the minute, for 12 minutes"),
+    example("emom-12-3m", "Every 3 minutes, for 12 minutes"),
+    example("emom-12-r30s", "Every minute, resting 30 seconds"),
+    example("emom-12-alt", "Every minute, alternating the movements"),
+    example("tabata", "Tabata, 8 rounds of 20 seconds work and 10 rest"),
+    example(
+        "tabata-6-30s-15s",
+        "Tabata of 6 rounds, 30 seconds work and 15 rest",
+    ),
+    example("wl", "Weightlifting"),
+    example("5rd-load", "5 rounds for load, scored by the heaviest load"),
+    example(
+        "5rd-load-total",
+        "5 rounds for load, scored by the total load",
+    ),
+];
+
+/// Notation for the repetitions of a movement.
+pub const REP_TYPES: &[Example] = &[
+    example("21", "Repetitions"),
+    example("21-15-9", "Repetitions per round (for time workouts)"),
+    example("15cal", "Calories"),
+    example("15/12cal", "Calories for men/women"),
+    example("400m", "Distance in meters, or kilometers with 5k"),
+    example("90sec", "Time, in seconds or minutes with 2min"),
+    example("max", "Max repetitions"),
+    example("r2m", "Rest period, in minutes or seconds with r30s"),
+];
+
+/// Notation for the loads and sets.
+pub const LOADS: &[Example] = &[
+    example("@43kg", "Load"),
+    example("@43/30kg", "Load for men/women"),
+    example("@52.5kg", "Load with decimals, in kg, lb or pood"),
+    example("@85%", "Percentage of the 1RM"),
+    example("@60,65,70kg", "Load of every set, or @70-75-80%"),
+    example(
+        "@70%+2%",
+        "Load adding 2% every set, or @start 70% add 2% per set",
+    ),
+    example("@rpe8", "Rate of perceived exertion, or a range @rpe7-8"),
+    example("5x5", "Sets x repetitions (weightlifting)"),
+    example("3x(2+1)", "Sets of a complex (weightlifting)"),
+    example("3x(3-2-1)", "Waves, paired with the loads @80-85-90%"),
+    example("1rm", "Build up to a 1 repetition max (weightlifting)"),
+    example("[...; ...]", "Block of weightlifting workouts"),
+];
+
+/// Complete workouts, as passed to `wod add` or written in a wod file.
+pub const WORKOUTS: &[Example] = &[
+    example("ft 21-15-9 pull up, thruster @43/30kg", "Fran"),
+    example("4rd 21 box jump over, 15 bar mu", "4 rounds for time"),
+    example("emom-12-3m-r1m 15cal row, 12 toes to bar", "EMOM with rest"),
+    example("wl 3x(2+1) clean, split jerk @85%", "Weightlifting complex"),
+    example("wl [1rm snatch; 1rm clean and jerk]", "Weightlifting block"),
+    example("ft 21 pull up (strict), 15 push up", "Note of a movement"),
+    example(
+        "wl A1 4x8 bench press @70% / A2 4x10 ring row",
+        "Superset, the lines alternate",
+    ),
+    example(
+        "A) wl 5x3 back squat @80%; B) emom-12 10 pull up",
+        "Parts of a class, separated by ;",
+    ),
+    example(
+        "ft 21 pull up|Time Cap 10'|Warm up",
+        "Comments and name, separated by |",
+    ),
+];
+
+fn section(title: &str, examples: &[Example]) -> String {
+    let width = examples.iter().map(|e| e.syntax.len()).max().unwrap_or(0);
+    let mut content = format!("{}:\n", title);
+    for e in examples {
+        content.push_str(&format!(
+            "  {:width$}  {}\n",
+            e.syntax,
+            e.description,
+            width = width
+        ));
+    }
+    content
+}
+
+/// Renders the reference of the shorthand as plain text, used for the help of the
+/// command line and the man page.
+///
+/// # Examples
+///
+/// ```
+/// let reference = wod::grammar::reference();
+/// assert!(reference.starts_with("Workout types:\n  ft "));
+/// ```
+pub fn reference() -> String {
+    [
+        section("Workout types", WORKOUT_TYPES),
+        section("Repetitions", REP_TYPES),
+        section("Loads", LOADS),
+        section("Examples", WORKOUTS),
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parts::create_workouts;
+    use crate::workout::create_workout;
+    use crate::{RepType, Weight, WorkoutType};
+
+    #[test]
+    fn test_workout_types_parse() {
+        for e in WORKOUT_TYPES {
+            assert!(
+                e.syntax.parse::<WorkoutType>().is_ok(),
+                "invalid example: {}",
+                e.syntax
+            );
+        }
+    }
+
+    #[test]
+    fn test_rep_types_parse() {
+        for e in REP_TYPES {
+            let syntax = e.syntax.split('-').next().unwrap();
+            assert!(
+                syntax.parse::<RepType>().is_ok(),
+                "invalid example: {}",
+                e.syntax
+            );
+        }
+        for alternative in ["5k", "2min", "r30s"] {
+            assert!(alternative.parse::<RepType>().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_loads_parse() {
+        for e in &LOADS[..3] {
+            assert!(e.syntax[1..].parse::<Weight>().is_ok());
+        }
+        for e in &LOADS[3..11] {
+            // The loads follow the sets they are written for
+            let workout = match e.syntax.starts_with('@') {
+                true => format!("wl 3x2 snatch {}", e.syntax),
+                false => format!("wl {} snatch", e.syntax),
+            };
+            assert!(create_workout(&workout, None, None).is_ok(), "{}", workout);
+        }
+    }
+
+    #[test]
+    fn test_workouts_parse() {
+        for e in WORKOUTS {
+            let workout = e.syntax.split('|').next().unwrap();
+            assert!(
+                create_workouts(workout, None, None).is_ok(),
+                "invalid example: {}",
+                e.syntax
+            );
+        }
+    }
+
+    #[test]
+    fn test_reference() {
+        let reference = reference();
+        assert!(
+            reference.contains("  amrap-15          As many rounds as possible in 15 minutes\n")
+        );
+        assert!(reference.contains("\nExamples:\n"));
+    }
+}
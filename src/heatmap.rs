@@ -0,0 +1,139 @@
+//! Training calendar of the archive: the workouts programmed per day, to render a
+//! heatmap like the contributions calendar of GitHub on the blog, see `wod heatmap`.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::results::SeriesFormat;
+use crate::workout::Workout;
+
+/// The workouts programmed in a day.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Day {
+    /// The day, "YYYY-MM-DD".
+    pub date: String,
+    /// Number of workouts of the day.
+    pub count: usize,
+    /// The kinds of the workouts, i.e. "emom", in the order they were programmed.
+    pub types: Vec<String>,
+}
+
+/// The days with workouts, in order. The days without any are left out, the
+/// calendars fill the gaps.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wod::create_workout;
+/// use wod::heatmap::calendar;
+///
+/// let day = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+/// let workouts = vec![
+///     create_workout("wl 5x5 back squat @70%", None, None).unwrap(),
+///     create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap(),
+/// ];
+/// let days = calendar(&[(day, workouts)]);
+/// assert_eq!(days[0].date, "2025-03-21");
+/// assert_eq!(days[0].count, 2);
+/// assert_eq!(days[0].types, vec!["weightlifting", "for time"]);
+/// ```
+pub fn calendar(days: &[(NaiveDate, Vec<Workout>)]) -> Vec<Day> {
+    days.iter()
+        .filter(|(_, workouts)| !workouts.is_empty())
+        .map(|(date, workouts)| {
+            let mut types: Vec<String> = Vec::new();
+            for workout in workouts {
+                let kind = workout.workout_type.kind().to_string();
+                if !types.contains(&kind) {
+                    types.push(kind);
+                }
+            }
+            Day {
+                date: date.to_string(),
+                count: workouts.len(),
+                types,
+            }
+        })
+        .collect()
+}
+
+/// Writes the days as a JSON array or as CSV with a header, where the types are
+/// separated by `;`.
+///
+/// # Examples
+///
+/// ```
+/// use wod::heatmap::{export, Day};
+/// use wod::results::SeriesFormat;
+///
+/// let day = Day {
+///     date: "2025-03-21".to_string(),
+///     count: 2,
+///     types: vec!["weightlifting".to_string(), "for time".to_string()],
+/// };
+/// assert_eq!(
+///     export(&[day], SeriesFormat::Csv),
+///     "date,count,types\n2025-03-21,2,weightlifting;for time\n"
+/// );
+/// ```
+pub fn export(days: &[Day], format: SeriesFormat) -> String {
+    match format {
+        SeriesFormat::Json => serde_json::to_string_pretty(days).unwrap_or_default(),
+        SeriesFormat::Csv => {
+            let mut csv = String::from("date,count,types\n");
+            for day in days {
+                csv.push_str(&format!(
+                    "{},{},{}\n",
+                    day.date,
+                    day.count,
+                    day.types.join(";")
+                ));
+            }
+            csv
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    #[test]
+    fn test_calendar() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let workout = |w| create_workout(w, None, None).unwrap();
+        let days = vec![
+            (
+                day(17),
+                vec![
+                    workout("emom-10 3 power clean @60kg"),
+                    workout("emom-12 10 pull up"),
+                ],
+            ),
+            (day(18), vec![]),
+            (day(19), vec![workout("tabata 20 air squat")]),
+        ];
+        let calendar = calendar(&days);
+        // The kinds aren't repeated, the days without workouts are left out
+        assert_eq!(
+            calendar,
+            vec![
+                Day {
+                    date: "2025-03-17".to_string(),
+                    count: 2,
+                    types: vec!["emom".to_string()],
+                },
+                Day {
+                    date: "2025-03-19".to_string(),
+                    count: 1,
+                    types: vec!["tabata".to_string()],
+                },
+            ]
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&export(&calendar, SeriesFormat::Json)).unwrap();
+        assert_eq!(json[1]["types"][0], "tabata");
+    }
+}
@@ -0,0 +1,357 @@
+//! Definitions to highlight the shorthand in editors, generated from the rules of the
+//! parser so they don't drift from the language accepted: the workout types from the
+//! examples of [`grammar::WORKOUT_TYPES`] (checked against the parser), and the
+//! movements from the registry of [`Movement`].
+//!
+//! The patterns are written in the syntax of TextMate (Oniguruma) and translated to
+//! the "very magic" patterns of Vim.
+
+use std::str::FromStr;
+
+use serde_json::json;
+
+use crate::grammar;
+use crate::movement::Movement;
+
+/// The units of the loads, longest first so "lbs" isn't highlighted as "lb".
+const LOAD_UNITS: &[&str] = &["poods", "pood", "lbs", "lb", "kg", "%"];
+
+/// Editors the highlighting can be generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HighlightFormat {
+    /// A TextMate grammar, `wod.tmLanguage.json`, used by VS Code, Sublime Text and
+    /// the editors that support TextMate bundles.
+    #[default]
+    TextMate,
+    /// A Vim syntax file, `syntax/wod.vim`.
+    Vim,
+}
+
+impl FromStr for HighlightFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tmlanguage" | "textmate" => Ok(HighlightFormat::TextMate),
+            "vim" => Ok(HighlightFormat::Vim),
+            _ => Err(format!(
+                "Invalid format: `{}`, expected one of: tmLanguage, vim",
+                s
+            )),
+        }
+    }
+}
+
+/// A kind of token highlighted, with the TextMate pattern that matches it.
+struct Rule {
+    /// TextMate scope, i.e. "keyword.control.workout-type.wod".
+    scope: &'static str,
+    /// Vim syntax group and the group it's linked to, i.e. ("wodWorkoutType", "Keyword").
+    group: (&'static str, &'static str),
+    pattern: String,
+}
+
+/// The pattern of a piece of the examples, where the numbers can be any number.
+fn generalize(example: &str) -> String {
+    let mut pattern = String::new();
+    let mut chars = example.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            while chars.next_if(|c| c.is_ascii_digit()).is_some() {}
+            pattern.push_str(r"\d+(?:\.\d+)?");
+        } else {
+            pattern.push_str(&escape(&c.to_string()));
+        }
+    }
+    pattern
+}
+
+/// Escapes the characters with a meaning in the patterns.
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if "\\.+*?()[]{}|^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// An alternation of the patterns, the longest first as the first one that matches
+/// is taken.
+fn alternation(mut patterns: Vec<String>) -> String {
+    patterns.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    patterns.dedup();
+    format!("(?:{})", patterns.join("|"))
+}
+
+/// The rules in order of priority, the first one that matches at a position wins.
+fn rules() -> Vec<Rule> {
+    let workout_types = grammar::WORKOUT_TYPES
+        .iter()
+        .map(|e| generalize(e.syntax))
+        .collect();
+    let movements = Movement::all()
+        .flat_map(|m| m.aliases().map(escape).collect::<Vec<_>>())
+        .collect();
+    let units = alternation(LOAD_UNITS.iter().map(|u| escape(u)).collect());
+    vec![
+        Rule {
+            scope: "comment.line.number-sign.wod",
+            group: ("wodComment", "Comment"),
+            pattern: r"^\s*#.*$".to_string(),
+        },
+        Rule {
+            // The comments and the name of the workout, after a `|`
+            scope: "string.unquoted.comments.wod",
+            group: ("wodComments", "String"),
+            pattern: r"\|.*$".to_string(),
+        },
+        Rule {
+            scope: "keyword.control.workout-type.wod",
+            group: ("wodWorkoutType", "Keyword"),
+            pattern: format!(r"\b{}\b", alternation(workout_types)),
+        },
+        Rule {
+            // "A)" of the parts of a class and "A1" of the supersets
+            scope: "entity.name.tag.label.wod",
+            group: ("wodLabel", "Label"),
+            pattern: r"\b[A-Z](?:\d+\b|\))".to_string(),
+        },
+        Rule {
+            scope: "constant.numeric.load.wod",
+            group: ("wodLoad", "Special"),
+            pattern: format!(
+                r"@\s*(?:rpe\s*|start\s+)?[\d.,/+-]+(?:\s*{})?(?:\s*\+\s*[\d.]+{})?",
+                units, units
+            ),
+        },
+        Rule {
+            scope: "support.function.movement.wod",
+            group: ("wodMovement", "Identifier"),
+            pattern: format!(r"\b{}\b", alternation(movements)),
+        },
+        Rule {
+            // Sets, repetitions, calories, distances, times and rest periods
+            scope: "constant.numeric.reps.wod",
+            group: ("wodReps", "Number"),
+            pattern: r"\b(?:(?:\d+x)?\d+(?:\.\d+)?(?:/\d+(?:\.\d+)?)?(?:cal|sec|min|rm|km|k|m|s)?|max|r\d+[ms])\b".to_string(),
+        },
+        Rule {
+            // The notes start with a letter, "(strict)", unlike the complexes "3x(2+1)"
+            scope: "string.other.note.wod",
+            group: ("wodNote", "SpecialComment"),
+            pattern: r"\([^)0-9][^)]*\)".to_string(),
+        },
+        Rule {
+            scope: "punctuation.separator.wod",
+            group: ("wodDelimiter", "Delimiter"),
+            pattern: r"[;\[\]/,]".to_string(),
+        },
+    ]
+}
+
+/// Translates a TextMate pattern to a "very magic" Vim pattern (`\v`), where `\b`
+/// is the start (`<`) or the end (`>`) of a word and the groups don't capture (`%(`).
+fn to_vim(pattern: &str) -> String {
+    let mut vim = String::from(r"\v");
+    let mut in_class = false;
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let escaped = chars.next().unwrap_or('\\');
+                if in_class {
+                    // The classes of Vim don't take \d, and only some characters escaped
+                    match escaped {
+                        'd' => vim.push_str("0-9"),
+                        '[' | '.' => vim.push(escaped),
+                        _ => {
+                            vim.push('\\');
+                            vim.push(escaped);
+                        }
+                    }
+                } else if escaped == 'b' {
+                    let start = vim.ends_with(r"\v") || vim.ends_with('(') || vim.ends_with('|');
+                    vim.push(if start { '<' } else { '>' });
+                } else {
+                    vim.push('\\');
+                    vim.push(escaped);
+                }
+            }
+            '[' if !in_class => {
+                in_class = true;
+                vim.push(c);
+            }
+            ']' if in_class => {
+                in_class = false;
+                vim.push(c);
+            }
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                // (?: is a group that doesn't capture
+                chars.next();
+                chars.next();
+                vim.push_str("%(");
+            }
+            '@' | '%' | '=' | '{' | '}' | '<' | '>' if !in_class => {
+                vim.push('\\');
+                vim.push(c);
+            }
+            _ => vim.push(c),
+        }
+    }
+    vim
+}
+
+/// The TextMate grammar, as the JSON of a `wod.tmLanguage.json` file.
+///
+/// # Examples
+///
+/// ```
+/// let grammar: serde_json::Value = serde_json::from_str(&wod::highlight::textmate()).unwrap();
+/// assert_eq!(grammar["scopeName"], "source.wod");
+/// ```
+pub fn textmate() -> String {
+    let patterns: Vec<_> = rules()
+        .into_iter()
+        .map(|rule| json!({"name": rule.scope, "match": rule.pattern}))
+        .collect();
+    let grammar = json!({
+        "name": "wod",
+        "scopeName": "source.wod",
+        "fileTypes": ["wod"],
+        "patterns": patterns,
+    });
+    serde_json::to_string_pretty(&grammar).unwrap_or_default()
+}
+
+/// The Vim syntax file, to save as `syntax/wod.vim`.
+///
+/// # Examples
+///
+/// ```
+/// let syntax = wod::highlight::vim();
+/// assert!(syntax.contains("hi def link wodMovement Identifier"));
+/// ```
+pub fn vim() -> String {
+    let rules = rules();
+    let mut syntax = String::from(
+        "\" Vim syntax file\n\
+         \" Language: wod shorthand\n\
+         \" Generated by `wod grammar --format vim` from the rules of the parser\n\n\
+         if exists(\"b:current_syntax\")\n  finish\nendif\n\n",
+    );
+    // In Vim the last rule defined wins, the opposite of TextMate
+    for rule in rules.iter().rev() {
+        syntax.push_str(&format!(
+            "syn match {} \"{}\"\n",
+            rule.group.0,
+            to_vim(&rule.pattern)
+        ));
+    }
+    syntax.push('\n');
+    for rule in &rules {
+        syntax.push_str(&format!("hi def link {} {}\n", rule.group.0, rule.group.1));
+    }
+    syntax.push_str("\nlet b:current_syntax = \"wod\"\n");
+    syntax
+}
+
+/// The highlighting definition in a format.
+pub fn generate(format: HighlightFormat) -> String {
+    match format {
+        HighlightFormat::TextMate => textmate(),
+        HighlightFormat::Vim => vim(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movement::round_trips;
+    use crate::units::LoadUnit;
+    use regex::Regex;
+
+    /// The rule of a scope as a regex, the syntax of TextMate is close enough.
+    fn rule(scope: &str) -> Regex {
+        let rule = rules().into_iter().find(|r| r.scope == scope).unwrap();
+        Regex::new(&rule.pattern).unwrap()
+    }
+
+    /// The text matched by the rule in a workout.
+    fn matches<'a>(scope: &str, text: &'a str) -> Vec<&'a str> {
+        rule(scope).find_iter(text).map(|m| m.as_str()).collect()
+    }
+
+    #[test]
+    fn test_workout_types() {
+        for e in grammar::WORKOUT_TYPES {
+            assert_eq!(
+                matches("keyword.control.workout-type.wod", e.syntax),
+                vec![e.syntax]
+            );
+        }
+        assert_eq!(
+            matches(
+                "keyword.control.workout-type.wod",
+                "A) wl 5x3 snatch; B) amrap-7.5 10 burpee"
+            ),
+            vec!["wl", "amrap-7.5"]
+        );
+    }
+
+    #[test]
+    fn test_movements() {
+        let movement = "support.function.movement.wod";
+        for m in Movement::all() {
+            for name in m.aliases() {
+                assert_eq!(matches(movement, name), vec![name]);
+                // The names highlighted are the ones the parser reads back
+                assert!(round_trips(name, &m), "`{}`", name);
+            }
+        }
+        assert_eq!(
+            matches(movement, "wl 3x(2+1) power clean, split jerk @85%"),
+            vec!["power clean", "split jerk"]
+        );
+    }
+
+    #[test]
+    fn test_reps_and_loads() {
+        for e in grammar::REP_TYPES {
+            assert!(!matches("constant.numeric.reps.wod", e.syntax).is_empty());
+        }
+        for unit in LOAD_UNITS {
+            assert!(unit.parse::<LoadUnit>().is_ok());
+        }
+        for e in &grammar::LOADS[..7] {
+            assert_eq!(
+                matches("constant.numeric.load.wod", e.syntax),
+                vec![e.syntax]
+            );
+        }
+        assert_eq!(
+            matches("constant.numeric.load.wod", "wl 6x2 snatch @70%+2%"),
+            vec!["@70%+2%"]
+        );
+    }
+
+    #[test]
+    fn test_notes_and_labels() {
+        let workout = "wl A1 4x8 bench press (paused) / A2 3x(2+1) clean";
+        assert_eq!(matches("string.other.note.wod", workout), vec!["(paused)"]);
+        assert_eq!(
+            matches("entity.name.tag.label.wod", workout),
+            vec!["A1", "A2"]
+        );
+    }
+
+    #[test]
+    fn test_to_vim() {
+        assert_eq!(to_vim(r"\b(?:ft|wl)\b"), r"\v<%(ft|wl)>");
+        assert_eq!(to_vim(r"@\s*[\d%]+%"), r"\v\@\s*[0-9%]+\%");
+        assert_eq!(to_vim(r"[;\[\]/]"), r"\v[;[\]/]");
+        assert!(vim().contains("syn match wodComment \"\\v^\\s*#.*$\"\n"));
+    }
+}
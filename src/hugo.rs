@@ -0,0 +1,235 @@
+//! Helpers to work inside a [Hugo](https://gohugo.io) site.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Configuration files that identify the root of a Hugo site.
+const CONFIG_FILES: [&str; 2] = ["hugo.toml", "config.toml"];
+
+/// Section of the site where the workouts are placed.
+pub const WOD_SECTION: &str = "wod";
+
+/// Whether `root` is the root of a Hugo site, that is, it contains a configuration
+/// file (`config.toml` or `hugo.toml`) and a `content` directory.
+pub fn is_site(root: &Path) -> bool {
+    CONFIG_FILES.iter().any(|f| root.join(f).is_file()) && root.join("content").is_dir()
+}
+
+/// Directory for the workouts in the Hugo site at `root`, `content/wod`,
+/// or `None` if `root` is not a Hugo site.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use wod::hugo::content_dir;
+///
+/// assert_eq!(content_dir(Path::new("/not/a/site")), None);
+/// ```
+pub fn content_dir(root: &Path) -> Option<PathBuf> {
+    if is_site(root) {
+        Some(root.join("content").join(WOD_SECTION))
+    } else {
+        None
+    }
+}
+
+/// Files written to a site by `wod init-hugo`, so the generated files render without
+/// changes to the theme: the archetype of the workouts, a partial listing them and
+/// the shortcode of an interval timer. The paths are relative to the root of the site.
+pub const SCAFFOLD: &[(&str, &str)] = &[
+    (
+        "archetypes/wod.md",
+        include_str!("../assets/hugo/archetypes/wod.md"),
+    ),
+    (
+        "layouts/partials/wod-list.html",
+        include_str!("../assets/hugo/layouts/partials/wod-list.html"),
+    ),
+    (
+        "layouts/shortcodes/timer.html",
+        include_str!("../assets/hugo/layouts/shortcodes/timer.html"),
+    ),
+];
+
+/// Writes the files of [`SCAFFOLD`] to the site at `root`. The files that exist are
+/// kept unless `force` is given, as they may have been edited.
+///
+/// Returns the files written and the ones kept.
+pub fn scaffold(root: &Path, force: bool) -> io::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let mut written = Vec::new();
+    let mut kept = Vec::new();
+    for (path, content) in SCAFFOLD {
+        let file = root.join(path);
+        if file.exists() && !force {
+            kept.push(file);
+            continue;
+        }
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&file, content)?;
+        written.push(file);
+    }
+    Ok((written, kept))
+}
+
+/// Files of the site created by `wod init`, along with the [`SCAFFOLD`]: the
+/// configuration with the taxonomies written by `wod`, the `wod` section, the
+/// layouts (the site doesn't need a theme) and a wod file to start from. The title
+/// of the site replaces the `{{title}}` placeholder.
+pub const SITE: &[(&str, &str)] = &[
+    ("hugo.toml", include_str!("../assets/hugo/site/hugo.toml")),
+    (".gitignore", include_str!("../assets/hugo/site/gitignore")),
+    (
+        "content/_index.md",
+        include_str!("../assets/hugo/site/content/_index.md"),
+    ),
+    (
+        "content/wod/_index.md",
+        include_str!("../assets/hugo/site/content/wod/_index.md"),
+    ),
+    (
+        "layouts/_default/baseof.html",
+        include_str!("../assets/hugo/site/layouts/_default/baseof.html"),
+    ),
+    (
+        "layouts/_default/list.html",
+        include_str!("../assets/hugo/site/layouts/_default/list.html"),
+    ),
+    (
+        "layouts/_default/single.html",
+        include_str!("../assets/hugo/site/layouts/_default/single.html"),
+    ),
+    (
+        "layouts/index.html",
+        include_str!("../assets/hugo/site/layouts/index.html"),
+    ),
+    (
+        "programming/sample.wod",
+        include_str!("../assets/hugo/site/programming/sample.wod"),
+    ),
+];
+
+/// Creates a site at `root` with the files of [`SITE`] and [`SCAFFOLD`], ready to
+/// publish the workouts. The directory is expected to be empty, the files are
+/// overwritten otherwise.
+///
+/// Returns the files written.
+pub fn new_site(root: &Path, title: &str) -> io::Result<Vec<PathBuf>> {
+    let title = title.replace('\\', "\\\\").replace('"', "\\\"");
+    let mut written = Vec::new();
+    for (path, content) in SITE {
+        let file = root.join(path);
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&file, content.replace("{{title}}", &title))?;
+        written.push(file);
+    }
+    written.extend(scaffold(root, true)?.0);
+    Ok(written)
+}
+
+/// Directory for the workouts of a `section` of the site, in place of the default one.
+///
+/// The last directory of `output_dir` is replaced when it's the default section, so
+/// `content/wod` becomes `content/<section>`, otherwise the section is a subdirectory.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use wod::hugo::section_dir;
+///
+/// assert_eq!(
+///     section_dir(Path::new("content/wod"), "weightlifting"),
+///     PathBuf::from("content/weightlifting")
+/// );
+/// assert_eq!(section_dir(Path::new("wods"), "competition"), PathBuf::from("wods/competition"));
+/// ```
+pub fn section_dir(output_dir: &Path, section: &str) -> PathBuf {
+    match output_dir.parent() {
+        Some(parent) if output_dir.ends_with(WOD_SECTION) => parent.join(section),
+        _ => output_dir.join(section),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_content_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        assert_eq!(content_dir(root), None);
+
+        fs::write(root.join("config.toml"), "baseURL = '/'").unwrap();
+        assert_eq!(content_dir(root), None);
+
+        fs::create_dir(root.join("content")).unwrap();
+        assert_eq!(content_dir(root), Some(root.join("content").join("wod")));
+    }
+
+    #[test]
+    fn test_section_dir() {
+        assert_eq!(
+            section_dir(Path::new("/site/content/wod"), "competition"),
+            PathBuf::from("/site/content/competition")
+        );
+        assert_eq!(section_dir(Path::new(""), "wl"), PathBuf::from("wl"));
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let (written, kept) = scaffold(root, false).unwrap();
+        assert_eq!(written.len(), SCAFFOLD.len());
+        assert!(kept.is_empty());
+        assert!(root.join("layouts/shortcodes/timer.html").is_file());
+
+        // The files edited are kept
+        fs::write(root.join("archetypes/wod.md"), "edited").unwrap();
+        let (written, kept) = scaffold(root, false).unwrap();
+        assert!(written.is_empty());
+        assert_eq!(kept.len(), SCAFFOLD.len());
+        assert_eq!(
+            fs::read_to_string(root.join("archetypes/wod.md")).unwrap(),
+            "edited"
+        );
+        let (written, _) = scaffold(root, true).unwrap();
+        assert_eq!(written.len(), SCAFFOLD.len());
+        assert_eq!(
+            fs::read_to_string(root.join("archetypes/wod.md")).unwrap(),
+            SCAFFOLD[0].1
+        );
+    }
+
+    #[test]
+    fn test_new_site() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("myblog");
+        let written = new_site(&root, "Box \"Crossfit\"").unwrap();
+        assert_eq!(written.len(), SITE.len() + SCAFFOLD.len());
+        assert!(is_site(&root));
+        assert_eq!(content_dir(&root), Some(root.join("content").join("wod")));
+        let config = fs::read_to_string(root.join("hugo.toml")).unwrap();
+        let config: toml::Table = toml::from_str(&config).unwrap();
+        assert_eq!(config["title"].as_str(), Some("Box \"Crossfit\""));
+        assert_eq!(config["taxonomies"]["level"].as_str(), Some("level"));
+    }
+
+    #[test]
+    fn test_is_site_hugo_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("hugo.toml"), "").unwrap();
+        fs::create_dir(root.join("content")).unwrap();
+        assert!(is_site(root));
+    }
+}
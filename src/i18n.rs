@@ -0,0 +1,292 @@
+//! Catalog of the phrases used to render the workouts, keyed by language.
+//!
+//! The `Display` implementations of the workouts don't write the phrases directly,
+//! they look them up with [`text`] in the catalog of the current language, so a new
+//! locale only needs a [`Catalog`] with its translations. English is built in, and
+//! the messages missing from a catalog fall back to it.
+//!
+//! The catalogs are registered with [`register`] (the CLI registers the ones in the
+//! `messages` table of the configuration), and [`with_language`] renders in a given
+//! language:
+//!
+//! ```
+//! use wod::i18n::{self, Catalog, Message};
+//! use wod::ForTime;
+//!
+//! let mut catalog = Catalog::default();
+//! catalog.insert(Message::ForTime, "Por tiempo");
+//! catalog.insert(Message::RoundsForTime, "{} rondas por tiempo");
+//! i18n::register("es", catalog);
+//!
+//! let workout = "5rd".parse::<ForTime>().unwrap();
+//! assert_eq!(workout.to_string(), "5 rounds for time");
+//! assert_eq!(i18n::with_language("es", || workout.to_string()), "5 rondas por tiempo");
+//! ```
+//!
+//! Keep in mind that the markdown files can only be read back (see [`crate::markdown`])
+//! when they are written in English.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::RwLock;
+
+use chrono::Weekday;
+
+use crate::units::TimeUnit;
+
+/// Language used when none is set, the one of the built in catalog.
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+/// The phrases of the rendered workouts, `{}` is replaced by the values of the message
+/// in order. In the catalogs they are written in snake case, i.e. `rounds_for_time`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Message {
+    /// "For Time"
+    ForTime,
+    /// "{} rounds for time"
+    RoundsForTime,
+    /// "{} rounds for load"
+    RoundsForLoad,
+    /// "Score: heaviest load across rounds"
+    MaxLoad,
+    /// "Score: total load"
+    TotalLoad,
+    /// "AMRAP {} minutes"
+    Amrap,
+    /// "AMRAP {} seconds", for the AMRAPs below a minute or that need more than a
+    /// decimal in minutes
+    AmrapSeconds,
+    /// "EMOM {} minutes"
+    Emom,
+    /// "alternating"
+    Alternating,
+    /// "Tabata {} rounds"
+    Tabata,
+    /// "{} seconds work, {} seconds rest"
+    TabataIntervals,
+    /// "Weightlifting"
+    Weightlifting,
+    /// "work every {}"
+    WorkEvery,
+    /// "rest {}", the rest of an EMOM
+    RestEvery,
+    /// "Rest {}", a rest period between movements
+    Rest,
+    /// "{} calories"
+    Calories,
+    /// "Max reps of"
+    MaxRepsOf,
+    /// "Comments: {}"
+    Comments,
+    /// "Leaderboard: {}", the heading of the leaderboard of a workout
+    Leaderboard,
+    /// "Warm-up", the heading of the warm-up of the day
+    WarmUp,
+    /// "Equipment: {}", the equipment needed for the workouts of the day
+    Equipment,
+    /// "Part {}", the name of a part of a class, see [`crate::parts`]
+    Part,
+    /// "{} minute"
+    Minute,
+    /// "{} minutes"
+    Minutes,
+    /// "{} seconds"
+    Seconds,
+    /// "Monday"
+    Monday,
+    /// "Tuesday"
+    Tuesday,
+    /// "Wednesday"
+    Wednesday,
+    /// "Thursday"
+    Thursday,
+    /// "Friday"
+    Friday,
+    /// "Saturday"
+    Saturday,
+    /// "Sunday"
+    Sunday,
+}
+
+impl Message {
+    /// The phrase in English, used when a catalog doesn't contain the message.
+    fn english(self) -> &'static str {
+        match self {
+            Message::ForTime => "For Time",
+            Message::RoundsForTime => "{} rounds for time",
+            Message::RoundsForLoad => "{} rounds for load",
+            Message::MaxLoad => "Score: heaviest load across rounds",
+            Message::TotalLoad => "Score: total load",
+            Message::Amrap => "AMRAP {} minutes",
+            Message::AmrapSeconds => "AMRAP {} seconds",
+            Message::Emom => "EMOM {} minutes",
+            Message::Alternating => "alternating",
+            Message::Tabata => "Tabata {} rounds",
+            Message::TabataIntervals => "{} seconds work, {} seconds rest",
+            Message::Weightlifting => "Weightlifting",
+            Message::WorkEvery => "work every {}",
+            Message::RestEvery => "rest {}",
+            Message::Rest => "Rest {}",
+            Message::Calories => "{} calories",
+            Message::MaxRepsOf => "Max reps of",
+            Message::Comments => "Comments: {}",
+            Message::Leaderboard => "Leaderboard: {}",
+            Message::WarmUp => "Warm-up",
+            Message::Equipment => "Equipment: {}",
+            Message::Part => "Part {}",
+            Message::Minute => "{} minute",
+            Message::Minutes => "{} minutes",
+            Message::Seconds => "{} seconds",
+            Message::Monday => "Monday",
+            Message::Tuesday => "Tuesday",
+            Message::Wednesday => "Wednesday",
+            Message::Thursday => "Thursday",
+            Message::Friday => "Friday",
+            Message::Saturday => "Saturday",
+            Message::Sunday => "Sunday",
+        }
+    }
+}
+
+/// The translations of the messages for a language.
+///
+/// # Examples
+///
+/// ```
+/// use wod::i18n::{Catalog, Message};
+///
+/// let catalog: Catalog = toml::from_str("for_time = \"Por tiempo\"").unwrap();
+/// assert_eq!(catalog.get(Message::ForTime), Some("Por tiempo"));
+/// assert_eq!(catalog.get(Message::Comments), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(transparent))]
+pub struct Catalog {
+    messages: BTreeMap<Message, String>,
+}
+
+impl Catalog {
+    /// Adds the translation of a message, replacing the previous one.
+    pub fn insert(&mut self, message: Message, text: &str) {
+        self.messages.insert(message, text.to_string());
+    }
+
+    /// The translation of a message, if the catalog has it.
+    pub fn get(&self, message: Message) -> Option<&str> {
+        self.messages.get(&message).map(String::as_str)
+    }
+}
+
+static CATALOGS: RwLock<BTreeMap<String, Catalog>> = RwLock::new(BTreeMap::new());
+
+thread_local! {
+    static LANGUAGE: RefCell<String> = RefCell::new(DEFAULT_LANGUAGE.to_string());
+}
+
+/// Registers the catalog of a language, replacing the previous one.
+pub fn register(language: &str, catalog: Catalog) {
+    if let Ok(mut catalogs) = CATALOGS.write() {
+        catalogs.insert(language.to_string(), catalog);
+    }
+}
+
+/// Runs `f` rendering the messages in `language`, the previous language is restored after.
+pub fn with_language<T>(language: &str, f: impl FnOnce() -> T) -> T {
+    let previous = LANGUAGE.with(|current| current.replace(language.to_string()));
+    let result = f();
+    LANGUAGE.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// The phrase of a message in the current language, with the `{}` replaced by `args` in order.
+pub fn text(message: Message, args: &[&dyn fmt::Display]) -> String {
+    LANGUAGE.with(|language| {
+        // The translation is borrowed from the catalog while the phrase is written
+        let catalogs = CATALOGS.read().ok();
+        let template = catalogs
+            .as_ref()
+            .and_then(|catalogs| catalogs.get(language.borrow().as_str()))
+            .and_then(|catalog| catalog.get(message))
+            .unwrap_or(message.english());
+
+        let mut args = args.iter();
+        let mut phrase = String::with_capacity(template.len());
+        let mut parts = template.split("{}").peekable();
+        while let Some(part) = parts.next() {
+            phrase.push_str(part);
+            if parts.peek().is_some() {
+                if let Some(arg) = args.next() {
+                    let _ = write!(phrase, "{}", arg);
+                }
+            }
+        }
+        phrase
+    })
+}
+
+/// A duration in words, i.e. "1 minute" or "90 seconds".
+pub(crate) fn duration(value: u32, unit: TimeUnit) -> String {
+    match unit {
+        TimeUnit::Minutes if value == 1 => text(Message::Minute, &[&value]),
+        TimeUnit::Minutes => text(Message::Minutes, &[&value]),
+        TimeUnit::Seconds => text(Message::Seconds, &[&value]),
+    }
+}
+
+/// The name of a day of the week, i.e. "Monday".
+pub(crate) fn weekday(day: Weekday) -> String {
+    let message = match day {
+        Weekday::Mon => Message::Monday,
+        Weekday::Tue => Message::Tuesday,
+        Weekday::Wed => Message::Wednesday,
+        Weekday::Thu => Message::Thursday,
+        Weekday::Fri => Message::Friday,
+        Weekday::Sat => Message::Saturday,
+        Weekday::Sun => Message::Sunday,
+    };
+    text(message, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        assert_eq!(text(Message::ForTime, &[]), "For Time");
+        assert_eq!(
+            text(Message::TabataIntervals, &[&20, &10]),
+            "20 seconds work, 10 seconds rest"
+        );
+        assert_eq!(duration(1, TimeUnit::Minutes), "1 minute");
+        assert_eq!(duration(90, TimeUnit::Seconds), "90 seconds");
+    }
+
+    #[test]
+    fn test_with_language() {
+        let mut catalog = Catalog::default();
+        catalog.insert(Message::Minutes, "{} minuti");
+        register("it", catalog);
+
+        assert_eq!(
+            with_language("it", || duration(2, TimeUnit::Minutes)),
+            "2 minuti"
+        );
+        // Missing messages and unknown languages fall back to English
+        assert_eq!(
+            with_language("it", || text(Message::ForTime, &[])),
+            "For Time"
+        );
+        assert_eq!(
+            with_language("xx", || duration(2, TimeUnit::Minutes)),
+            "2 minutes"
+        );
+        assert_eq!(duration(2, TimeUnit::Minutes), "2 minutes");
+    }
+}
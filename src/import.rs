@@ -0,0 +1,390 @@
+//! Import of the workouts published on the web, i.e. the workout of the day of
+//! crossfit.com or the site of a gym, to copy them to the blog.
+//!
+//! The text of the page is extracted from its HTML, and the workout is found with a
+//! few heuristics: a line with the type ("For time:", "Complete as many rounds as
+//! possible in 20 minutes of:", "5 rounds for time of:"...) followed by the lines of
+//! the movements, i.e. "21 kettlebell swings (53/35 lb)" or "400-meter run". The
+//! lines that can't be mapped to the shorthand are reported to be written by hand.
+//!
+//! [`text`] and [`import`] work on the page already fetched, `fetch` runs `curl` and
+//! is only part of the `cli` feature.
+
+#[cfg(feature = "cli")]
+use std::process::Command;
+
+#[cfg(feature = "cli")]
+use crate::error::WodError;
+use crate::movement::Movement;
+use crate::weight::Weight;
+use crate::workout::create_workout;
+
+/// Lines read after the type of the workout looking for its movements.
+const MAX_LINES: usize = 15;
+
+/// Names of the movements used in the published workouts that aren't aliases of
+/// the shorthand.
+const PUBLISHED: &[(&str, &str)] = &[
+    ("squat", "air squat"),
+    ("kb swing", "kettlebell swing"),
+    ("hspu", "handstand push up"),
+    ("ttb", "toes to bar"),
+];
+
+/// A workout imported from a page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Imported {
+    /// The workout in the shorthand, `None` if the page doesn't have one.
+    pub shorthand: Option<String>,
+    /// The lines of the workout that couldn't be mapped to the shorthand.
+    pub unmapped: Vec<String>,
+}
+
+/// Fetches a page with `curl`, which must be installed.
+///
+/// # Errors
+///
+/// Returns an error if the link isn't an http(s) one, `curl` can't be run or the
+/// page can't be fetched.
+#[cfg(feature = "cli")]
+pub fn fetch(url: &str) -> Result<String, WodError> {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(format!("Invalid link: `{}`, expected an http(s) one", url).into());
+    }
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .args(["--max-time", "30", url])
+        .output()
+        .map_err(|e| format!("Can't run curl to fetch the page: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Can't fetch {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The lines of text of an HTML page, without the scripts and the styles.
+///
+/// # Examples
+///
+/// ```
+/// use wod::import::text;
+///
+/// let html = "<h1>WOD</h1><script>let x = 1;</script><p>For time:<br>21-15-9 reps of:</p>\
+///             <ul><li>Thrusters (95/65 lb)</li><li>Pull&#8209;ups &amp; more</li></ul>";
+/// assert_eq!(
+///     text(html),
+///     ["WOD", "For time:", "21-15-9 reps of:", "Thrusters (95/65 lb)", "Pull-ups & more"]
+/// );
+/// ```
+pub fn text(html: &str) -> Vec<String> {
+    let mut content = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        content.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+        let Some(end) = rest.find('>') else {
+            rest = "";
+            break;
+        };
+        let closing = rest[1..end].starts_with('/');
+        let name: String = rest[1..end]
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        rest = &rest[end + 1..];
+        match name.as_str() {
+            // The content of the scripts and the styles isn't text of the page
+            "script" | "style" | "noscript" if !closing => {
+                let lower = rest.to_ascii_lowercase();
+                rest = lower
+                    .find(&format!("</{}", name))
+                    .map_or("", |i| &rest[i..]);
+            }
+            "br" | "p" | "div" | "li" | "ul" | "ol" | "tr" | "td" | "section" | "article"
+            | "header" | "footer" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => content.push('\n'),
+            _ => {}
+        }
+    }
+    content.push_str(rest);
+
+    decode(&content)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// Decodes the entities of the text, the common ones only.
+fn decode(text: &str) -> String {
+    [
+        ("&nbsp;", " "),
+        ("&#160;", " "),
+        ("&#8209;", "-"),
+        ("&ndash;", "-"),
+        ("&#8211;", "-"),
+        ("&mdash;", "-"),
+        ("&rsquo;", "'"),
+        ("&#8217;", "'"),
+        ("&#39;", "'"),
+        ("&quot;", "\""),
+        ("&lt;", "<"),
+        ("&gt;", ">"),
+        ("&amp;", "&"),
+    ]
+    .iter()
+    .fold(text.to_string(), |text, (entity, c)| {
+        text.replace(entity, c)
+    })
+}
+
+/// The first number written in a line, i.e. 20 for "AMRAP in 20 minutes".
+fn first_number(line: &str) -> Option<u32> {
+    line.split(|c: char| !c.is_ascii_digit())
+        .find(|n| !n.is_empty())
+        .and_then(|n| n.parse().ok())
+}
+
+/// A rep scheme like "21-15-9", the first word of the line.
+fn rep_scheme(line: &str) -> Option<String> {
+    let first = line.split_whitespace().next()?;
+    let parts: Vec<&str> = first.split('-').collect();
+    (parts.len() > 1
+        && parts
+            .iter()
+            .all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())))
+    .then(|| first.to_string())
+}
+
+/// The type of the workout in the shorthand if the line introduces one.
+fn workout_type(line: &str) -> Option<String> {
+    let lower = line.to_lowercase();
+    if lower.contains("as many rounds") || lower.contains("amrap") {
+        return first_number(&lower).map(|minutes| format!("amrap-{}", minutes));
+    }
+    if lower.contains("every minute") || lower.contains("emom") {
+        return first_number(&lower).map(|minutes| format!("emom-{}", minutes));
+    }
+    if lower.contains("rounds for load") || lower.contains("rounds, for load") {
+        return first_number(&lower).map(|rounds| format!("{}rd-load", rounds));
+    }
+    if lower.contains("rounds for time") || lower.contains("rounds, for time") {
+        return first_number(&lower).map(|rounds| format!("{}rd", rounds));
+    }
+    lower.contains("for time").then(|| "ft".to_string())
+}
+
+/// A movement from its name as published, i.e. "Pull-ups" or "kettlebell swings".
+fn movement(name: &str) -> Option<Movement> {
+    let name = name
+        .to_lowercase()
+        .replace('-', " ")
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_string();
+    let singular = name.strip_suffix('s').unwrap_or(&name).to_string();
+    [name.as_str(), singular.as_str()].iter().find_map(|name| {
+        let name = PUBLISHED
+            .iter()
+            .find(|(published, _)| published == name)
+            .map_or(*name, |(_, alias)| *alias);
+        name.parse().ok()
+    })
+}
+
+/// A load as published, i.e. "95/65 lb" or "20/14-lb ball", in the shorthand.
+fn load(published: &str) -> Option<String> {
+    let compact: String = published
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_lowercase();
+    let split = compact.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '/'))?;
+    let (numbers, rest) = compact.split_at(split);
+    let unit = ["poods", "pood", "lbs", "lb", "kg"]
+        .into_iter()
+        .find(|unit| rest.starts_with(unit))?;
+    let weight: Weight = format!("{}{}", numbers, unit).parse().ok()?;
+    Some(format!("@{}", weight))
+}
+
+/// A line of a movement as published in the shorthand, i.e. "21 kettlebell swings
+/// (53/35 lb)" as "21 kettlebell swing @53/35lb". `None` if it can't be mapped.
+fn movement_line(line: &str) -> Option<String> {
+    let line = line.trim_start_matches(['-', '*', '•', ' ']);
+    let (line, weight) = match line.split_once('(') {
+        Some((line, rest)) => {
+            let published = rest.split(')').next().unwrap_or_default();
+            (line, Some(load(published)?))
+        }
+        None => (line, None),
+    };
+    let words: Vec<String> = line
+        .replace('-', " ")
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let (reps, name) = match words.first().and_then(|w| w.parse::<u32>().ok()) {
+        Some(number) => {
+            let unit = words.get(1).map(String::as_str);
+            match unit {
+                Some("meter" | "meters" | "m") => (format!("{}m ", number), &words[2..]),
+                Some("mile" | "miles") => (format!("{}mile ", number), &words[2..]),
+                Some("calorie" | "calories" | "cal" | "cals") => {
+                    (format!("{}cal ", number), &words[2..])
+                }
+                _ => (format!("{} ", number), &words[1..]),
+            }
+        }
+        None => (String::new(), &words[..]),
+    };
+    let movement = movement(&name.join(" "))?;
+    let shorthand = match weight {
+        Some(weight) => format!("{}{} {}", reps, movement.shorthand(), weight),
+        None => format!("{}{}", reps, movement.shorthand()),
+    };
+    // The line has to be valid on its own, i.e. the distances for a run
+    create_workout(&format!("ft {}", shorthand), None, None).ok()?;
+    Some(shorthand)
+}
+
+/// Finds the workout in the lines of text of a page, see the [module documentation](self)
+/// for the heuristics used.
+///
+/// # Examples
+///
+/// ```
+/// use wod::import::import;
+///
+/// let lines = [
+///     "Complete as many rounds as possible in 20 minutes of:",
+///     "5 pull-ups",
+///     "10 push-ups",
+///     "15 squats",
+///     "3 pegboard ascents",
+/// ];
+/// let imported = import(&lines.map(String::from));
+/// assert_eq!(imported.shorthand.as_deref(), Some("amrap-20 5 pull up, 10 push up, 15 air squat"));
+/// assert_eq!(imported.unmapped, ["3 pegboard ascents"]);
+/// ```
+pub fn import(lines: &[String]) -> Imported {
+    let mut imported = Imported {
+        shorthand: None,
+        unmapped: Vec::new(),
+    };
+    let Some(start) = lines.iter().position(|line| workout_type(line).is_some()) else {
+        return imported;
+    };
+    let mut kind = workout_type(&lines[start]).unwrap_or_default();
+    let mut scheme = rep_scheme(&lines[start]);
+    let mut movements: Vec<String> = Vec::new();
+    for line in lines.iter().skip(start + 1).take(MAX_LINES) {
+        if movements.is_empty() && scheme.is_none() {
+            if let Some(found) = rep_scheme(line) {
+                scheme = Some(found);
+                // "21-15-9 reps for time of:" after a title
+                if let Some(found) = workout_type(line) {
+                    kind = found;
+                }
+                continue;
+            }
+        }
+        match movement_line(line) {
+            Some(movement) => movements.push(movement),
+            // A paragraph of text, the workout is over
+            None if line.split_whitespace().count() > 8 => break,
+            None => imported.unmapped.push(line.clone()),
+        }
+    }
+    if movements.is_empty() {
+        return imported;
+    }
+
+    let shorthand = match scheme {
+        Some(scheme) => format!("{} {} {}", kind, scheme, movements.join(", ")),
+        None => format!("{} {}", kind, movements.join(", ")),
+    };
+    if create_workout(&shorthand, None, None).is_ok() {
+        imported.shorthand = Some(shorthand);
+    } else {
+        imported.unmapped.extend(movements);
+    }
+    imported
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_page() {
+        let html = r#"<html><head><style>p { color: red; }</style></head><body>
+            <h2>Wednesday 250326</h2>
+            <p>For time:<br>
+            21-15-9 reps of:<br>
+            Thrusters (95/65 lb)<br>
+            Pull-ups</p>
+            <p>Post time to comments. Compare to 250101. Tips for the thrusters are below the video, as usual.</p>
+            <p>5 rounds for time of:</p>
+            </body></html>"#;
+        let imported = import(&text(html));
+        assert_eq!(
+            imported.shorthand.as_deref(),
+            Some("ft 21-15-9 thruster @95/65lb, pull up")
+        );
+        assert!(imported.unmapped.is_empty());
+    }
+
+    #[test]
+    fn test_movement_line() {
+        assert_eq!(
+            movement_line("21 Kettlebell Swings (53/35 lb)").as_deref(),
+            Some("21 kettlebell swing @53/35lb")
+        );
+        assert_eq!(movement_line("400-meter run").as_deref(), Some("400m run"));
+        assert_eq!(movement_line("- 20 wall-ball shots (20/14-lb ball)"), None);
+        assert_eq!(
+            movement_line("- 20 wall balls (20/14-lb ball)").as_deref(),
+            Some("20 wall ball @20/14lb")
+        );
+        assert_eq!(
+            movement_line("15-calorie row").as_deref(),
+            Some("15cal row")
+        );
+        assert_eq!(movement_line("Rest as needed"), None);
+    }
+
+    #[test]
+    fn test_import_without_workout() {
+        let lines = ["Rest Day".to_string(), "See you tomorrow".to_string()];
+        assert_eq!(
+            import(&lines),
+            Imported {
+                shorthand: None,
+                unmapped: vec![]
+            }
+        );
+        let lines = [
+            "5 rounds for time of:".to_string(),
+            "400-meter run".to_string(),
+        ];
+        assert_eq!(import(&lines).shorthand.as_deref(), Some("5rd 400m run"));
+        let lines = [
+            "5 rounds for load of:".to_string(),
+            "3 deadlifts".to_string(),
+        ];
+        assert_eq!(
+            import(&lines).shorthand.as_deref(),
+            Some("5rd-load 3 deadlift")
+        );
+    }
+}
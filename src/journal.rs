@@ -0,0 +1,118 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::WodError;
+
+/// Number of additions remembered, the oldest ones are dropped.
+const MAX_ENTRIES: usize = 100;
+
+/// A workout block added to a file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Absolute path of the file the workout was added to.
+    pub file: PathBuf,
+    /// The markdown block added.
+    pub block: String,
+}
+
+/// Journal of the workouts added with `wod add`, so the last one added to a file
+/// can be removed with `wod undo`, wherever it was placed in the file.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use wod::journal::Journal;
+///
+/// let mut journal = Journal::default();
+/// journal.record(Path::new("/blog/wod.md"), "---\n\n**For Time**\n\n".to_string());
+/// assert_eq!(journal.pop(Path::new("/blog/wod.md")), Some("---\n\n**For Time**\n\n".to_string()));
+/// assert_eq!(journal.pop(Path::new("/blog/wod.md")), None);
+/// ```
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+}
+
+impl Journal {
+    /// Default location of the journal, `<wod home>/journal.json`.
+    pub fn default_path() -> PathBuf {
+        crate::wod_home().join("journal.json")
+    }
+
+    /// Loads the journal from a file, an empty journal is returned if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, WodError> {
+        if !path.exists() {
+            return Ok(Journal::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Saves the journal to a file, creating the parent directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), WodError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Records a block added to a file.
+    pub fn record(&mut self, file: &Path, block: String) {
+        self.entries.push(JournalEntry {
+            file: file.to_path_buf(),
+            block,
+        });
+        if self.entries.len() > MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    /// Removes and returns the last block added to a file, if any.
+    pub fn pop(&mut self, file: &Path) -> Option<String> {
+        let index = self.entries.iter().rposition(|e| e.file == file)?;
+        Some(self.entries.remove(index).block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_pop_by_file() {
+        let mut journal = Journal::default();
+        journal.record(Path::new("a.md"), "a1".to_string());
+        journal.record(Path::new("b.md"), "b1".to_string());
+        journal.record(Path::new("a.md"), "a2".to_string());
+        assert_eq!(journal.pop(Path::new("a.md")), Some("a2".to_string()));
+        assert_eq!(journal.pop(Path::new("a.md")), Some("a1".to_string()));
+        assert_eq!(journal.pop(Path::new("b.md")), Some("b1".to_string()));
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let mut journal = Journal::default();
+        for i in 0..=MAX_ENTRIES {
+            journal.record(Path::new("a.md"), i.to_string());
+        }
+        assert_eq!(journal.entries.len(), MAX_ENTRIES);
+        assert_eq!(journal.entries[0].block, "1");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("journal.json");
+        assert_eq!(Journal::load(&path).unwrap(), Journal::default());
+
+        let mut journal = Journal::default();
+        journal.record(Path::new("a.md"), "---\n\n**For Time**\n\n".to_string());
+        journal.save(&path).unwrap();
+        assert_eq!(Journal::load(&path).unwrap(), journal);
+    }
+}
@@ -0,0 +1,311 @@
+//! Leaderboards of the athletes of a gym for a workout of the day.
+//!
+//! The scores are read from CSV lines, `athlete,score[,division]`, where the division
+//! is `rx` (the default) or `scaled`:
+//!
+//! ```text
+//! athlete,score,division
+//! Ana,3:45,rx
+//! Bob,4:10
+//! Carla,5:02,scaled
+//! ```
+//!
+//! The finished times rank first, the fastest on top, followed by the scores that
+//! are not a time (rounds and reps, loads or the reps done at the time cap), the
+//! highest on top.
+//!
+//! The leaderboard is a block of the file of the day like the workouts, marked with
+//! a comment (see [`MARKER`]) so it's told apart from them in any language.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::WodError;
+use crate::i18n::{self, Message};
+use crate::weight::Weight;
+
+/// Division of a score, done as prescribed or scaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Division {
+    #[default]
+    Rx,
+    Scaled,
+}
+
+impl FromStr for Division {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "rx" | "" => Ok(Division::Rx),
+            "scaled" => Ok(Division::Scaled),
+            _ => Err(format!(
+                "Invalid division: `{}`, expected one of: rx, scaled",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Division {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Division::Rx => write!(f, "Rx"),
+            Division::Scaled => write!(f, "Scaled"),
+        }
+    }
+}
+
+/// The score of an athlete.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Name of the athlete.
+    pub athlete: String,
+    /// The score as written, i.e. "3:45", "5+12" or "100kg".
+    pub score: String,
+    /// Whether the workout was done as prescribed or scaled.
+    pub division: Division,
+}
+
+impl FromStr for Entry {
+    type Err = String;
+
+    /// Parses a CSV line, `athlete,score[,division]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.split(',').map(str::trim).collect();
+        match fields[..] {
+            [athlete, score] | [athlete, score, _] if !athlete.is_empty() && !score.is_empty() => {
+                Ok(Entry {
+                    athlete: athlete.to_string(),
+                    score: score.to_string(),
+                    division: fields.get(2).copied().unwrap_or_default().parse()?,
+                })
+            }
+            _ => Err(format!(
+                "Invalid score: `{}`, expected `athlete,score[,division]`",
+                s
+            )),
+        }
+    }
+}
+
+/// Reads the scores of a CSV, skipping the header, the blank lines and the comments.
+///
+/// # Examples
+///
+/// ```
+/// use wod::leaderboard::{parse_csv, Division};
+///
+/// let entries = parse_csv("athlete,score,division\nAna,3:45\nBob,5:02,scaled\n").unwrap();
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[1].division, Division::Scaled);
+/// ```
+pub fn parse_csv(content: &str) -> Result<Vec<Entry>, WodError> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .enumerate()
+        .filter(|(i, line)| !(*i == 0 && line.to_lowercase().starts_with("athlete,")))
+        .map(|(_, line)| line.parse().map_err(WodError::Invalid))
+        .collect()
+}
+
+/// The value used to rank a score: a finished time, or any other score.
+#[derive(Debug, PartialEq)]
+enum Rank {
+    /// Seconds, the lower the better.
+    Time(u32),
+    /// Rounds and reps, a load or reps, the higher the better.
+    Points(f64),
+    /// A score that can't be compared, ranked last.
+    Unknown,
+}
+
+impl Rank {
+    fn of(score: &str) -> Self {
+        let score = score.trim().to_lowercase();
+        let time: Vec<&str> = score.split(':').collect();
+        if (2..=3).contains(&time.len()) {
+            if let Some(seconds) = time.iter().try_fold(0, |seconds, part| {
+                Some(seconds * 60 + part.parse::<u32>().ok()?)
+            }) {
+                return Rank::Time(seconds);
+            }
+        }
+        if let Ok(weight) = score.replace(' ', "").parse::<Weight>() {
+            return Rank::Points(weight.weight_man);
+        }
+        // "5+12" or "5 rounds + 12", the reps break the ties of the rounds
+        let numbers: Vec<f64> = score
+            .split(|c: char| !c.is_ascii_digit() && c != '.')
+            .filter_map(|n| n.parse().ok())
+            .collect();
+        match numbers[..] {
+            [rounds, reps] if score.contains('+') => Rank::Points(rounds * 1000.0 + reps),
+            [points, ..] => Rank::Points(points),
+            [] => Rank::Unknown,
+        }
+    }
+
+    fn compare(&self, other: &Rank) -> Ordering {
+        match (self, other) {
+            (Rank::Time(a), Rank::Time(b)) => a.cmp(b),
+            (Rank::Points(a), Rank::Points(b)) => b.total_cmp(a),
+            (Rank::Time(_), _) | (Rank::Points(_), Rank::Unknown) => Ordering::Less,
+            (_, Rank::Time(_)) | (Rank::Unknown, Rank::Points(_)) => Ordering::Greater,
+            (Rank::Unknown, Rank::Unknown) => Ordering::Equal,
+        }
+    }
+}
+
+/// Sorts the scores by division and rank, returning the position of each one.
+/// The athletes with the same score share the position, "1, 2, 2, 4".
+///
+/// # Examples
+///
+/// ```
+/// use wod::leaderboard::{parse_csv, rank};
+///
+/// let entries = parse_csv("Ana,4:10\nBob,3:45\nCarla,4:10\nDan,250").unwrap();
+/// let ranked: Vec<(usize, &str)> = rank(&entries)
+///     .into_iter()
+///     .map(|(position, entry)| (position, entry.athlete.as_str()))
+///     .collect();
+/// assert_eq!(ranked, [(1, "Bob"), (2, "Ana"), (2, "Carla"), (4, "Dan")]);
+/// ```
+pub fn rank(entries: &[Entry]) -> Vec<(usize, &Entry)> {
+    let mut sorted: Vec<(&Entry, Rank)> = entries.iter().map(|e| (e, Rank::of(&e.score))).collect();
+    sorted.sort_by(|(a, rank_a), (b, rank_b)| {
+        a.division
+            .cmp(&b.division)
+            .then_with(|| rank_a.compare(rank_b))
+    });
+    let mut ranked: Vec<(usize, &Entry)> = Vec::with_capacity(sorted.len());
+    // Index of the first entry of the current division
+    let mut start = 0;
+    for (i, (entry, rank)) in sorted.iter().enumerate() {
+        let position = match i.checked_sub(1).map(|prev| &sorted[prev]) {
+            Some((prev, _)) if prev.division != entry.division => {
+                start = i;
+                1
+            }
+            Some((_, prev_rank)) if prev_rank.compare(rank).is_eq() => ranked[i - 1].0,
+            _ => i + 1 - start,
+        };
+        ranked.push((position, *entry));
+    }
+    ranked
+}
+
+/// Opening of the comment that marks the block of a leaderboard,
+/// `<!-- leaderboard: Fran -->`.
+pub const MARKER: &str = "<!-- leaderboard:";
+
+/// The comment that marks the leaderboard of a workout.
+fn marker(workout: &str) -> String {
+    format!("{} {} -->", MARKER, workout)
+}
+
+/// Whether a block of a file is a leaderboard instead of a workout.
+pub fn is_leaderboard(block: &str) -> bool {
+    block.lines().any(|line| line.starts_with(MARKER))
+}
+
+/// Whether a block of a file is the leaderboard of a workout.
+pub fn is_leaderboard_of(block: &str, workout: &str) -> bool {
+    let marker = marker(workout);
+    block.lines().any(|line| line.trim_end() == marker)
+}
+
+/// Renders the leaderboard of a workout as a markdown block of the file of the day,
+/// a list per division.
+///
+/// # Examples
+///
+/// ```
+/// use wod::leaderboard::{parse_csv, render};
+///
+/// let entries = parse_csv("Ana,3:45\nBob,4:10\nCarla,5:02,scaled").unwrap();
+/// assert_eq!(
+///     render("Fran", &entries),
+///     "---\n\n<!-- leaderboard: Fran -->\n**Leaderboard: Fran**\n\n*Rx*\n\n1. Ana: 3:45\n2. Bob: 4:10\n\n*Scaled*\n\n1. Carla: 5:02\n\n"
+/// );
+/// ```
+pub fn render(workout: &str, entries: &[Entry]) -> String {
+    let heading = i18n::text(Message::Leaderboard, &[&workout]);
+    let mut block = format!("---\n\n{}\n**{}**\n\n", marker(workout), heading);
+    let mut division = None;
+    for (position, entry) in rank(entries) {
+        if division != Some(entry.division) {
+            if division.is_some() {
+                block.push('\n');
+            }
+            block.push_str(&format!("*{}*\n\n", entry.division));
+            division = Some(entry.division);
+        }
+        block.push_str(&format!(
+            "{}. {}: {}\n",
+            position, entry.athlete, entry.score
+        ));
+    }
+    block.push('\n');
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_of() {
+        assert_eq!(Rank::of("4:32"), Rank::Time(272));
+        assert_eq!(Rank::of("5+12"), Rank::Points(5012.0));
+        assert_eq!(Rank::of("5 rounds + 12"), Rank::Points(5012.0));
+        assert_eq!(Rank::of("102.5kg"), Rank::Points(102.5));
+        assert_eq!(Rank::of("245 reps"), Rank::Points(245.0));
+        assert_eq!(Rank::of("DNF"), Rank::Unknown);
+    }
+
+    #[test]
+    fn test_rank_divisions() {
+        // The times come first, then the reps at the time cap and the unknown scores
+        let entries =
+            parse_csv("Ana,DNF\nBob,230,scaled\nCarla,200\nDan,9:58\nEve,4:01,scaled\nFin,250")
+                .unwrap();
+        let ranked: Vec<(usize, &str, Division)> = rank(&entries)
+            .into_iter()
+            .map(|(position, entry)| (position, entry.athlete.as_str(), entry.division))
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                (1, "Dan", Division::Rx),
+                (2, "Fin", Division::Rx),
+                (3, "Carla", Division::Rx),
+                (4, "Ana", Division::Rx),
+                (1, "Eve", Division::Scaled),
+                (2, "Bob", Division::Scaled),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_leaderboard() {
+        let block = render("Fran", &parse_csv("Ana,3:45").unwrap());
+        assert!(is_leaderboard(&block));
+        assert!(is_leaderboard_of(&block, "Fran"));
+        assert!(!is_leaderboard_of(&block, "Grace"));
+        assert!(!is_leaderboard(
+            "---\n\n**For Time**\n\n- 30 Clean and Jerk\n\n"
+        ));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("Ana".parse::<Entry>().is_err());
+        assert!("Ana,3:45,elite".parse::<Entry>().is_err());
+        assert!(parse_csv("Ana,3:45\n,4:10").is_err());
+    }
+}
@@ -1,15 +1,20 @@
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
+use std::ops::Range;
 use std::str::Chars;
 
 use crate::movement::Movement;
 use crate::rep_types::rep_type::RepType;
 use crate::rep_types::rest_period::RestPeriod;
 use crate::rm::RM;
-use crate::weight::Weight;
+use crate::rpe::Rpe;
+use crate::units::LoadUnit;
+use crate::weight::{self, Weight};
 use crate::WorkoutType;
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Token {
     /// Represents different types of workouts such as ft (for time), amrap (as many reps as possible),
     /// emom (every minute on the minute), wl (weightlifting), and potentially other types.
@@ -39,13 +44,30 @@ pub enum Token {
     RightBracket,
     /// Represents ';' - separates individual workouts within a block
     Semicolon,
+    /// A note of a movement in parentheses, like "(strict)", kept verbatim.
+    Note(String),
+    /// Represents the intensity as a rate of perceived exertion, like 'rpe8' or
+    /// 'rpe7-8', instead of a load.
+    Rpe(Rpe),
+    /// The repetitions of the sets of a wave, the '3-2-1' of '3x(3-2-1)', each set
+    /// is done with the load in the same position, '@80-85-90%'.
+    Wave(Vec<RepType>),
+    /// The load of every set of a line, like '60,65,70kg' or '70-75-80%'.
+    Loads(Vec<Weight>),
+    /// The load of the first set and the load added every set after it, '70%+2%' or
+    /// 'start 70% add 2% per set', the load of each set follows from the number of sets.
+    Progression(Weight, Weight),
+    /// Label of a line of a superset, like "A1", the lines sharing the letter are
+    /// done alternating. The lines are separated by '/'.
+    Superset(String),
 }
 
 /// Represents a lexical analyzer for parsing workout input strings.
 ///
 /// The "Lexer" struct is designed to tokenize a string input representing a workout routine.
 /// It reads through the input character by character, identifying and categorizing different
-/// components of the routine into tokens.
+/// components of the routine into tokens. The text of the tokens is sliced from the input,
+/// it's only copied when a token has to be rewritten to be parsed.
 ///
 /// # Examples
 ///
@@ -77,10 +99,16 @@ pub enum Token {
 /// );
 /// ```
 pub struct Lexer<'a> {
+    /// The input string.
+    source: &'a str,
     /// An iterator over the characters of the input string.
     input: Chars<'a>,
     /// The current character being analyzed by the lexer.
     current_char: Option<char>,
+    /// Byte offset of the current character in the input.
+    position: usize,
+    /// Byte offset where the token being read starts.
+    token_start: usize,
 }
 
 impl<'a> Lexer<'a> {
@@ -88,12 +116,34 @@ impl<'a> Lexer<'a> {
         let mut chars = input.chars();
         let current_char = chars.next();
         Lexer {
+            source: input,
             input: chars,
             current_char,
+            position: 0,
+            token_start: 0,
         }
     }
 
+    /// Byte range in the input of the last token read. After `tokenize` fails,
+    /// it points to the token that caused the error.
+    ///
+    /// ```
+    /// use wod::lexer::Lexer;
+    ///
+    /// let input = "ft 21-15-9 pull up, thrustr @ 43/30kg";
+    /// let mut lexer = Lexer::new(input);
+    /// assert!(lexer.tokenize().is_err());
+    /// assert_eq!(&input[lexer.span()], "thrustr");
+    /// ```
+    pub fn span(&self) -> Range<usize> {
+        let token = &self.source[self.token_start..self.position];
+        self.token_start..self.token_start + token.trim_end().len()
+    }
+
     fn advance(&mut self) {
+        if let Some(c) = self.current_char {
+            self.position += c.len_utf8();
+        }
         self.current_char = self.input.next();
     }
 
@@ -106,18 +156,22 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    fn read_workout_type(&mut self) -> Result<WorkoutType, LexerError> {
-        let mut result = String::new();
-
+    /// Advances while the characters match the predicate, returning the slice of the
+    /// input that was read.
+    fn read_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let start = self.position;
         while let Some(c) = self.current_char {
-            // To include the hyphen in the workout type
-            if c.is_alphanumeric() || c == '-' {
-                result.push(c);
-                self.advance();
-            } else {
+            if !predicate(c) {
                 break;
             }
+            self.advance();
         }
+        &self.source[start..self.position]
+    }
+
+    fn read_workout_type(&mut self) -> Result<WorkoutType, LexerError> {
+        // To include the hyphen in the workout type, and the decimals of "amrap-7.5"
+        let result = self.read_while(|c| c.is_alphanumeric() || c == '-' || c == '.');
 
         let workout_type: Result<WorkoutType, _> = result.parse();
         match workout_type {
@@ -128,21 +182,97 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    fn read_movement(&mut self) -> String {
-        let mut result = String::new();
+    fn read_movement(&mut self) -> &'a str {
+        self.read_while(|c| c.is_alphanumeric() || c == ' ').trim()
+    }
 
-        while let Some(c) = self.current_char {
-            if c.is_alphanumeric() || c == ' ' {
-                result.push(c);
+    /// Reads a note in parentheses, "(to 15in target)", returning the text inside.
+    fn read_note(&mut self) -> Result<&'a str, LexerError> {
+        self.advance();
+        let note = self.read_while(|c| c != ')');
+        if self.current_char.is_none() {
+            return Err(LexerError::InvalidNote(format!(
+                "Unclosed note: `({}`, expected a `)`",
+                note
+            )));
+        }
+        self.advance();
+        Ok(note.trim())
+    }
+
+    /// Reads the label of a line of a superset, a letter followed by digits like "A1",
+    /// returned in uppercase. Nothing is read if the word is anything else, so
+    /// "c2b" is still a movement.
+    fn read_superset(&mut self) -> Option<String> {
+        let rest = &self.source[self.position..];
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let mut chars = rest[..end].chars();
+        let is_label = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+            && end > 1
+            && chars.all(|c| c.is_ascii_digit());
+        if !is_label {
+            return None;
+        }
+        let label = self.read_while(|c| !c.is_whitespace());
+        Some(label.to_uppercase())
+    }
+
+    /// Reads a rate of perceived exertion after an '@', "rpe8" or "rpe7-8".
+    fn read_rpe(&mut self) -> &'a str {
+        let start = self.position;
+        self.read_while(|c| c.is_alphabetic());
+        self.read_while(|c| c.is_numeric() || c == '.' || c == '-');
+        &self.source[start..self.position]
+    }
+
+    /// Consumes the word if the input continues with it, ignoring the case.
+    fn read_keyword(&mut self, word: &str) -> bool {
+        let rest = &self.source[self.position..];
+        let found = rest
+            .get(..word.len())
+            .is_some_and(|w| w.eq_ignore_ascii_case(word))
+            && !rest[word.len()..].starts_with(char::is_alphabetic);
+        if found {
+            let end = self.position + word.len();
+            while self.position < end {
                 self.advance();
-            } else {
-                break;
             }
         }
-        result.trim().to_string()
+        found
     }
 
-    fn read_number_scheme(&mut self) -> String {
+    /// Reads the increment of the load every set after the load of the first one,
+    /// the "2%" after the '+' of "@70%+2%" or after the "add" of "@start 70% add 2% per set".
+    fn read_progression(&mut self, start: Weight, verbose: bool) -> Result<Token, LexerError> {
+        self.skip_whitespace();
+        let step = self.read_weight();
+        if step.is_empty() {
+            return Err(LexerError::InvalidWeight(
+                "expected the load added every set, i.e. '70%+2%'".to_string(),
+            ));
+        }
+        let step: Weight = step.parse().map_err(LexerError::InvalidWeight)?;
+        if step.unit != start.unit {
+            return Err(LexerError::InvalidWeight(format!(
+                "the increment {} must be in the unit of the first load, {}",
+                step, start
+            )));
+        }
+        if verbose {
+            self.skip_whitespace();
+            if !(self.read_keyword("per") && {
+                self.skip_whitespace();
+                self.read_keyword("set")
+            }) {
+                return Err(LexerError::InvalidWeight(
+                    "expected 'start 70% add 2% per set'".to_string(),
+                ));
+            }
+        }
+        Ok(Token::Progression(start, step))
+    }
+
+    fn read_number_scheme(&mut self) -> &'a str {
         // Read number will read until it finds a non-numeric character,
         // it takes into account the following cases:
         // 21-15-9
@@ -153,10 +283,8 @@ impl<'a> Lexer<'a> {
         // 60/40kg
         // 70%
         // max
-        let mut result = String::new();
-
-        while let Some(c) = self.current_char {
-            if c.is_numeric()
+        self.read_while(|c| {
+            c.is_numeric()
                 || matches!(
                     c.to_lowercase().next().unwrap(),
                     '-' | '+'
@@ -176,15 +304,48 @@ impl<'a> Lexer<'a> {
                         | 'a'
                         | 's'
                 )
+        })
+    }
+
+    fn read_weight(&mut self) -> Cow<'a, str> {
+        // Read the load after an '@', the numbers can have decimals and the unit
+        // can be separated by spaces:
+        // 60kg
+        // 52.5/35kg
+        // 70%
+        // 1.5 pood
+        // 60,65,70kg or 70-75-80%, a load per set
+        let start = self.position;
+        loop {
+            self.read_while(|c| c.is_numeric() || c == '.' || c == '/');
+            let mut next = self.source[self.position..].chars();
+            if !(matches!(next.next(), Some(',' | '-'))
+                && next.next().is_some_and(char::is_numeric))
             {
-                result.push(c);
-                self.advance();
-            } else {
                 break;
             }
+            self.advance();
         }
-
-        result
+        let number = &self.source[start..self.position];
+
+        let rest = &self.source[self.position..];
+        let spaces = rest.len() - rest.trim_start().len();
+        let unit_len = rest[spaces..]
+            .find(|c: char| !(c.is_alphabetic() || c == '%'))
+            .unwrap_or(rest.len() - spaces);
+        let unit = &rest[spaces..spaces + unit_len];
+        if spaces == 0 {
+            self.read_while(|c| c.is_alphabetic() || c == '%');
+            return Cow::Borrowed(&self.source[start..self.position]);
+        }
+        // Only skip the spaces for a unit of weight, "@60 pull up" has no unit
+        if unit.parse::<LoadUnit>().is_err() {
+            return Cow::Borrowed(number);
+        }
+        while self.position < start + number.len() + spaces + unit_len {
+            self.advance();
+        }
+        Cow::Owned(format!("{}{}", number, unit))
     }
 
     pub fn tokenize(&mut self) -> Result<Vec<Token>, LexerError> {
@@ -197,6 +358,7 @@ impl<'a> Lexer<'a> {
                 continue;
             }
 
+            self.token_start = self.position;
             if first_token {
                 let workout_type = self.read_workout_type()?;
                 tokens.push(Token::WorkoutType(workout_type));
@@ -205,9 +367,81 @@ impl<'a> Lexer<'a> {
             }
             match c {
                 '@' => {
-                    // @70% or @60kg
+                    // @70%, @60kg or @rpe8
                     tokens.push(Token::At);
                     self.advance();
+                    self.skip_whitespace();
+                    if self.source[self.position..]
+                        .get(..3)
+                        .is_some_and(|p| p.eq_ignore_ascii_case("rpe"))
+                    {
+                        self.token_start = self.position;
+                        let rpe = self.read_rpe();
+                        tokens.push(Token::Rpe(rpe.parse().map_err(LexerError::InvalidRpe)?));
+                        self.skip_whitespace();
+                        if self.current_char == Some('+') {
+                            // @rpe8+2%
+                            self.token_start = self.position;
+                            self.advance();
+                            self.skip_whitespace();
+                            self.read_weight();
+                            return Err(LexerError::InvalidWeight(
+                                "the load added every set needs the load of the first one, \
+                                 i.e. '70%+2%'"
+                                    .to_string(),
+                            ));
+                        }
+                    } else if self.read_keyword("start") {
+                        // @start 70% add 2% per set
+                        self.skip_whitespace();
+                        self.token_start = self.position;
+                        let start = self.read_weight();
+                        let start = start.parse().map_err(LexerError::InvalidWeight)?;
+                        self.skip_whitespace();
+                        if !self.read_keyword("add") {
+                            return Err(LexerError::InvalidWeight(
+                                "expected 'start 70% add 2% per set'".to_string(),
+                            ));
+                        }
+                        tokens.push(self.read_progression(start, true)?);
+                        check_progression(&tokens)?;
+                    } else if self.current_char.is_some_and(|c| c.is_numeric()) {
+                        self.token_start = self.position;
+                        let weight = self.read_weight();
+                        if self.source[self.position..].trim_start().starts_with('+') {
+                            // @70%+2%
+                            let start = weight.parse().map_err(LexerError::InvalidWeight)?;
+                            self.skip_whitespace();
+                            self.advance();
+                            tokens.push(self.read_progression(start, false)?);
+                            check_progression(&tokens)?;
+                        } else if weight.contains([',', '-']) {
+                            let loads =
+                                weight::parse_loads(&weight).map_err(LexerError::InvalidWeight)?;
+                            match line_scheme(&tokens) {
+                                (_, Some(wave)) if loads.len() != wave.len() => {
+                                    return Err(LexerError::InvalidWeight(format!(
+                                        "{} loads for a wave of {} sets, write one per set",
+                                        loads.len(),
+                                        wave.len()
+                                    )));
+                                }
+                                (sets, None) if loads.len() != sets => {
+                                    return Err(LexerError::InvalidWeight(format!(
+                                        "{} loads for {} sets, write one per set",
+                                        loads.len(),
+                                        sets
+                                    )));
+                                }
+                                _ => {}
+                            }
+                            tokens.push(Token::Loads(loads));
+                        } else {
+                            tokens.push(Token::Weight(
+                                weight.parse().map_err(LexerError::InvalidWeight)?,
+                            ));
+                        }
+                    }
                     continue;
                 }
                 '[' => {
@@ -222,10 +456,18 @@ impl<'a> Lexer<'a> {
                     tokens.push(Token::Semicolon);
                     self.advance();
                 }
+                '(' => {
+                    let note = self.read_note()?;
+                    tokens.push(Token::Note(note.to_string()));
+                }
                 c if c.is_numeric() => {
                     self.parse_numeric(&mut tokens)?;
                 }
                 c if c.is_alphabetic() => {
+                    if let Some(label) = self.read_superset() {
+                        tokens.push(Token::Superset(label));
+                        continue;
+                    }
                     self.parse_alphabetic(&mut tokens)?;
                 }
                 _ => {
@@ -239,10 +481,19 @@ impl<'a> Lexer<'a> {
     }
 
     fn parse_numeric(&mut self, tokens: &mut Vec<Token>) -> Result<(), LexerError> {
-        fn process_buf(buf: &mut Vec<char>, tokens: &mut Vec<Token>) -> Result<(), LexerError> {
+        fn process_buf(buf: &str, tokens: &mut Vec<Token>) -> Result<(), LexerError> {
+            // The sets of a wave, 3x(3-2-1)
+            if buf.contains('-') {
+                let wave = buf
+                    .split('-')
+                    .map(|rep| rep.parse())
+                    .collect::<Result<Vec<RepType>, _>>()
+                    .map_err(|e| LexerError::InvalidRepType(e.to_string()))?;
+                tokens.push(Token::Wave(wave));
+                return Ok(());
+            }
             if !buf.is_empty() {
-                let maybe_rep_type = buf.iter().collect::<String>();
-                let rep_type: Result<RepType, _> = maybe_rep_type.parse();
+                let rep_type: Result<RepType, _> = buf.parse();
                 match rep_type {
                     Ok(rep_type) => {
                         tokens.push(Token::RepType(rep_type));
@@ -253,7 +504,6 @@ impl<'a> Lexer<'a> {
                         ));
                     }
                 }
-                buf.clear();
             }
             Ok(())
         }
@@ -261,27 +511,29 @@ impl<'a> Lexer<'a> {
 
         // Workouts like 5x5, or 21-15-9 are parsed here
         if number.contains('x') {
-            // In case of numbers, store the chars to cast them as a single number at the end
-            let mut buf = Vec::new();
-            for c in number.chars() {
+            // The numbers are the slices between the symbols, parsed when a symbol is found
+            let mut start = 0;
+            for (i, c) in number.char_indices() {
+                if !matches!(c, 'x' | '+' | '(' | ')') {
+                    continue;
+                }
+                process_buf(&number[start..i], tokens)?;
+                start = i + c.len_utf8();
                 match c {
-                    'x' => {
-                        process_buf(&mut buf, tokens)?;
-                        tokens.push(Token::X)
-                    }
-                    '+' => {
-                        process_buf(&mut buf, tokens)?;
-                        tokens.push(Token::Plus)
-                    }
-                    '(' | ')' => {
-                        // Skip these tokens
-                        process_buf(&mut buf, tokens)?;
-                    }
-                    _ => buf.push(c),
+                    'x' => tokens.push(Token::X),
+                    '+' => tokens.push(Token::Plus),
+                    // Skip the parentheses
+                    _ => {}
                 }
             }
-            // Push any pending number in the buffer
-            process_buf(&mut buf, tokens)?;
+            // Push any pending number
+            process_buf(&number[start..], tokens)?;
+            if tokens.last() == Some(&Token::X) {
+                return Err(LexerError::InvalidRepType(format!(
+                    "`{}` needs the reps of every set, i.e. '5x5'",
+                    number
+                )));
+            }
         } else if number.contains("kg") || number.contains('%') {
             let w: Result<Weight, _> = number.parse();
             match w {
@@ -302,19 +554,9 @@ impl<'a> Lexer<'a> {
                     return Err(LexerError::InvalidRM(rm.unwrap_err().to_string()));
                 }
             }
-        } else if number.contains("sec") || number.contains("min") {
-            let rep_type: Result<RepType, _> = number.parse();
-            match rep_type {
-                Ok(rep_type) => {
-                    tokens.push(Token::RepType(rep_type));
-                }
-                Err(_) => {
-                    return Err(LexerError::InvalidRepType(
-                        rep_type.unwrap_err().to_string(),
-                    ));
-                }
-            }
-        } else if number.to_lowercase().contains('K')
+        } else if number.contains("sec")
+            || number.contains("min")
+            || number.to_lowercase().contains('K')
             || number.contains('m')
             || number.contains('i')
             || number.contains('l')
@@ -352,12 +594,12 @@ impl<'a> Lexer<'a> {
     }
 
     fn parse_alphabetic(&mut self, tokens: &mut Vec<Token>) -> Result<(), LexerError> {
-        let mut movement = self.read_movement();
+        let mut movement: Cow<str> = Cow::Borrowed(self.read_movement());
 
         // "max db snatch" or "max ring muscle up" will be a movement,
         // We have to strip the "max" part if occurs and assign it the corresponding token
         if movement.starts_with("max") {
-            movement = movement.replace("max ", "");
+            movement = Cow::Owned(movement.replace("max ", ""));
             tokens.push(Token::RepType(RepType::Max));
         }
         // Check if it could be rest before any other type of movement
@@ -365,13 +607,26 @@ impl<'a> Lexer<'a> {
             tokens.push(Token::RepType(RepType::RestPeriod(rest)));
             // This is a hacky way of ensuring the rest is properly
             // rendered, it works but it's ugly
-            movement = "rest".to_string();
+            movement = Cow::Borrowed("rest");
         }
 
         if !movement.is_empty() {
             let mov: Result<Movement, _> = movement.parse();
             match mov {
                 Ok(parsed_movement) => {
+                    // Two movements need a comma or a line between them, "clean-deadlift"
+                    // isn't a clean followed by a deadlift
+                    let gap = self.source[..self.token_start]
+                        .trim_end_matches(|c: char| !c.is_alphanumeric());
+                    let gap = &self.source[gap.len()..self.token_start];
+                    if matches!(tokens.last(), Some(Token::Movement(_)))
+                        && !gap.contains([',', '\n'])
+                    {
+                        return Err(LexerError::InvalidMovement(format!(
+                            "`{}` needs its reps, or a comma before it",
+                            movement
+                        )));
+                    }
                     tokens.push(Token::Movement(parsed_movement));
                 }
                 Err(_) => {
@@ -385,6 +640,49 @@ impl<'a> Lexer<'a> {
     }
 }
 
+/// The sets of the line a load is written for, read back from the tokens before it:
+/// the 5 of "5x5 back squat @", or 1 when they aren't written. Along with the
+/// repetitions of the wave of "3x(3-2-1) power clean @", if any.
+fn line_scheme(tokens: &[Token]) -> (usize, Option<&[RepType]>) {
+    let mut wave = None;
+    for (i, token) in tokens.iter().enumerate().rev() {
+        match token {
+            Token::Wave(reps) => wave = Some(reps.as_slice()),
+            Token::X => {
+                let sets = match &tokens[..i] {
+                    [.., Token::RepType(RepType::Reps(sets))] => sets.reps_man as usize,
+                    _ => 1,
+                };
+                return (sets, wave);
+            }
+            // The end of the previous line, or the start of this one
+            Token::WorkoutType(_)
+            | Token::Weight(_)
+            | Token::Loads(_)
+            | Token::Progression(..)
+            | Token::Rpe(_)
+            | Token::RM(_)
+            | Token::Superset(_)
+            | Token::LeftBracket
+            | Token::RightBracket
+            | Token::Semicolon => break,
+            _ => {}
+        }
+    }
+    (1, wave)
+}
+
+/// A load that increases every set needs the sets of the line, "6x2 snatch @70%+2%".
+fn check_progression(tokens: &[Token]) -> Result<(), LexerError> {
+    if let (1, None) = line_scheme(&tokens[..tokens.len() - 1]) {
+        return Err(LexerError::InvalidWeight(
+            "the load added every set needs the sets of the line, i.e. '6x2 snatch @70%+2%'"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub enum LexerError {
     InvalidWorkoutType(String),
@@ -392,18 +690,38 @@ pub enum LexerError {
     InvalidRepType(String),
     InvalidRM(String),
     InvalidMovement(String),
+    InvalidNote(String),
+    InvalidRpe(String),
 }
 
 impl Error for LexerError {}
 
+impl LexerError {
+    /// Identifier of the kind of error, i.e. "invalid-movement", stable to be read by
+    /// editors and scripts.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexerError::InvalidWorkoutType(_) => "invalid-workout-type",
+            LexerError::InvalidWeight(_) => "invalid-weight",
+            LexerError::InvalidRepType(_) => "invalid-rep-type",
+            LexerError::InvalidRM(_) => "invalid-rm",
+            LexerError::InvalidMovement(_) => "invalid-movement",
+            LexerError::InvalidNote(_) => "invalid-note",
+            LexerError::InvalidRpe(_) => "invalid-rpe",
+        }
+    }
+}
+
 impl fmt::Display for LexerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             LexerError::InvalidWorkoutType(s) => write!(f, "Invalid WorkoutType: {}", s),
-            LexerError::InvalidWeight(s) => write!(f, "Invalid Eright: {}", s),
+            LexerError::InvalidWeight(s) => write!(f, "Invalid Weight: {}", s),
             LexerError::InvalidRepType(s) => write!(f, "Invalid RepType: {}", s),
             LexerError::InvalidRM(s) => write!(f, "Invalid RM: {}", s),
             LexerError::InvalidMovement(s) => write!(f, "Invalid Movement: {}", s),
+            LexerError::InvalidNote(s) => write!(f, "Invalid Note: {}", s),
+            LexerError::InvalidRpe(s) => write!(f, "Invalid RPE: {}", s),
         }
     }
 }
@@ -685,6 +1003,167 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rpe() {
+        let input = "wl 3x5 back squat @rpe8, 3x3 deadlift @ RPE7-8";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[6], Token::Rpe("rpe8".parse().unwrap()));
+        assert_eq!(tokens[12], Token::Rpe("rpe7-8".parse().unwrap()));
+        assert!(matches!(
+            Lexer::new("wl 3x5 back squat @rpe12").tokenize(),
+            Err(LexerError::InvalidRpe(_))
+        ));
+    }
+
+    #[test]
+    fn test_wave() {
+        let input = "wl 3x(3-2-1) power clean @80-85-90%";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[2], Token::X);
+        assert_eq!(
+            tokens[3],
+            Token::Wave(vec![
+                RepType::from_str("3").unwrap(),
+                RepType::from_str("2").unwrap(),
+                RepType::from_str("1").unwrap(),
+            ])
+        );
+        assert!(matches!(tokens[6], Token::Loads(_)));
+        // A load for every set of the wave, or a single one for all of them
+        assert!(Lexer::new("wl 3x(3-2-1) power clean @80%")
+            .tokenize()
+            .is_ok());
+        for (input, invalid) in [
+            ("wl 3x(3-2-1) power clean @80-85%", "80-85%"),
+            ("wl 3x(3-2) power clean @80,85,90%", "80,85,90%"),
+        ] {
+            let mut lexer = Lexer::new(input);
+            assert!(
+                matches!(lexer.tokenize(), Err(LexerError::InvalidWeight(_))),
+                "{}",
+                input
+            );
+            assert_eq!(&input[lexer.span()], invalid);
+        }
+        assert!(Lexer::new("wl 3x(3-a-1) power clean").tokenize().is_err());
+    }
+
+    #[test]
+    fn test_progression() {
+        let start = |w: &str| w.parse::<Weight>().unwrap();
+        for input in [
+            "wl 6x2 snatch @70%+2%",
+            "wl 6x2 snatch @ 70% + 2%",
+            "wl 6x2 snatch @ start 70% add 2% per set",
+            "wl 6x2 snatch @Start 70 % Add 2% Per Set",
+        ] {
+            let tokens = Lexer::new(input).tokenize().expect(input);
+            assert_eq!(
+                tokens.last(),
+                Some(&Token::Progression(start("70%"), start("2%"))),
+                "{}",
+                input
+            );
+        }
+        for invalid in [
+            "wl 6x2 snatch @70%+2kg",
+            "wl 6x2 snatch @start 70%",
+            "wl 6x2 snatch @start 70% add 2%",
+            "wl 6x2 snatch @70%+-2%",
+            "wl 6x2 snatch @70%+",
+            "wl 6x2 snatch @rpe7-8+2%",
+            "wl 2 snatch @70%+2%",
+            "wl snatch @start 70% add 2% per set",
+        ] {
+            assert!(
+                matches!(
+                    Lexer::new(invalid).tokenize(),
+                    Err(LexerError::InvalidWeight(_))
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_loads_per_set() {
+        let loads = |input: &str| match Lexer::new(input).tokenize().unwrap().pop() {
+            Some(Token::Loads(loads)) => loads.iter().map(|w| w.to_string()).collect(),
+            _ => Vec::new(),
+        };
+        assert_eq!(
+            loads("wl 5x5 back squat @60,65,70,75,80kg"),
+            vec!["60kg", "65kg", "70kg", "75kg", "80kg"]
+        );
+        assert_eq!(
+            loads("wl 3x3 snatch @ 70-75-80%"),
+            vec!["70%", "75%", "80%"]
+        );
+        assert_eq!(
+            loads("wl 2x3 clean @ 60/40,70/45 kg"),
+            vec!["60/40kg", "70/45kg"]
+        );
+        // A load per set, neither more nor fewer, the error points to them
+        for (input, invalid) in [
+            ("wl 5x5 back squat @60,65kg", "60,65kg"),
+            (
+                "wl 5x5 back squat @60,65,70,75,80,85,90kg",
+                "60,65,70,75,80,85,90kg",
+            ),
+            (
+                "wl 5x5 back squat @60kg, 2x3 snatch @50,60,70kg",
+                "50,60,70kg",
+            ),
+            ("ft 21 thruster @40,50kg", "40,50kg"),
+        ] {
+            let mut lexer = Lexer::new(input);
+            assert!(
+                matches!(lexer.tokenize(), Err(LexerError::InvalidWeight(_))),
+                "{}",
+                input
+            );
+            assert_eq!(&input[lexer.span()], invalid);
+        }
+        // The comma after a single load still separates the lines
+        let tokens = Lexer::new("ft 10 thruster @40kg,10 burpee")
+            .tokenize()
+            .unwrap();
+        assert!(tokens.contains(&Token::Weight("40kg".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_superset() {
+        let input = "wl A1 4x8 bench press @70% / a2 4x10 ring row";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[1], Token::Superset("A1".to_string()));
+        assert_eq!(tokens[8], Token::Superset("A2".to_string()));
+        assert_eq!(tokens[12], Token::Movement(Movement::RingRow));
+        // A movement with digits isn't a label
+        let tokens = Lexer::new("ft 10 c2b").tokenize().unwrap();
+        assert_eq!(tokens[2], Token::Movement(Movement::ChestToBar));
+    }
+
+    #[test]
+    fn test_notes() {
+        let input = "emom-10 10 box jump (to 15in target), 12 push up @20kg (no pushup)";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens[3], Token::Note("to 15in target".to_string()));
+        assert_eq!(tokens[8], Token::Note("no pushup".to_string()));
+        assert!(matches!(
+            Lexer::new("ft 10 pull up (strict").tokenize(),
+            Err(LexerError::InvalidNote(_))
+        ));
+    }
+
     #[test]
     fn test_emom_0() {
         let input = "emom-10 10 pull up";
@@ -760,4 +1239,70 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_decimal_weights() {
+        let input = "3rd 5 deadlift @102.5kg, 21 kettlebell swing @ 1.5 pood, 9 clean @52.5/35kg";
+        let mut lexer = Lexer::new(input);
+        let tokens = lexer.tokenize().unwrap();
+        let weights: Vec<String> = tokens
+            .iter()
+            .filter_map(|t| match t {
+                Token::Weight(w) => Some(w.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(weights, vec!["102.5kg", "1.5pood", "52.5/35kg"]);
+
+        let mut lexer = Lexer::new("ft 21 thruster @42.5.5kg");
+        assert!(lexer.tokenize().is_err());
+        assert_eq!(&"ft 21 thruster @42.5.5kg"[lexer.span()], "42.5.5kg");
+    }
+
+    #[test]
+    fn test_weight_followed_by_movement() {
+        // The space after a load without unit is not part of it, "pull up" is a movement
+        let mut lexer = Lexer::new("emom-10 5 clean @60 pull up");
+        let tokens = lexer.tokenize();
+        assert!(tokens.is_err());
+        assert_eq!(&"emom-10 5 clean @60 pull up"[lexer.span()], "60");
+
+        let mut lexer = Lexer::new("amrap-12 10 kettlebell swing @ 24 kg, 10 burpee");
+        let tokens = lexer.tokenize().unwrap();
+        assert!(tokens.contains(&Token::Weight(Weight::from_str("24kg").unwrap())));
+        assert_eq!(
+            tokens.last(),
+            Some(&Token::Movement(Movement::from_str("burpee").unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_movements_without_separator() {
+        // "clean-deadlift" isn't a clean and a deadlift
+        let mut lexer = Lexer::new("ft 10 clean-deadlift");
+        assert!(matches!(
+            lexer.tokenize(),
+            Err(LexerError::InvalidMovement(_))
+        ));
+        assert_eq!(&"ft 10 clean-deadlift"[lexer.span()], "deadlift");
+
+        let tokens = Lexer::new("ft 21-15-9 thruster, pull up")
+            .tokenize()
+            .unwrap();
+        assert_eq!(tokens.last(), Some(&Token::Movement(Movement::PullUp)));
+        let tokens = Lexer::new("wl 3x(2+1) clean, split jerk")
+            .tokenize()
+            .unwrap();
+        assert_eq!(tokens.last(), Some(&Token::Movement(Movement::SplitJerk)));
+    }
+
+    #[test]
+    fn test_sets_without_reps() {
+        for input in ["wl 5x", "wl 5x() back squat"] {
+            assert!(matches!(
+                Lexer::new(input).tokenize(),
+                Err(LexerError::InvalidRepType(_))
+            ));
+        }
+    }
 }
@@ -1,20 +1,89 @@
 mod tests;
 
+pub mod benchmarks;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod cycles;
+pub mod date;
+pub mod diagnostics;
+pub mod diff;
+#[cfg(feature = "cli")]
+pub mod doctor;
+pub mod document;
+pub mod error;
+#[cfg(feature = "cli")]
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod formatter;
+#[cfg(feature = "cli")]
+pub mod frequency;
+pub mod front_matter;
+pub mod generator;
+pub mod grammar;
+#[cfg(feature = "cli")]
+pub mod heatmap;
+#[cfg(feature = "cli")]
+pub mod highlight;
+#[cfg(feature = "cli")]
+pub mod hugo;
+pub mod i18n;
+pub mod import;
+#[cfg(feature = "cli")]
+pub mod journal;
+pub mod leaderboard;
 pub mod lexer;
+pub mod lint;
+#[cfg(feature = "cli")]
+pub mod lsp;
+pub mod markdown;
+pub mod media;
 pub mod movement;
+pub mod pacing;
+pub mod parts;
+#[cfg(feature = "cli")]
+pub mod planner;
+#[cfg(feature = "cli")]
+pub mod pr;
+#[cfg(feature = "cli")]
+pub mod qr;
+pub mod render;
 pub mod rep_types;
+#[cfg(feature = "cli")]
+pub mod results;
 pub mod rm;
+pub mod rpe;
+pub mod scaling;
+#[cfg(feature = "cli")]
+pub mod serve;
+pub mod templates;
+pub mod timer;
+pub mod units;
+#[cfg(feature = "cli")]
+pub mod volume;
+pub mod warmup;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod watch;
 pub mod weight;
 pub mod workout;
 pub mod workout_types;
 
+pub use self::error::WodError;
 pub use self::movement::{Movement, MovementParseError};
 pub use self::rm::RM;
+pub use self::rpe::Rpe;
 pub use self::weight::Weight;
-pub use self::workout::{create_workout, Workout};
+pub use self::workout::{create_workout, Prescription, Workout};
 
 pub use self::workout_types::{
-    amrap::AMRAP, emom::EMOM, every::Every, for_time::ForTime, workout_type::WorkoutType,
+    amrap::AMRAP,
+    emom::EMOM,
+    every::Every,
+    for_load::{ForLoad, LoadScore},
+    for_time::ForTime,
+    tabata::Tabata,
+    workout_type::{Kind, WorkoutType},
 };
 
 pub use self::rep_types::{
@@ -22,30 +91,257 @@ pub use self::rep_types::{
     time::Time,
 };
 
-use std::error::Error;
-use std::fmt;
+use crate::render::Renderer;
+#[cfg(feature = "cli")]
+use crate::units::LoadUnit;
 use std::fs;
+#[cfg(feature = "cli")]
 use std::fs::File;
 use std::fs::OpenOptions;
+#[cfg(feature = "cli")]
+use std::io::BufRead;
 use std::io::Write;
-use std::io::{self, BufRead};
-use std::path::PathBuf;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 
+#[cfg(feature = "date")]
 use chrono::Local;
+use chrono::{Datelike, NaiveDate};
 
-/// Returns today's date as "YYYY-MM-DD"
-pub fn today() -> String {
-    Local::now().format("%Y-%m-%d").to_string()
+/// Returns today's date, in local time.
+#[cfg(feature = "date")]
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
 }
 
+/// Template used for the filenames when none is configured.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "wod-{date}";
+
 /// Get the default filename for the workout of the day
 /// The filename is in the format "wod-<date>", without
 /// the extension that will be added later
+#[cfg(feature = "date")]
 pub fn default_filename() -> String {
     // format!("wod-{}.md", today())
-    format!("wod-{}", today())
+    expand_filename(DEFAULT_FILENAME_TEMPLATE, today()).unwrap()
+}
+
+/// Expands the placeholders of a filename template for a given date.
+///
+/// The placeholders available are `{date}` (YYYY-MM-DD), `{year}`, `{month}` and `{day}`.
+/// The template may contain directories, and shouldn't contain the extension of the file.
+///
+/// # Arguments
+///
+/// * `template` - The template to expand, i.e. "wod/{year}/{month}/{date}".
+/// * `date` - The date of the file.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The filename, or `WodError::Invalid` if the
+///   template contains an unknown placeholder.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wod::expand_filename;
+///
+/// let date = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+/// assert_eq!(expand_filename("{date}-wod", date).unwrap(), "2025-03-21-wod");
+/// assert_eq!(
+///     expand_filename("wod/{year}/{month}/{date}", date).unwrap(),
+///     "wod/2025/03/2025-03-21"
+/// );
+/// assert!(expand_filename("wod-{week}", date).is_err());
+/// ```
+pub fn expand_filename(template: &str, date: NaiveDate) -> Result<String, WodError> {
+    expand_placeholders(template, "filename template", |name| {
+        date_placeholder(name, date)
+    })
+}
+
+/// Expands the placeholders of the title format of the configuration for a file.
+///
+/// The placeholders are the ones of [`expand_filename`], plus `{weekday}` (the name
+/// of the day in the current language, see [`i18n`]) and `{name}`, the name of the
+/// file without the "wod-" prefix.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wod::expand_title;
+///
+/// let date = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+/// assert_eq!(
+///     expand_title("WOD – {weekday} {date}", date, "2025-03-21").unwrap(),
+///     "WOD – Friday 2025-03-21"
+/// );
+/// assert_eq!(expand_title("Programming for {name}", date, "open").unwrap(), "Programming for open");
+/// ```
+pub fn expand_title(format: &str, date: NaiveDate, name: &str) -> Result<String, WodError> {
+    expand_placeholders(format, "title format", |placeholder| match placeholder {
+        "weekday" => Some(i18n::weekday(date.weekday())),
+        "name" => Some(name.to_string()),
+        other => date_placeholder(other, date),
+    })
+}
+
+/// The value of the date placeholders shared by the filenames and the titles.
+fn date_placeholder(name: &str, date: NaiveDate) -> Option<String> {
+    let format = match name {
+        "date" => "%Y-%m-%d",
+        "year" => "%Y",
+        "month" => "%m",
+        "day" => "%d",
+        _ => return None,
+    };
+    Some(date.format(format).to_string())
+}
+
+/// Replaces the `{name}` placeholders of a template with their `value`, `kind` is
+/// the name of the template in the errors.
+fn expand_placeholders(
+    template: &str,
+    kind: &str,
+    value: impl Fn(&str) -> Option<String>,
+) -> Result<String, WodError> {
+    let mut expanded = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        let end = rest[start..].find('}').ok_or_else(|| {
+            WodError::Invalid(format!("Unclosed placeholder in {}: `{}`", kind, template))
+        })?;
+        let name = &rest[start + 1..start + end];
+        let value = value(name).ok_or_else(|| {
+            WodError::Invalid(format!("Unknown placeholder in {}: `{{{}}}`", kind, name))
+        })?;
+        expanded.push_str(&value);
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Directory where the local data (results, personal records...) is stored.
+///
+/// Defaults to "~/.wod", it can be changed with the `WOD_HOME` environment variable.
+pub fn wod_home() -> PathBuf {
+    if let Ok(home) = std::env::var("WOD_HOME") {
+        return PathBuf::from(home);
+    }
+    std::env::var("HOME")
+        .map(|home| PathBuf::from(home).join(".wod"))
+        .unwrap_or_else(|_| PathBuf::from(".wod"))
+}
+
+/// Directory where the workout files are written.
+///
+/// It's the first found of: the `--output-dir` given in the command line, the
+/// `output_dir` of the configuration, or `content/wod` if `cwd` is the root of a Hugo
+/// site. Otherwise an empty path is returned, so the files stay in the working directory.
+/// When the configuration has a `section`, the files are written to its directory
+/// instead, see [`hugo::section_dir`].
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use wod::config::Config;
+/// use wod::resolve_output_dir;
+///
+/// let config = Config::default();
+/// assert_eq!(resolve_output_dir(Some(Path::new("wods")), &config, Path::new(".")), PathBuf::from("wods"));
+/// assert_eq!(resolve_output_dir(None, &config, Path::new("/not/a/site")), PathBuf::new());
+/// ```
+#[cfg(feature = "cli")]
+pub fn resolve_output_dir(
+    output_dir: Option<&Path>,
+    config: &config::Config,
+    cwd: &Path,
+) -> PathBuf {
+    let output_dir = output_dir
+        .map(Path::to_path_buf)
+        .or_else(|| config.output_dir.clone())
+        .or_else(|| hugo::content_dir(cwd))
+        .unwrap_or_default();
+    match &config.section {
+        Some(section) => hugo::section_dir(&output_dir, section),
+        None => output_dir,
+    }
+}
+
+/// The section of the site for the workouts of a wod file.
+///
+/// A line `# section: <name>` in the file sets it explicitly, otherwise it's the
+/// section given in the `sections` of the configuration to the kind of the workouts,
+/// when all of them are routed to the same one.
+///
+/// # Arguments
+///
+/// * `wodfile` - Path to the wod file.
+/// * `config` - The configuration, with the section per kind of workout.
+///
+/// # Returns
+///
+/// * `Result<Option<String>, WodError>` - The section, `None` to use the default
+///   one, or an error if the file can't be read.
+#[cfg(feature = "cli")]
+pub fn wodfile_section(
+    wodfile: &Path,
+    config: &config::Config,
+) -> Result<Option<String>, WodError> {
+    let content = fs::read_to_string(wodfile)?;
+    let directive = wodfile_directive(content.lines(), "section");
+    if directive.is_some() {
+        return Ok(directive);
+    }
+
+    let mut sections = content
+        .lines()
+        .filter(|line| !is_wodfile_comment(line))
+        .filter_map(|line| {
+            let (workout, _, _) = parse_wodfile_line(line).ok()?;
+            parts::create_workouts(workout, None, None).ok()
+        })
+        .flatten()
+        .map(|workout| config.sections.get(workout.workout_type.kind().as_str()));
+    let first = sections.next().flatten();
+    Ok(first
+        .filter(|first| sections.all(|section| section == Some(*first)))
+        .cloned())
+}
+
+/// The directives of a wod file, comments like `# section: competition` that apply to
+/// the whole file.
+pub(crate) const WODFILE_DIRECTIVES: &[&str] = &["section", "level"];
+
+/// The value of a directive of a wod file, a line like `# section: competition`.
+pub(crate) fn wodfile_directive<'a>(
+    mut lines: impl Iterator<Item = &'a str>,
+    name: &str,
+) -> Option<String> {
+    lines.find_map(|line| {
+        let (key, value) = line.strip_prefix('#')?.split_once(':')?;
+        (key.trim() == name).then(|| value.trim().to_string())
+    })
+}
+
+/// Whether a line of a wod file is a comment (or a directive like `# section:`).
+pub(crate) fn is_wodfile_comment(line: &str) -> bool {
+    line.trim_start().starts_with('#')
+}
+
+/// Whether a line of a wod file separates the workouts of two days, a line of dashes
+/// like `---`, see [`run_split`].
+pub(crate) fn is_day_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && line.chars().all(|c| c == '-')
 }
 
+#[cfg(feature = "cli")]
 fn get_languages(languages: &str) -> Vec<String> {
     languages.split(',').map(|s| s.to_string()).collect()
 }
@@ -54,6 +350,9 @@ fn get_languages(languages: &str) -> Vec<String> {
 /// or multiple equal files if "languages" is provided.
 ///
 /// This function ensures that the file has a `.md` extension and creates it if it doesn't already exist.
+/// The file starts with the front matter template of the configuration, where `{{title}}`,
+/// `{{date}}`, `{{languages}}`, `{{draft}}` and `{{published}}` are replaced, see
+/// [`front_matter::expand`].
 /// If `languages` is provided, it will generate a default file with `.md` extension,
 /// plus one for each one of the languages.
 /// If the file exists and `force` is `false`, it will print a message and do nothing.
@@ -63,66 +362,85 @@ fn get_languages(languages: &str) -> Vec<String> {
 ///
 /// * `filename` - A mutable `PathBuf` representing the path to the file.
 /// * `force` - A reference to a boolean indicating whether to overwrite the file if it exists.
-/// * `date` - Optional date to include in the file metadata. This file will be
-///     used by Hugo to sort the pages. If not given, the current day will be used.
+/// * `date` - The date to include in the file metadata, used by Hugo to sort
+///   the pages. The CLI fills it with the current day by default.
 /// * `languages` - A list of ISO languages. This field is optional, if given, will be
-///     used to generate duplicates of the file with the language extension so they
-///     can be rendered in the Hugo blog. i.e. "es,it" for italian and spanish. English
-///     will be generated by default, without extension. If "en,es" for example is passed,
-///     a file without extension will represent "en", and another with file extension
-///     "es.md" will correspond to the spanish one.
+///   used to generate duplicates of the file with the language extension so they
+///   can be rendered in the Hugo blog. i.e. "es,it" for italian and spanish. English
+///   will be generated by default, without extension. If "en,es" for example is passed,
+///   a file without extension will represent "en", and another with file extension
+///   "es.md" will correspond to the spanish one.
+/// * `config` - The configuration, with the template of the front matter, whether
+///   the files are drafts, and the format of the title, slug and url of the pages.
 ///
 /// # Returns
 ///
-/// * `Result<(), Box<dyn std::error::Error>>` - Returns `Ok(())` if the file is successfully created or already exists.
-///   Returns an error if there are any issues creating the directory or writing to the file.
+/// * `Result<(), WodError>` - Returns `Ok(())` if the file is successfully created or already exists.
+///   Returns an error if there are any issues reading the template, creating the directory
+///   or writing to the file.
 ///
 /// # Examples
 ///
 /// ```
 /// use std::path::PathBuf;
-/// use wod::{run_base, today};
+/// use chrono::NaiveDate;
+/// use wod::config::Config;
+/// use wod::run_base;
 ///
 /// // Example usage
 /// let mut filename = PathBuf::from("workout.md");
 /// let force = false;
-/// let date = today();
+/// let date = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
 /// let languages: Option<String> = None; // Or Some("es".to_string) for english and spanish files
 ///
-/// // match run_base(filename, &force, date, languages) {
+/// // match run_base(filename, &force, date, languages, &Config::default()) {
 /// //     Ok(_) => println!("File created successfully or already exists."),
 /// //     Err(e) => eprintln!("Error: {}", e),
 /// // }
 /// ```
+#[cfg(feature = "cli")]
 pub fn run_base(
     filename: PathBuf,
     force: &bool,
-    date: String,
+    date: NaiveDate,
     languages: Option<String>,
-) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    config: &config::Config,
+) -> Result<Vec<PathBuf>, WodError> {
     let mut filenames: Vec<PathBuf> = Vec::new();
-    let langs = languages.map_or_else(
-        || vec!["en".to_string()],
-        |lang| get_languages(lang.as_str()),
-    );
+    let langs = languages
+        .as_deref()
+        .map_or_else(|| vec!["en".to_string()], get_languages);
+    let template = match &config.front_matter_template {
+        Some(path) => fs::read_to_string(path).map_err(|err| {
+            WodError::Invalid(format!(
+                "Can't read the front matter template {}: {}",
+                path.display(),
+                err
+            ))
+        })?,
+        None => config.profile.template().to_string(),
+    };
 
     // Creates a markdown file with the Hugo expected metadata.
     fn create_file(
         filename: &PathBuf,
         force: &bool,
-        date: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        template: &str,
+        values: &[(&str, &str)],
+        fields: &[(&str, String)],
+        intro: &str,
+    ) -> Result<(), WodError> {
         if filename.exists() && !force {
             println!("File '{}' already exists", filename.display());
             // Don't recreate the file
             return Ok(());
         }
 
-        let dir_path = filename.parent().unwrap();
-
         // Create parent dir if doesn't exist
-        if !dir_path.exists() {
-            fs::create_dir_all(dir_path)?;
+        if let Some(dir_path) = filename.parent() {
+            if !dir_path.exists() {
+                fs::create_dir_all(dir_path)?;
+            }
         }
         // Create the file
         let mut file = OpenOptions::new()
@@ -131,34 +449,17 @@ pub fn run_base(
             .truncate(true)
             .open(filename)?;
 
-        let title = filename
-            .file_name()
-            .unwrap()
-            .to_string_lossy()
-            .split('.')
-            .next()
-            .unwrap_or("")
-            .replace("wod-", "");
-
         // Write the markdown header of the file
-        file.write_all(
-            format!(
-                r#"---
-title: "{}"
-date: {}
-draft: false
----
-
-Workout for the day, {}.
-
-"#,
-                title, date, title
-            )
-            .as_bytes(),
-        )?;
+        let mut header = front_matter::expand(template, values)?;
+        for (key, value) in fields {
+            header = front_matter::set_field(&header, key, &format!("\"{}\"", value));
+        }
+        file.write_all(format!("{}\n\n{}", header.trim_end(), intro).as_bytes())?;
         Ok(())
     }
 
+    let day = date.to_string();
+
     for lang in langs {
         // Check if the filename has an extension:
         // If the language is english, just set .md as the file extension,
@@ -177,7 +478,38 @@ Workout for the day, {}.
             filename
         };
 
-        create_file(&lang_filename, force, date.clone())?;
+        let name = lang_filename
+            .file_name()
+            .ok_or_else(|| format!("Invalid filename: {}", lang_filename.display()))?
+            .to_string_lossy()
+            .split('.')
+            .next()
+            .unwrap_or("")
+            .replace("wod-", "");
+        let title = match &config.title_format {
+            Some(format) => i18n::with_language(&lang, || expand_title(format, date, &name))?,
+            None => name.clone(),
+        };
+        let mut fields = Vec::new();
+        for (key, format) in [("slug", &config.slug), ("url", &config.url)] {
+            if let Some(format) = format {
+                fields.push((config.profile.key(key), expand_title(format, date, &name)?));
+            }
+        }
+        let values = [
+            ("title", title.as_str()),
+            ("date", day.as_str()),
+            ("languages", languages.as_deref().unwrap_or("en")),
+            ("draft", if config.draft { "true" } else { "false" }),
+            ("published", if config.draft { "false" } else { "true" }),
+        ];
+        // The line of the equipment is filled as the workouts are added
+        let intro = if config.equipment {
+            format!("{}\n\n", EQUIPMENT_MARKER)
+        } else {
+            String::new()
+        };
+        create_file(&lang_filename, force, &template, &values, &fields, &intro)?;
         filenames.push(lang_filename);
     }
 
@@ -191,17 +523,28 @@ Workout for the day, {}.
 /// and appends it if the file already exists. If the file does not exist, it will
 /// be created.
 ///
+/// If the file already contains the same workout it's not added again, unless `force` is used.
+/// The tags of the workout are added to the `tags` of the front matter, see [`Workout::tags`].
+///
 /// # Arguments
 ///
 /// * `filename` - A `PathBuf` representing the path to the file where the workout
-///                will be appended.
+///   will be appended.
 /// * `workout` - A string slice representing the workout to be added.
+/// * `media` - Videos and images shown under the workout, see [`media::Media`]. An
+///   entry with parts gets them under the last one.
+/// * `scaling` - The scaled versions rendered under the workout, see [`scaling::Scaling`].
+/// * `markdown` - The markdown of the workout, see [`config::Config::markdown`].
+/// * `position` - Where to place the workout in the file, the end of it by default.
+///   Any other position requires the file to exist.
+/// * `force` - Add the workout even if the file already contains it.
 ///
 /// # Returns
 ///
-/// * `Result<(), Box<dyn std::error::Error>>` - Returns `Ok(())` if the operation
-///                                              is successful, or an error if
-///                                              something goes wrong.
+/// * `Result<Option<String>, WodError>` - Returns the markdown block added
+///   if the operation is successful (`None` if it was skipped as a duplicate), or an error if
+///   something goes wrong: a parse error if the workout can't be parsed,
+///   `WodError::Io` if the file can't be written, or if the position is not found.
 ///
 /// # Examples
 ///
@@ -214,262 +557,2583 @@ Workout for the day, {}.
 /// // let comments = None;
 /// // let name = None;
 /// // run_add_workout(filename.clone(), workout).expect("Failed to add workout");
+#[allow(clippy::too_many_arguments)]
 pub fn run_add_workout(
     filename: PathBuf,
     workout: &str,
     comments: Option<String>,
     name: Option<String>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let wkt = create_workout(workout, comments, name);
-    let content: String = match wkt {
-        Ok(wkt) => wkt.write(),
-        Err(e) => {
-            eprintln!("While reading workout: '{}'", workout);
-            eprintln!("Error: {:#?}", e);
-            std::process::exit(1);
-        }
-    };
+    media: &[media::Media],
+    scaling: &scaling::Scaling,
+    markdown: &render::Markdown,
+    position: &document::Position,
+    force: bool,
+) -> Result<Option<String>, WodError> {
+    let (workouts, blocks): (Vec<Workout>, Vec<String>) =
+        render_for_file(workout, comments, name, media, scaling, markdown, &filename)?
+            .into_iter()
+            .unzip();
+    let content = blocks.concat();
+
+    if !force
+        && filename.exists()
+        && document::Document::parse(&fs::read_to_string(&filename)?).contains(&content)
+    {
+        return Ok(None);
+    }
 
-    let mut file = OpenOptions::new()
-        .append(true)
-        .create(true)
-        .open(filename)?;
+    let existing = filename.exists() && {
+        let doc = document::Document::parse(&fs::read_to_string(&filename)?);
+        !doc.blocks().is_empty()
+    };
+    if *position != document::Position::End {
+        if !filename.exists() {
+            return Err(WodError::Invalid(format!(
+                "{} doesn't exist, the workout can only be added at the end",
+                filename.display()
+            )));
+        }
+        let mut doc = document::Document::parse(&fs::read_to_string(&filename)?);
+        doc.insert(position, content.clone())?;
+        fs::write(&filename, doc.to_string())?;
+    } else {
+        // The output directory of a new site, i.e. "content/wod", may not exist yet
+        if let Some(dir_path) = filename.parent() {
+            if !dir_path.exists() {
+                fs::create_dir_all(dir_path)?;
+            }
+        }
+        let mut file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&filename)?;
 
-    file.write_all(content.as_bytes())?;
+        file.write_all(content.as_bytes())?;
+    }
+    for wkt in &workouts {
+        add_taxonomies(&filename, wkt, scaling)?;
+    }
+    if existing {
+        touch_lastmod(&filename)?;
+    }
 
-    Ok(())
+    Ok(Some(content))
 }
 
-/// Creates a WOD from a file containing the workouts line by line.
+/// Adds a workout to an Obsidian daily note, at the end of the section of a heading.
 ///
-/// This function takes a `filename` and a `wodfile`, creates a workout.
-/// using the `create_workout` function, writes the workout to the specified file,
-/// and appends it if the file already exists. If the file does not exist, it will
-/// be created.
+/// The workout is rendered with [`render::Obsidian`], linking the movements to their
+/// notes. The note is created if it doesn't exist, and the heading is added at the
+/// end of the note if it's missing.
 ///
 /// # Arguments
 ///
-/// * `filename` - A `PathBuf` representing the path to the file where the workout
-///                will be appended.
-/// * `wodfile` - A `PathBuf` representing the path to the file containing the workouts.
-/// * `date` - A date that will be used as in Hugo's metadata to sort the files.
-///     It must be informed in "YYYY-MM-DD", the CLI will fill this value with the current
-///     day by default.
-/// * `languages` - A comma separated list of languages, optional. If not informed
-///     a single file will be generated without a language extension, corresponding to english,
-///     otherwise, there will be created as much filenames as languages. i.e. "en,es" will
-///     generate 2 copies of the filenames, one with ".md" and other with ".es.md" file
-///     extension.
+/// * `note` - Path to the daily note, see [`config::Obsidian::note`].
+/// * `heading` - The heading the workout is added under, i.e. "## Workout".
+/// * `workout` - The workout to add.
+/// * `comments` - Optional comments for the workout.
+/// * `name` - Optional name for the workout.
+/// * `scaling` - The scaled versions rendered under the workout, see [`scaling::Scaling`].
 ///
 /// # Returns
 ///
-/// * `Result<(), Box<dyn std::error::Error>>` - Returns `Ok(())` if the operation
-///                                              is successful, or an error if
-///                                              something goes wrong.
+/// * `Result<Option<String>, WodError>` - The block added, `None` if the note
+///   already contains it, or an error if the workout can't be parsed or the note
+///   can't be read or written.
 ///
 /// # Examples
 ///
-/// Suppose we have a file named `example_wod.wod` with the following content:
-///
-/// ```text
-/// wl 3x(2+1) clean, split jerk @85%
-/// wl 4x2 front squat @85%
-/// wl 3x4 push press @75%
-/// ft 21 pull up, 42 du, 21 thruster @43kg, 18 chest to bar, 36 du, 18 thruster @51kg, 15 bar mu, 30 du, 15 thruster @61kg
-/// ```
-///
 /// ```
 /// use std::path::PathBuf;
-/// use wod::run_add_wod_from_file;
+/// use wod::run_add_daily_note;
+/// use wod::scaling::Scaling;
 ///
-/// // let filename = PathBuf::from("workouts.md");
-/// // let wodfile = PathBuf::from(".example_wod.wod");
-/// // run_add_wod_from_file(filename.clone(), wodfile.clone(), "2025-03-19".to_string()).expect("Failed create WOD from file");
-pub fn run_add_wod_from_file(
-    filename: PathBuf,
-    wodfile: PathBuf,
-    date: String,
-    languages: Option<String>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // If languages was used, more than one filename will be generated, and
-    // we have to keep track of those when adding the workouts
-    let filenames = run_base(filename.clone(), &true, date, languages)?;
-    let lines = read_wodfile(wodfile)?;
-
-    fn parse_line(line: &str) -> Result<(&str, Option<String>, Option<String>), WodFileError> {
-        let sections: Vec<&str> = line.split('|').collect();
-        let (workout, comments, name) = match sections.len() {
-            1 => (sections[0], None, None),
-            2 => (
-                sections[0],
-                if sections[1].is_empty() {
-                    None
-                } else {
-                    Some(sections[1].to_string())
-                },
-                None,
-            ),
-            3 => (
-                sections[0],
-                if sections[1].is_empty() {
-                    None
-                } else {
-                    Some(sections[1].to_string())
-                },
-                if sections[2].is_empty() {
-                    None
-                } else {
-                    Some(sections[2].to_string())
-                },
-            ),
-            _ => {
-                return Err(WodFileError::InvalidFile(format!(
-                    "Invalid format, expected 1-3 parts, got {}, content: '{}'",
-                    sections.len(),
-                    line
-                )))
-            }
-        };
+/// // let note = PathBuf::from("vault/2025-03-21.md");
+/// // run_add_daily_note(note, "## Workout", "wl 5x5 back squat", None, None, &Scaling::default())?;
+/// ```
+pub fn run_add_daily_note(
+    note: PathBuf,
+    heading: &str,
+    workout: &str,
+    comments: Option<String>,
+    name: Option<String>,
+    scaling: &scaling::Scaling,
+) -> Result<Option<String>, WodError> {
+    let mut block = String::new();
+    for mut wkt in parts::create_workouts(workout, comments, name)? {
+        for level in &scaling.levels {
+            wkt.add_scaled(*level, scaling);
+        }
+        block.push_str(&render::Obsidian.render(&wkt));
+    }
 
-        Ok((workout, comments, name))
+    let content = if note.exists() {
+        fs::read_to_string(&note)?
+    } else {
+        String::new()
+    };
+    if content.contains(&block) {
+        return Ok(None);
     }
+    if let Some(dir) = note.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&note, insert_under_heading(&content, heading, &block))?;
+    Ok(Some(block))
+}
 
-    for line in lines.map_while(Result::ok) {
-        match parse_line(&line) {
-            Ok((workout, comments, name)) => {
-                // To avoid rereading the file, wite the workout to each of the filenames
-                for fname in filenames.iter() {
-                    run_add_workout(fname.clone(), workout, comments.clone(), name.clone())?;
-                }
-            }
-            Err(err) => {
-                eprintln!("Error parsing line. {}", err);
-            }
+/// Inserts a block at the end of the section of a heading, which ends at the next
+/// heading of the same level or above. The heading is added if it's missing.
+fn insert_under_heading(content: &str, heading: &str, block: &str) -> String {
+    let heading = heading.trim();
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let Some(start) = lines.iter().position(|line| line.trim() == heading) else {
+        let mut note = content.trim_end().to_string();
+        if !note.is_empty() {
+            note.push_str("\n\n");
         }
+        note.push_str(&format!("{}\n\n{}", heading, block));
+        return note;
+    };
+
+    let level = match heading.chars().take_while(|c| *c == '#').count() {
+        0 => 6,
+        level => level,
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| {
+            let hashes = line.chars().take_while(|c| *c == '#').count();
+            (1..=level).contains(&hashes) && line[hashes..].starts_with(' ')
+        })
+        .map_or(lines.len(), |i| start + 1 + i);
+
+    let mut note = lines[..start].concat();
+    note.push_str(lines[start..end].concat().trim_end());
+    note.push_str("\n\n");
+    note.push_str(block);
+    if end < lines.len() {
+        note.push('\n');
+        note.push_str(&lines[end..].concat());
+    }
+    note
+}
+
+/// Sets the `lastmod` field of the front matter to today, for the files modified
+/// after they were written. Nothing is done if the date can't be known (without
+/// the `date` feature).
+fn touch_lastmod(filename: &Path) -> Result<(), WodError> {
+    let content = fs::read_to_string(filename)?;
+    let updated = with_lastmod(&content);
+    if updated != content {
+        fs::write(filename, updated)?;
     }
-    println!("Created filenames from WOD file: {}", filename.display());
     Ok(())
 }
 
-fn read_wodfile(filename: PathBuf) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+/// The content of a file with the `lastmod` of the front matter set to today, see
+/// [`touch_lastmod`].
+fn with_lastmod(content: &str) -> String {
+    match current_date() {
+        Ok(today) => {
+            let key = front_matter::Profile::detect(content).key("lastmod");
+            front_matter::set_field(content, key, &today.to_string())
+        }
+        Err(_) => content.to_string(),
+    }
 }
 
-#[derive(Debug)]
-enum WodFileError {
-    InvalidFile(String),
+/// Comment that marks the line of the equipment of the day in the pages, see
+/// [`config::Config::equipment`].
+const EQUIPMENT_MARKER: &str = "<!-- equipment -->";
+
+/// The content of a file with the equipment of a workout (see [`Workout::equipment`])
+/// added to the line of the equipment of the day, if the file has one. The line is
+/// written in the current language.
+fn with_equipment(content: &str, workout: &Workout) -> String {
+    let items = workout.equipment().into_iter().map(|item| item.to_string());
+    add_equipment(content, items)
 }
 
-impl Error for WodFileError {}
+/// The line of the equipment of the day of a file and the equipment listed in it,
+/// `None` if the file has no such line. The line is read in the current language.
+fn equipment_line(content: &str) -> Option<(String, Vec<String>)> {
+    let doc = document::Document::parse(content);
+    let line = doc
+        .preamble()
+        .lines()
+        .find(|line| line.trim_end().ends_with(EQUIPMENT_MARKER))?;
+
+    let template = i18n::text(i18n::Message::Equipment, &[&"{}"]);
+    let (prefix, suffix) = template.split_once("{}").unwrap_or((&template, ""));
+    let listed = line
+        .trim_end()
+        .trim_end_matches(EQUIPMENT_MARKER)
+        .trim_end();
+    let equipment = listed
+        .strip_prefix(prefix)
+        .and_then(|listed| listed.strip_suffix(suffix))
+        .map_or_else(Vec::new, |listed| {
+            listed.split(", ").map(str::to_string).collect()
+        });
+    Some((line.to_string(), equipment))
+}
 
-impl fmt::Display for WodFileError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            WodFileError::InvalidFile(s) => write!(f, "Invalid wodfile: {}", s),
+/// The content of a file with the `items` it doesn't list yet added to the line of
+/// the equipment of the day, if the file has one.
+fn add_equipment(content: &str, items: impl IntoIterator<Item = String>) -> String {
+    let Some((line, mut equipment)) = equipment_line(content) else {
+        return content.to_string();
+    };
+    let mut added = false;
+    for item in items {
+        if !equipment.contains(&item) {
+            equipment.push(item);
+            added = true;
         }
     }
+    if !added {
+        return content.to_string();
+    }
+    let updated = format!(
+        "{} {}",
+        i18n::text(i18n::Message::Equipment, &[&equipment.join(", ")]),
+        EQUIPMENT_MARKER
+    );
+    content.replacen(&line, &updated, 1)
 }
 
-/// Generates a list of movements with explanatory videos in markdown format.
-///
-/// This function takes a `page` boolean to determine whether to create a markdown
-/// page or just list the links. To generate a file just redirect the content.
-///
-/// # Arguments
-///
-/// * `page` - A `bool` to decide whether this is a markdown page prepared for a Hugo blog
-///     or a list of markdown links.
-///
-/// # Returns
-///
-/// * `String` - The content as a string.
-///
-/// # Examples
-///
-/// Print the content to the console:
-///
-/// ```
-/// use wod::run_create_list_movements;
-///
-/// let movement_list = run_create_list_movements(false);
-/// let air_squat = movement_list.split("\n\n").next().unwrap();
-/// assert_eq!(
-///     air_squat,
-///     "- [Air Squat](https://www.crossfit.com/essentials/the-air-squat)".to_string()
-/// );
-pub fn run_create_list_movements(page: bool) -> String {
-    let mut content: String = "".to_string();
-    if page {
-        content.push_str(
-            r#"---
-title: "CrossFit Movements"
-description: "List of movements with explanatory video"
----
+/// Parses a workout and renders it as it's written to a file: with the scaled versions
+/// of the configuration, in the language of the file. An entry with parts (see
+/// [`parts`]) gives a workout and a block per part, the media go under the last one.
+fn render_for_file(
+    workout: &str,
+    comments: Option<String>,
+    name: Option<String>,
+    media: &[media::Media],
+    scaling: &scaling::Scaling,
+    markdown: &render::Markdown,
+    filename: &Path,
+) -> Result<Vec<(Workout, String)>, WodError> {
+    let language = file_language(filename);
+    let mut workouts = parts::create_workouts(workout, comments, name)?;
+    if let Some(last) = workouts.last_mut() {
+        for m in media {
+            last.add_media(m.clone());
+        }
+    }
+    let rendered = workouts
+        .into_iter()
+        .map(|mut wkt| {
+            for level in &scaling.levels {
+                wkt.add_scaled(*level, scaling);
+            }
+            let content = i18n::with_language(&language, || markdown.render(&wkt));
+            (wkt, content)
+        })
+        .collect();
+    Ok(rendered)
+}
 
-List of CrossFit movements, click on them to see an explanation.
+/// Adds the tags of a workout (see [`Workout::tags`]) to the ones in the front matter
+/// of a file, so Hugo can list the days by movement or type of workout, and raises
+/// the `level` of the day to the one of the workout (see [`Workout::level`]). The
+/// equipment of the workout is added to the line of the equipment of the day.
+fn add_taxonomies(
+    filename: &Path,
+    workout: &Workout,
+    scaling: &scaling::Scaling,
+) -> Result<(), WodError> {
+    let content = fs::read_to_string(filename)?;
+    let updated = i18n::with_language(&file_language(filename), || {
+        with_taxonomies(&content, workout, scaling)
+    });
+    if updated != content {
+        fs::write(filename, updated)?;
+    }
+    Ok(())
+}
 
----
+/// The content of a file with the taxonomies of a workout added to its front matter,
+/// see [`add_taxonomies`].
+fn with_taxonomies(content: &str, workout: &Workout, scaling: &scaling::Scaling) -> String {
+    let profile = front_matter::Profile::detect(content);
+    let mut tags = front_matter::get_list(content, profile.key("tags"));
+    let new_tags: Vec<String> = workout
+        .tags()
+        .into_iter()
+        .filter(|tag| !tags.contains(tag))
+        .collect();
+    let mut updated = content.to_string();
+    if !new_tags.is_empty() {
+        tags.extend(new_tags);
+        let tags = front_matter::format_list(&tags);
+        updated = front_matter::set_field(&updated, profile.key("tags"), &tags);
+    }
 
-"#,
+    let level = front_matter::get_field(content, profile.key("level"))
+        .and_then(|level| level.parse::<scaling::Difficulty>().ok())
+        .map_or(workout.level(scaling), |level| {
+            level.max(workout.level(scaling))
+        });
+    let updated =
+        front_matter::set_field(&updated, profile.key("level"), &format!("\"{}\"", level));
+    with_equipment(&updated, workout)
+}
+
+/// The language of a markdown file from its extension, "<name>.es.md" is written in
+/// spanish, and "<name>.md" in the default language.
+fn file_language(filename: &Path) -> String {
+    filename
+        .file_stem()
+        .map(Path::new)
+        .and_then(Path::extension)
+        .map_or_else(
+            || i18n::DEFAULT_LANGUAGE.to_string(),
+            |lang| lang.to_string_lossy().to_string(),
         )
-    }
-    content.push_str(
-        Movement::list_with_url()
-            .iter()
-            .filter_map(|(key, value)| {
-                if value.is_empty() {
-                    None
-                } else {
-                    Some(format!("- [{}]({})", key, value))
-                }
-            })
-            .collect::<Vec<_>>()
-            .join("\n\n")
-            .as_str(),
-    );
-    content
 }
 
-/// Generates a textual representation of a given workout in markdown format.
-///
-/// This function takes a workout and attempts to
-/// parse it into a valid workout structure. If successful, it returns a markdown-formatted string
-/// representing the workout. If parsing fails, it returns the error as a string for
-/// the caller to handle or display.
+/// Renders a workout in the given format, without writing it to any file.
 ///
 /// # Arguments
 ///
-/// * `workout` - A `&str` representing the wod to render.
+/// * `workout` - A string slice representing the workout.
+/// * `comments` - Optional comments for the workout.
+/// * `name` - Optional name for the workout.
+/// * `format` - The output format, markdown, json, plain text, html or a checklist.
 ///
 /// # Returns
 ///
-/// * `Result<String, String>` - The markdown string on success,
-///   or a human-readable error message on failure.
+/// * `Result<String, WodError>` - The rendered workout, or the error found parsing it.
 ///
 /// # Examples
 ///
 /// ```
-/// use wod::run_check_wod;
+/// use wod::render::Format;
+/// use wod::run_render_workout;
 ///
-/// match run_check_wod("ft 21-15-9 pulup, thruster @ 43/30kg") {
-///     Ok(content) => println!("{}", content),
-///     Err(e) => eprintln!("Failed to parse workout: {}", e),
-/// }
+/// let html = run_render_workout("wl 5x5 back squat", None, None, Format::Html).unwrap();
+/// assert!(html.starts_with("<section class=\"wod\">"));
 /// ```
-pub fn run_check_wod(workout: &str) -> Result<String, String> {
-    let wkt = create_workout(workout, None, None)
-        .map_err(|e| format!("While reading workout '{}': {:#?}", workout, e))?;
-    Ok(wkt.write())
+pub fn run_render_workout(
+    workout: &str,
+    comments: Option<String>,
+    name: Option<String>,
+    format: render::Format,
+) -> Result<String, WodError> {
+    let wkt = create_workout(workout, comments, name)?;
+    Ok(format.renderer().render(&wkt))
 }
 
-#[cfg(test)]
-mod test_cmd {
-    use super::*;
-
-    #[test]
-    fn test_run_list_movements() {
-        let result = run_create_list_movements(false);
+/// Merges generated files into a single page, i.e. the AM and PM sessions of a day.
+///
+/// The files are ordered by the `date` of their front matter (keeping the order
+/// given for the same date), and the page takes the front matter and introduction
+/// of the first one, with the tags, level and equipment of all of them. The warm-ups
+/// go before the workouts, and the blocks repeated in several files are written once.
+///
+/// # Arguments
+///
+/// * `files` - The markdown files generated by `wod`.
+/// * `output` - The file to write the page to, it can be one of the `files`.
+/// * `force` - Overwrite the output if it exists and isn't one of the `files`.
+///
+/// # Returns
+///
+/// * `Result<usize, WodError>` - The number of blocks of the page, or an error if a
+///   file can't be read or the output exists.
+pub fn run_merge(files: &[PathBuf], output: &Path, force: bool) -> Result<usize, WodError> {
+    if output.exists() && !force && !files.iter().any(|f| f == output) {
+        return Err(WodError::Invalid(format!(
+            "File '{}' already exists, use --force to overwrite it",
+            output.display()
+        )));
+    }
+    let mut contents = files
+        .iter()
+        .map(fs::read_to_string)
+        .collect::<Result<Vec<_>, _>>()?;
+    // The files without a date go last
+    contents.sort_by_key(|content| {
+        let date = front_matter::get_field(content, "date");
+        (date.is_none(), date)
+    });
+    let Some(first) = contents.first() else {
+        return Err(WodError::Invalid("No files to merge".to_string()));
+    };
+
+    let mut page = document::Document::parse(first);
+    let (mut warmups, mut blocks) = (Vec::new(), Vec::new());
+    for content in &contents {
+        for block in document::Document::parse(content).blocks() {
+            let blocks = if warmup::is_warmup(block) {
+                &mut warmups
+            } else {
+                &mut blocks
+            };
+            if !blocks.contains(block) {
+                blocks.push(block.clone());
+            }
+        }
+    }
+    warmups.extend(blocks);
+    page.set_blocks(warmups);
+
+    // The tags and level are merged from the front matter, as not every workout can
+    // be read back from the markdown (see [`markdown`])
+    let mut content = page.to_string();
+    let profile = front_matter::Profile::detect(&content);
+    let mut tags: Vec<String> = Vec::new();
+    let mut level: Option<scaling::Difficulty> = None;
+    for other in &contents {
+        for tag in front_matter::get_list(other, profile.key("tags")) {
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        let other_level = front_matter::get_field(other, profile.key("level"))
+            .and_then(|level| level.parse().ok());
+        level = level.max(other_level);
+    }
+    if !tags.is_empty() {
+        let tags = front_matter::format_list(&tags);
+        content = front_matter::set_field(&content, profile.key("tags"), &tags);
+    }
+    if let Some(level) = level {
+        let level = format!("\"{}\"", level);
+        content = front_matter::set_field(&content, profile.key("level"), &level);
+    }
+    // And so is the equipment listed by the files
+    i18n::with_language(&file_language(output), || {
+        for other in &contents {
+            if let Some((_, equipment)) = equipment_line(other) {
+                content = add_equipment(&content, equipment);
+            }
+        }
+    });
+    fs::write(output, content)?;
+    Ok(page.blocks().len())
+}
+
+/// Removes from a file the last workout added with `wod add`.
+///
+/// The workouts added are tracked in the journal, so the right one is removed even
+/// if it was inserted with `--at` or `--before`. If the journal has no record for the
+/// file, the last workout of the file is removed.
+///
+/// # Arguments
+///
+/// * `filename` - A `PathBuf` pointing to the markdown file.
+/// * `journal` - Path to the journal with the workouts added.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The markdown block removed, or an
+///   error if the file can't be read, has no workouts, or the last workout added
+///   was edited afterwards.
+#[cfg(feature = "cli")]
+pub fn run_undo(filename: PathBuf, journal: &Path) -> Result<String, WodError> {
+    let mut doc = document::Document::parse(&fs::read_to_string(&filename)?);
+    let mut history = journal::Journal::load(journal)?;
+
+    let removed = match history.pop(&fs::canonicalize(&filename)?) {
+        Some(block) => {
+            // The entry is dropped even if the block is not found, it can't be undone anymore
+            history.save(journal)?;
+            if !doc.remove(&block) {
+                return Err(format!(
+                    "The last workout added to {} was modified, nothing was removed",
+                    filename.display()
+                )
+                .into());
+            }
+            block
+        }
+        None => doc
+            .pop()
+            .ok_or_else(|| format!("No workouts found in {}", filename.display()))?,
+    };
+
+    fs::write(&filename, doc.to_string())?;
+    Ok(removed)
+}
+
+/// Publishes a file, setting `draft: false` in its front matter, or sets it back
+/// as a draft. The files written for Jekyll use `published` instead, see
+/// [`front_matter::Profile`].
+///
+/// # Arguments
+///
+/// * `filename` - A `PathBuf` pointing to the markdown file.
+/// * `draft` - The value for the `draft` field, `false` to publish the file.
+///
+/// # Returns
+///
+/// * `Result<(), WodError>` - An error if the file can't be read or written,
+///   or it doesn't start with a front matter block.
+pub fn run_publish(filename: PathBuf, draft: bool) -> Result<(), WodError> {
+    let content = fs::read_to_string(&filename)?;
+    if !front_matter::has_front_matter(&content) {
+        return Err(format!("No front matter found in {}", filename.display()).into());
+    }
+    let updated = match front_matter::Profile::detect(&content) {
+        front_matter::Profile::Jekyll => {
+            front_matter::set_field(&content, "published", &(!draft).to_string())
+        }
+        _ => front_matter::set_field(&content, "draft", &draft.to_string()),
+    };
+    fs::write(&filename, updated)?;
+    Ok(())
+}
+
+/// Creates a WOD from a file containing the workouts line by line.
+///
+/// This function takes a `filename` and a `wodfile`, creates a workout.
+/// using the `create_workout` function, writes the workout to the specified file,
+/// and appends it if the file already exists. If the file does not exist, it will
+/// be created.
+///
+/// # Arguments
+///
+/// * `filename` - A `PathBuf` representing the path to the file where the workout
+///   will be appended.
+/// * `wodfile` - A `PathBuf` representing the path to the file containing the workouts.
+/// * `date` - A date that will be used as in Hugo's metadata to sort the files.
+///   The CLI will fill this value with the current day by default.
+/// * `languages` - A comma separated list of languages, optional. If not informed
+///   a single file will be generated without a language extension, corresponding to english,
+///   otherwise, there will be created as much filenames as languages. i.e. "en,es" will
+///   generate 2 copies of the filenames, one with ".md" and other with ".es.md" file
+///   extension.
+/// * `config` - The configuration, with the scaled versions rendered under every workout
+///   (see [`scaling::Scaling`]) and the template of the front matter.
+/// * `force` - Whether to overwrite the files if they exist. Otherwise the workouts
+///   are added to the existing files, skipping the ones already there, so running
+///   it again with the same `wodfile` leaves the files untouched.
+///
+/// With the `per_workout` of the configuration every workout is written to its own
+/// file instead, named after `filename` followed by the name of the workout, or its
+/// position in the file if it has none, i.e. "wod-2025-03-21-fran" and "wod-2025-03-21-2".
+///
+/// # Returns
+///
+/// * `Result<(), WodError>` - Returns `Ok(())` if the operation
+///   is successful, or an error if
+///   something goes wrong.
+///
+/// # Examples
+///
+/// Suppose we have a file named `example_wod.wod` with the following content:
+///
+/// ```text
+/// wl 3x(2+1) clean, split jerk @85%
+/// wl 4x2 front squat @85%
+/// wl 3x4 push press @75%
+/// ft 21 pull up, 42 du, 21 thruster @43kg, 18 chest to bar, 36 du, 18 thruster @51kg, 15 bar mu, 30 du, 15 thruster @61kg
+/// ```
+///
+/// ```
+/// use std::path::PathBuf;
+/// use wod::run_add_wod_from_file;
+///
+/// // let filename = PathBuf::from("workouts.md");
+/// // let wodfile = PathBuf::from(".example_wod.wod");
+/// // let date = NaiveDate::from_ymd_opt(2025, 3, 19).unwrap();
+/// // run_add_wod_from_file(filename.clone(), wodfile.clone(), date, None, &Config::default(), &false).expect("Failed create WOD from file");
+#[cfg(feature = "cli")]
+pub fn run_add_wod_from_file(
+    filename: PathBuf,
+    wodfile: PathBuf,
+    date: NaiveDate,
+    languages: Option<String>,
+    config: &config::Config,
+    force: &bool,
+) -> Result<(), WodError> {
+    let lines = read_wodfile(wodfile)?;
+    if config.per_workout {
+        // The directives apply to every workout, the whole file is needed to find them
+        let lines: Vec<(usize, String)> = lines.collect();
+        let (directives, workouts): (Vec<_>, Vec<_>) = lines
+            .iter()
+            .filter(|(_, line)| !line.trim().is_empty() && !is_day_separator(line))
+            .partition(|(_, line)| is_wodfile_comment(line));
+        for (i, (_, line)) in workouts.iter().enumerate() {
+            let name = parse_wodfile_line(line).ok().and_then(|(_, _, name)| name);
+            let part = name
+                .map(|name| slugify(&name))
+                .filter(|slug| !slug.is_empty())
+                .unwrap_or_else(|| (i + 1).to_string());
+            let filenames = run_base(
+                workout_filename(&filename, &part),
+                force,
+                date,
+                languages.clone(),
+                config,
+            )?;
+            let lines = directives.iter().chain(std::iter::once(&workouts[i]));
+            add_wodfile_lines(&filenames, lines.map(|line| (*line).clone()), config, force)?;
+        }
+        println!(
+            "Created filenames per workout from WOD file: {}",
+            filename.display()
+        );
+        return Ok(());
+    }
+
+    // If languages was used, more than one filename will be generated, and
+    // we have to keep track of those when adding the workouts
+    let filenames = run_base(filename.clone(), force, date, languages, config)?;
+    add_wodfile_lines(&filenames, lines, config, force)?;
+    println!("Created filenames from WOD file: {}", filename.display());
+    Ok(())
+}
+
+/// The file of a workout when every workout is written to its own file, the name
+/// of the file of the day followed by the part, i.e. "wod-2025-03-21-fran".
+#[cfg(feature = "cli")]
+fn workout_filename(filename: &Path, part: &str) -> PathBuf {
+    let stem = match filename.extension() {
+        Some(ext) if ext == "md" => filename.with_extension(""),
+        _ => filename.to_path_buf(),
+    };
+    let mut name = stem.into_os_string();
+    name.push(format!("-{}", part));
+    PathBuf::from(name)
+}
+
+/// The name of a workout as part of a filename, i.e. "Open 25.2" is "open-25-2".
+#[cfg(feature = "cli")]
+fn slugify(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Adds the workouts of the lines of a wod file to every one of the `filenames`.
+/// The lines, with their numbers, are processed as they are read. The ones that
+/// can't be parsed are reported and skipped, and a `# level:` line overrides the
+/// level given to the day from its workouts.
+///
+/// The workouts are parsed once, and every file is read and written once, with all
+/// the workouts added to it. Returns the files written, the ones that already had
+/// all the workouts are left untouched.
+#[cfg(feature = "cli")]
+fn add_wodfile_lines(
+    filenames: &[PathBuf],
+    lines: impl Iterator<Item = (usize, String)>,
+    config: &config::Config,
+    force: &bool,
+) -> Result<Vec<PathBuf>, WodError> {
+    let scaling = &config.scaling;
+    let markdown = config.markdown();
+    let mut workouts = Vec::new();
+    let mut level = None;
+    for (number, line) in lines {
+        if is_day_separator(&line) {
+            continue;
+        }
+        if is_wodfile_comment(&line) {
+            if level.is_none() {
+                level = wodfile_directive(std::iter::once(line.as_str()), "level");
+            }
+            continue;
+        }
+        match parse_wodfile_line(&line)
+            .and_then(|(workout, comments, name)| parts::create_workouts(workout, comments, name))
+        {
+            Ok(parts) => {
+                for mut workout in parts {
+                    for level in &scaling.levels {
+                        workout.add_scaled(*level, scaling);
+                    }
+                    workouts.push(workout);
+                }
+            }
+            Err(err) => {
+                eprintln!("Error parsing line {}. {}", number, err);
+            }
+        }
+    }
+    let level = level
+        .map(|level| level.parse::<scaling::Difficulty>())
+        .transpose()?;
+
+    // The files of the languages are written at the same time
+    let written = in_parallel(filenames.iter().collect(), |fname| {
+        let original = if fname.exists() {
+            fs::read_to_string(fname)?
+        } else {
+            String::new()
+        };
+        let doc = document::Document::parse(&original);
+        let mut content = original.clone();
+        // The files written from scratch are not modified after they were written,
+        // only the ones that already had workouts get a `lastmod`
+        let existing = !doc.blocks().is_empty();
+        let language = file_language(fname);
+        let mut added: Vec<String> = Vec::new();
+        for workout in &workouts {
+            let block = i18n::with_language(&language, || markdown.render(workout));
+            if !force && (doc.contains(&block) || added.contains(&block)) {
+                continue;
+            }
+            content.push_str(&block);
+            content =
+                i18n::with_language(&language, || with_taxonomies(&content, workout, scaling));
+            added.push(block);
+        }
+        if existing && !added.is_empty() {
+            content = with_lastmod(&content);
+        }
+        if let Some(level) = level {
+            let key = front_matter::Profile::detect(&content).key("level");
+            content = front_matter::set_field(&content, key, &format!("\"{}\"", level));
+        }
+        if content == original {
+            return Ok(false);
+        }
+
+        let mut file = io::BufWriter::new(File::create(fname)?);
+        file.write_all(content.as_bytes())?;
+        file.flush()?;
+        Ok(true)
+    })?;
+    Ok(filenames
+        .iter()
+        .zip(written)
+        .filter(|(_, written)| *written)
+        .map(|(fname, _)| fname.clone())
+        .collect())
+}
+
+/// Creates the file for a day from a template, with a workout per section.
+///
+/// # Arguments
+///
+/// * `filename` - A `PathBuf` representing the path to the file to create.
+/// * `template` - Name of the template, i.e. "strength+metcon" (see [`templates`]).
+/// * `templates_dir` - Directory with the user defined templates.
+/// * `date` - The date for the metadata of the file.
+/// * `languages` - A comma separated list of languages, see [`run_base`].
+/// * `config` - The configuration, with the scaled versions rendered under every workout
+///   and the template of the front matter, see [`run_base`].
+/// * `force` - Whether to overwrite the files if they exist.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, WodError>` - The files created, or an
+///   error if the template doesn't exist or the files can't be written.
+#[cfg(feature = "cli")]
+pub fn run_new(
+    filename: PathBuf,
+    template: &str,
+    templates_dir: &Path,
+    date: NaiveDate,
+    languages: Option<String>,
+    config: &config::Config,
+    force: &bool,
+) -> Result<Vec<PathBuf>, WodError> {
+    let content = templates::load(template, templates_dir)?;
+    let filenames = run_base(filename, force, date, languages, config)?;
+    add_wodfile_lines(
+        &filenames,
+        content
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.to_string())),
+        config,
+        force,
+    )?;
+    Ok(filenames)
+}
+
+/// Splits a line of a wod file in the workout, the comments and the name.
+///
+/// The parts are separated by `|`, i.e. "ft 21-15-9 pull up, thruster|T.C. 10'|Fran".
+pub(crate) fn parse_wodfile_line(
+    line: &str,
+) -> Result<(&str, Option<String>, Option<String>), WodError> {
+    let sections: Vec<&str> = line.split('|').collect();
+    let (workout, comments, name) = match sections.len() {
+        1 => (sections[0], None, None),
+        2 => (
+            sections[0],
+            if sections[1].is_empty() {
+                None
+            } else {
+                Some(sections[1].to_string())
+            },
+            None,
+        ),
+        3 => (
+            sections[0],
+            if sections[1].is_empty() {
+                None
+            } else {
+                Some(sections[1].to_string())
+            },
+            if sections[2].is_empty() {
+                None
+            } else {
+                Some(sections[2].to_string())
+            },
+        ),
+        _ => {
+            return Err(WodError::WodFile(format!(
+                "Invalid format, expected 1-3 parts, got {}, content: '{}'",
+                sections.len(),
+                line
+            )))
+        }
+    };
+
+    Ok((workout, comments, name))
+}
+
+/// Reads the lines of a wod file as they are needed, numbered from 1, so files of
+/// any size are processed without loading them. The lines that can't be read (i.e.
+/// not valid UTF-8) are reported and skipped, and reading stops at any other error.
+#[cfg(feature = "cli")]
+fn read_wodfile(filename: PathBuf) -> io::Result<impl Iterator<Item = (usize, String)>> {
+    let file = File::open(&filename)?;
+    let mut lines = io::BufReader::new(file).lines();
+    let mut number = 0;
+    let lines = std::iter::from_fn(move || loop {
+        number += 1;
+        match lines.next()? {
+            Ok(line) => return Some((number, line)),
+            Err(err) => {
+                eprintln!(
+                    "Error reading line {} of {}. {}",
+                    number,
+                    filename.display(),
+                    err
+                );
+                if err.kind() != io::ErrorKind::InvalidData {
+                    return None;
+                }
+            }
+        }
+    })
+    .fuse();
+    Ok(lines)
+}
+
+/// Generates the markdown files for every `.wod` file found in a directory tree.
+///
+/// The date of each workout is taken from the name of the `.wod` file if it contains
+/// one in "YYYY-MM-DD" format (i.e. "2025-03-21.wod" or "week-12/wod-2025-03-21.wod"),
+/// otherwise the date the file was last modified is used. The name of the generated
+/// file is derived from the date with the `filename_template` of the configuration,
+/// and the files are written to the section of the site of their workouts, see
+/// [`wodfile_section`].
+///
+/// # Arguments
+///
+/// * `dir` - The directory with the `.wod` files, subdirectories are included.
+/// * `output_dir` - The directory where the markdown files are written.
+/// * `config` - The configuration, used for the filename template and the scaled versions.
+/// * `languages` - A comma separated list of languages, see [`run_add_wod_from_file`].
+/// * `force` - Whether to overwrite the files if they exist.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, WodError>` - The `.wod` files processed,
+///   or an error if a file can't be read or written.
+#[cfg(feature = "cli")]
+pub fn run_build(
+    dir: &Path,
+    output_dir: &Path,
+    config: &config::Config,
+    languages: Option<String>,
+    force: &bool,
+) -> Result<Vec<PathBuf>, WodError> {
+    let wodfiles = find_wodfiles(dir)?;
+    // The wod files of the same day are written to the same files, one after the other
+    let mut days: Vec<(PathBuf, NaiveDate, Vec<&PathBuf>)> = Vec::new();
+    for wodfile in wodfiles.iter() {
+        let name = wodfile.file_stem().unwrap_or_default().to_string_lossy();
+        let date = match find_date(&name) {
+            Some(date) => date,
+            None => modified_date(wodfile)?,
+        };
+        let section = match config.section {
+            Some(_) => None,
+            None => wodfile_section(wodfile, config)?,
+        };
+        let output_dir = match section {
+            Some(section) => hugo::section_dir(output_dir, &section),
+            None => output_dir.to_path_buf(),
+        };
+        let filename = output_dir.join(expand_filename(&config.filename_template, date)?);
+        match days.iter_mut().find(|(f, _, _)| *f == filename) {
+            Some((_, _, files)) => files.push(wodfile),
+            None => days.push((filename, date, vec![wodfile])),
+        }
+    }
+    in_parallel(days, |(filename, date, files)| {
+        for wodfile in files {
+            run_add_wod_from_file(
+                filename.clone(),
+                wodfile.clone(),
+                date,
+                languages.clone(),
+                config,
+                force,
+            )?;
+        }
+        Ok(())
+    })?;
+    Ok(wodfiles)
+}
+
+/// Splits a wod file with the workouts of several days, i.e. a month drafted in one
+/// document, into a file per day.
+///
+/// The days are separated by lines of dashes, `---`, the lines before the first one
+/// are the first day, the one of `start`, and every separator moves to the next day.
+/// The days without workouts are rest days, and no file is written for them:
+///
+/// ```text
+/// # section: competition
+/// wl 5x5 back squat @75%
+/// ft 21-15-9 pull up, thruster @43/30kg||Fran
+/// ---
+/// # level: rx
+/// amrap-12 10 burpee, 15 wall ball @9/6kg
+/// ---
+/// ---
+/// wl 5x3 deadlift @80%
+/// ```
+///
+/// The name of each file is derived from its date with the `filename_template` of
+/// the configuration, and the files are written as with [`run_add_wod_from_file`]:
+/// with [`run_base`], followed by the workouts of the day. A `# level:` line applies to
+/// the day it's written in, and a `# section:` line to the whole file, see
+/// [`wodfile_section`].
+///
+/// # Arguments
+///
+/// * `wodfile` - The wod file with the days.
+/// * `start` - The date of the first day.
+/// * `output_dir` - The directory where the markdown files are written.
+/// * `languages` - A comma separated list of languages, see [`run_add_wod_from_file`].
+/// * `config` - The configuration, used for the filename template and the scaled versions.
+/// * `force` - Whether to overwrite the files if they exist.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, WodError>` - The files written, one per language for every
+///   day with workouts, without the ones that already had all the workouts of their
+///   day, or an error if a file can't be read or written.
+#[cfg(feature = "cli")]
+pub fn run_split(
+    wodfile: &Path,
+    start: NaiveDate,
+    output_dir: &Path,
+    languages: Option<String>,
+    config: &config::Config,
+    force: &bool,
+) -> Result<Vec<PathBuf>, WodError> {
+    let section = match config.section {
+        Some(_) => None,
+        None => wodfile_section(wodfile, config)?,
+    };
+    let output_dir = match section {
+        Some(section) => hugo::section_dir(output_dir, &section),
+        None => output_dir.to_path_buf(),
+    };
+    let mut days: Vec<Vec<(usize, String)>> = vec![Vec::new()];
+    for (number, line) in read_wodfile(wodfile.to_path_buf())? {
+        if is_day_separator(&line) {
+            days.push(Vec::new());
+        } else if let Some(day) = days.last_mut() {
+            day.push((number, line));
+        }
+    }
+
+    let mut filenames = Vec::new();
+    for (date, lines) in start.iter_days().zip(days) {
+        let rest = lines
+            .iter()
+            .all(|(_, line)| line.trim().is_empty() || is_wodfile_comment(line));
+        if rest {
+            continue;
+        }
+        let filename = output_dir.join(expand_filename(&config.filename_template, date)?);
+        let files = run_base(filename, force, date, languages.clone(), config)?;
+        filenames.extend(add_wodfile_lines(&files, lines.into_iter(), config, force)?);
+    }
+    Ok(filenames)
+}
+
+/// Runs `f` for every item in as many threads as the machine has cores, returning
+/// the results in the order of the items, or the first error found.
+#[cfg(feature = "cli")]
+fn in_parallel<T, R>(
+    items: Vec<T>,
+    f: impl Fn(T) -> Result<R, WodError> + Sync,
+) -> Result<Vec<R>, WodError>
+where
+    T: Send,
+    R: Send,
+{
+    let workers = std::thread::available_parallelism().map_or(1, usize::from);
+    in_threads(items, workers, f)
+}
+
+/// Runs `f` for every item in up to `workers` threads, see [`in_parallel`].
+#[cfg(feature = "cli")]
+fn in_threads<T, R>(
+    items: Vec<T>,
+    workers: usize,
+    f: impl Fn(T) -> Result<R, WodError> + Sync,
+) -> Result<Vec<R>, WodError>
+where
+    T: Send,
+    R: Send,
+{
+    let workers = workers.min(items.len());
+    if workers <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+    let queue = std::sync::Mutex::new(items.into_iter().enumerate());
+    let mut results: Vec<(usize, Result<R, WodError>)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    // The lock is released before running `f`, the items are taken one at a time
+                    while let Some((i, item)) = {
+                        let next = queue.lock().map(|mut queue| queue.next());
+                        next.ok().flatten()
+                    } {
+                        results.push((i, f(item)));
+                    }
+                    results
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("worker thread panicked"))
+            .collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Finds the `.wod` files in a directory and its subdirectories, sorted by path.
+#[cfg(feature = "cli")]
+fn find_wodfiles(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    find_files(dir, "wod")
+}
+
+/// Finds the files with an extension in a directory and its subdirectories, sorted by path.
+#[cfg(feature = "cli")]
+fn find_files(dir: &Path, extension: &str) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(find_files(&path, extension)?);
+        } else if path.extension().is_some_and(|ext| ext == extension) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Opening line of the block with the source of the workouts embedded in a markdown
+/// file, an HTML comment so it's not shown in the site:
+///
+/// ```text
+/// <!-- wod
+/// ft 21-15-9 pull up, thruster @43/30kg||Fran
+/// -->
+/// ```
+pub const SOURCE_BLOCK: &str = "<!-- wod";
+
+/// Regenerates the markdown files of a directory with the current renderer, so the
+/// changes in the format of the workouts can be applied to the whole archive.
+///
+/// The workouts of each file are parsed again from their source: the block embedded
+/// in the file (see [`SOURCE_BLOCK`]), or the wod file next to it with the same name,
+/// i.e. "wod-2025-03-21.wod" for "wod-2025-03-21.md" and "wod-2025-03-21.es.md". The
+/// front matter and the introduction of the files are kept, and the files without a
+/// source are left untouched.
+///
+/// # Arguments
+///
+/// * `dir` - The content directory, subdirectories are included.
+/// * `config` - The configuration, with the scaled versions rendered under every workout.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, WodError>` - The files regenerated, or an error if a file
+///   can't be read or written, or one of the workouts of a source can't be parsed.
+#[cfg(feature = "cli")]
+pub fn run_regen(dir: &Path, config: &config::Config) -> Result<Vec<PathBuf>, WodError> {
+    let mut regenerated = Vec::new();
+    for filename in find_files(dir, "md")? {
+        let content = fs::read_to_string(&filename)?;
+        let Some(lines) = workout_source(&filename, &content)? else {
+            continue;
+        };
+
+        let mut workouts = Vec::new();
+        let mut blocks = Vec::new();
+        for line in lines.iter().filter(|line| !is_wodfile_comment(line)) {
+            let rendered = parse_wodfile_line(line).and_then(|(workout, comments, name)| {
+                render_for_file(
+                    workout,
+                    comments,
+                    name,
+                    &[],
+                    &config.scaling,
+                    &config.markdown(),
+                    &filename,
+                )
+            });
+            let rendered = rendered
+                .map_err(|err| WodError::WodFile(format!("{}: {}", filename.display(), err)))?;
+            for (workout, block) in rendered {
+                workouts.push(workout);
+                blocks.push(block);
+            }
+        }
+
+        let mut doc = document::Document::parse(&content);
+        // The warm-up and the leaderboards are not part of the source, they are kept
+        // before and after the workouts
+        if let Some(warmup) = doc.blocks().iter().find(|block| warmup::is_warmup(block)) {
+            blocks.insert(0, warmup.clone());
+        }
+        blocks.extend(
+            doc.blocks()
+                .iter()
+                .filter(|block| leaderboard::is_leaderboard(block))
+                .cloned(),
+        );
+        let embedded = source_block(&content);
+        let moved = embedded.filter(|block| !doc.preamble().contains(block));
+        doc.set_blocks(blocks);
+        let mut updated = doc.to_string();
+        if let Some(block) = moved {
+            updated.push_str(block);
+            updated.push('\n');
+        }
+        if updated != content {
+            fs::write(&filename, updated)?;
+        }
+        for workout in &workouts {
+            add_taxonomies(&filename, workout, &config.scaling)?;
+        }
+        regenerated.push(filename);
+    }
+    Ok(regenerated)
+}
+
+/// The block with the source of the workouts embedded in a file, from [`SOURCE_BLOCK`]
+/// to the closing `-->`.
+#[cfg(feature = "cli")]
+fn source_block(content: &str) -> Option<&str> {
+    let start = content.find(SOURCE_BLOCK)?;
+    let end = content[start..].find("-->")? + start + "-->".len();
+    Some(&content[start..end])
+}
+
+/// The lines with the source of the workouts of a markdown file, from the block
+/// embedded in it or the wod file next to it, `None` if it has neither.
+#[cfg(feature = "cli")]
+fn workout_source(filename: &Path, content: &str) -> Result<Option<Vec<String>>, WodError> {
+    if let Some(block) = source_block(content) {
+        let lines = block[SOURCE_BLOCK.len()..block.len() - "-->".len()]
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+        return Ok(Some(lines));
+    }
+
+    // "wod-2025-03-21.es.md" is written from "wod-2025-03-21.wod" too
+    let stem = filename
+        .file_stem()
+        .map(Path::new)
+        .and_then(Path::file_stem);
+    let sidecar = stem.map(|stem| filename.with_file_name(format!("{}.wod", stem.display())));
+    match sidecar {
+        Some(sidecar) if sidecar.is_file() => {
+            let lines = fs::read_to_string(sidecar)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(String::from)
+                .collect();
+            Ok(Some(lines))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Today's date, when it's needed and not given.
+#[cfg(feature = "date")]
+fn current_date() -> Result<NaiveDate, WodError> {
+    Ok(today())
+}
+
+#[cfg(not(feature = "date"))]
+fn current_date() -> Result<NaiveDate, WodError> {
+    Err(WodError::Invalid(
+        "The date is required, `wod` was built without the `date` feature".to_string(),
+    ))
+}
+
+/// The date a file was last modified, in local time.
+#[cfg(feature = "cli")]
+fn modified_date(path: &Path) -> Result<NaiveDate, WodError> {
+    Ok(chrono::DateTime::<Local>::from(fs::metadata(path)?.modified()?).date_naive())
+}
+
+/// Finds the first date in "YYYY-MM-DD" format in a name.
+#[cfg(feature = "cli")]
+fn find_date(name: &str) -> Option<NaiveDate> {
+    name.char_indices()
+        .find_map(|(i, _)| date::parse_iso(name.get(i..i + 10)?))
+}
+
+/// Generates a list of movements with explanatory videos in markdown format.
+///
+/// This function takes a `page` boolean to determine whether to create a markdown
+/// page or just list the links. To generate a file just redirect the content.
+///
+/// # Arguments
+///
+/// * `page` - A `bool` to decide whether this is a markdown page prepared for a Hugo blog
+///   or a list of markdown links.
+///
+/// # Returns
+///
+/// * `String` - The content as a string.
+///
+/// # Examples
+///
+/// Print the content to the console:
+///
+/// ```
+/// use wod::run_create_list_movements;
+///
+/// let movement_list = run_create_list_movements(false);
+/// let air_squat = movement_list.split("\n\n").next().unwrap();
+/// assert_eq!(
+///     air_squat,
+///     "- [Air Squat](https://www.crossfit.com/essentials/the-air-squat)".to_string()
+/// );
+pub fn run_create_list_movements(page: bool) -> String {
+    let mut content: String = "".to_string();
+    if page {
+        content.push_str(
+            r#"---
+title: "CrossFit Movements"
+description: "List of movements with explanatory video"
+---
+
+List of CrossFit movements, click on them to see an explanation.
+
+---
+
+"#,
+        )
+    }
+    let mut videos: Vec<_> = Movement::all()
+        .filter_map(|m| m.video().map(|url| (m.to_string(), url)))
+        .collect();
+    videos.sort();
+    content.push_str(
+        videos
+            .iter()
+            .map(|(name, url)| format!("- [{}]({})", name, url))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            .as_str(),
+    );
+    content
+}
+
+/// Generates a textual representation of a given workout in markdown format.
+///
+/// This function takes a workout and attempts to
+/// parse it into a valid workout structure. If successful, it returns a markdown-formatted string
+/// representing the workout. If parsing fails, it returns the error as a string for
+/// the caller to handle or display.
+///
+/// # Arguments
+///
+/// * `workout` - A `&str` representing the wod to render.
+/// * `loads` - Optional store of personal records, if given the percentage based
+///   weightlifting lines include the absolute load.
+/// * `paces` - Optional store of personal records, if given the monostructural lines
+///   include the target time and split from the baselines (see [`pacing::target`]).
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The markdown string on success,
+///   or the error found while parsing the workout.
+///
+/// # Examples
+///
+/// ```
+/// use wod::run_check_wod;
+///
+/// match run_check_wod("ft 21-15-9 pulup, thruster @ 43/30kg", None, None) {
+///     Ok(content) => println!("{}", content),
+///     Err(e) => eprintln!("Failed to parse workout: {}", e),
+/// }
+/// ```
+#[cfg(feature = "cli")]
+pub fn run_check_wod(
+    workout: &str,
+    loads: Option<&pr::PrStore>,
+    paces: Option<&pr::PrStore>,
+) -> Result<String, WodError> {
+    let mut rendered = String::new();
+    for mut wkt in parts::create_workouts(workout, None, None)? {
+        if let Some(prs) = loads {
+            wkt.set_one_rms(prs.records());
+        }
+        if let Some(prs) = paces {
+            wkt.set_baselines(prs.baselines());
+        }
+        rendered.push_str(&wkt.to_string());
+    }
+    Ok(rendered)
+}
+
+/// Summarizes a workout: the number of movements, the machines used and the total
+/// load lifted (see [`Workout::tonnage`]).
+///
+/// # Arguments
+///
+/// * `workout` - A `&str` representing the wod, i.e. "wl 5x5 back squat @70%".
+/// * `store` - Path to the personal records store, used to resolve the percentages.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - A line per statistic, or the error found while
+///   parsing the workout or reading the store.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use wod::run_stats;
+///
+/// let stats = run_stats("ft 21-15-9 pull up, thruster @43/30kg", Path::new("no-prs.json")).unwrap();
+/// assert_eq!(stats, "Movements: 2\nMachines: -\nTonnage: 1935/1350kg");
+/// ```
+#[cfg(feature = "cli")]
+pub fn run_stats(workout: &str, store: &Path) -> Result<String, WodError> {
+    let wkt = create_workout(workout, None, None)?;
+    let prs = pr::PrStore::load(store)?;
+    let join = |items: Vec<String>| {
+        if items.is_empty() {
+            "-".to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    Ok(format!(
+        "Movements: {}\nMachines: {}\nTonnage: {}",
+        wkt.movements().count(),
+        join(wkt.machines().iter().map(|m| m.to_string()).collect()),
+        join(
+            wkt.tonnage(&prs.records())
+                .iter()
+                .map(|t| t.to_string())
+                .collect()
+        ),
+    ))
+}
+
+/// A report over the generated files of a directory, along with the workouts left
+/// out of it because they can't be read back from the markdown (see [`markdown`]),
+/// i.e. the ones edited by hand.
+#[cfg(feature = "cli")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilesReport {
+    /// The report, in the format asked for.
+    pub report: String,
+    /// The workouts left out, as "<file> (workout <n>): <error>".
+    pub unreadable: Vec<String>,
+}
+
+/// The workouts of the generated files of a directory by day, for the reports over
+/// a period, along with the errors of the ones that can't be read back from the
+/// markdown. The day is the `date` of the front matter, or the one in the name of
+/// the file. The files of the other languages are skipped.
+#[cfg(feature = "cli")]
+#[allow(clippy::type_complexity)]
+fn workouts_by_day(dir: &Path) -> Result<(Vec<(NaiveDate, Vec<Workout>)>, Vec<String>), WodError> {
+    let mut days: Vec<(NaiveDate, Vec<Workout>)> = Vec::new();
+    let mut unreadable = Vec::new();
+    for file in find_files(dir, "md")? {
+        if file_language(&file) != i18n::DEFAULT_LANGUAGE {
+            continue;
+        }
+        let content = fs::read_to_string(&file)?;
+        let date = front_matter::get_field(&content, "date")
+            .and_then(|date| date::parse_iso(date.get(..10)?))
+            .or_else(|| find_date(&file.file_stem()?.to_string_lossy()));
+        let Some(date) = date else {
+            continue;
+        };
+        let document = document::Document::parse(&content);
+        let mut workouts = Vec::new();
+        for (i, block) in document.blocks().iter().enumerate() {
+            if leaderboard::is_leaderboard(block) || warmup::is_warmup(block) {
+                continue;
+            }
+            match markdown::parse_workout(block) {
+                Ok(workout) => workouts.push(workout),
+                Err(e) => unreadable.push(format!("{} (workout {}): {}", file.display(), i + 1, e)),
+            }
+        }
+        match days.iter_mut().find(|(day, _)| *day == date) {
+            Some((_, day)) => day.extend(workouts),
+            None => days.push((date, workouts)),
+        }
+    }
+    days.sort_by_key(|(date, _)| *date);
+    Ok((days, unreadable))
+}
+
+/// Reports how often the movements and their patterns were programmed in the
+/// generated files of a directory, with recommendations for the ones left out for
+/// too long or repeated too often, see [`frequency::report`].
+///
+/// # Arguments
+///
+/// * `dir` - The directory with the generated files, subdirectories are included.
+/// * `today` - The day of the report, the files after it are left out.
+/// * `rules` - The windows and limits of the configuration, see [`frequency::Frequency`].
+/// * `format` - Whether to write the report as markdown or JSON.
+///
+/// # Returns
+///
+/// * `Result<FilesReport, WodError>` - The report and the workouts left out of it, or
+///   an error if the files can't be read or the rules name movements or patterns that
+///   don't exist.
+#[cfg(feature = "cli")]
+pub fn run_frequency(
+    dir: &Path,
+    today: NaiveDate,
+    rules: &frequency::Frequency,
+    format: frequency::ReportFormat,
+) -> Result<FilesReport, WodError> {
+    rules.validate()?;
+    let (days, unreadable) = workouts_by_day(dir)?;
+    Ok(FilesReport {
+        report: frequency::report(&days, today, rules).format(format),
+        unreadable,
+    })
+}
+
+/// Exports the load lifted per week and movement pattern in the generated files of a
+/// directory, to plot the training load over a cycle, see [`volume::weekly_tonnage`].
+///
+/// # Arguments
+///
+/// * `dir` - The directory with the generated files, subdirectories are included.
+/// * `store` - Path to the personal records store, used to resolve the percentages.
+/// * `format` - Whether to write the weeks as JSON or CSV.
+///
+/// # Returns
+///
+/// * `Result<FilesReport, WodError>` - The weekly loads and the workouts left out of
+///   them, or an error if the files or the store can't be read.
+#[cfg(feature = "cli")]
+pub fn run_volume(
+    dir: &Path,
+    store: &Path,
+    format: results::SeriesFormat,
+) -> Result<FilesReport, WodError> {
+    let (days, unreadable) = workouts_by_day(dir)?;
+    let prs = pr::PrStore::load(store)?;
+    let weeks = volume::weekly_tonnage(&days, &prs.records());
+    Ok(FilesReport {
+        report: volume::export(&weeks, format),
+        unreadable,
+    })
+}
+
+/// Exports the workouts programmed per day in the generated files of a directory, to
+/// render a training calendar, see [`heatmap::calendar`].
+///
+/// # Arguments
+///
+/// * `dir` - The directory with the generated files, subdirectories are included.
+/// * `format` - Whether to write the days as JSON or CSV.
+///
+/// # Returns
+///
+/// * `Result<FilesReport, WodError>` - The days with workouts and the workouts left
+///   out of them, or an error if the files can't be read.
+#[cfg(feature = "cli")]
+pub fn run_heatmap(dir: &Path, format: results::SeriesFormat) -> Result<FilesReport, WodError> {
+    let (days, unreadable) = workouts_by_day(dir)?;
+    Ok(FilesReport {
+        report: heatmap::export(&heatmap::calendar(&days), format),
+        unreadable,
+    })
+}
+
+/// Compares two workouts, a line per difference (see [`Workout::diff`]).
+///
+/// # Arguments
+///
+/// * `first` - A `&str` representing the wod to compare from, i.e. the Rx version.
+/// * `second` - A `&str` representing the wod to compare to, i.e. the scaled version.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The differences, "No differences" if there are none,
+///   or the error found while parsing any of the workouts.
+///
+/// # Examples
+///
+/// ```
+/// use wod::run_diff;
+///
+/// let diff = run_diff("wl 5x5 back squat @70%", "wl 5x3 back squat @80%").unwrap();
+/// assert_eq!(diff, "Line 1 reps: 5x5 -> 5x3\nLine 1 load: 70% -> 80%");
+/// assert_eq!(run_diff("ft 21 c2b", "ft 21 chest to bar").unwrap(), "No differences");
+/// ```
+pub fn run_diff(first: &str, second: &str) -> Result<String, WodError> {
+    let first = create_workout(first, None, None)?;
+    let second = create_workout(second, None, None)?;
+    let changes = first.diff(&second);
+    if changes.is_empty() {
+        return Ok("No differences".to_string());
+    }
+    Ok(changes
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Generates a random workout, see [`generator::random_workout`].
+///
+/// # Arguments
+///
+/// * `constraints` - The length, equipment, movements and stimulus of the workout.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The workout in the shorthand, to add it with
+///   `wod add` or to a wod file, or an error if no movement fits the constraints.
+///
+/// # Examples
+///
+/// ```
+/// use wod::generator::Constraints;
+/// use wod::run_random;
+///
+/// let constraints = Constraints { minutes: 8, seed: Some(3), ..Default::default() };
+/// assert!(run_random(&constraints).unwrap().starts_with("ft 21-15-9 "));
+/// ```
+pub fn run_random(constraints: &generator::Constraints) -> Result<String, WodError> {
+    Ok(generator::random_workout(constraints)?.to_shorthand())
+}
+
+/// Checks the quality of the programming of a workout, see [`lint`].
+///
+/// # Arguments
+///
+/// * `wod` - A `&str` representing the wod to check.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - A warning per line, "No problems found" if there
+///   are none, or the error found while parsing the workout.
+///
+/// # Examples
+///
+/// ```
+/// use wod::run_lint;
+///
+/// let lints = run_lint("ft 21-15-9 pull up, toes to bar, deadlift @100/70kg").unwrap();
+/// assert_eq!(
+///     lints,
+///     "warning[grip-interference]: 3 grip intensive movements in the same workout: Pull Up, Toes To Bar, Deadlift"
+/// );
+/// assert_eq!(run_lint("ft 21-15-9 pull up, thruster @43/30kg").unwrap(), "No problems found");
+/// ```
+pub fn run_lint(wod: &str) -> Result<String, WodError> {
+    let workout = create_workout(wod, None, None)?;
+    Ok(join_lints(lint::lint_workout(&workout)))
+}
+
+/// Checks the quality of the programming of consecutive days, i.e. the files of a
+/// week, see [`lint`].
+///
+/// # Arguments
+///
+/// * `files` - The markdown files generated by `wod`, a day each, in order.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - A warning per line along with the file (and
+///   workout) it was found in, "No problems found" if there are none, or the error
+///   found while reading the files.
+pub fn run_lint_files(files: &[PathBuf]) -> Result<String, WodError> {
+    let mut days = Vec::new();
+    for file in files {
+        let label = file.file_name().unwrap_or_default().to_string_lossy();
+        let post = markdown::Post::read(file)
+            .map_err(|e| WodError::Invalid(format!("{}: {}", label, e)))?;
+        days.push((label.to_string(), post.workouts));
+    }
+    Ok(join_lints(lint::lint_days(&days)))
+}
+
+/// Checks every workout of a wod file, or a single workout, for editors and CI jobs,
+/// see [`diagnostics::check_wodfile`].
+///
+/// # Arguments
+///
+/// * `content` - The content of the wod file, or the workout.
+/// * `label` - Name of the file, prefixed to the lines of the text report.
+/// * `lint` - Whether to run the lint rules over the valid workouts too.
+/// * `json` - Whether to report the problems as a JSON array instead of text.
+///
+/// # Returns
+///
+/// * `(String, bool)` - The report, and whether the workouts are valid, the
+///   warnings of the lint rules don't make them invalid.
+///
+/// # Examples
+///
+/// ```
+/// use wod::run_check_wodfile;
+///
+/// let (report, valid) = run_check_wodfile("ft 10 pulup", "plan.wod", false, false);
+/// assert!(!valid);
+/// assert!(report.starts_with("plan.wod:1:7: error[invalid-movement]: "));
+///
+/// let (report, valid) = run_check_wodfile("ft 10 pull up", "plan.wod", false, true);
+/// assert!(valid);
+/// assert_eq!(report, "[]");
+/// ```
+#[cfg(feature = "cli")]
+pub fn run_check_wodfile(content: &str, label: &str, lint: bool, json: bool) -> (String, bool) {
+    let annotations = diagnostics::check_wodfile(content, lint);
+    let valid = annotations
+        .iter()
+        .all(|a| a.severity != diagnostics::Severity::Error);
+    let report = if json {
+        serde_json::to_string_pretty(&annotations).unwrap_or_default()
+    } else if annotations.is_empty() {
+        "No problems found".to_string()
+    } else {
+        annotations
+            .iter()
+            .map(|a| format!("{}:{}", label, a))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    (report, valid)
+}
+
+/// Rewrites wod files in their canonical form, see [`formatter`].
+///
+/// # Arguments
+///
+/// * `files` - The wod files to format.
+/// * `check` - Whether to only report the files that aren't formatted, without
+///   writing them, for CI jobs.
+///
+/// # Returns
+///
+/// * `Result<(String, usize), WodError>` - The report, a file per line, and the number
+///   of files that weren't formatted, or the error of the first line that can't be
+///   parsed.
+pub fn run_fmt(files: &[PathBuf], check: bool) -> Result<(String, usize), WodError> {
+    let mut changed = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(file)?;
+        let formatted = formatter::format_wodfile(&content).map_err(|e| match e {
+            WodError::WodFile(msg) => WodError::WodFile(format!("{}: {}", file.display(), msg)),
+            e => e,
+        })?;
+        if formatted != content {
+            if !check {
+                fs::write(file, &formatted)?;
+            }
+            changed.push(file.display().to_string());
+        }
+    }
+    let report = match (changed.is_empty(), check) {
+        (true, _) => "All files formatted".to_string(),
+        (false, true) => format!("Files not formatted:\n{}", changed.join("\n")),
+        (false, false) => format!("Files formatted:\n{}", changed.join("\n")),
+    };
+    Ok((report, changed.len()))
+}
+
+fn join_lints(lints: Vec<lint::Lint>) -> String {
+    if lints.is_empty() {
+        return "No problems found".to_string();
+    }
+    lints
+        .iter()
+        .map(|l| l.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses a workout and returns it as JSON, `{"workout": {...}}` with the fields of
+/// [`Workout`], or `{"error": {"message": ..., "start": ..., "end": ...}}` when it
+/// isn't valid. Used by the bindings to other languages and the server.
+///
+/// The `start` and `end` of the error are the positions of the offending token in
+/// UTF-16 code units (the indices of a JavaScript string), `null` if the error isn't
+/// in a single token.
+#[cfg(any(feature = "cli", feature = "wasm", feature = "ffi"))]
+pub(crate) fn parse_workout_value(source: &str) -> Result<serde_json::Value, serde_json::Value> {
+    match create_workout(source, None, None) {
+        Ok(workout) => Ok(serde_json::json!({ "workout": workout })),
+        Err(e) => {
+            let position = |byte: usize| source[..byte].encode_utf16().count();
+            let span = diagnostics::Diagnostic::from_source(source).map(|d| d.span);
+            Err(serde_json::json!({
+                "error": {
+                    "message": e.to_string(),
+                    "start": span.as_ref().map(|s| position(s.start)),
+                    "end": span.as_ref().map(|s| position(s.end)),
+                }
+            }))
+        }
+    }
+}
+
+/// Same as [`parse_workout_value`], as a JSON string either way.
+#[cfg(any(feature = "wasm", feature = "ffi"))]
+pub(crate) fn parse_workout_json(source: &str) -> String {
+    match parse_workout_value(source) {
+        Ok(value) | Err(value) => value.to_string(),
+    }
+}
+
+/// Sets the personal record of a movement in the store.
+///
+/// When the weight was lifted for more than one repetition the 1RM is estimated
+/// with the `formula`, and that is what gets stored.
+///
+/// # Arguments
+///
+/// * `movement` - The movement, as written in the workouts, i.e. "back squat".
+/// * `weight` - The weight lifted, i.e. "140kg".
+/// * `reps` - The repetitions done with the weight, 1 for a 1RM.
+/// * `formula` - The formula to estimate the 1RM from a rep max.
+/// * `store` - Path to the personal records store.
+///
+/// # Returns
+///
+/// * `Result<Weight, WodError>` - The 1RM stored.
+#[cfg(feature = "cli")]
+pub fn run_pr_set(
+    movement: &str,
+    weight: &str,
+    reps: u32,
+    formula: rm::Formula,
+    store: &Path,
+) -> Result<Weight, WodError> {
+    let movement: Movement = movement.parse()?;
+    let weight = one_rep_max(weight.parse()?, reps, formula)?;
+    let mut prs = pr::PrStore::load(store)?;
+    prs.set(&movement, weight.clone());
+    prs.save(store)?;
+    Ok(weight)
+}
+
+/// Sets the baseline score of a monostructural movement in the store, used for the
+/// target paces of the workouts (see [`pacing`]).
+///
+/// # Arguments
+///
+/// * `movement` - The movement, row, run, bike or ski.
+/// * `baseline` - The distance and the time it was done in, i.e. "2k 7:30".
+/// * `store` - Path to the personal records store.
+///
+/// # Returns
+///
+/// * `Result<pacing::Baseline, WodError>` - The baseline stored, or an error if the
+///   movement isn't monostructural or the baseline can't be parsed.
+#[cfg(feature = "cli")]
+pub fn run_pr_baseline(
+    movement: &str,
+    baseline: &str,
+    store: &Path,
+) -> Result<pacing::Baseline, WodError> {
+    let movement: Movement = movement.parse()?;
+    if pacing::split(&movement).is_none() {
+        return Err(format!(
+            "Invalid movement for a baseline: `{}`, expected one of: row, run, bike, echo bike, ski",
+            movement
+        )
+        .into());
+    }
+    let baseline: pacing::Baseline = baseline.parse()?;
+    let mut prs = pr::PrStore::load(store)?;
+    prs.set_baseline(&movement, &baseline);
+    prs.save(store)?;
+    Ok(baseline)
+}
+
+/// Estimates the 1RM from a rep max, rounded to a whole number.
+#[cfg(feature = "cli")]
+fn one_rep_max(weight: Weight, reps: u32, formula: rm::Formula) -> Result<Weight, WodError> {
+    if weight.unit == LoadUnit::Percent {
+        return Err(format!("Expected a weight, i.e. 100kg, got: '{}'", weight).into());
+    }
+    if reps <= 1 {
+        return Ok(weight);
+    }
+    let estimate = |w: f64| rm::estimate_1rm(w, reps, formula);
+    if estimate(weight.weight_man).is_infinite() {
+        return Err(format!("Can't estimate the 1RM from {} repetitions", reps).into());
+    }
+    Ok(Weight {
+        weight_man: estimate(weight.weight_man).round(),
+        weight_woman: estimate(weight.weight_woman).round(),
+        unit: weight.unit,
+    })
+}
+
+/// Computes the working weight for a percentage of the 1RM.
+///
+/// The 1RM is taken from `one_rm` if given, otherwise from the personal record
+/// of the `movement`. The result is rounded to the `plate_increment` of the configuration.
+///
+/// # Arguments
+///
+/// * `percent` - The percentage, i.e. "82%".
+/// * `movement` - Optional movement to look up in the personal records, i.e. "clean".
+/// * `one_rm` - Optional explicit 1RM, i.e. "100kg".
+/// * `reps` - The repetitions done with `one_rm`, to estimate the 1RM from a rep max.
+/// * `formula` - The formula to estimate the 1RM from a rep max.
+/// * `store` - Path to the personal records store.
+/// * `config` - The configuration with the plate increment.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The working weight, i.e. "82.5kg".
+#[cfg(feature = "cli")]
+pub fn run_percent(
+    percent: &str,
+    movement: Option<&str>,
+    one_rm: Option<&str>,
+    reps: u32,
+    formula: rm::Formula,
+    store: &Path,
+    config: &config::Config,
+) -> Result<String, WodError> {
+    let percent: Weight = percent.parse()?;
+    if percent.unit != LoadUnit::Percent {
+        return Err(format!("Expected a percentage, i.e. 80%, got: '{}'", percent).into());
+    }
+    let one_rm: Weight = match (one_rm, movement) {
+        (Some(one_rm), _) => one_rep_max(one_rm.parse()?, reps, formula)?,
+        (None, Some(movement)) => {
+            let movement: Movement = movement.parse()?;
+            pr::PrStore::load(store)?
+                .get(&movement)
+                .ok_or_else(|| format!("No personal record found for: {}", movement))?
+        }
+        (None, None) => return Err("Either a movement or a 1RM must be given".into()),
+    };
+    let load = one_rm.weight_man * percent.weight_man / 100.0;
+    Ok(weight::format_load(
+        weight::round_to_increment(load, config.plate_increment),
+        &one_rm.unit.to_string(),
+    ))
+}
+
+/// Returns the personal records in the store, one per line sorted by movement,
+/// followed by the baselines.
+#[cfg(feature = "cli")]
+pub fn run_pr_list(store: &Path) -> Result<String, WodError> {
+    let prs = pr::PrStore::load(store)?;
+    let records = prs
+        .records()
+        .into_iter()
+        .map(|(movement, weight)| format!("{}: {}", movement, weight));
+    let baselines = prs
+        .baselines()
+        .into_iter()
+        .map(|(movement, baseline)| format!("{}: {}", movement, baseline));
+    Ok(records.chain(baselines).collect::<Vec<_>>().join("\n"))
+}
+
+/// Generates the sessions of a strength cycle for the movements, see [`cycles::sessions`].
+///
+/// The loads are computed from the personal records of the movements, rounded to the
+/// `plate_increment` of the configuration. The movements without a record keep the
+/// percentages of the 1RM.
+///
+/// # Arguments
+///
+/// * `cycle` - The cycle, i.e. 5/3/1.
+/// * `movements` - The lifts of the cycle, i.e. `["back squat", "deadlift"]`.
+/// * `start` - The first day of the cycle.
+/// * `weeks` - The number of weeks.
+/// * `days` - The days of the week to train, the weekday of `start` if empty.
+/// * `store` - Path to the personal records store.
+/// * `config` - The configuration with the plate increment.
+///
+/// # Returns
+///
+/// * `Result<Vec<cycles::Session>, WodError>` - The lines of the wod file of every
+///   day of training, or an error if a movement isn't valid.
+#[cfg(feature = "cli")]
+pub fn run_cycle(
+    cycle: cycles::Cycle,
+    movements: &[String],
+    start: NaiveDate,
+    weeks: usize,
+    days: &[chrono::Weekday],
+    store: &Path,
+    config: &config::Config,
+) -> Result<Vec<cycles::Session>, WodError> {
+    let prs = pr::PrStore::load(store)?;
+    let lifts = movements
+        .iter()
+        .map(|movement| {
+            let movement: Movement = movement.trim().parse()?;
+            let one_rm = prs.get(&movement);
+            Ok((movement, one_rm))
+        })
+        .collect::<Result<Vec<_>, WodError>>()?;
+    Ok(cycles::sessions(
+        cycle,
+        &lifts,
+        start,
+        weeks,
+        days,
+        config.plate_increment,
+    ))
+}
+
+/// Writes the file of every session, named after its date with the `filename_template`
+/// of the configuration.
+///
+/// # Arguments
+///
+/// * `sessions` - The sessions, i.e. the ones of [`run_cycle`].
+/// * `output_dir` - The directory where the files are written.
+/// * `languages` - Optional languages for the files, i.e. "en,es".
+/// * `config` - The configuration.
+/// * `force` - Whether to overwrite the files if they exist.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, WodError>` - The files created.
+#[cfg(feature = "cli")]
+pub fn run_add_sessions(
+    sessions: &[cycles::Session],
+    output_dir: &Path,
+    languages: Option<String>,
+    config: &config::Config,
+    force: &bool,
+) -> Result<Vec<PathBuf>, WodError> {
+    let mut created = Vec::new();
+    for session in sessions {
+        let filename = output_dir.join(expand_filename(&config.filename_template, session.date)?);
+        let filenames = run_base(filename, force, session.date, languages.clone(), config)?;
+        add_wodfile_lines(
+            &filenames,
+            session
+                .lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line.clone())),
+            config,
+            force,
+        )?;
+        created.extend(filenames);
+    }
+    Ok(created)
+}
+
+/// Generates the skeleton `.wod` file of every day of training of a plan, see
+/// [`planner::skeleton`].
+///
+/// The files are named after their date, i.e. "2025-03-24.wod", so `wod build` takes
+/// the date of the pages from them once they are refined. The files that already
+/// exist are kept, to not lose the changes of the coaches, unless `force` is set.
+///
+/// # Arguments
+///
+/// * `plan` - The TOML file with the plan.
+/// * `dir` - The directory where the `.wod` files are written.
+/// * `templates_dir` - The directory with the user templates.
+/// * `force` - Whether to overwrite the files if they exist.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, WodError>` - The files written, or an error if the plan
+///   isn't valid or the files can't be written.
+#[cfg(feature = "cli")]
+pub fn run_plan(
+    plan: &Path,
+    dir: &Path,
+    templates_dir: &Path,
+    force: &bool,
+) -> Result<Vec<PathBuf>, WodError> {
+    let plan = planner::Plan::parse(&fs::read_to_string(plan)?)?;
+    let sessions = planner::skeleton(&plan, templates_dir)?;
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for session in sessions {
+        let filename = dir.join(format!("{}.wod", session.date));
+        if filename.exists() && !force {
+            println!("File '{}' already exists", filename.display());
+            continue;
+        }
+        fs::write(&filename, session.lines.join("\n") + "\n")?;
+        written.push(filename);
+    }
+    Ok(written)
+}
+
+/// Imports the workout published in a page, see [`import::import`].
+///
+/// # Arguments
+///
+/// * `url` - The link of the page, i.e. the workout of the day of a gym.
+///
+/// # Returns
+///
+/// * `Result<import::Imported, WodError>` - The workout in the shorthand and the lines
+///   that couldn't be mapped, or an error if the page can't be fetched or doesn't
+///   have a workout.
+#[cfg(feature = "cli")]
+pub fn run_import_url(url: &str) -> Result<import::Imported, WodError> {
+    let imported = import::import(&import::text(&import::fetch(url)?));
+    if imported.shorthand.is_none() && imported.unmapped.is_empty() {
+        return Err(format!("No workout found in {}", url).into());
+    }
+    Ok(imported)
+}
+
+/// The link to the published page of a file, from the `base_url` of the configuration.
+///
+/// The path of the page is its `url` in the front matter if it has one, otherwise
+/// the section (the directory of the file) followed by its `slug`, or the name of
+/// the file without the extension, as Hugo does.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use wod::config::Config;
+/// use wod::page_url;
+///
+/// let config = Config {
+///     base_url: Some("https://wod.example.com".to_string()),
+///     ..Default::default()
+/// };
+/// // A page without `slug` nor `url` in the front matter
+/// let url = page_url(Path::new("content/wod/wod-2025-03-24.md"), &config).unwrap();
+/// assert_eq!(url, "https://wod.example.com/wod/wod-2025-03-24/");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the configuration doesn't have a `base_url`, or the file
+/// can't be read.
+#[cfg(feature = "cli")]
+pub fn page_url(filename: &Path, config: &config::Config) -> Result<String, WodError> {
+    let base_url = config
+        .base_url
+        .as_deref()
+        .ok_or("The `base_url` of the site must be set in the configuration")?
+        .trim_end_matches('/');
+    let content = fs::read_to_string(filename)?;
+    let profile = config.profile;
+    if let Some(url) = front_matter::get_field(&content, profile.key("url")) {
+        return Ok(format!("{}/{}", base_url, url.trim_start_matches('/')));
+    }
+    let section = filename
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .map(|dir| dir.to_string_lossy().to_string())
+        .or_else(|| config.section.clone())
+        .unwrap_or_else(|| "wod".to_string());
+    let slug = match front_matter::get_field(&content, profile.key("slug")) {
+        Some(slug) => slug,
+        None => filename
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    };
+    Ok(format!(
+        "{}/{}/{}/",
+        base_url,
+        section.to_lowercase(),
+        slug.to_lowercase()
+    ))
+}
+
+/// Generates the QR code of the link to the published page of a file, see [`page_url`].
+///
+/// # Arguments
+///
+/// * `filename` - The file of the page, i.e. the one of the day.
+/// * `config` - The configuration with the `base_url` of the site.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The QR code as an SVG image.
+#[cfg(feature = "cli")]
+pub fn run_qr(filename: &Path, config: &config::Config) -> Result<String, WodError> {
+    qr::svg(&page_url(filename, config)?)
+}
+
+/// Runs a countdown in the terminal following the structure of the workout.
+///
+/// EMOM workouts ring the bell at the start of every interval, AMRAPs count down
+/// the total time and Tabata workouts alternate work and rest periods.
+///
+/// # Arguments
+///
+/// * `workout` - A `&str` representing the wod to time, i.e. "emom-12 10 pull up".
+///
+/// # Returns
+///
+/// * `Result<(), WodError>` - Returns `Ok(())` once the timer finishes,
+///   or an error if the workout can't be parsed or has no time structure.
+pub fn run_timer(workout: &str) -> Result<(), WodError> {
+    let wkt = create_workout(workout, None, None)?;
+    let intervals = timer::schedule(&wkt)?;
+    timer::countdown(&wkt, &intervals)?;
+    Ok(())
+}
+
+/// Checks the environment and reports the problems found.
+///
+/// It checks the configuration is valid, the registry of movements is consistent,
+/// whether the working directory is a Hugo site, and that the output and data
+/// directories can be written.
+///
+/// # Arguments
+///
+/// * `output_dir` - Directory where the workouts are written.
+///
+/// # Returns
+///
+/// * `(String, bool)` - The report, with a line per check, and whether no errors were found.
+#[cfg(feature = "cli")]
+pub fn run_doctor(output_dir: &Path) -> (String, bool) {
+    let checks = doctor::run_checks(
+        &config::Config::default_path(),
+        Path::new("."),
+        output_dir,
+        &wod_home(),
+    );
+    let healthy = checks.iter().all(|c| c.status != doctor::Status::Error);
+    let report = checks
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (report, healthy)
+}
+
+/// Watches a wod file and prints the rendered workouts every time it changes.
+///
+/// The terminal is cleared before every render when stdout is a terminal, so it
+/// works as a live preview while writing the programming of the day.
+///
+/// # Arguments
+///
+/// * `wodfile` - A `&Path` pointing to the wod file to watch.
+/// * `color` - Whether to use colors for the errors found.
+///
+/// # Returns
+///
+/// * `Result<(), WodError>` - It only returns if the file can't be read.
+pub fn run_watch(wodfile: &Path, color: bool) -> Result<(), WodError> {
+    let clear = io::stdout().is_terminal();
+    watch::watch(wodfile, std::time::Duration::from_millis(500), |content| {
+        if clear {
+            print!("\x1b[2J\x1b[H");
+        }
+        println!("{}", watch::render(content, color));
+        println!(
+            "Watching {} for changes, press Ctrl+C to stop.",
+            wodfile.display()
+        );
+    })?;
+    Ok(())
+}
+
+/// Creates a Hugo site ready to publish the workouts, see [`hugo::new_site`]. The
+/// title of the site is the name of the directory.
+///
+/// # Arguments
+///
+/// * `root` - The directory of the site, created if it doesn't exist.
+///
+/// # Returns
+///
+/// * `Result<Vec<PathBuf>, WodError>` - The files created, or an error if the
+///   directory isn't empty or the files can't be written.
+#[cfg(feature = "cli")]
+pub fn run_init(root: &Path) -> Result<Vec<PathBuf>, WodError> {
+    if root.is_file() || (root.is_dir() && fs::read_dir(root)?.next().is_some()) {
+        return Err(WodError::Invalid(format!(
+            "Directory '{}' already exists and is not empty",
+            root.display()
+        )));
+    }
+    let title = root
+        .canonicalize()
+        .unwrap_or_else(|_| root.to_path_buf())
+        .file_name()
+        .map_or_else(
+            || "wod".to_string(),
+            |name| name.to_string_lossy().to_string(),
+        );
+    Ok(hugo::new_site(root, &title)?)
+}
+
+/// Writes the archetype, the partial and the shortcodes of [`hugo::SCAFFOLD`] to an
+/// existing Hugo site, so the generated files render without work on the theme.
+///
+/// # Arguments
+///
+/// * `root` - The root of the Hugo site, with its configuration and `content` directory.
+/// * `force` - Overwrite the files that exist, otherwise they are kept.
+///
+/// # Returns
+///
+/// * `Result<(Vec<PathBuf>, Vec<PathBuf>), WodError>` - The files written and the
+///   ones kept, or an error if `root` isn't a Hugo site or the files can't be written.
+#[cfg(feature = "cli")]
+pub fn run_init_hugo(root: &Path, force: bool) -> Result<(Vec<PathBuf>, Vec<PathBuf>), WodError> {
+    if !hugo::is_site(root) {
+        return Err(WodError::Invalid(format!(
+            "Not a Hugo site: {}, it needs a hugo.toml or config.toml and a content directory",
+            root.display()
+        )));
+    }
+    Ok(hugo::scaffold(root, force)?)
+}
+
+/// Binds the server of the parser over HTTP, see [`serve`] for the endpoints. It
+/// starts serving with [`serve::Server::run`], so the address can be shown first.
+///
+/// # Arguments
+///
+/// * `host` - The address to listen on, i.e. "127.0.0.1".
+/// * `port` - The port to listen on, 0 to pick any free one.
+///
+/// # Returns
+///
+/// * `Result<serve::Server, WodError>` - The server, or an error if the address
+///   can't be bound.
+#[cfg(feature = "cli")]
+pub fn run_serve(host: &str, port: u16) -> Result<serve::Server, WodError> {
+    Ok(serve::Server::bind((host, port))?)
+}
+
+/// Runs the language server for wod files over stdin and stdout, see [`lsp`].
+///
+/// # Returns
+///
+/// * `Result<(), WodError>` - When the editor exits, or the error found while
+///   reading or writing the messages.
+#[cfg(feature = "cli")]
+pub fn run_lsp() -> Result<(), WodError> {
+    lsp::run(io::stdin().lock(), io::stdout().lock())?;
+    Ok(())
+}
+
+/// Exports an interval workout to the Garmin Connect workout JSON, so it can be
+/// imported and loaded onto a watch.
+///
+/// # Arguments
+///
+/// * `workout` - A `&str` representing the wod to export, i.e. "emom-12 10 pull up".
+/// * `name` - Name of the workout in Garmin Connect, the workout itself is used if `None`.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The pretty printed JSON, or an error
+///   if the workout can't be parsed or has no time structure.
+#[cfg(feature = "cli")]
+pub fn run_export_garmin(workout: &str, name: Option<&str>) -> Result<String, WodError> {
+    let wkt = create_workout(workout, None, None)?;
+    let garmin = export::garmin_workout(&wkt, name.unwrap_or(workout))?;
+    Ok(serde_json::to_string_pretty(&garmin)?)
+}
+
+/// Exports the cue script of an interval workout, the announcements to call out
+/// during the workout, see [`export::cues`].
+///
+/// # Arguments
+///
+/// * `workout` - A `&str` representing the wod to export, i.e. "emom-12 10 pull up".
+/// * `format` - Whether to write the cues as JSON or as SubRip subtitles.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The cue script, or an error if the workout can't
+///   be parsed or has no time structure.
+#[cfg(feature = "cli")]
+pub fn run_export_cues(workout: &str, format: export::CueFormat) -> Result<String, WodError> {
+    let wkt = create_workout(workout, None, None)?;
+    let cues = export::cues(&wkt)?;
+    Ok(export::cue_script(&cues, format))
+}
+
+/// Records the result of a workout from a generated file into the results store.
+///
+/// The date is read from the front matter of the file (or today if it's not found),
+/// and the workout is identified by `workout` if given, otherwise by the title of the file.
+///
+/// # Arguments
+///
+/// * `filename` - A `PathBuf` pointing to the markdown file of the day.
+/// * `score` - The score for the workout, i.e. "12:34".
+/// * `notes` - Optional notes, i.e. "rx".
+/// * `workout` - Optional name of the workout, i.e. "Fran".
+/// * `store` - Path to the results store.
+///
+/// # Returns
+///
+/// * `Result<ResultEntry, WodError>` - The recorded entry, or an
+///   error if the file can't be read or the store can't be written.
+#[cfg(feature = "cli")]
+pub fn run_log(
+    filename: PathBuf,
+    score: String,
+    notes: Option<String>,
+    workout: Option<String>,
+    store: &Path,
+) -> Result<results::ResultEntry, WodError> {
+    let content = fs::read_to_string(&filename)?;
+    let date = match front_matter::get_field(&content, "date") {
+        Some(date) => date,
+        None => current_date()?.to_string(),
+    };
+    let workout = workout
+        .or_else(|| front_matter::get_field(&content, "title"))
+        .unwrap_or_else(|| {
+            filename
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        });
+
+    let entry = results::ResultEntry {
+        date,
+        workout,
+        score,
+        notes,
+    };
+    let mut results = results::ResultsStore::load(store)?;
+    results.add(entry.clone());
+    results.save(store)?;
+    Ok(entry)
+}
+
+/// Returns the history of results in the store, one per line sorted by date.
+#[cfg(feature = "cli")]
+pub fn run_log_show(store: &Path) -> Result<String, WodError> {
+    let results = results::ResultsStore::load(store)?;
+    Ok(results
+        .entries()
+        .iter()
+        .map(|entry| entry.to_string())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Adds the leaderboard of a workout to the file of the day, a block at the end of
+/// the file with the scores of the athletes sorted per division, see
+/// [`leaderboard::render`]. The leaderboard already in the file for the same
+/// workout is replaced, so it can be updated as the scores come in.
+///
+/// # Arguments
+///
+/// * `filename` - A `PathBuf` pointing to the markdown file of the day.
+/// * `workout` - Name of the workout, i.e. "Fran".
+/// * `entries` - The scores of the athletes, see [`leaderboard::parse_csv`].
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The block written, or an error if there are no
+///   scores or the file can't be read or written.
+pub fn run_leaderboard(
+    filename: PathBuf,
+    workout: &str,
+    entries: &[leaderboard::Entry],
+) -> Result<String, WodError> {
+    if entries.is_empty() {
+        return Err(format!("No scores given for the leaderboard of {}", workout).into());
+    }
+    let block = i18n::with_language(&file_language(&filename), || {
+        leaderboard::render(workout, entries)
+    });
+
+    let mut doc = document::Document::parse(&fs::read_to_string(&filename)?);
+    let mut blocks = doc.blocks().to_vec();
+    match blocks
+        .iter()
+        .position(|b| leaderboard::is_leaderboard_of(b, workout))
+    {
+        Some(index) => blocks[index] = block.clone(),
+        None => blocks.push(block.clone()),
+    }
+    doc.set_blocks(blocks);
+    fs::write(&filename, doc.to_string())?;
+    Ok(block)
+}
+
+/// Adds a warm-up for the workouts of a file before the first of them, a general
+/// part and drills for the movements of the day, see [`warmup::WarmUp::for_workouts`].
+/// The warm-up already in the file is replaced, so it follows the workouts added
+/// since.
+///
+/// # Arguments
+///
+/// * `filename` - A `PathBuf` pointing to the markdown file of the day.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The block written, or an error if the file has
+///   no workouts or can't be read or written.
+pub fn run_warmup(filename: PathBuf) -> Result<String, WodError> {
+    let content = fs::read_to_string(&filename)?;
+    let workouts = markdown::Post::parse(&content)?.workouts;
+    if workouts.is_empty() {
+        return Err(format!("No workouts found in {}", filename.display()).into());
+    }
+    let block = i18n::with_language(&file_language(&filename), || {
+        warmup::WarmUp::for_workouts(&workouts).render()
+    });
+
+    let mut doc = document::Document::parse(&content);
+    let mut blocks = doc.blocks().to_vec();
+    match blocks.iter().position(|b| warmup::is_warmup(b)) {
+        Some(index) => blocks[index] = block.clone(),
+        None => blocks.insert(0, block.clone()),
+    }
+    doc.set_blocks(blocks);
+    fs::write(&filename, doc.to_string())?;
+    Ok(block)
+}
+
+/// Renders the workouts of a file personalized for an athlete, with the movements
+/// ruled out by their restrictions replaced (see [`Workout::substitute`]). The file
+/// is not modified.
+///
+/// # Arguments
+///
+/// * `filename` - A `Path` pointing to the markdown file of the day.
+/// * `profile` - The restrictions of the athlete.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The markdown of the personalized workouts, or an
+///   error if there are no restrictions or the file can't be read.
+pub fn run_personalize(
+    filename: &Path,
+    profile: &scaling::AthleteProfile,
+) -> Result<String, WodError> {
+    if profile.restrictions.is_empty() {
+        return Err(
+            "No restrictions given, i.e. \"no overhead\", the workouts would be the same".into(),
+        );
+    }
+    let workouts = markdown::Post::read(filename)?.workouts;
+    Ok(i18n::with_language(&file_language(filename), || {
+        workouts
+            .iter()
+            .map(|workout| workout.substitute(profile).to_string())
+            .collect()
+    }))
+}
+
+/// Exports the history of results as a time series to plot it, with the scores as
+/// numbers and the 1RMs estimated from the loads, see [`results::DataPoint`].
+///
+/// # Arguments
+///
+/// * `store` - Path to the results store.
+/// * `movement` - Only the results for a movement, i.e. "back squat", see
+///   [`results::ResultEntry::is_for`]. All of them if not given.
+/// * `formula` - The formula to estimate the 1RMs.
+/// * `format` - JSON or CSV.
+///
+/// # Returns
+///
+/// * `Result<String, WodError>` - The series sorted by date, or an error if the
+///   movement doesn't exist or the store can't be read.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use wod::results::SeriesFormat;
+/// use wod::rm::Formula;
+/// use wod::run_log_export;
+///
+/// let csv = run_log_export(Path::new("missing.json"), None, Formula::Epley, SeriesFormat::Csv);
+/// assert_eq!(csv.unwrap(), "date,workout,score,value,unit,one_rm\n");
+/// ```
+#[cfg(feature = "cli")]
+pub fn run_log_export(
+    store: &Path,
+    movement: Option<&str>,
+    formula: rm::Formula,
+    format: results::SeriesFormat,
+) -> Result<String, WodError> {
+    let movement = movement
+        .map(|m| m.parse::<Movement>().map_err(|e| e.to_string()))
+        .transpose()?;
+    let results = results::ResultsStore::load(store)?;
+    let points: Vec<_> = results
+        .entries()
+        .iter()
+        .filter(|entry| movement.as_ref().is_none_or(|m| entry.is_for(m)))
+        .map(|entry| entry.data_point(formula))
+        .collect();
+    Ok(results::export(&points, format))
+}
+
+#[cfg(test)]
+mod test_cmd {
+    use super::*;
+
+    #[test]
+    fn test_run_list_movements() {
+        let result = run_create_list_movements(false);
         let air_squat = result.split("\n\n").next().unwrap();
         assert_eq!(
             air_squat,
@@ -477,6 +3141,7 @@ mod test_cmd {
         );
     }
 
+    #[cfg(feature = "cli")]
     #[test]
     fn test_get_languages() {
         let langs = get_languages("en");
@@ -485,4 +3150,10 @@ mod test_cmd {
         let langs = get_languages("en,es");
         assert_eq!(langs, vec!["en", "es"]);
     }
+
+    #[test]
+    fn test_file_language() {
+        assert_eq!(file_language(Path::new("wod-2025-03-21.md")), "en");
+        assert_eq!(file_language(Path::new("wod/wod-2025-03-21.es.md")), "es");
+    }
 }
@@ -0,0 +1,482 @@
+//! Checks on the quality of the programming, beyond the workouts being valid.
+//!
+//! Every check is a [`Rule`]: some look at a single workout (too many grip intensive
+//! movements, an EMOM that can't be done in its interval), others at consecutive days
+//! of programming (heavy hinges two days in a row). The warnings are suggestions, a
+//! coach may program them on purpose.
+
+use std::fmt;
+
+use crate::movement::Movement;
+use crate::rep_types::rep_type::RepType;
+use crate::units::LoadUnit;
+use crate::weight::Weight;
+use crate::workout::{Prescription, Workout, WorkoutStructure};
+use crate::WorkoutType;
+
+/// A check over the workouts.
+pub trait Rule {
+    /// Name of the rule, shown along with its warnings.
+    fn name(&self) -> &'static str;
+
+    /// Problems found in a single workout.
+    fn check_workout(&self, _workout: &Workout) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Problems found between the workouts of a day and those of the day before.
+    fn check_days(&self, _previous: &[Workout], _day: &[Workout]) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// A warning of a rule.
+#[derive(Debug, PartialEq)]
+pub struct Lint {
+    /// Name of the rule that found the problem.
+    pub rule: &'static str,
+    /// Where it was found, i.e. "wod-2025-03-21.md, workout 2", `None` for a single workout.
+    pub location: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "warning[{}]: {}: {}", self.rule, location, self.message),
+            None => write!(f, "warning[{}]: {}", self.rule, self.message),
+        }
+    }
+}
+
+/// Heavy hinges (deadlifts and pulls) on two consecutive days.
+pub struct BackToBackHinge;
+
+/// Three or more grip intensive movements in the same workout, the grip gives up
+/// before the rest of the body does.
+pub struct GripInterference;
+
+/// Lines of an EMOM that take longer than the interval they have to fit in.
+pub struct EmomSlot;
+
+/// Loads out of the range that makes sense for the movement (see
+/// [`Movement::plausible_load`]), likely a missing decimal point or a wrong unit.
+pub struct ImplausibleLoad;
+
+/// Every rule available.
+pub fn rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(BackToBackHinge),
+        Box::new(GripInterference),
+        Box::new(EmomSlot),
+        Box::new(ImplausibleLoad),
+    ]
+}
+
+fn is_hinge(movement: &Movement) -> bool {
+    use Movement::*;
+
+    matches!(
+        movement,
+        Deadlift
+            | SumoDeadlift
+            | RomanianDeadlift
+            | CleanDeadlift
+            | SnatchDeadlift
+            | CleanPull
+            | SnatchPull
+    )
+}
+
+fn is_grip_intensive(movement: &Movement) -> bool {
+    use Movement::*;
+
+    is_hinge(movement)
+        || matches!(
+            movement,
+            PullUp
+                | ChinUp
+                | ChestToBar
+                | StrictPullUp
+                | BurpeePullUp
+                | MuscleUp
+                | BarMuscleUp
+                | RingMuscleUp
+                | ToesToBar
+                | KneesToElbows
+                | RopeClimb
+                | LeglessRopeClimb
+                | FarmersCarry
+                | KettlebellSwing
+        )
+}
+
+/// The heavy hinges of a workout: those of a weightlifting workout, or done with
+/// 100kg or more.
+fn heavy_hinges(workouts: &[Workout]) -> Vec<&Movement> {
+    let mut hinges = Vec::new();
+    for workout in workouts {
+        for line in workout.iter_prescriptions() {
+            let heavy = workout.workout_type == WorkoutType::Weightlifting
+                || line
+                    .weight
+                    .as_ref()
+                    .and_then(|w| w.to_unit(LoadUnit::Kilograms))
+                    .is_some_and(|w| w.weight_man >= 100.0);
+            for movement in line.movements.iter().filter(|m| heavy && is_hinge(m)) {
+                if !hinges.contains(&movement) {
+                    hinges.push(movement);
+                }
+            }
+        }
+    }
+    hinges
+}
+
+fn names(movements: &[&Movement]) -> String {
+    movements
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Rule for BackToBackHinge {
+    fn name(&self) -> &'static str {
+        "back-to-back-hinge"
+    }
+
+    fn check_days(&self, previous: &[Workout], day: &[Workout]) -> Vec<String> {
+        let (before, today) = (heavy_hinges(previous), heavy_hinges(day));
+        if before.is_empty() || today.is_empty() {
+            return Vec::new();
+        }
+        vec![format!(
+            "heavy hinge two days in a row: {} after {} the day before",
+            names(&today),
+            names(&before)
+        )]
+    }
+}
+
+impl Rule for GripInterference {
+    fn name(&self) -> &'static str {
+        "grip-interference"
+    }
+
+    fn check_workout(&self, workout: &Workout) -> Vec<String> {
+        let mut grip: Vec<&Movement> = Vec::new();
+        for movement in workout.movements().filter(|m| is_grip_intensive(m)) {
+            if !grip.contains(&movement) {
+                grip.push(movement);
+            }
+        }
+        if grip.len() < 3 {
+            return Vec::new();
+        }
+        vec![format!(
+            "{} grip intensive movements in the same workout: {}",
+            grip.len(),
+            names(&grip)
+        )]
+    }
+}
+
+/// Rough seconds a repetition of a movement takes at a steady pace.
+fn seconds_per_rep(movement: &Movement) -> f64 {
+    use Movement::*;
+
+    match movement {
+        DoubleUnder => 0.5,
+        AirSquat | SitUp | PushUp | VUp | PullUp | ChinUp | ChestToBar | KettlebellSwing => 2.0,
+        ToesToBar | KneesToElbows => 2.5,
+        Burpee | BurpeeToTarget => 4.0,
+        BurpeeBoxJump | BurpeeBoxJumpOver | BurpeeOverTheBar | BurpeePullUp => 5.0,
+        BarMuscleUp => 4.0,
+        MuscleUp | RingMuscleUp => 5.0,
+        WallWalk => 12.0,
+        RopeClimb | LeglessRopeClimb => 15.0,
+        _ => 3.0,
+    }
+}
+
+/// Rough seconds a meter of a movement takes.
+fn seconds_per_meter(movement: &Movement) -> f64 {
+    match movement {
+        Movement::Bike | Movement::EchoBike => 0.1,
+        Movement::Row => 0.22,
+        Movement::Run | Movement::Ski => 0.25,
+        Movement::HandstandWalk => 1.0,
+        _ => 0.5,
+    }
+}
+
+/// Estimates the seconds a line takes, `None` when it fills the interval anyway
+/// (max repetitions).
+fn estimate(line: &Prescription) -> Option<f64> {
+    let sets = match &line.sets {
+        Some(RepType::Reps(sets)) => sets.reps_man as f64,
+        _ => 1.0,
+    };
+    let mut seconds = 0.0;
+    for rep_type in &line.reps {
+        seconds += match rep_type {
+            RepType::Reps(reps) => {
+                reps.reps_man as f64 * line.movements.iter().map(seconds_per_rep).sum::<f64>()
+            }
+            // A calorie takes about 4 seconds in any machine
+            RepType::Cals(cals) => cals.cals_man as f64 * 4.0,
+            RepType::Distance(distance) => {
+                distance.meters() * line.movements.iter().map(seconds_per_meter).sum::<f64>()
+            }
+            RepType::Time(time) => time.seconds() as f64,
+            RepType::RestPeriod(rest) => rest.seconds() as f64,
+            RepType::Max => return None,
+        };
+    }
+    Some(seconds * sets)
+}
+
+fn describe(line: &Prescription) -> String {
+    let reps: Vec<String> = line.reps.iter().map(|r| r.shorthand()).collect();
+    let movements: Vec<String> = line
+        .movements
+        .iter()
+        .map(|m| m.to_string().to_lowercase())
+        .collect();
+    format!("{} {}", reps.join("+"), movements.join(" + "))
+}
+
+impl Rule for EmomSlot {
+    fn name(&self) -> &'static str {
+        "emom-slot"
+    }
+
+    fn check_workout(&self, workout: &Workout) -> Vec<String> {
+        let (WorkoutType::EMOM(emom), WorkoutStructure::Simple(simple)) =
+            (&workout.workout_type, &workout.structure)
+        else {
+            return Vec::new();
+        };
+        let slot = emom.every.seconds() as f64;
+        let too_long = |seconds: f64| seconds > slot;
+        if emom.alternating {
+            simple
+                .prescriptions
+                .iter()
+                .filter_map(|line| Some((line, estimate(line)?)))
+                .filter(|(_, seconds)| too_long(*seconds))
+                .map(|(line, seconds)| {
+                    format!(
+                        "`{}` takes about {:.0}s, more than the {}s of the interval",
+                        describe(line),
+                        seconds,
+                        slot
+                    )
+                })
+                .collect()
+        } else {
+            let seconds: Option<f64> = simple.prescriptions.iter().map(estimate).sum();
+            match seconds {
+                Some(seconds) if too_long(seconds) => vec![format!(
+                    "every interval takes about {:.0}s, more than the {}s available",
+                    seconds, slot
+                )],
+                _ => Vec::new(),
+            }
+        }
+    }
+}
+
+impl Rule for ImplausibleLoad {
+    fn name(&self) -> &'static str {
+        "implausible-load"
+    }
+
+    fn check_workout(&self, workout: &Workout) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for line in workout.iter_prescriptions() {
+            // Every load of the lines with a load per set
+            let weights: Vec<&Weight> = if line.loads.is_empty() {
+                line.weight.iter().collect()
+            } else {
+                line.loads.iter().collect()
+            };
+            for weight in weights {
+                // The percentages depend on the 1RM of the athlete
+                let Some(kg) = weight.to_unit(LoadUnit::Kilograms) else {
+                    continue;
+                };
+                for movement in &line.movements {
+                    let Some((min, max)) = movement.plausible_load() else {
+                        continue;
+                    };
+                    let loads = [kg.weight_man, kg.weight_woman];
+                    if loads.iter().all(|load| (min..=max).contains(load)) {
+                        continue;
+                    }
+                    warnings.push(format!(
+                        "`{}` is an implausible load for {}, expected between {} and {}kg",
+                        weight, movement, min, max
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+}
+
+/// Runs the rules over a single workout.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::lint::lint_workout;
+///
+/// let workout = create_workout("emom-10 20cal row, 15 burpee", None, None).unwrap();
+/// let lints: Vec<String> = lint_workout(&workout).iter().map(|l| l.to_string()).collect();
+/// assert_eq!(
+///     lints,
+///     vec!["warning[emom-slot]: every interval takes about 140s, more than the 60s available"]
+/// );
+/// ```
+pub fn lint_workout(workout: &Workout) -> Vec<Lint> {
+    rules()
+        .iter()
+        .flat_map(|rule| {
+            rule.check_workout(workout).into_iter().map(|message| Lint {
+                rule: rule.name(),
+                location: None,
+                message,
+            })
+        })
+        .collect()
+}
+
+/// Runs the rules over consecutive days of programming, each day given by a label
+/// (i.e. the name of its file) and its workouts.
+pub fn lint_days(days: &[(String, Vec<Workout>)]) -> Vec<Lint> {
+    let rules = rules();
+    let mut lints = Vec::new();
+    for (i, (label, workouts)) in days.iter().enumerate() {
+        for rule in &rules {
+            if i > 0 {
+                for message in rule.check_days(&days[i - 1].1, workouts) {
+                    lints.push(Lint {
+                        rule: rule.name(),
+                        location: Some(label.clone()),
+                        message,
+                    });
+                }
+            }
+            for (j, workout) in workouts.iter().enumerate() {
+                for message in rule.check_workout(workout) {
+                    lints.push(Lint {
+                        rule: rule.name(),
+                        location: Some(format!("{}, workout {}", label, j + 1)),
+                        message,
+                    });
+                }
+            }
+        }
+    }
+    lints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    fn workout(shorthand: &str) -> Workout {
+        create_workout(shorthand, None, None).unwrap()
+    }
+
+    #[test]
+    fn test_grip_interference() {
+        let lints = GripInterference.check_workout(&workout(
+            "ft 21-15-9 pull up, toes to bar, deadlift @100/70kg",
+        ));
+        assert_eq!(
+            lints,
+            vec!["3 grip intensive movements in the same workout: Pull Up, Toes To Bar, Deadlift"]
+        );
+        assert!(GripInterference
+            .check_workout(&workout("ft 21-15-9 pull up, thruster @43/30kg"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_implausible_load() {
+        assert_eq!(
+            ImplausibleLoad.check_workout(&workout("ft 21-15-9 pull up, thruster @400kg")),
+            vec!["`400kg` is an implausible load for Thruster, expected between 10 and 200kg"]
+        );
+        assert_eq!(
+            ImplausibleLoad
+                .check_workout(&workout("wl 5x5 deadlift @2kg"))
+                .len(),
+            1
+        );
+        // The units are converted, 225lb is a usual deadlift
+        for shorthand in [
+            "wl 5x5 deadlift @225lb",
+            "wl 5x5 deadlift @80%",
+            "wl 3x5 deadlift @100,120,140kg",
+            "ft 50 wall ball @9/6kg, 1000m row",
+            "amrap-12 10 kettlebell swing @1.5/1 pood",
+        ] {
+            assert!(
+                ImplausibleLoad
+                    .check_workout(&workout(shorthand))
+                    .is_empty(),
+                "{}",
+                shorthand
+            );
+        }
+    }
+
+    #[test]
+    fn test_emom_slot() {
+        assert!(EmomSlot
+            .check_workout(&workout("emom-10 10 pull up, 10 air squat"))
+            .is_empty());
+        assert!(EmomSlot
+            .check_workout(&workout("emom-10 max burpee"))
+            .is_empty());
+        assert!(EmomSlot
+            .check_workout(&workout("emom-12-alt 15cal row, 3 rope climb, 10 burpee"))
+            .is_empty());
+        let lints = EmomSlot.check_workout(&workout("emom-12-alt 20cal row, 5 rope climb"));
+        assert_eq!(
+            lints,
+            vec![
+                "`20cal row` takes about 80s, more than the 60s of the interval",
+                "`5 rope climb` takes about 75s, more than the 60s of the interval",
+            ]
+        );
+        assert!(EmomSlot
+            .check_workout(&workout("emom-12-3m 20cal row, 5 rope climb"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_back_to_back_hinge() {
+        let monday = vec![workout("wl 5x3 deadlift @80%")];
+        let tuesday = vec![workout("ft 21-15-9 romanian deadlift @100/70kg, burpee")];
+        let light = vec![workout("ft 21-15-9 romanian deadlift @60/40kg, burpee")];
+        assert_eq!(BackToBackHinge.check_days(&monday, &tuesday).len(), 1);
+        assert!(BackToBackHinge.check_days(&monday, &light).is_empty());
+
+        let days = vec![
+            ("monday.md".to_string(), monday),
+            ("tuesday.md".to_string(), tuesday),
+        ];
+        let lints: Vec<String> = lint_days(&days).iter().map(|l| l.to_string()).collect();
+        assert_eq!(
+            lints,
+            vec!["warning[back-to-back-hinge]: tuesday.md: heavy hinge two days in a row: Romanian Deadlift after Deadlift the day before"]
+        );
+    }
+}
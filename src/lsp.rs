@@ -0,0 +1,410 @@
+//! A small language server for wod files, so editors show the problems of the
+//! workouts as they are written, see `wod lsp`.
+//!
+//! It speaks the [Language Server Protocol](https://microsoft.github.io/language-server-protocol/)
+//! over stdin and stdout, and provides:
+//!
+//! - Diagnostics when a file is opened or saved, the same problems as
+//!   `wod check --lint` (see [`check_wodfile`]).
+//! - Completion of the movements, with their aliases.
+//! - Hover of a movement, with its name, equipment and video.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use serde_json::{json, Value};
+
+use crate::diagnostics::{check_wodfile, Severity};
+use crate::movement::Movement;
+
+/// The JSON-RPC error code of a method that isn't supported.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// The state of the server, the text of the documents open in the editor.
+#[derive(Debug, Default)]
+pub struct Server {
+    documents: HashMap<String, String>,
+    /// Whether the client asked the server to stop, with `exit`.
+    pub exit: bool,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Server::default()
+    }
+
+    /// Handles a message from the client, returning the messages to send back: the
+    /// response of a request and the notifications, i.e. the diagnostics of a file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use wod::lsp::Server;
+    ///
+    /// let mut server = Server::new();
+    /// let replies = server.handle(&json!({
+    ///     "jsonrpc": "2.0",
+    ///     "method": "textDocument/didOpen",
+    ///     "params": {"textDocument": {"uri": "file:///plan.wod", "text": "ft 10 pulup"}}
+    /// }));
+    /// let diagnostic = &replies[0]["params"]["diagnostics"][0];
+    /// assert_eq!(diagnostic["code"], "invalid-movement");
+    /// assert_eq!(diagnostic["range"]["start"], json!({"line": 0, "character": 6}));
+    /// ```
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let result = match method {
+            "initialize" => capabilities(),
+            "shutdown" => Value::Null,
+            "textDocument/completion" => completion(),
+            "textDocument/hover" => {
+                let text = self.documents.get(uri).map(String::as_str);
+                hover(text.unwrap_or_default(), &params["position"])
+            }
+            "exit" => {
+                self.exit = true;
+                return Vec::new();
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.to_string(), text.to_string());
+                return vec![publish_diagnostics(uri, text)];
+            }
+            "textDocument/didChange" => {
+                // The whole text is sent on every change, the diagnostics wait for the save
+                if let Some(text) = params["contentChanges"][0]["text"].as_str() {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+                return Vec::new();
+            }
+            "textDocument/didSave" => {
+                if let Some(text) = params["text"].as_str() {
+                    self.documents.insert(uri.to_string(), text.to_string());
+                }
+                let text = self.documents.get(uri).map(String::as_str);
+                return vec![publish_diagnostics(uri, text.unwrap_or_default())];
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                return vec![publish_diagnostics(uri, "")];
+            }
+            // The rest of the notifications, like `initialized`, need no answer
+            _ if message.get("id").is_none() => return Vec::new(),
+            _ => {
+                return vec![json!({
+                    "jsonrpc": "2.0",
+                    "id": message["id"],
+                    "error": {
+                        "code": METHOD_NOT_FOUND,
+                        "message": format!("Method not supported: {}", method),
+                    },
+                })]
+            }
+        };
+        vec![json!({"jsonrpc": "2.0", "id": message["id"], "result": result})]
+    }
+}
+
+/// What the server supports, the answer to `initialize`.
+fn capabilities() -> Value {
+    json!({
+        "capabilities": {
+            // The whole text of the document is sent on every change
+            "textDocumentSync": {"openClose": true, "change": 1, "save": {"includeText": true}},
+            "completionProvider": {},
+            "hoverProvider": true,
+        },
+        "serverInfo": {"name": "wod", "version": env!("CARGO_PKG_VERSION")},
+    })
+}
+
+/// The position in a line as counted by the editors, in UTF-16 code units, of the
+/// character at `column` (from 0).
+fn utf16_position(line: &str, column: usize) -> usize {
+    line.chars().take(column).map(char::len_utf16).sum()
+}
+
+/// The notification with the problems of a document, an empty list clears them.
+fn publish_diagnostics(uri: &str, text: &str) -> Value {
+    let lines: Vec<&str> = text.lines().collect();
+    let diagnostics: Vec<Value> = check_wodfile(text, true)
+        .into_iter()
+        .map(|a| {
+            let line = lines.get(a.line - 1).copied().unwrap_or_default();
+            let position = |column: usize| {
+                json!({"line": a.line - 1, "character": utf16_position(line, column - 1)})
+            };
+            let severity = match a.severity {
+                Severity::Error => 1,
+                Severity::Warning => 2,
+            };
+            json!({
+                "range": {"start": position(a.column), "end": position(a.end_column)},
+                "severity": severity,
+                "code": a.code,
+                "source": "wod",
+                "message": a.message,
+            })
+        })
+        .collect();
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {"uri": uri, "diagnostics": diagnostics},
+    })
+}
+
+/// The movements with all their aliases, the editor filters them as they are typed.
+fn completion() -> Value {
+    let items: Vec<Value> = Movement::all()
+        .flat_map(|movement| {
+            let name = movement.to_string();
+            movement
+                .aliases()
+                .map(|alias| json!({"label": alias, "detail": name, "kind": 12}))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    json!(items)
+}
+
+/// The description of the movement under the cursor, `null` if there's none.
+fn hover(text: &str, position: &Value) -> Value {
+    let line = position["line"].as_u64().unwrap_or_default() as usize;
+    let character = position["character"].as_u64().unwrap_or_default() as usize;
+    let Some(line) = text.lines().nth(line) else {
+        return Value::Null;
+    };
+    // From UTF-16 code units to the byte in the line
+    let mut units = 0;
+    let cursor = line
+        .char_indices()
+        .find(|(_, c)| {
+            units += c.len_utf16();
+            units > character
+        })
+        .map_or(line.len(), |(i, _)| i);
+    match movement_at(line, cursor) {
+        Some(movement) => {
+            let mut contents = format!("**{}**", movement);
+            if let Some(equipment) = movement.equipment() {
+                contents.push_str(&format!("\n\nEquipment: {}", equipment));
+            }
+            if let Some(video) = movement.video() {
+                contents.push_str(&format!("\n\n[Video]({})", video));
+            }
+            json!({"contents": {"kind": "markdown", "value": contents}})
+        }
+        None => Value::Null,
+    }
+}
+
+/// The movement whose name (or alias) contains the byte `cursor` of the line, the
+/// longest name when several do, "power clean" instead of "clean".
+fn movement_at(line: &str, cursor: usize) -> Option<Movement> {
+    // ASCII only, so the bytes of the cursor are the same in both
+    let lower = line.to_ascii_lowercase();
+    let is_boundary = |i: usize| {
+        lower[..i]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric())
+    };
+    Movement::all()
+        .flat_map(|movement| {
+            movement
+                .aliases()
+                .map(|alias| (alias, movement.clone()))
+                .collect::<Vec<_>>()
+        })
+        .filter(|(alias, _)| {
+            lower.match_indices(alias).any(|(start, _)| {
+                let end = start + alias.len();
+                start <= cursor
+                    && cursor <= end
+                    && is_boundary(start)
+                    && lower[end..]
+                        .chars()
+                        .next()
+                        .is_none_or(|c| !c.is_alphanumeric())
+            })
+        })
+        .max_by_key(|(alias, _)| alias.len())
+        .map(|(_, movement)| movement)
+}
+
+/// Reads a message of the protocol, a `Content-Length` header followed by the JSON
+/// body, `None` at the end of the input.
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().ok();
+            }
+        }
+    }
+    let Some(length) = content_length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message without Content-Length",
+        ));
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+/// Runs the server until the client exits or closes the input.
+pub fn run(mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut server = Server::new();
+    while let Some(message) = read_message(&mut input)? {
+        for reply in server.handle(&message) {
+            write_message(&mut output, &reply)?;
+        }
+        if server.exit {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movement::round_trips;
+
+    fn open(server: &mut Server, text: &str) -> Vec<Value> {
+        server.handle(&json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {"textDocument": {"uri": "file:///plan.wod", "text": text}},
+        }))
+    }
+
+    #[test]
+    fn test_initialize() {
+        let mut server = Server::new();
+        let replies = server.handle(&json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"}));
+        assert_eq!(replies[0]["id"], 1);
+        assert_eq!(replies[0]["result"]["capabilities"]["hoverProvider"], true);
+
+        let replies = server.handle(&json!({"jsonrpc": "2.0", "id": 2, "method": "unknown"}));
+        assert_eq!(replies[0]["error"]["code"], METHOD_NOT_FOUND);
+        assert!(server
+            .handle(&json!({"jsonrpc": "2.0", "method": "initialized"}))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics_on_save() {
+        let mut server = Server::new();
+        let replies = open(&mut server, "ft 21 pull up\nft 21 deadlift @1000kg");
+        let diagnostics = &replies[0]["params"]["diagnostics"];
+        assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+        assert_eq!(diagnostics[0]["severity"], 2);
+        assert_eq!(diagnostics[0]["range"]["start"]["line"], 1);
+
+        let change = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": {"uri": "file:///plan.wod"},
+                "contentChanges": [{"text": "ft 21 pul up"}],
+            },
+        });
+        assert!(server.handle(&change).is_empty());
+        let save = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didSave",
+            "params": {"textDocument": {"uri": "file:///plan.wod"}},
+        });
+        let replies = server.handle(&save);
+        let diagnostic = &replies[0]["params"]["diagnostics"][0];
+        assert_eq!(diagnostic["severity"], 1);
+        assert_eq!(diagnostic["range"]["end"]["character"], 12);
+    }
+
+    #[test]
+    fn test_utf16_position() {
+        assert_eq!(utf16_position("ft 21 pull up", 3), 3);
+        assert_eq!(utf16_position("💪 ft 21 pull up", 2), 3);
+    }
+
+    #[test]
+    fn test_completion() {
+        let items = completion();
+        let items = items.as_array().unwrap();
+        assert!(items
+            .iter()
+            .any(|i| i["label"] == "ohs" && i["detail"] == "Overhead Squat"));
+        // Every name offered reads back as its movement
+        for item in items {
+            let label = item["label"].as_str().unwrap();
+            let movement: Movement = label.parse().unwrap();
+            assert_eq!(item["detail"], movement.to_string());
+            assert!(round_trips(label, &movement), "`{}`", label);
+        }
+    }
+
+    #[test]
+    fn test_hover() {
+        let mut server = Server::new();
+        open(&mut server, "# Monday\nwl 5x3 power clean @70%");
+        let hover = |server: &mut Server, character: usize| {
+            server.handle(&json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "textDocument/hover",
+                "params": {
+                    "textDocument": {"uri": "file:///plan.wod"},
+                    "position": {"line": 1, "character": character},
+                },
+            }))[0]["result"]
+                .clone()
+        };
+        let contents = hover(&mut server, 15);
+        assert!(contents["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .starts_with("**Power Clean**"));
+        assert!(hover(&mut server, 3).is_null());
+    }
+
+    #[test]
+    fn test_run() {
+        let mut input = Vec::new();
+        for message in [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+            json!({"jsonrpc": "2.0", "method": "exit"}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "shutdown"}),
+        ] {
+            write_message(&mut input, &message).unwrap();
+        }
+        let mut output = Vec::new();
+        run(input.as_slice(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        // The messages after `exit` aren't read
+        assert_eq!(output.matches("Content-Length").count(), 2);
+        assert!(output.ends_with(r#"{"id":2,"jsonrpc":"2.0","result":null}"#));
+    }
+}
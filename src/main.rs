@@ -1,28 +1,223 @@
 mod cli;
 
-use std::path::PathBuf;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 use clap::Parser;
 
-use cli::{Cli, Commands};
+use cli::{
+    AddCommand, CheckFormat, Cli, Commands, ExportFormat, LeaderboardCommand, LogAction,
+    LogCommand, OutputArgs, PersonalizeCommand, PrAction, PublishCommand, UndoCommand,
+    WarmupCommand,
+};
+use wod::benchmarks;
+use wod::config::Config;
+use wod::cycles;
+use wod::diagnostics::{use_color, Diagnostic};
+use wod::document::Position;
+use wod::export::CueFormat;
+use wod::generator::Constraints;
+use wod::highlight;
+use wod::hugo;
+use wod::journal::Journal;
+use wod::media::Media;
+use wod::pr::PrStore;
+use wod::render::Format;
+use wod::results::ResultsStore;
+use wod::scaling::AthleteProfile;
+use wod::templates;
 use wod::{
-    run_add_wod_from_file, run_add_workout, run_base, run_check_wod, run_create_list_movements,
+    expand_filename, resolve_output_dir, run_add_daily_note, run_add_sessions,
+    run_add_wod_from_file, run_add_workout, run_base, run_build, run_check_wod, run_check_wodfile,
+    run_create_list_movements, run_cycle, run_diff, run_doctor, run_export_cues, run_export_garmin,
+    run_fmt, run_frequency, run_heatmap, run_import_url, run_init, run_init_hugo, run_leaderboard,
+    run_lint, run_lint_files, run_log, run_log_export, run_log_show, run_lsp, run_merge, run_new,
+    run_percent, run_personalize, run_plan, run_pr_baseline, run_pr_list, run_pr_set, run_publish,
+    run_qr, run_random, run_regen, run_render_workout, run_serve, run_split, run_stats, run_timer,
+    run_undo, run_volume, run_warmup, run_watch, today, wodfile_section, WodError,
 };
 
-fn main() {
+/// Exit code for any failure that is not a parse or I/O error.
+const EXIT_FAILURE: u8 = 1;
+/// Exit code when a workout (or one of its parts) can't be parsed.
+const EXIT_PARSE_ERROR: u8 = 2;
+/// Exit code when a file can't be read or written.
+const EXIT_IO_ERROR: u8 = 3;
+/// Exit code when `wod fmt --check` finds files that aren't formatted.
+const EXIT_UNFORMATTED: u8 = 4;
+
+fn exit_code(err: &WodError) -> u8 {
+    match err {
+        e if e.is_parse_error() => EXIT_PARSE_ERROR,
+        WodError::Io(_) => EXIT_IO_ERROR,
+        WodError::Unformatted(_) => EXIT_UNFORMATTED,
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// Warns about the workouts left out of a report because they can't be read back.
+fn warn_unreadable(unreadable: &[String]) {
+    if !unreadable.is_empty() {
+        eprintln!("These workouts can't be read back and were left out:");
+        for workout in unreadable {
+            eprintln!("  {}", workout);
+        }
+    }
+}
+
+/// The file given in the command line, or the markdown file of today generated
+/// from the filename template.
+fn todays_file(file: Option<&str>, config: &Config) -> Result<String, WodError> {
+    match file {
+        Some(file) => Ok(file.to_string()),
+        None => Ok(format!(
+            "{}.md",
+            expand_filename(&config.filename_template, today())?
+        )),
+    }
+}
+
+fn main() -> ExitCode {
     let cli = Cli::parse();
 
+    let color = use_color(cli.no_color);
+
+    match run(cli, color) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            match &e {
+                WodError::Diagnostic(diagnostic) => eprint!("{}", diagnostic.render(color)),
+                _ => eprintln!("Error: {}", e),
+            }
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
+
+fn run(cli: Cli, color: bool) -> Result<(), WodError> {
+    if let Some(Commands::Doctor) = &cli.command {
+        // Runs before loading the config, as it may be the problem to report
+        let output_dir = resolve_output_dir(
+            None,
+            &Config::load(&Config::default_path()).unwrap_or_default(),
+            Path::new("."),
+        );
+        let (report, healthy) = run_doctor(&output_dir);
+        println!("{}", report);
+        if !healthy {
+            return Err("wod doctor found problems, see the errors above".into());
+        }
+        return Ok(());
+    }
+
+    let mut config = Config::load(&Config::default_path())?;
+    config.register_messages();
+    let (output, draft, per_workout) = output_args(&cli);
+    config.draft |= draft;
+    config.per_workout |= per_workout;
+    if let Some(section) = output.and_then(|output| output.section.clone()) {
+        config.section = Some(section);
+    }
+    let output_dir = resolve_output_dir(
+        output.and_then(|output| output.output_dir.as_deref()),
+        &config,
+        Path::new("."),
+    );
+
     match &cli.command {
         Some(Commands::Add(add_command)) => {
             // The add command "wod add 'workout' -f 'date-filename.md' "
-            let filename = PathBuf::from(add_command.filename.to_string());
-            let _ = run_add_workout(
-                filename,
-                &add_command.workout,
+            let filename = output_dir.join(todays_file(add_command.filename.as_deref(), &config)?);
+            // wod add "Fran" adds the benchmark with its name
+            let benchmark = benchmarks::find(&add_command.workout);
+            let workout = benchmark.map_or(add_command.workout.as_str(), |b| b.shorthand);
+            let name = add_command
+                .name
+                .clone()
+                .or_else(|| benchmark.map(|b| b.name.to_string()));
+            if let Some(diagnostic) = Diagnostic::from_source(workout) {
+                return Err(diagnostic.into());
+            }
+            if add_command.format != Format::Md {
+                // wod add "ft 21-15-9 pull up, thruster" --format text
+                let rendered = run_render_workout(
+                    workout,
+                    add_command.comments.clone(),
+                    name.clone(),
+                    add_command.format,
+                )?;
+                println!("{}", rendered);
+                return Ok(());
+            }
+            // wod add "ft 21-15-9 bar mu, thruster @43/30kg" --scaled intermediate
+            let mut scaling = config.scaling.clone();
+            for level in &add_command.scaled {
+                if !scaling.levels.contains(level) {
+                    scaling.levels.push(*level);
+                }
+            }
+            // wod add "wl 5x(1+1) clean, split jerk @75%" --video https://youtu.be/...
+            let media = add_command
+                .video
+                .iter()
+                .map(|url| Media::video(url))
+                .chain(add_command.image.iter().map(|url| Media::image(url)))
+                .collect::<Result<Vec<Media>, WodError>>()?;
+            if let (Some(obsidian), None) = (&config.obsidian, &add_command.filename) {
+                if !media.is_empty() {
+                    return Err(WodError::Invalid(
+                        "--video and --image are only supported in the generated files, \
+                         not in the daily notes"
+                            .to_string(),
+                    ));
+                }
+                // The workouts go to the daily note of the vault instead
+                let note = obsidian.note(today())?;
+                let added = run_add_daily_note(
+                    note.clone(),
+                    &obsidian.heading,
+                    workout,
+                    add_command.comments.clone(),
+                    name.clone(),
+                    &scaling,
+                )?;
+                match added {
+                    Some(_) => println!("Added workout to note: {}", note.display()),
+                    None => println!("The workout is already in note: {}", note.display()),
+                }
+                return Ok(());
+            }
+            let position = match (add_command.at, &add_command.before) {
+                (Some(n), _) => Position::At(n),
+                (None, Some(text)) => Position::Before(text.clone()),
+                (None, None) => Position::End,
+            };
+            let added = run_add_workout(
+                filename.clone(),
+                workout,
                 add_command.comments.clone(),
-                add_command.name.clone(),
-            );
-            println!("Added workout to file: {}", add_command.filename);
+                name.clone(),
+                &media,
+                &scaling,
+                &config.markdown(),
+                &position,
+                add_command.force,
+            )?;
+            match added {
+                Some(block) => {
+                    let journal_path = Journal::default_path();
+                    let mut journal = Journal::load(&journal_path)?;
+                    journal.record(&fs::canonicalize(&filename)?, block);
+                    journal.save(&journal_path)?;
+                    println!("Added workout to file: {}", filename.display());
+                }
+                None => println!(
+                    "The workout is already in file: {}, use --force to add it again",
+                    filename.display()
+                ),
+            }
         }
         Some(Commands::List(list_command)) => {
             let movement_list = run_create_list_movements(list_command.page);
@@ -30,22 +225,587 @@ fn main() {
         }
         Some(Commands::Check(check_wod)) => {
             // wod check "ft 21-15-9 pull up, thruster @ 43/30kg"
-            match run_check_wod(&check_wod.wod) {
-                Ok(content) => println!("{}", content),
-                Err(e) => eprintln!("Failed to parse workout: {}", e),
+            // wod check --lint --files content/posts/wod-2025-03-1*.md
+            let Some(wod) = &check_wod.wod else {
+                println!("{}", run_lint_files(&check_wod.files)?);
+                return Ok(());
+            };
+            // wod check plan.wod --format json
+            let wodfile = Path::new(wod);
+            let is_wodfile =
+                wodfile.extension().is_some_and(|ext| ext == "wod") && wodfile.is_file();
+            if is_wodfile || check_wod.format == CheckFormat::Json {
+                let content = if is_wodfile {
+                    fs::read_to_string(wodfile)?
+                } else {
+                    wod.clone()
+                };
+                let json = check_wod.format == CheckFormat::Json;
+                let (report, valid) = run_check_wodfile(&content, wod, check_wod.lint, json);
+                println!("{}", report);
+                if !valid {
+                    return Err(WodError::InvalidWorkout(
+                        "wod check found errors, see the report above".to_string(),
+                    ));
+                }
+                return Ok(());
+            }
+            if let Some(diagnostic) = Diagnostic::from_source(wod) {
+                return Err(diagnostic.into());
+            }
+            let prs = if check_wod.loads || check_wod.paces {
+                Some(PrStore::load(&PrStore::default_path())?)
+            } else {
+                None
+            };
+            let loads = prs.as_ref().filter(|_| check_wod.loads);
+            let paces = prs.as_ref().filter(|_| check_wod.paces);
+            println!("{}", run_check_wod(wod, loads, paces)?);
+            if check_wod.lint {
+                println!("{}", run_lint(wod)?);
+            }
+        }
+        Some(Commands::Stats(stats_command)) => {
+            // wod stats "wl 5x5 back squat @70%"
+            // wod stats --frequency --format json
+            let Some(wod) = &stats_command.wod else {
+                let frequency = run_frequency(
+                    &output_dir,
+                    stats_command.date,
+                    &config.frequency,
+                    stats_command.format,
+                )?;
+                println!("{}", frequency.report.trim_end());
+                warn_unreadable(&frequency.unreadable);
+                return Ok(());
+            };
+            if let Some(diagnostic) = Diagnostic::from_source(wod) {
+                return Err(diagnostic.into());
+            }
+            println!("{}", run_stats(wod, &PrStore::default_path())?);
+        }
+        Some(Commands::Volume(volume_command)) => {
+            // wod volume --format csv > volume.csv
+            let volume = run_volume(&output_dir, &PrStore::default_path(), volume_command.format)?;
+            println!("{}", volume.report.trim_end());
+            warn_unreadable(&volume.unreadable);
+        }
+        Some(Commands::Heatmap(heatmap_command)) => {
+            // wod heatmap content/wod --format csv > static/heatmap.csv
+            let dir = heatmap_command.dir.as_deref().unwrap_or(&output_dir);
+            let heatmap = run_heatmap(dir, heatmap_command.format)?;
+            println!("{}", heatmap.report.trim_end());
+            warn_unreadable(&heatmap.unreadable);
+        }
+        Some(Commands::Diff(diff_command)) => {
+            // wod diff "wl 5x5 back squat @70%" "wl 5x3 back squat @80%"
+            for wod in [&diff_command.first, &diff_command.second] {
+                if let Some(diagnostic) = Diagnostic::from_source(wod) {
+                    return Err(diagnostic.into());
+                }
+            }
+            println!("{}", run_diff(&diff_command.first, &diff_command.second)?);
+        }
+        Some(Commands::Random(random_command)) => {
+            // wod random --minutes 20 --equipment barbell,rower
+            let equipment = match (random_command.bodyweight, &random_command.equipment) {
+                (true, _) => Some(Vec::new()),
+                (false, equipment) if equipment.is_empty() => None,
+                (false, equipment) => Some(equipment.clone()),
+            };
+            let movements = Some(random_command.movements.clone()).filter(|m| !m.is_empty());
+            let constraints = Constraints {
+                minutes: random_command.minutes,
+                equipment,
+                movements,
+                stimulus: random_command.stimulus,
+                seed: random_command.seed,
+            };
+            println!("{}", run_random(&constraints)?);
+        }
+        Some(Commands::Timer(timer_command)) => {
+            // wod timer "emom-12 10 pull up"
+            if let Some(diagnostic) = Diagnostic::from_source(&timer_command.wod) {
+                return Err(diagnostic.into());
+            }
+            run_timer(&timer_command.wod)?;
+        }
+        Some(Commands::Log(log_command)) => {
+            let store = ResultsStore::default_path();
+            match (&log_command.action, &log_command.score) {
+                (Some(LogAction::Show), _) => println!("{}", run_log_show(&store)?),
+                (
+                    Some(LogAction::Export {
+                        movement,
+                        format,
+                        formula,
+                    }),
+                    _,
+                ) => {
+                    let series = run_log_export(&store, movement.as_deref(), *formula, *format)?;
+                    println!("{}", series.trim_end());
+                }
+                (None, Some(score)) => {
+                    // wod log --file wod-2025-03-21.md --score "12:34" --notes "rx"
+                    let entry = run_log(
+                        output_dir.join(todays_file(log_command.file.as_deref(), &config)?),
+                        score.clone(),
+                        log_command.notes.clone(),
+                        log_command.workout.clone(),
+                        &store,
+                    )?;
+                    println!("Logged result: {}", entry);
+                }
+                (None, None) => return Err("A --score is required to log a result".into()),
+            }
+        }
+        Some(Commands::Leaderboard(leaderboard_command)) => {
+            let content = match &leaderboard_command.scores {
+                Some(scores) => fs::read_to_string(scores)?,
+                None => {
+                    // Interactive, a score per line until an empty one
+                    eprintln!(
+                        "Enter the scores as `athlete,score[,division]`, an empty line to finish:"
+                    );
+                    io::stdin()
+                        .lines()
+                        .map_while(Result::ok)
+                        .take_while(|line| !line.trim().is_empty())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            };
+            let entries = wod::leaderboard::parse_csv(&content)?;
+            let filename =
+                output_dir.join(todays_file(leaderboard_command.file.as_deref(), &config)?);
+            let block = run_leaderboard(filename.clone(), &leaderboard_command.workout, &entries)?;
+            println!(
+                "Leaderboard added to {}:\n\n{}",
+                filename.display(),
+                block.trim_end()
+            );
+        }
+        Some(Commands::Warmup(warmup_command)) => {
+            // wod warmup --from wod-2025-03-21.md
+            let filename = output_dir.join(todays_file(warmup_command.from.as_deref(), &config)?);
+            let block = run_warmup(filename.clone())?;
+            println!(
+                "Warm-up added to {}:\n\n{}",
+                filename.display(),
+                block.trim_end()
+            );
+        }
+        Some(Commands::Personalize(personalize_command)) => {
+            // wod personalize --restrictions "no overhead"
+            let filename =
+                output_dir.join(todays_file(personalize_command.file.as_deref(), &config)?);
+            let profile = if personalize_command.restrictions.is_empty() {
+                config.athlete.clone()
+            } else {
+                AthleteProfile {
+                    restrictions: personalize_command.restrictions.clone(),
+                }
+            };
+            print!("{}", run_personalize(&filename, &profile)?);
+        }
+        Some(Commands::Pr(pr_command)) => {
+            let store = PrStore::default_path();
+            match &pr_command.action {
+                PrAction::Set {
+                    movement,
+                    weight,
+                    reps,
+                    formula,
+                } => {
+                    let one_rm = run_pr_set(movement, weight, *reps, *formula, &store)?;
+                    println!("Personal record set: {} {}", movement, one_rm);
+                }
+                PrAction::Baseline { movement, baseline } => {
+                    let baseline = run_pr_baseline(movement, baseline, &store)?;
+                    println!("Baseline set: {} {}", movement, baseline);
+                }
+                PrAction::List => println!("{}", run_pr_list(&store)?),
+            }
+        }
+        Some(Commands::Percent(percent_command)) => {
+            // wod percent 82% clean
+            let load = run_percent(
+                &percent_command.percent,
+                percent_command.movement.as_deref(),
+                percent_command.one_rm.as_deref(),
+                percent_command.reps,
+                percent_command.formula,
+                &PrStore::default_path(),
+                &config,
+            )?;
+            println!("{}", load);
+        }
+        Some(Commands::Cycle(cycle_command)) => {
+            // wod cycle 531 "back squat,deadlift" --start "next monday" --days mon,thu
+            let sessions = run_cycle(
+                cycle_command.cycle,
+                &cycle_command.movements,
+                cycle_command.start,
+                cycle_command.weeks,
+                &cycle_command.days,
+                &PrStore::default_path(),
+                &config,
+            )?;
+            if cycle_command.write {
+                let filenames = run_add_sessions(
+                    &sessions,
+                    &output_dir,
+                    cycle_command.languages.clone(),
+                    &config,
+                    &cycle_command.force,
+                )?;
+                for filename in filenames {
+                    println!("Created file: {}", filename.display());
+                }
+            } else {
+                print!("{}", cycles::wodfile(&sessions));
+            }
+        }
+        Some(Commands::Qr(qr_command)) => {
+            // wod qr -o whiteboard.svg
+            let filename = output_dir.join(todays_file(qr_command.file.as_deref(), &config)?);
+            let image = run_qr(&filename, &config)?;
+            match &qr_command.output {
+                Some(output) => fs::write(output, image)?,
+                None => print!("{}", image),
+            }
+        }
+        Some(Commands::ImportUrl(import_command)) => {
+            // wod import-url https://www.crossfit.com/250326
+            let imported = run_import_url(&import_command.link)?;
+            if let Some(shorthand) = &imported.shorthand {
+                println!("{}", shorthand);
+            }
+            if !imported.unmapped.is_empty() {
+                eprintln!("Couldn't map these lines of the workout:");
+                for line in &imported.unmapped {
+                    eprintln!("  {}", line);
+                }
+            }
+        }
+        Some(Commands::Plan(plan_command)) => {
+            // wod plan plan.toml --dir programming
+            let filenames = run_plan(
+                &plan_command.plan,
+                &plan_command.dir,
+                &templates::default_dir(),
+                &plan_command.force,
+            )?;
+            for filename in filenames {
+                println!("Created file: {}", filename.display());
+            }
+        }
+        Some(Commands::Export(export_command)) => {
+            // wod export "tabata 20 air squat" --format garmin -o squats.json
+            if let Some(diagnostic) = Diagnostic::from_source(&export_command.wod) {
+                return Err(diagnostic.into());
+            }
+            let exported = match export_command.format {
+                ExportFormat::Garmin => {
+                    run_export_garmin(&export_command.wod, export_command.name.as_deref())?
+                }
+                ExportFormat::Cues => run_export_cues(&export_command.wod, CueFormat::Json)?,
+                ExportFormat::Srt => run_export_cues(&export_command.wod, CueFormat::Srt)?,
+            };
+            match &export_command.output {
+                Some(output) => {
+                    std::fs::write(output, exported)?;
+                    println!("Exported workout to file: {}", output);
+                }
+                None => println!("{}", exported),
+            }
+        }
+        Some(Commands::Undo(undo_command)) => {
+            // wod undo wod-2025-03-21.md
+            let filename = output_dir.join(todays_file(undo_command.file.as_deref(), &config)?);
+            run_undo(filename.clone(), &Journal::default_path())?;
+            println!("Removed last workout from file: {}", filename.display());
+        }
+        Some(Commands::Publish(publish_command)) => {
+            // wod publish wod-2025-03-21.md
+            let filename = output_dir.join(todays_file(publish_command.file.as_deref(), &config)?);
+            run_publish(filename.clone(), publish_command.unpublish)?;
+            if publish_command.unpublish {
+                println!("Set file as a draft: {}", filename.display());
+            } else {
+                println!("Published file: {}", filename.display());
+            }
+        }
+        Some(Commands::Build(build_command)) => {
+            // wod build programming/march
+            let wodfiles = run_build(
+                &build_command.dir,
+                &output_dir,
+                &config,
+                build_command.languages.clone(),
+                &build_command.force,
+            )?;
+            println!("Processed {} wod files", wodfiles.len());
+        }
+        Some(Commands::Regen(regen_command)) => {
+            // wod regen content/wod
+            let files = run_regen(&regen_command.dir, &config)?;
+            println!("Regenerated {} files", files.len());
+        }
+        Some(Commands::Split(split_command)) => {
+            // wod split april.wod --start 2025-04-01
+            let files = run_split(
+                &split_command.wodfile,
+                split_command.start,
+                &output_dir,
+                split_command.languages.clone(),
+                &config,
+                &split_command.force,
+            )?;
+            println!(
+                "Wrote {} files from WOD file: {}",
+                files.len(),
+                split_command.wodfile.display()
+            );
+        }
+        Some(Commands::Merge(merge_command)) => {
+            // wod merge wod-2025-03-21-am.md wod-2025-03-21-pm.md -o wod-2025-03-21.md
+            let workouts = run_merge(
+                &merge_command.files,
+                &merge_command.output,
+                merge_command.force,
+            )?;
+            println!(
+                "Merged {} workouts into: {}",
+                workouts,
+                merge_command.output.display()
+            );
+        }
+        Some(Commands::New(new_command)) => {
+            // wod new --template strength+metcon
+            let templates_dir = templates::default_dir();
+            match &new_command.template {
+                Some(template) if !new_command.list => {
+                    let filename = match &new_command.filename {
+                        Some(filename) => output_dir.join(filename),
+                        None => output_dir.join(expand_filename(
+                            &config.filename_template,
+                            new_command.file_date,
+                        )?),
+                    };
+                    let filenames = run_new(
+                        filename,
+                        template,
+                        &templates_dir,
+                        new_command.file_date,
+                        new_command.languages.clone(),
+                        &config,
+                        &new_command.force,
+                    )?;
+                    for filename in filenames {
+                        println!("Created file: {}", filename.display());
+                    }
+                }
+                _ => println!("{}", templates::list(&templates_dir).join("\n")),
+            }
+        }
+        Some(Commands::Manpage(manpage_command)) => match &manpage_command.output {
+            Some(output) => {
+                cli::manpage(&mut fs::File::create(output)?)?;
+                println!("Man page written to: {}", output.display());
+            }
+            None => cli::manpage(&mut io::stdout())?,
+        },
+        Some(Commands::Init(init_command)) => {
+            // wod init myblog
+            let files = run_init(&init_command.root)?;
+            println!(
+                "Created site with {} files: {}",
+                files.len(),
+                init_command.root.display()
+            );
+            println!(
+                "Add the first workout from the site with `wod --wodfile programming/sample.wod`"
+            );
+        }
+        Some(Commands::InitHugo(init_command)) => {
+            // wod init-hugo ~/blog
+            let (written, kept) = run_init_hugo(&init_command.root, init_command.force)?;
+            for file in written {
+                println!("Created file: {}", file.display());
+            }
+            for file in kept {
+                println!(
+                    "File already exists, use --force to overwrite it: {}",
+                    file.display()
+                );
             }
         }
+        Some(Commands::Serve(serve_command)) => {
+            // wod serve --port 8080
+            let server = run_serve(&serve_command.host, serve_command.port)?;
+            println!("Listening on http://{}", server.local_addr()?);
+            server.run()?;
+        }
+        Some(Commands::Grammar(grammar_command)) => {
+            // wod grammar --format vim -o ~/.vim/syntax/wod.vim
+            let definition = highlight::generate(grammar_command.format);
+            match &grammar_command.output {
+                Some(output) => {
+                    fs::write(output, definition)?;
+                    println!("Highlighting written to: {}", output.display());
+                }
+                None => print!("{}", definition),
+            }
+        }
+        Some(Commands::Fmt(fmt_command)) => {
+            // wod fmt plan.wod --check
+            let (report, unformatted) = run_fmt(&fmt_command.files, fmt_command.check)?;
+            println!("{}", report);
+            if fmt_command.check && unformatted > 0 {
+                let files = match unformatted {
+                    1 => "1 file".to_string(),
+                    n => format!("{} files", n),
+                };
+                return Err(WodError::Unformatted(files));
+            }
+        }
+        Some(Commands::Lsp) => {
+            // Started by the editor, it talks to it over stdin and stdout
+            run_lsp()?;
+        }
+        Some(Commands::Doctor) => unreachable!("handled before loading the config"),
+        Some(Commands::Watch(watch_command)) => {
+            // wod watch plan.wod
+            run_watch(&watch_command.wodfile, color)?;
+        }
         None => {
             // The base command "wod 'date-filename.md'"
-            let filename = PathBuf::from(cli.filename.to_string());
-            if cli.wodfile.is_some() {
+            let section = match (&config.section, &cli.wodfile) {
+                (None, Some(wodfile)) => wodfile_section(Path::new(wodfile), &config)?,
+                _ => None,
+            };
+            let output_dir = match section {
+                Some(section) => hugo::section_dir(&output_dir, &section),
+                None => output_dir,
+            };
+            let filename = match &cli.filename {
+                Some(filename) => output_dir.join(filename),
+                None => output_dir.join(expand_filename(&config.filename_template, cli.file_date)?),
+            };
+            if let Some(wodfile) = &cli.wodfile {
                 // Check/Parse the filename
-                let wodfile = PathBuf::from(cli.wodfile.unwrap());
-                let _ = run_add_wod_from_file(filename, wodfile, cli.file_date, cli.languages);
+                let wodfile = PathBuf::from(wodfile);
+                run_add_wod_from_file(
+                    filename,
+                    wodfile,
+                    cli.file_date,
+                    cli.languages.clone(),
+                    &config,
+                    &cli.force,
+                )?;
             } else {
                 println!("Creating file: {}", filename.display());
-                let _ = run_base(filename, &cli.force, cli.file_date, cli.languages);
+                run_base(
+                    filename,
+                    &cli.force,
+                    cli.file_date,
+                    cli.languages.clone(),
+                    &config,
+                )?;
             }
         }
     }
+    Ok(())
+}
+
+/// The flags of the command that writes the files: where they go, and whether they
+/// are created as drafts or one per workout.
+fn output_args(cli: &Cli) -> (Option<&OutputArgs>, bool, bool) {
+    match &cli.command {
+        None => (Some(&cli.output), cli.draft, cli.per_workout),
+        Some(Commands::Build(command)) => {
+            (Some(&command.output), command.draft, command.per_workout)
+        }
+        Some(Commands::Split(command)) => (Some(&command.output), command.draft, false),
+        Some(Commands::New(command)) => (Some(&command.output), command.draft, false),
+        Some(Commands::Cycle(command)) => (Some(&command.output), command.draft, false),
+        Some(Commands::Add(AddCommand { output, .. }))
+        | Some(Commands::Undo(UndoCommand { output, .. }))
+        | Some(Commands::Publish(PublishCommand { output, .. }))
+        | Some(Commands::Log(LogCommand { output, .. }))
+        | Some(Commands::Leaderboard(LeaderboardCommand { output, .. }))
+        | Some(Commands::Warmup(WarmupCommand { output, .. }))
+        | Some(Commands::Personalize(PersonalizeCommand { output, .. })) => {
+            (Some(output), false, false)
+        }
+        _ => (None, false, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_exit_code() {
+        let err = wod::create_workout("ft 21-15-9 pulup", None, None).unwrap_err();
+        assert_eq!(exit_code(&err), EXIT_PARSE_ERROR);
+
+        let err = wod::create_workout("ft [21 pull up; 10 thruster]", None, None).unwrap_err();
+        assert_eq!(exit_code(&err), EXIT_PARSE_ERROR);
+
+        let err: WodError = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+        assert_eq!(exit_code(&err), EXIT_IO_ERROR);
+
+        let err = WodError::Unformatted("1 file".to_string());
+        assert_eq!(exit_code(&err), EXIT_UNFORMATTED);
+        assert_eq!(
+            err.to_string(),
+            "1 file not formatted, run `wod fmt` without --check"
+        );
+
+        let err: WodError = "other".into();
+        assert_eq!(exit_code(&err), EXIT_FAILURE);
+    }
+
+    #[test]
+    fn test_manpage() {
+        let mut out = Vec::new();
+        cli::manpage(&mut out).unwrap();
+        let man = String::from_utf8(out).unwrap();
+        assert!(man.starts_with(".ie"));
+        assert!(man.contains(".SH SUBCOMMANDS"));
+        assert!(man.contains(".SH SHORTHAND\n.nf\nWorkout types:\n  ft "));
+    }
+
+    #[test]
+    fn test_output_args() {
+        let cli = Cli::try_parse_from(["wod", "--draft", "--section", "competition"]).unwrap();
+        let (output, draft, per_workout) = output_args(&cli);
+        assert_eq!(output.unwrap().section.as_deref(), Some("competition"));
+        assert!(draft && !per_workout);
+
+        let cli = Cli::try_parse_from(["wod", "build", "plan", "--per-workout"]).unwrap();
+        let (_, draft, per_workout) = output_args(&cli);
+        assert!(!draft && per_workout);
+
+        let cli = Cli::try_parse_from(["wod", "add", "5 pull up", "--output-dir", "out"]).unwrap();
+        let (output, _, _) = output_args(&cli);
+        assert_eq!(output.unwrap().output_dir, Some(PathBuf::from("out")));
+
+        // Only the commands writing the files take them
+        assert!(Cli::try_parse_from(["wod", "pr", "list", "--draft"]).is_err());
+        assert!(Cli::try_parse_from(["wod", "stats", "--output-dir", "out"]).is_err());
+        assert!(Cli::try_parse_from(["wod", "add", "5 pull up", "--per-workout"]).is_err());
+        // Nor do they change the help of the commands
+        let cli = Cli::command();
+        assert_eq!(cli.get_about(), None);
+        let add = cli.find_subcommand("add").unwrap();
+        assert_eq!(
+            add.get_about().unwrap().to_string(),
+            "Command to add a new workout to a file"
+        );
+    }
 }
@@ -0,0 +1,583 @@
+//! Reads the generated markdown files back into workouts.
+//!
+//! Every workout block written by the `Display` implementation of [`Workout`] is
+//! turned back into the shorthand and parsed again, so the commands can edit, remove
+//! or summarize the workouts already published:
+//!
+//! ```text
+//! ---
+//!
+//! **For Time**
+//!
+//! 21-15-9
+//!
+//! - Pull Up
+//!
+//! - Thruster @ 43/30kg
+//! ```
+//!
+//! is read as `ft 21-15-9 pull up, thruster @43/30kg`. The scaled versions written
+//! under a workout are skipped.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::document::Document;
+use crate::error::WodError;
+use crate::front_matter;
+use crate::leaderboard;
+use crate::media::Media;
+use crate::movement::Movement;
+use crate::render;
+use crate::rpe::Rpe;
+use crate::warmup;
+use crate::weight::{self, Weight};
+use crate::workout::{create_workout, Workout};
+
+/// A generated file: the fields of the front matter and its workouts.
+///
+/// # Examples
+///
+/// ```
+/// use wod::markdown::Post;
+///
+/// let content = "---\ntitle: \"2025-03-21\"\ndate: 2025-03-21\ndraft: false\n---\n\n\
+///                Workout for the day, 2025-03-21.\n\n\
+///                ---\n\n**Weightlifting**\n\n5x5 Back Squat @ 70%\n\n";
+/// let post = Post::parse(content).unwrap();
+/// assert_eq!(post.date.as_deref(), Some("2025-03-21"));
+/// assert_eq!(post.workouts[0].to_shorthand(), "wl 5x5 back squat @70%");
+/// ```
+#[derive(Debug)]
+pub struct Post {
+    /// The `title` of the front matter.
+    pub title: Option<String>,
+    /// The `date` of the front matter.
+    pub date: Option<String>,
+    /// The workouts of the file, in order.
+    pub workouts: Vec<Workout>,
+}
+
+impl Post {
+    /// Reads the workouts from the content of a generated file.
+    ///
+    /// Returns an error with the number of the workout (starting from 1) if one of
+    /// them can't be read. The leaderboards and the warm-up of the file are skipped.
+    pub fn parse(content: &str) -> Result<Post, WodError> {
+        let workouts = Document::parse(content)
+            .blocks()
+            .iter()
+            .filter(|block| !leaderboard::is_leaderboard(block) && !warmup::is_warmup(block))
+            .enumerate()
+            .map(|(i, block)| {
+                parse_workout(block).map_err(|e| {
+                    WodError::InvalidWorkout(format!("workout {} of the file: {}", i + 1, e))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Post {
+            title: front_matter::get_field(content, "title"),
+            date: front_matter::get_field(content, "date"),
+            workouts,
+        })
+    }
+
+    /// Reads a generated file, see [`Post::parse`].
+    pub fn read(path: &Path) -> Result<Post, WodError> {
+        Post::parse(&fs::read_to_string(path)?)
+    }
+}
+
+/// Reads a workout block of a generated file back into a [`Workout`], with its name,
+/// comments and media.
+///
+/// # Examples
+///
+/// ```
+/// use wod::markdown::parse_workout;
+///
+/// let block = "---\n\n*Fran*\n\n**For Time**\n\n21-15-9\n\n- Pull Up\n\n- Thruster @ 43/30kg\n\n";
+/// let workout = parse_workout(block).unwrap();
+/// assert_eq!(workout.name(), Some("Fran"));
+/// assert_eq!(workout.to_string(), block);
+/// ```
+pub fn parse_workout(block: &str) -> Result<Workout, WodError> {
+    let (shorthand, comments, name) = to_shorthand(block)?;
+    let mut workout = create_workout(&shorthand, comments, name)?;
+    for media in block
+        .lines()
+        .take_while(|l| !l.starts_with("*Scaled: "))
+        .filter_map(Media::from_markdown)
+    {
+        workout.add_media(media);
+    }
+    Ok(workout)
+}
+
+/// Workout type written in a header, i.e. "**EMOM 12 minutes**" along with the
+/// paragraph that follows it, "work every 3 minutes, rest 1 minute".
+fn workout_type(header: &str, details: Option<&str>) -> Option<String> {
+    let alternating =
+        header.contains(", alternating") || details.is_some_and(|d| d.contains(", alternating"));
+    let header = header.replace(", alternating", "");
+    let details = details.map(|d| d.replace(", alternating", ""));
+    let words: Vec<&str> = header.split_whitespace().collect();
+    match words.as_slice() {
+        ["For", "Time"] => Some("ft".to_string()),
+        [rounds, "rounds", "for", "time"] => Some(format!("{}rd", rounds)),
+        ["Weightlifting"] => Some("wl".to_string()),
+        ["AMRAP", minutes, "minutes"] => Some(format!("amrap-{}", minutes)),
+        ["AMRAP", seconds, "seconds"] => Some(format!("amrap-{}s", seconds)),
+        ["EMOM", rounds, "minutes"] => {
+            let mut emom = format!("emom-{}", rounds);
+            for part in details.iter().flat_map(|d| d.split(", ")) {
+                match part.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    ["work", "every", duration, unit] => {
+                        emom.push_str(&format!("-{}{}", duration, time_unit(unit)?))
+                    }
+                    ["rest", duration, unit] => {
+                        emom.push_str(&format!("-r{}{}", duration, time_unit(unit)?))
+                    }
+                    _ => return None,
+                }
+            }
+            if alternating {
+                emom.push_str("-alt");
+            }
+            Some(emom)
+        }
+        ["Tabata", rounds, "rounds"] => {
+            let details = details?;
+            let parts: Vec<&str> = details.split_whitespace().collect();
+            match parts.as_slice() {
+                [work, "seconds", "work,", rest, "seconds", "rest"] => {
+                    Some(format!("tabata-{}-{}s-{}s", rounds, work, rest))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn time_unit(unit: &str) -> Option<&'static str> {
+    match unit {
+        "minute" | "minutes" => Some("m"),
+        "seconds" => Some("s"),
+        _ => None,
+    }
+}
+
+/// Splits the repetitions written at the start of a line from the rest of it,
+/// returned as shorthand, i.e. "15 calories Row" gives ("15cal", "Row").
+fn split_reps(line: &str) -> (Option<String>, &str) {
+    if let Some(rest) = line.strip_prefix("Max reps of ") {
+        return (Some("max".to_string()), rest);
+    }
+    if let Some((rm, rest)) = line
+        .strip_prefix("Build to a ")
+        .and_then(|l| l.split_once("RM "))
+    {
+        return (Some(format!("{}rm", rm)), rest);
+    }
+    let mut words = line.splitn(3, ' ');
+    let (first, second) = (words.next().unwrap_or(""), words.next().unwrap_or(""));
+    let rest = words.next().unwrap_or("");
+    if first == "Rest" {
+        if let (Some(duration), Some(unit)) = (
+            second.chars().all(|c| c.is_ascii_digit()).then_some(second),
+            rest.split(' ').next().and_then(time_unit),
+        ) {
+            // The rest period is followed by the "Rest" movement
+            let rest = rest.split_once(' ').map(|(_, r)| r).unwrap_or("");
+            let rest = rest.strip_prefix("Rest").unwrap_or(rest).trim_start();
+            return (Some(format!("r{}{}", duration, unit)), rest);
+        }
+    }
+    if !first.starts_with(|c: char| c.is_ascii_digit()) {
+        return (None, line);
+    }
+    match second {
+        "calories" => (Some(format!("{}cal", first)), rest),
+        "sec" | "min" => (Some(format!("{}{}", first, second)), rest),
+        _ => (Some(first.to_string()), line[first.len()..].trim_start()),
+    }
+}
+
+/// The repetitions per round, "21-15-9" or "20 calories-15 calories".
+fn rounds(line: &str) -> Option<String> {
+    line.split('-')
+        .map(|part| match split_reps(part) {
+            (Some(reps), "") => Some(reps),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|parts| parts.join("-"))
+}
+
+/// The movements of a line, "Clean + Split Jerk", as shorthand.
+fn movements(text: &str, names: &BTreeMap<String, &'static str>) -> Result<String, String> {
+    text.split(" + ")
+        .map(|name| {
+            names
+                .get(name.trim())
+                .copied()
+                .ok_or_else(|| format!("unknown movement `{}`", name.trim()))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|movements| movements.join(", "))
+}
+
+/// Splits the label of a line of a superset from the rest, "A1) 4x8 Bench Press".
+fn split_superset(line: &str) -> (Option<&str>, &str) {
+    match line.split_once(") ") {
+        Some((label, rest))
+            if label.len() > 1 && label[1..].chars().all(|c| c.is_ascii_digit()) =>
+        {
+            (Some(label), rest)
+        }
+        _ => (None, line),
+    }
+}
+
+/// A line of the workout as shorthand, i.e. "- 21 Pull Up @ 43/30kg" or "5x5 Back
+/// Squat @ 70% (98kg)".
+fn line(line: &str, names: &BTreeMap<String, &'static str>) -> Result<String, String> {
+    let (label, line) = split_superset(line);
+    let (text, weight) = match line.rsplit_once(" @ ") {
+        // The absolute load of a percentage goes after it, "70% (98kg)", and the
+        // percentage of a rep max is of the 1RM, "85% of 1RM"
+        Some((text, weight)) => (
+            text,
+            weight
+                .split(" (")
+                .next()
+                .map(|w| w.trim_end_matches(" of 1RM")),
+        ),
+        None => (line, None),
+    };
+    let (reps, text) = split_reps(text);
+    let mut parts: Vec<String> = label.into_iter().map(str::to_string).collect();
+    parts.extend(reps);
+    // The note of the movements goes after them, "Pull Up (strict)"
+    let (text, note) = match text.strip_suffix(')').and_then(|t| t.rsplit_once(" (")) {
+        Some((text, note)) => (text, Some(note)),
+        None => (text, None),
+    };
+    if !text.is_empty() {
+        parts.push(movements(text, names)?);
+    }
+    if let Some(note) = note {
+        parts.push(format!("({})", note));
+    }
+    if let Some(weight) = weight {
+        // The loads per set are a list, "70, 75, 80%", and the RPE is written as "RPE 8"
+        match weight.parse::<Rpe>() {
+            Ok(rpe) => parts.push(format!("@{}", rpe.shorthand())),
+            Err(_) => parts.push(format!("@{}", weight.replace(", ", ","))),
+        }
+    }
+    Ok(parts.join(" "))
+}
+
+/// The load of a row of the table of loads per set, "| 1 | 70% (98kg) |".
+fn set_load(row: &str) -> Option<Weight> {
+    let cells: Vec<&str> = row.trim_matches('|').split('|').map(str::trim).collect();
+    match cells.as_slice() {
+        [set, load] if set.parse::<u32>().is_ok() => load.split(" (").next()?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Appends the loads read from a table to the line it follows, "5x5 back squat".
+fn append_loads(lines: Option<&mut Vec<String>>, loads: &mut Vec<Weight>) {
+    if let (Some(line), false) = (lines.and_then(|l| l.last_mut()), loads.is_empty()) {
+        line.push_str(&format!(" @{}", weight::format_loads(loads, ",")));
+    }
+    loads.clear();
+}
+
+/// A row of the table of the movements (see [`crate::render::Layout::Table`]) as the
+/// line it stands for, "| 5x3 | Back Squat | 75% |" is "5x3 Back Squat @ 75%". The
+/// header and the separator are empty, and the rows of other tables `None`.
+fn table_row(row: &str) -> Option<String> {
+    let cells: Vec<&str> = row
+        .strip_prefix('|')?
+        .strip_suffix('|')?
+        .split('|')
+        .map(str::trim)
+        .collect();
+    match cells.as_slice() {
+        ["Reps", "Movement", "Load"] => Some(String::new()),
+        [_, _, _] if cells.iter().all(|c| c.chars().all(|c| c == '-')) => Some(String::new()),
+        [reps, movements, load] => {
+            let mut line = [*reps, *movements]
+                .iter()
+                .filter(|c| !c.is_empty())
+                .copied()
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !load.is_empty() {
+                line.push_str(&format!(" @ {}", load));
+            }
+            Some(line)
+        }
+        _ => None,
+    }
+}
+
+/// A line of the comments as written, the lines in italics are escaped, see
+/// `CommentStyle::Emphasis`. A line of the other styles in italics with `*` is
+/// read without them.
+fn comment_line(line: &str) -> String {
+    match line.strip_prefix('*').and_then(|l| l.strip_suffix('*')) {
+        Some(emphasis) => render::unescape_markdown(emphasis),
+        None => line.to_string(),
+    }
+}
+
+/// Converts a workout block back into the shorthand, along with the comments and name.
+fn to_shorthand(block: &str) -> Result<(String, Option<String>, Option<String>), String> {
+    let names: BTreeMap<String, &'static str> = Movement::all()
+        .map(|m| (m.to_string(), m.shorthand()))
+        .collect();
+    let mut lines = block
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty() && *l != "---")
+        .peekable();
+
+    let mut name = None;
+    let header = loop {
+        match lines.next() {
+            Some(l) if l.starts_with("**") && l.ends_with("**") => {
+                break l.trim_matches('*').to_string();
+            }
+            Some(l) if l.starts_with('*') && l.ends_with('*') && name.is_none() => {
+                name = Some(l.trim_matches('*').to_string());
+            }
+            _ => return Err("the type of workout is missing".to_string()),
+        }
+    };
+
+    let mut body: Vec<String> = Vec::new();
+    let mut comments: Vec<String> = Vec::new();
+    for l in lines {
+        // The comments written as a blockquote, see `CommentStyle::Quote`
+        let l = match l.strip_prefix('>') {
+            Some(quoted) if !comments.is_empty() || quoted.starts_with(" Comments: ") => {
+                quoted.strip_prefix(' ').unwrap_or(quoted)
+            }
+            _ => l,
+        };
+        // The scaled versions are derived from the workout, see `Workout::add_scaled`
+        if l.starts_with("*Scaled: ") {
+            break;
+        }
+        // The media are read by `parse_workout`
+        if Media::from_markdown(l).is_some() {
+            continue;
+        }
+        if let Some(first) = l.strip_prefix("Comments: ") {
+            comments.push(comment_line(first));
+        } else if !comments.is_empty() {
+            comments.push(comment_line(l));
+        } else {
+            match table_row(l) {
+                // The rows of the table of the movements are read as the lines they
+                // stand for, items of a list in the metcons
+                Some(row) if row.is_empty() => {}
+                Some(row) if header == "Weightlifting" => body.push(row),
+                Some(row) => body.push(format!("- {}", row)),
+                None => body.push(l.to_string()),
+            }
+        }
+    }
+    let comments = (!comments.is_empty()).then(|| comments.join("\n"));
+
+    // EMOM and Tabata describe the intervals in the paragraph after the header
+    let details = match body.first() {
+        Some(d)
+            if !d.starts_with("- ")
+                && (header.starts_with("EMOM") || header.starts_with("Tabata")) =>
+        {
+            let details = d.clone();
+            body.remove(0);
+            Some(details)
+        }
+        _ => None,
+    };
+    let workout_type = workout_type(&header, details.as_deref())
+        .ok_or_else(|| format!("unknown workout type `{}`", header))?;
+
+    let shorthand = if workout_type == "wl" {
+        let mut parts: Vec<Vec<String>> = Vec::new();
+        let is_block = body.iter().any(|l| l.starts_with("- "));
+        let mut loads = Vec::new();
+        for l in &body {
+            if l.starts_with('|') {
+                loads.extend(set_load(l));
+                continue;
+            }
+            // The labels of the superset are written again in every line, "A1) ...",
+            // the header can start a part of a block
+            let header = l.strip_prefix("- ").unwrap_or(l);
+            if header.starts_with("**") && header.ends_with(" superset**") {
+                if header.len() < l.len() {
+                    parts.push(Vec::new());
+                }
+                continue;
+            }
+            append_loads(parts.last_mut(), &mut loads);
+            match l.strip_prefix("- ") {
+                Some(l) => parts.push(vec![line(l, &names)?]),
+                None => match parts.last_mut() {
+                    Some(part) if is_block => part.push(line(l, &names)?),
+                    _ => parts.push(vec![line(l, &names)?]),
+                },
+            }
+        }
+        append_loads(parts.last_mut(), &mut loads);
+        let parts: Vec<String> = parts.into_iter().map(|p| p.join(", ")).collect();
+        if is_block {
+            format!("wl [{}]", parts.join("; "))
+        } else {
+            format!("wl {}", parts.join(", "))
+        }
+    } else {
+        let mut parts = vec![workout_type];
+        let mut lines = Vec::new();
+        for l in &body {
+            match l.strip_prefix("- ") {
+                Some(l) => lines.push(line(l, &names)?),
+                None if lines.is_empty() => {
+                    parts.push(rounds(l).ok_or_else(|| format!("unexpected line `{}`", l))?)
+                }
+                None => return Err(format!("unexpected line `{}`", l)),
+            }
+        }
+        parts.push(lines.join(", "));
+        parts.join(" ")
+    };
+    Ok((shorthand, comments, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{Layout, Markdown};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_round_trip() {
+        for shorthand in [
+            "ft 21-15-9 pull up, thruster @43/30kg",
+            "4rd 400m run, 15/12cal row, max pull up, 21/15 burpee",
+            "ft 400m run, r2m, 400m run",
+            "emom-12-3m-r1m 15cal row, 12 toes to bar, max db clean and jerk @22/15kg",
+            "emom-10-alt 3 bar mu, 5 hspu",
+            "emom-12-3m-alt 3 bar mu, 5 hspu",
+            "emom-10-r30s 5 pull up",
+            "tabata 20 air squat",
+            "amrap-12 5 pull up, 10 push up, 15 air squat",
+            "amrap-90s max burpee",
+            "tabata-6-30s-15s 10 burpee",
+            "wl 5x5 back squat @70%",
+            "wl 3x(2+1) clean, split jerk @85%",
+            "wl [1rm snatch; 5x3 deadlift @80%]",
+            "wl 1rm snatch, 3rm clean, 5x5 back squat",
+            "wl 5rm back squat @85%, 1rm clean, split jerk",
+            "ft 20/15cal-15/12cal-10/8cal bike, burpee",
+            "3rd 21 kettlebell swing @1.5pood, 9 clean @52.5/35kg",
+            "wl 5x5 back squat @60,65,70,75,80kg, 3x3 snatch @70,75,80%",
+            "wl [2x2 clean @60/40,70/45kg; 1rm snatch]",
+            "ft 21-15-9 pull up (strict), thruster @43/30kg",
+            "wl 3x5 back squat @rpe8, 5rm deadlift @rpe7-8",
+            "wl 3x(3-2-1) power clean @80,85,90%, 2x(5-3-1) deadlift @70%",
+            "emom-10 10 box jump (to 15in target), 12 push up (no pushup)",
+            "wl 5x5 back squat @75% / A1 4x8 bench press @70% / A2 4x10 ring row / B1 3x12 push up",
+            "wl [A1 4x8 bench press / A2 4x10 ring row; 1rm snatch]",
+        ] {
+            let workout = create_workout(
+                shorthand,
+                Some("T.C. 10'\nscale the load".to_string()),
+                Some("Test".to_string()),
+            )
+            .unwrap();
+            let read = parse_workout(&workout.to_string()).expect(shorthand);
+            assert_eq!(read, workout, "{}", shorthand);
+            assert_eq!(read.to_string(), workout.to_string(), "{}", shorthand);
+            let table = workout.markdown_with(&Markdown {
+                layouts: BTreeMap::from([(workout.workout_type.kind(), Layout::Table)]),
+                ..Default::default()
+            });
+            assert_eq!(
+                parse_workout(&table).expect(shorthand),
+                workout,
+                "{}",
+                table
+            );
+        }
+    }
+
+    #[test]
+    fn test_comments_with_markdown() {
+        let comments = "See [the standards](https://example.com/a_b)\n- 2 * 3 rounds\n\n_Hard_";
+        let workout =
+            create_workout("tabata 20 air squat", Some(comments.to_string()), None).unwrap();
+        let markdown = workout.to_string();
+        assert!(
+            markdown.contains("Comments: *See \\[the standards\\](https://example.com/a\\_b)*\n")
+        );
+        assert_eq!(parse_workout(&markdown).unwrap(), workout);
+
+        // Written as a blockquote or as they are
+        let header = markdown.split("Comments: ").next().unwrap();
+        let quote = format!(
+            "{}> Comments: {}\n\n",
+            header,
+            comments.replace('\n', "\n> ").replace("> \n", ">\n")
+        );
+        assert_eq!(parse_workout(&quote).unwrap(), workout);
+        let raw = format!("{}Comments: {}\n\n", header, comments);
+        // The blank lines can't be told apart from the end of the comments
+        assert_eq!(
+            parse_workout(&raw).unwrap().comments(),
+            Some(comments.replace("\n\n", "\n").as_str())
+        );
+    }
+
+    #[test]
+    fn test_progression_read_as_loads() {
+        let workout = create_workout("wl 3x2 snatch @70%+2.5%", None, None).unwrap();
+        let read = parse_workout(&workout.to_string()).unwrap();
+        assert_eq!(read.to_shorthand(), "wl 3x2 snatch @70,72.5,75%");
+    }
+
+    #[test]
+    fn test_scaled_skipped() {
+        let mut workout =
+            create_workout("ft 21-15-9 bar mu", Some("T.C. 8'".to_string()), None).unwrap();
+        workout.add_scaled(crate::scaling::Level::Intermediate, &Default::default());
+        let read = parse_workout(&workout.to_string()).unwrap();
+        assert_eq!(read, workout);
+    }
+
+    #[test]
+    fn test_absolute_load_ignored() {
+        let block = "---\n\n**Weightlifting**\n\n5x5 Back Squat @ 70% (98kg)\n\n";
+        let workout = parse_workout(block).unwrap();
+        assert_eq!(workout.to_shorthand(), "wl 5x5 back squat @70%");
+    }
+
+    #[test]
+    fn test_invalid_blocks() {
+        assert!(parse_workout("---\n\nSome text\n\n").is_err());
+        assert!(parse_workout("---\n\n**For Time**\n\n- 21 Pul Up\n\n").is_err());
+
+        let content = "---\ntitle: \"wod\"\n---\n\n---\n\n**For Time**\n\n- 21 Pull Up\n\n---\n\n**AMRAP**\n\n";
+        let err = Post::parse(content).unwrap_err();
+        assert!(err.to_string().contains("workout 2 of the file"));
+    }
+}
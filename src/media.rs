@@ -0,0 +1,292 @@
+//! Media attached to a workout, i.e. the demo video of the complex of the day, see
+//! `wod add --video` and [`crate::workout::Workout::add_media`].
+//!
+//! The YouTube videos and the images are written with the `youtube` and `figure`
+//! shortcodes of Hugo, the other videos as a link. The sites of other generators
+//! (see [`crate::render::Markdown`]) get a link and an image in markdown.
+
+use std::fmt;
+
+use crate::error::WodError;
+
+/// A video or an image shown under a workout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Media {
+    /// The URL of a video, the ones of YouTube are kept as "watch?v=<id>", followed
+    /// by "&t=<seconds>s" when they start at a time.
+    Video(String),
+    /// The URL of an image.
+    Image(String),
+}
+
+impl Media {
+    /// A video, the links of YouTube ("youtu.be/<id>", "youtube.com/shorts/<id>") are
+    /// written as "https://www.youtube.com/watch?v=<id>", the URL read back from the
+    /// shortcode, keeping the time they start at. See [`check_url`] for the URLs
+    /// accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::media::Media;
+    ///
+    /// assert_eq!(
+    ///     Media::video("https://youtu.be/9FGilxCbdz8?si=abc&t=1m30s").unwrap(),
+    ///     Media::Video("https://www.youtube.com/watch?v=9FGilxCbdz8&t=90s".to_string())
+    /// );
+    /// assert!(Media::video("javascript:alert(1)").is_err());
+    /// ```
+    pub fn video(url: &str) -> Result<Media, WodError> {
+        let url = check_url(url)?;
+        match youtube_id(&url) {
+            Some(id) => Ok(Media::Video(youtube_url(id, youtube_start(&url)))),
+            None => Ok(Media::Video(url)),
+        }
+    }
+
+    /// An image, see [`check_url`] for the URLs accepted.
+    pub fn image(url: &str) -> Result<Media, WodError> {
+        check_url(url).map(Media::Image)
+    }
+
+    /// The line of markdown of the media, with the shortcodes of Hugo if `shortcodes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::media::Media;
+    ///
+    /// let video = Media::video("https://youtu.be/9FGilxCbdz8").unwrap();
+    /// assert_eq!(video.markdown(true), "{{< youtube 9FGilxCbdz8 >}}");
+    /// assert_eq!(video.markdown(false), "[Video](https://www.youtube.com/watch?v=9FGilxCbdz8)");
+    /// let video = Media::video("https://youtu.be/9FGilxCbdz8?t=10").unwrap();
+    /// assert_eq!(video.markdown(true), "{{< youtube id=\"9FGilxCbdz8\" start=\"10\" >}}");
+    /// let image = Media::image("https://example.com/complex.png").unwrap();
+    /// assert_eq!(image.markdown(true), "{{< figure src=\"https://example.com/complex.png\" >}}");
+    /// assert_eq!(image.markdown(false), "![](https://example.com/complex.png)");
+    /// ```
+    pub fn markdown(&self, shortcodes: bool) -> String {
+        match self {
+            Media::Video(url) => match (youtube_id(url), youtube_start(url)) {
+                (Some(id), None) if shortcodes => format!("{{{{< youtube {} >}}}}", id),
+                (Some(id), Some(start)) if shortcodes => {
+                    format!("{{{{< youtube id=\"{}\" start=\"{}\" >}}}}", id, start)
+                }
+                _ => format!("[Video]({})", url),
+            },
+            Media::Image(url) if shortcodes => format!("{{{{< figure src=\"{}\" >}}}}", url),
+            Media::Image(url) => format!("![]({})", url),
+        }
+    }
+
+    /// Reads the media back from a line written by [`Media::markdown`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::media::Media;
+    ///
+    /// let video = Media::video("https://www.youtube.com/watch?v=9FGilxCbdz8").unwrap();
+    /// assert_eq!(Media::from_markdown("{{< youtube 9FGilxCbdz8 >}}"), Some(video));
+    /// assert_eq!(Media::from_markdown("- Pull Up"), None);
+    /// ```
+    pub fn from_markdown(line: &str) -> Option<Media> {
+        let line = line.trim();
+        if let Some(shortcode) = line.strip_prefix("{{<").and_then(|l| l.strip_suffix(">}}")) {
+            let shortcode = shortcode.trim();
+            if let Some(args) = shortcode.strip_prefix("youtube ") {
+                let start = attribute(args, "start").and_then(|start| start.parse().ok());
+                let id = attribute(args, "id").unwrap_or(args.trim());
+                return Some(Media::Video(youtube_url(id, start)));
+            }
+            let src = attribute(shortcode.strip_prefix("figure ")?, "src")?;
+            return Some(Media::Image(src.to_string()));
+        }
+        if let Some(url) = line
+            .strip_prefix("[Video](")
+            .and_then(|l| l.strip_suffix(')'))
+        {
+            return Some(Media::video(url).unwrap_or_else(|_| Media::Video(url.to_string())));
+        }
+        let url = line.strip_prefix("![](")?.strip_suffix(')')?;
+        Some(Media::Image(url.to_string()))
+    }
+}
+
+/// Displays the media with the shortcodes of Hugo.
+impl fmt::Display for Media {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.markdown(true))
+    }
+}
+
+/// Checks the URL of a media: it must be an absolute http(s) URL, without quotes or
+/// whitespace, which would end the shortcode or the link it's written in. The
+/// parentheses and angle brackets, that would end the link of markdown, are
+/// percent-encoded.
+///
+/// # Examples
+///
+/// ```
+/// use wod::media::check_url;
+///
+/// assert_eq!(
+///     check_url("https://example.com/snatch_(slow).mp4").unwrap(),
+///     "https://example.com/snatch_%28slow%29.mp4"
+/// );
+/// assert!(check_url("/images/complex.png").is_err());
+/// assert!(check_url("https://example.com/a.png\" onload=\"alert(1)").is_err());
+/// ```
+pub fn check_url(url: &str) -> Result<String, WodError> {
+    let url = url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(WodError::Invalid(format!(
+            "Invalid URL: `{}`, it must start with https:// or http://",
+            url
+        )));
+    }
+    if url.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'') {
+        return Err(WodError::Invalid(format!(
+            "Invalid URL: `{}`, it can't contain quotes or whitespace",
+            url
+        )));
+    }
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            '(' => escaped.push_str("%28"),
+            ')' => escaped.push_str("%29"),
+            '<' => escaped.push_str("%3C"),
+            '>' => escaped.push_str("%3E"),
+            c => escaped.push(c),
+        }
+    }
+    Ok(escaped)
+}
+
+/// The value of an attribute of a shortcode, the `src` of `figure src="<url>"`.
+fn attribute<'a>(shortcode: &'a str, name: &str) -> Option<&'a str> {
+    let start = shortcode.find(&format!("{}=\"", name))? + name.len() + 2;
+    let value = &shortcode[start..];
+    Some(&value[..value.find('"')?])
+}
+
+/// The id of a YouTube video from its URL.
+fn youtube_id(url: &str) -> Option<&str> {
+    let url = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .trim_start_matches("m.");
+    let id = if let Some(rest) = url.strip_prefix("youtu.be/") {
+        rest
+    } else if let Some(rest) = url.strip_prefix("youtube.com/") {
+        match rest.strip_prefix("shorts/") {
+            Some(rest) => rest,
+            None => rest
+                .strip_prefix("watch?")?
+                .split('&')
+                .find_map(|param| param.strip_prefix("v="))?,
+        }
+    } else {
+        return None;
+    };
+    let id = id.split(['?', '&', '#', '/']).next()?;
+    (!id.is_empty()).then_some(id)
+}
+
+/// The second a YouTube video starts at, from the `t` of its URL: "90", "90s" or
+/// "1m30s".
+fn youtube_start(url: &str) -> Option<u32> {
+    let (_, query) = url.split_once('?')?;
+    let time = query
+        .split(['&', '#'])
+        .find_map(|param| param.strip_prefix("t="))?;
+    let mut seconds = 0;
+    let mut number = 0;
+    for c in time.chars() {
+        match c {
+            '0'..='9' => number = number * 10 + c.to_digit(10)?,
+            'h' => (seconds, number) = (seconds + number * 3600, 0),
+            'm' => (seconds, number) = (seconds + number * 60, 0),
+            's' => (seconds, number) = (seconds + number, 0),
+            _ => return None,
+        }
+    }
+    let seconds = seconds + number;
+    (seconds > 0).then_some(seconds)
+}
+
+fn youtube_url(id: &str, start: Option<u32>) -> String {
+    match start {
+        Some(start) => format!("https://www.youtube.com/watch?v={}&t={}s", id, start),
+        None => format!("https://www.youtube.com/watch?v={}", id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_youtube_id() {
+        for url in [
+            "https://www.youtube.com/watch?v=9FGilxCbdz8",
+            "https://youtube.com/watch?feature=share&v=9FGilxCbdz8",
+            "https://youtu.be/9FGilxCbdz8?si=abc",
+            "https://m.youtube.com/shorts/9FGilxCbdz8",
+        ] {
+            assert_eq!(youtube_id(url), Some("9FGilxCbdz8"), "{}", url);
+        }
+        assert_eq!(youtube_id("https://vimeo.com/76979871"), None);
+        assert_eq!(youtube_id("https://www.youtube.com/watch?v="), None);
+    }
+
+    #[test]
+    fn test_youtube_start() {
+        assert_eq!(youtube_start("https://youtu.be/9FGilxCbdz8?t=10"), Some(10));
+        assert_eq!(
+            youtube_start("https://www.youtube.com/watch?v=9FGilxCbdz8&t=1h2m3s"),
+            Some(3723)
+        );
+        assert_eq!(youtube_start("https://youtu.be/9FGilxCbdz8?si=abc"), None);
+        assert_eq!(youtube_start("https://youtu.be/9FGilxCbdz8?t=abc"), None);
+    }
+
+    #[test]
+    fn test_check_url() {
+        for invalid in [
+            "",
+            "ftp://example.com/a.mp4",
+            "javascript:alert(1)",
+            "https://example.com/a b.png",
+            "https://example.com/a.png' onerror='alert(1)",
+        ] {
+            assert!(check_url(invalid).is_err(), "{}", invalid);
+            assert!(Media::video(invalid).is_err(), "{}", invalid);
+            assert!(Media::image(invalid).is_err(), "{}", invalid);
+        }
+        assert_eq!(
+            Media::image("https://example.com/a.png)<b>").unwrap(),
+            Media::Image("https://example.com/a.png%29%3Cb%3E".to_string())
+        );
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        for media in [
+            Media::video("https://youtu.be/9FGilxCbdz8").unwrap(),
+            Media::video("https://youtu.be/9FGilxCbdz8?t=95").unwrap(),
+            Media::video("https://vimeo.com/76979871").unwrap(),
+            Media::image("https://example.com/complex.png").unwrap(),
+        ] {
+            for shortcodes in [true, false] {
+                assert_eq!(
+                    Media::from_markdown(&media.markdown(shortcodes)),
+                    Some(media.clone())
+                );
+            }
+        }
+    }
+}
@@ -1,133 +1,311 @@
 use std::fmt;
 // For reference: https://www.crossfit.com/crossfit-movements
-use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::OnceLock;
 
 use strsim::levenshtein;
 
-/// Available movements
-static MOVEMENTS: &[&str] = &[
+use crate::workout::create_workout;
+
+/// The registry of movements: every name accepted by the parser, along with the
+/// movement it stands for. It's the only source of the names, shared by the parser
+/// ([`Movement::from_str`]), the suggestions for the invalid names and the listings.
+static MOVEMENTS: &[(&str, Movement)] = &[
     // Squat movements
-    "air squat",
-    "front squat",
-    "back squat",
-    "ohs",
-    "overhead squat",
-    "pistol squat",
-    "goblet squat",
+    ("air squat", Movement::AirSquat),
+    ("front squat", Movement::FrontSquat),
+    ("back squat", Movement::BackSquat),
+    ("ohs", Movement::OverheadSquat),
+    ("overhead squat", Movement::OverheadSquat),
+    ("pistol squat", Movement::PistolSquat),
+    ("goblet squat", Movement::GobletSquat),
     // Deadlift movements
-    "deadlift",
-    "sumo deadlift",
-    "romanian deadlift",
+    ("deadlift", Movement::Deadlift),
+    ("sumo deadlift", Movement::SumoDeadlift),
+    ("romanian deadlift", Movement::RomanianDeadlift),
     // Press movements
-    "shoulder press",
-    "push press",
-    "push jerk",
-    "split jerk",
-    "bench press",
+    ("shoulder press", Movement::ShoulderPress),
+    ("push press", Movement::PushPress),
+    ("push jerk", Movement::PushJerk),
+    ("split jerk", Movement::SplitJerk),
+    ("bench press", Movement::BenchPress),
     // Weightlifting movements
-    "clean",
-    "power clean",
-    "hang clean",
-    "hang power clean",
-    "clean and jerk",
-    "power clean and jerk",
-    "clean pull",
-    "clean-deadlift",
-    "snatch",
-    "power snatch",
-    "hang snatch",
-    "hang power snatch",
-    "snatch balance",
-    "snatch pull",
-    "snatch deadlift",
-    "muscle snatch",
+    ("clean", Movement::Clean),
+    ("power clean", Movement::PowerClean),
+    ("hang clean", Movement::HangClean),
+    ("hang power clean", Movement::HangPowerClean),
+    ("clean and jerk", Movement::CleanAndJerk),
+    ("power clean and jerk", Movement::PowerCleanAndJerk),
+    ("clean pull", Movement::CleanPull),
+    ("clean deadlift", Movement::CleanDeadlift),
+    ("snatch", Movement::Snatch),
+    ("power snatch", Movement::PowerSnatch),
+    ("hang snatch", Movement::HangSnatch),
+    ("hang power snatch", Movement::HangPowerSnatch),
+    ("snatch balance", Movement::SnatchBalance),
+    ("snatch pull", Movement::SnatchPull),
+    ("snatch deadlift", Movement::SnatchDeadlift),
+    ("muscle snatch", Movement::MuscleSnatch),
     // Gymnastics
-    "push up",
-    "pull up",
-    "chin up",
-    "c2b",
-    "chest to bar",
-    "muscle up",
-    "bar muscle up",
-    "bar mu",
-    "ring muscle up",
-    "ring mu",
-    "t2b",
-    "toes to bar",
-    "knees to elbows",
-    "L-sit",
-    "strict pull up",
-    "shspu",
-    "hspu",
-    "handstand push up",
-    "wall walk",
-    "handstand walk",
-    "hsw",
-    "hs walk",
-    "handstand hold",
-    "sit up",
-    "v up",
-    "ghd",
+    ("push up", Movement::PushUp),
+    ("pull up", Movement::PullUp),
+    ("chin up", Movement::ChinUp),
+    ("c2b", Movement::ChestToBar),
+    ("chest to bar", Movement::ChestToBar),
+    ("muscle up", Movement::MuscleUp),
+    ("bar muscle up", Movement::BarMuscleUp),
+    ("bar mu", Movement::BarMuscleUp),
+    ("ring muscle up", Movement::RingMuscleUp),
+    ("ring mu", Movement::RingMuscleUp),
+    ("t2b", Movement::ToesToBar),
+    ("toes to bar", Movement::ToesToBar),
+    ("knees to elbows", Movement::KneesToElbows),
+    ("l sit", Movement::LSit),
+    ("strict pull up", Movement::StrictPullUp),
+    ("ring row", Movement::RingRow),
+    ("shspu", Movement::StrictHandstandPushUp),
+    ("hspu", Movement::HandstandPushUp),
+    ("handstand push up", Movement::HandstandPushUp),
+    ("wall walk", Movement::WallWalk),
+    ("handstand walk", Movement::HandstandWalk),
+    ("hsw", Movement::HandstandWalk),
+    ("hs walk", Movement::HandstandWalk),
+    ("handstand hold", Movement::HandstandHold),
+    ("sit up", Movement::SitUp),
+    ("v up", Movement::VUp),
+    ("ghd", Movement::GHD),
     // Other bar movements
-    "thruster",
-    "front rack lunge",
-    "back rack lunge",
-    "overhead walking lunge",
+    ("thruster", Movement::Thruster),
+    ("front rack lunge", Movement::FrontRackLunge),
+    ("back rack lunge", Movement::BackRackLunge),
+    ("overhead walking lunge", Movement::OverheadWalkingLunge),
     // Other movements
-    "burpee",
-    "box jump",
-    "box jump over",
-    "burpee box jump",
-    "burpee box jump over",
-    "burpee over the bar",
-    "burpee to target",
-    "du",
-    "double under",
-    "wall ball",
-    "kettlebell swing",
-    "kts",
-    "turkish get up",
-    "db farmer carry",
-    "farmer carry",
-    "sled push",
-    "sled pull",
-    "sled drag",
-    "rope climb",
-    "rc",
-    "legless rope climb",
-    "legless rc",
-    "sandbag clean",
-    "dball",
-    "dball hold",
-    "dball carry",
+    ("burpee", Movement::Burpee),
+    ("box jump", Movement::BoxJump),
+    ("box jump over", Movement::BoxJumpOver),
+    ("burpee box jump", Movement::BurpeeBoxJump),
+    ("burpee box jump over", Movement::BurpeeBoxJumpOver),
+    ("burpee over the bar", Movement::BurpeeOverTheBar),
+    ("burpee to target", Movement::BurpeeToTarget),
+    ("burpee pull up", Movement::BurpeePullUp),
+    ("du", Movement::DoubleUnder),
+    ("double under", Movement::DoubleUnder),
+    ("wall ball", Movement::WallBall),
+    ("kettlebell swing", Movement::KettlebellSwing),
+    ("kts", Movement::KettlebellSwing),
+    ("turkish get up", Movement::TurkishGetUp),
+    ("db farmer carry", Movement::FarmersCarry),
+    ("farmer carry", Movement::FarmersCarry),
+    ("sled push", Movement::SledPush),
+    ("sled pull", Movement::SledPull),
+    ("sled drag", Movement::SledDrag),
+    ("rope climb", Movement::RopeClimb),
+    ("rc", Movement::RopeClimb),
+    ("legless rope climb", Movement::LeglessRopeClimb),
+    ("legless rc", Movement::LeglessRopeClimb),
+    ("sandbag clean", Movement::SandbagClean),
+    ("dball", Movement::DBall),
+    ("dball hold", Movement::DBallHold),
+    ("dball carry", Movement::DBallCarry),
     // Cardio/machines
-    "row",
-    "run",
-    "bike",
-    "echo bike",
-    "ski",
+    ("row", Movement::Row),
+    ("run", Movement::Run),
+    ("bike", Movement::Bike),
+    ("echo bike", Movement::EchoBike),
+    ("ski", Movement::Ski),
     // Dumbbell
-    "db snatch",
-    "db clean",
-    "db power clean",
-    "db hang clean",
-    "dumbbell snatch",
-    "db snatch",
-    "dumbbell clean",
-    "db clean",
-    "dumbbell power clean",
-    "db power clean",
-    "dumbbell hang clean",
-    "db hang clean",
-    "dumbbell clean and jerk",
-    "db clean and jerk",
-    "db hang clean and jerk",
-    "devil press",
+    ("db snatch", Movement::DumbbellSnatch),
+    ("db clean", Movement::DumbbellClean),
+    ("db power clean", Movement::DumbbellPowerClean),
+    ("db hang clean", Movement::DumbbellHangClean),
+    ("dumbbell snatch", Movement::DumbbellSnatch),
+    ("dumbbell clean", Movement::DumbbellClean),
+    ("dumbbell power clean", Movement::DumbbellPowerClean),
+    ("dumbbell hang clean", Movement::DumbbellHangClean),
+    ("dumbbell clean and jerk", Movement::DumbbellCleanAndJerk),
+    ("db clean and jerk", Movement::DumbbellCleanAndJerk),
+    (
+        "dumbbell hang clean and jerk",
+        Movement::DumbbellHangCleanAndJerk,
+    ),
+    ("db hang clean and jerk", Movement::DumbbellHangCleanAndJerk),
+    ("devil press", Movement::DevilPress),
     // Placeholder for resting
-    "rest",
+    ("rest", Movement::Rest),
 ];
 
+/// Equipment needed for the movements, see [`Movement::equipment`].
+///
+/// # Examples
+///
+/// ```
+/// use wod::movement::Equipment;
+///
+/// let equipment: Equipment = "pull-up bar".parse().unwrap();
+/// assert_eq!(equipment, Equipment::PullUpBar);
+/// assert_eq!(Equipment::JumpRope.to_string(), "jump rope");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Equipment {
+    Barbell,
+    Dumbbell,
+    Kettlebell,
+    PullUpBar,
+    Rings,
+    Box,
+    JumpRope,
+    WallBall,
+    Rope,
+    Sled,
+    Sandbag,
+    DBall,
+    Ghd,
+    Rower,
+    Bike,
+    SkiErg,
+}
+
+impl Equipment {
+    /// Every piece of equipment, in the order of the enum.
+    pub const ALL: [Equipment; 16] = [
+        Equipment::Barbell,
+        Equipment::Dumbbell,
+        Equipment::Kettlebell,
+        Equipment::PullUpBar,
+        Equipment::Rings,
+        Equipment::Box,
+        Equipment::JumpRope,
+        Equipment::WallBall,
+        Equipment::Rope,
+        Equipment::Sled,
+        Equipment::Sandbag,
+        Equipment::DBall,
+        Equipment::Ghd,
+        Equipment::Rower,
+        Equipment::Bike,
+        Equipment::SkiErg,
+    ];
+}
+
+impl FromStr for Equipment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('-', " ");
+        Equipment::ALL
+            .into_iter()
+            .find(|e| e.to_string().replace('-', " ") == name)
+            .ok_or_else(|| {
+                let names: Vec<String> = Equipment::ALL.iter().map(|e| e.to_string()).collect();
+                format!(
+                    "Invalid equipment: `{}`, expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for Equipment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Equipment::Barbell => "barbell",
+            Equipment::Dumbbell => "dumbbell",
+            Equipment::Kettlebell => "kettlebell",
+            Equipment::PullUpBar => "pull-up bar",
+            Equipment::Rings => "rings",
+            Equipment::Box => "box",
+            Equipment::JumpRope => "jump rope",
+            Equipment::WallBall => "wall ball",
+            Equipment::Rope => "rope",
+            Equipment::Sled => "sled",
+            Equipment::Sandbag => "sandbag",
+            Equipment::DBall => "d-ball",
+            Equipment::Ghd => "ghd",
+            Equipment::Rower => "rower",
+            Equipment::Bike => "bike",
+            Equipment::SkiErg => "ski erg",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The movement pattern of the movements, see [`Movement::pattern`].
+///
+/// # Examples
+///
+/// ```
+/// use wod::movement::Pattern;
+///
+/// let pattern: Pattern = "Olympic lifting".parse().unwrap();
+/// assert_eq!(pattern, Pattern::Olympic);
+/// assert_eq!(Pattern::Monostructural.to_string(), "monostructural");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Pattern {
+    Squat,
+    Hinge,
+    Press,
+    Pull,
+    Olympic,
+    Inverted,
+    Midline,
+    Jump,
+    Monostructural,
+}
+
+impl Pattern {
+    /// Every pattern, in the order of the enum.
+    pub const ALL: [Pattern; 9] = [
+        Pattern::Squat,
+        Pattern::Hinge,
+        Pattern::Press,
+        Pattern::Pull,
+        Pattern::Olympic,
+        Pattern::Inverted,
+        Pattern::Midline,
+        Pattern::Jump,
+        Pattern::Monostructural,
+    ];
+}
+
+impl FromStr for Pattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase();
+        Pattern::ALL
+            .into_iter()
+            .find(|p| p.to_string() == name)
+            .ok_or_else(|| {
+                let names: Vec<String> = Pattern::ALL.iter().map(|p| p.to_string()).collect();
+                format!(
+                    "Invalid pattern: `{}`, expected one of: {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Pattern::Squat => "squat",
+            Pattern::Hinge => "hinge",
+            Pattern::Press => "press",
+            Pattern::Pull => "pull",
+            Pattern::Olympic => "olympic lifting",
+            Pattern::Inverted => "inverted",
+            Pattern::Midline => "midline",
+            Pattern::Jump => "jump",
+            Pattern::Monostructural => "monostructural",
+        };
+        f.write_str(name)
+    }
+}
+
 /// Represents various types of movements that can be performed in a workout.
 ///
 /// This enum includes a wide range of exercises from weightlifting and bodyweight training
@@ -142,6 +320,7 @@ static MOVEMENTS: &[&str] = &[
 /// println!("Movement: {}", movement);
 /// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Movement {
     AirSquat,
     FrontSquat,
@@ -187,6 +366,7 @@ pub enum Movement {
     VUp,
     GHD,
     StrictPullUp,
+    RingRow,
     StrictHandstandPushUp,
     HandstandPushUp,
     WallWalk,
@@ -235,18 +415,24 @@ pub enum Movement {
 
 #[derive(Debug)]
 pub enum MovementParseError {
-    InvalidMovement(String, String),
+    /// The name that wasn't found and the closest movements, the closest first.
+    InvalidMovement(String, Vec<String>),
 }
 
 impl fmt::Display for MovementParseError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            MovementParseError::InvalidMovement(movement_name, suggestion) => write!(
-                f,
-                "Invalid movement: `{}`, did you mean: `{}`?",
-                movement_name,
-                suggestion // suggest_closest_movement(movement_name).unwrap_or("None")
-            ),
+            MovementParseError::InvalidMovement(movement_name, suggestions) => {
+                write!(f, "Invalid movement: `{}`", movement_name)?;
+                let quoted: Vec<String> = suggestions.iter().map(|s| format!("`{}`", s)).collect();
+                match quoted.split_last() {
+                    None => Ok(()),
+                    Some((only, [])) => write!(f, ", did you mean: {}?", only),
+                    Some((last, rest)) => {
+                        write!(f, ", did you mean: {} or {}?", rest.join(", "), last)
+                    }
+                }
+            }
         }
     }
 }
@@ -255,10 +441,16 @@ impl fmt::Display for MovementParseError {
 impl std::error::Error for MovementParseError {}
 
 impl MovementParseError {
-    // Factory method that automatically suggests the closest movement.
+    /// Number of movements suggested for a name that isn't found.
+    const SUGGESTIONS: usize = 3;
+
+    // Factory method that automatically suggests the closest movements.
     pub fn new_invalid(movement_name: String) -> Self {
-        let suggestion = suggest_closest_movement(&movement_name);
-        MovementParseError::InvalidMovement(movement_name, suggestion.unwrap().to_string())
+        let suggestions = suggest_closest_movements(&movement_name, Self::SUGGESTIONS)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        MovementParseError::InvalidMovement(movement_name, suggestions)
     }
 }
 
@@ -266,383 +458,508 @@ impl FromStr for Movement {
     type Err = MovementParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "air squat" => Ok(Movement::AirSquat),
-            "front squat" => Ok(Movement::FrontSquat),
-            "back squat" => Ok(Movement::BackSquat),
-            "ohs" => Ok(Movement::OverheadSquat),
-            "overhead squat" => Ok(Movement::OverheadSquat),
-            "pistol squat" => Ok(Movement::PistolSquat),
-            "goblet squat" => Ok(Movement::GobletSquat),
-            "deadlift" => Ok(Movement::Deadlift),
-            "sumo deadlift" => Ok(Movement::SumoDeadlift),
-            "romanian deadlift" => Ok(Movement::RomanianDeadlift),
-            "shoulder press" => Ok(Movement::ShoulderPress),
-            "push press" => Ok(Movement::PushPress),
-            "push jerk" => Ok(Movement::PushJerk),
-            "split jerk" => Ok(Movement::SplitJerk),
-            "bench press" => Ok(Movement::BenchPress),
-            "clean" => Ok(Movement::Clean),
-            "power clean" => Ok(Movement::PowerClean),
-            "hang clean" => Ok(Movement::HangClean),
-            "hang power clean" => Ok(Movement::HangPowerClean),
-            "clean and jerk" => Ok(Movement::CleanAndJerk),
-            "power clean and jerk" => Ok(Movement::PowerCleanAndJerk),
-            "clean pull" => Ok(Movement::CleanPull),
-            "clean-deadlift" => Ok(Movement::CleanDeadlift),
-            "snatch" => Ok(Movement::Snatch),
-            "power snatch" => Ok(Movement::PowerSnatch),
-            "hang snatch" => Ok(Movement::HangSnatch),
-            "hang power snatch" => Ok(Movement::HangPowerSnatch),
-            "snatch balance" => Ok(Movement::SnatchBalance),
-            "snatch pull" => Ok(Movement::SnatchPull),
-            "snatch deadlift" => Ok(Movement::SnatchDeadlift),
-            "muscle snatch" => Ok(Movement::MuscleSnatch),
-            "push up" => Ok(Movement::PushUp),
-            "pull up" => Ok(Movement::PullUp),
-            "chin up" => Ok(Movement::ChinUp),
-            "c2b" => Ok(Movement::ChestToBar),
-            "chest to bar" => Ok(Movement::ChestToBar),
-            "muscle up" => Ok(Movement::MuscleUp),
-            "bar muscle up" => Ok(Movement::BarMuscleUp),
-            "bar mu" => Ok(Movement::BarMuscleUp),
-            "ring muscle up" => Ok(Movement::RingMuscleUp),
-            "ring mu" => Ok(Movement::RingMuscleUp),
-            "t2b" => Ok(Movement::ToesToBar),
-            "toes to bar" => Ok(Movement::ToesToBar),
-            "knees to elbows" => Ok(Movement::KneesToElbows),
-            "L-sit" => Ok(Movement::LSit),
-            "sit up" => Ok(Movement::SitUp),
-            "v up" => Ok(Movement::VUp),
-            "ghd" => Ok(Movement::GHD),
-            "strict pull up" => Ok(Movement::StrictPullUp),
-            "shspu" => Ok(Movement::StrictHandstandPushUp),
-            "hspu" => Ok(Movement::HandstandPushUp),
-            "handstand push up" => Ok(Movement::HandstandPushUp),
-            "handstand walk" => Ok(Movement::HandstandWalk),
-            "hs walk" => Ok(Movement::HandstandWalk),
-            "hsw" => Ok(Movement::HandstandWalk),
-            "wall walk" => Ok(Movement::WallWalk),
-            "handstand hold" => Ok(Movement::HandstandHold),
-            "thruster" => Ok(Movement::Thruster),
-            "front rack lunge" => Ok(Movement::FrontRackLunge),
-            "back rack lunge" => Ok(Movement::BackRackLunge),
-            "overhead walking lunge" => Ok(Movement::OverheadWalkingLunge),
-            "burpee" => Ok(Movement::Burpee),
-            "box jump" => Ok(Movement::BoxJump),
-            "box jump over" => Ok(Movement::BoxJumpOver),
-            "burpee box jump" => Ok(Movement::BurpeeBoxJump),
-            "burpee box jump over" => Ok(Movement::BurpeeBoxJumpOver),
-            "burpee over the bar" => Ok(Movement::BurpeeOverTheBar),
-            "burpee to target" => Ok(Movement::BurpeeToTarget),
-            "burpee pull up" => Ok(Movement::BurpeePullUp),
-            "du" => Ok(Movement::DoubleUnder),
-            "double under" => Ok(Movement::DoubleUnder),
-            "wall ball" => Ok(Movement::WallBall),
-            "kettlebell swing" => Ok(Movement::KettlebellSwing),
-            "turkish get up" => Ok(Movement::TurkishGetUp),
-            "farmer carry" => Ok(Movement::FarmersCarry),
-            "sled push" => Ok(Movement::SledPush),
-            "sled pull" => Ok(Movement::SledPull),
-            "sled drag" => Ok(Movement::SledDrag),
-            "rope climb" => Ok(Movement::RopeClimb),
-            "rc" => Ok(Movement::RopeClimb),
-            "legless rope climb" => Ok(Movement::LeglessRopeClimb),
-            "legless rc" => Ok(Movement::LeglessRopeClimb),
-            "sandbag clean" => Ok(Movement::SandbagClean),
-            "dball" => Ok(Movement::DBall),
-            "dball carry" => Ok(Movement::DBallCarry),
-            "dball hold" => Ok(Movement::DBallHold),
-            "row" => Ok(Movement::Row),
-            "run" => Ok(Movement::Run),
-            "bike" => Ok(Movement::Bike),
-            "echo bike" => Ok(Movement::EchoBike),
-            "ski" => Ok(Movement::Ski),
-            "db snatch" => Ok(Movement::DumbbellSnatch),
-            "db clean" => Ok(Movement::DumbbellClean),
-            "db power clean" => Ok(Movement::DumbbellPowerClean),
-            "db hang clean" => Ok(Movement::DumbbellHangClean),
-            "dumbbell snatch" => Ok(Movement::DumbbellSnatch),
-            "dumbbell clean" => Ok(Movement::DumbbellClean),
-            "dumbbell power clean" => Ok(Movement::DumbbellPowerClean),
-            "dumbbell hang clean" => Ok(Movement::DumbbellHangClean),
-            "dumbbell clean and jerk" => Ok(Movement::DumbbellCleanAndJerk),
-            "db clean and jerk" => Ok(Movement::DumbbellCleanAndJerk),
-            "devil press" => Ok(Movement::DevilPress),
-            "rest" => Ok(Movement::Rest),
-            _ => Err(MovementParseError::new_invalid(s.to_string())),
-        }
+        static LOOKUP: OnceLock<HashMap<&str, &Movement>> = OnceLock::new();
+        let lookup = LOOKUP.get_or_init(|| MOVEMENTS.iter().map(|(name, m)| (*name, m)).collect());
+        lookup
+            .get(s)
+            .map(|m| (*m).clone())
+            .ok_or_else(|| MovementParseError::new_invalid(s.to_string()))
     }
 }
 
 impl fmt::Display for Movement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Movement::AirSquat => "Air Squat",
-                Movement::FrontSquat => "Front Squat",
-                Movement::BackSquat => "Back Squat",
-                Movement::OverheadSquat => "Overhead Squat",
-                Movement::PistolSquat => "Pistol Squat",
-                Movement::GobletSquat => "Goblet Squat",
-                Movement::Deadlift => "Deadlift",
-                Movement::SumoDeadlift => "Sumo Deadlift",
-                Movement::RomanianDeadlift => "Romanian Deadlift",
-                Movement::ShoulderPress => "Shoulder Press",
-                Movement::PushPress => "Push Press",
-                Movement::PushJerk => "Push Jerk",
-                Movement::SplitJerk => "Split Jerk",
-                Movement::BenchPress => "Bench Press",
-                Movement::Clean => "Clean",
-                Movement::PowerClean => "Power Clean",
-                Movement::HangClean => "Hang Clean",
-                Movement::HangPowerClean => "Hang Power Clean",
-                Movement::CleanAndJerk => "Clean And Jerk",
-                Movement::PowerCleanAndJerk => "Power Clean And Jerk",
-                Movement::CleanPull => "Clean Pull",
-                Movement::CleanDeadlift => "Clean Deadlift",
-                Movement::Snatch => "Snatch",
-                Movement::PowerSnatch => "Power Snatch",
-                Movement::HangSnatch => "Hang Snatch",
-                Movement::HangPowerSnatch => "Hang Power Snatch",
-                Movement::SnatchBalance => "Snatch Balance",
-                Movement::SnatchPull => "Snatch Pull",
-                Movement::SnatchDeadlift => "Snatch Deadlift",
-                Movement::MuscleSnatch => "Muscle Snatch",
-                Movement::PushUp => "Push Up",
-                Movement::PullUp => "Pull Up",
-                Movement::ChinUp => "Chin Up",
-                Movement::ChestToBar => "Chest To Bar",
-                Movement::MuscleUp => "Muscle Up",
-                Movement::BarMuscleUp => "Bar Muscle Up",
-                Movement::RingMuscleUp => "Ring Muscle Up",
-                Movement::ToesToBar => "Toes To Bar",
-                Movement::KneesToElbows => "Knees To Elbows",
-                Movement::LSit => "L Sit",
-                Movement::SitUp => "Sit Up",
-                Movement::VUp => "V Up",
-                Movement::GHD => "GHD",
-                Movement::StrictPullUp => "Strict Pull Up",
-                Movement::StrictHandstandPushUp => "Strict Handstand Push Up",
-                Movement::HandstandPushUp => "Handstand Push Up",
-                Movement::HandstandWalk => "Handstand Walk",
-                Movement::WallWalk => "Wall Walk",
-                Movement::HandstandHold => "Handstand Hold",
-                Movement::Thruster => "Thruster",
-                Movement::FrontRackLunge => "Front Rack Lunge",
-                Movement::BackRackLunge => "Back Rack Lunge",
-                Movement::OverheadWalkingLunge => "Overhead Walking Lunge",
-                Movement::Burpee => "Burpee",
-                Movement::BoxJump => "Box Jump",
-                Movement::BoxJumpOver => "Box Jump Over",
-                Movement::BurpeeBoxJump => "Burpee Box Jump",
-                Movement::BurpeeBoxJumpOver => "Burpee Box Jump Over",
-                Movement::BurpeeOverTheBar => "Burpee Over The Bar",
-                Movement::BurpeeToTarget => "Burpee To Target",
-                Movement::BurpeePullUp => "Burpee Pull Up",
-                Movement::DoubleUnder => "Double Under",
-                Movement::WallBall => "Wall Ball",
-                Movement::KettlebellSwing => "Kettlebell Swing",
-                Movement::TurkishGetUp => "Turkish Get Up",
-                Movement::FarmersCarry => "Farmer's Carry",
-                Movement::SledPush => "Sled Push",
-                Movement::SledPull => "Sled Pull",
-                Movement::SledDrag => "Sled Drag",
-                Movement::RopeClimb => "Rope Climb",
-                Movement::LeglessRopeClimb => "Legless Rope Climb",
-                Movement::SandbagClean => "Sandbag Clean",
-                Movement::DBall => "DBall",
-                Movement::DBallCarry => "DBall Carry",
-                Movement::DBallHold => "DBall Hold",
-                Movement::Row => "Row",
-                Movement::Run => "Run",
-                Movement::Bike => "Bike",
-                Movement::EchoBike => "Echo Bike",
-                Movement::Ski => "Ski",
-                Movement::DumbbellSnatch => "Dumbbell Snatch",
-                Movement::DumbbellClean => "Dumbbell Clean",
-                Movement::DumbbellPowerClean => "Dumbbell Power Clean",
-                Movement::DumbbellHangClean => "Dumbbell Hang Clean",
-                Movement::DumbbellCleanAndJerk => "Dumbbell Clean and Jerk",
-                Movement::DumbbellHangCleanAndJerk => "Dumbbell Hang Clean and Jerk",
-                Movement::DevilPress => "Devil Press",
-                Movement::Rest => "",
-            }
-        )
+        f.write_str(self.name())
     }
 }
 
 impl Movement {
-    pub fn list_with_url() -> BTreeMap<String, String> {
-        BTreeMap::from([
-            (
-                "Air Squat".to_string(),
-                "https://www.crossfit.com/essentials/the-air-squat".to_string(),
-            ),
-            (
-                "Front Squat".to_string(),
-                "https://www.crossfit.com/essentials/the-front-squat".to_string(),
-            ),
-            (
-                "Back Squat".to_string(),
-                "https://www.crossfit.com/essentials/the-back-squat".to_string(),
-            ),
-            (
-                "Overhead Squat".to_string(),
-                "https://www.crossfit.com/essentials/the-overhead-squat".to_string(),
-            ),
-            (
-                "Pistol Squat".to_string(),
-                "https://www.crossfit.com/essentials/the-single-leg-squat".to_string(),
-            ),
-            ("Goblet Squat".to_string(), "".to_string()),
-            (
-                "Deadlift".to_string(),
-                "https://www.crossfit.com/essentials/the-deadlift".to_string(),
-            ),
-            (
-                "Sumo Deadlift".to_string(),
-                "https://www.crossfit.com/essentials/the-sumo-deadlift".to_string(),
-            ),
-            ("Romanian Deadlift".to_string(), "".to_string()),
-            (
-                "Shoulder Press".to_string(),
-                "https://www.crossfit.com/essentials/the-shoulder-press".to_string(),
-            ),
-            (
-                "Push Press".to_string(),
-                "https://www.crossfit.com/essentials/the-push-press".to_string(),
-            ),
-            (
-                "Push Jerk".to_string(),
-                "https://www.crossfit.com/essentials/the-push-jerk".to_string(),
-            ),
-            (
-                "Split Jerk".to_string(),
-                "https://www.crossfit.com/essentials/the-split-jerk".to_string(),
-            ),
-            (
-                "Bench Press".to_string(),
-                "https://www.crossfit.com/essentials/the-bench-press".to_string(),
-            ),
-            (
-                "Clean".to_string(),
-                "https://www.crossfit.com/essentials/the-clean-2".to_string(),
-            ),
-            (
-                "Power Clean".to_string(),
-                "https://www.crossfit.com/essentials/the-power-clean".to_string(),
-            ),
-            (
-                "Hang Clean".to_string(),
-                "https://www.crossfit.com/essentials/the-hang-squat-clean".to_string(),
-            ),
-            (
-                "Hang Power Clean".to_string(),
-                "https://www.crossfit.com/essentials/the-hang-power-clean".to_string(),
-            ),
-            (
-                "Clean And Jerk".to_string(),
-                "https://www.crossfit.com/essentials/the-clean-and-jerk".to_string(),
-            ),
-            (
-                "Power Clean And Jerk".to_string(),
-                "https://www.crossfit.com/essentials/the-squat-clean-and-push-jerk".to_string(),
-            ),
-            ("Clean Pull".to_string(), "".to_string()),
-            ("Clean Deadlift".to_string(), "".to_string()),
-            (
-                "Snatch".to_string(),
-                "https://www.crossfit.com/essentials/the-snatch".to_string(),
-            ),
-            (
-                "Power Snatch".to_string(),
-                "https://www.crossfit.com/essentials/the-power-snatch".to_string(),
-            ),
-            (
-                "Hang Snatch".to_string(),
-                "https://www.crossfit.com/essentials/the-hang-snatch".to_string(),
-            ),
-            (
-                "Hang Power Snatch".to_string(),
-                "https://www.crossfit.com/essentials/the-hang-power-snatch".to_string(),
-            ),
-            (
-                "Snatch Balance".to_string(),
-                "https://www.crossfit.com/essentials/the-snatch-balance".to_string(),
-            ),
-            ("Snatch Pull".to_string(), "".to_string()),
-            ("Snatch Deadlift".to_string(), "".to_string()),
-            (
-                "Muscle Snatch".to_string(),
-                "https://www.crossfit.com/essentials/the-muscle-snatch".to_string(),
-            ),
-            ("Push Up".to_string(), "".to_string()),
-            ("Pull Up".to_string(), "".to_string()),
-            ("Chin Up".to_string(), "".to_string()),
-            ("Chest To Bar".to_string(), "".to_string()),
-            ("Muscle Up".to_string(), "".to_string()),
-            ("Bar Muscle Up".to_string(), "".to_string()),
-            ("Ring Muscle Up".to_string(), "".to_string()),
-            ("Knees To Elbows".to_string(), "".to_string()),
-            ("L Sit".to_string(), "".to_string()),
-            ("Strict Pull Up".to_string(), "".to_string()),
-            ("Strict Handstand Push Up".to_string(), "".to_string()),
-            ("Handstand Push Up".to_string(), "".to_string()),
-            ("Handstand Walk".to_string(), "".to_string()),
-            ("Wall Walk".to_string(), "".to_string()),
-            ("Handstand Hold".to_string(), "".to_string()),
-            ("Front Rack Lunge".to_string(), "".to_string()),
-            ("Back Rack Lunge".to_string(), "".to_string()),
-            ("Overhead Walking Lunge".to_string(), "".to_string()),
-            ("Burpee".to_string(), "".to_string()),
-            ("Box Jump".to_string(), "".to_string()),
-            ("Box Jump Over".to_string(), "".to_string()),
-            ("Burpee Box Jump".to_string(), "".to_string()),
-            ("Burpee Box Jump Over".to_string(), "".to_string()),
-            ("Burpee Over The Bar".to_string(), "".to_string()),
-            ("Burpee To Target".to_string(), "".to_string()),
-            ("Double Under".to_string(), "".to_string()),
-            ("Rope Climb".to_string(), "".to_string()),
-            ("Wall Ball".to_string(), "".to_string()),
-            ("Kettlebell Swing".to_string(), "".to_string()),
-            ("Turkish Get Up".to_string(), "".to_string()),
-            ("Farmer's Carry".to_string(), "".to_string()),
-            ("Sandbag Clean".to_string(), "".to_string()),
-            ("Sled Push".to_string(), "".to_string()),
-            ("Sled Pull".to_string(), "".to_string()),
-            ("Sled Drag".to_string(), "".to_string()),
-            ("Row".to_string(), "".to_string()),
-            ("Run".to_string(), "".to_string()),
-            ("Bike".to_string(), "".to_string()),
-            ("Echo Bike".to_string(), "".to_string()),
-            ("Ski".to_string(), "".to_string()),
-            ("Dumbbell Snatch".to_string(), "".to_string()),
-            ("Dumbbell Clean".to_string(), "".to_string()),
-            ("Dumbbell Power Clean".to_string(), "".to_string()),
-            ("Dumbbell Hang Clean".to_string(), "".to_string()),
-            ("Dumbbell Clean And Jerk".to_string(), "".to_string()),
-            ("Dumbbell Hang Clean And Jerk".to_string(), "".to_string()),
-            ("Devil Press".to_string(), "".to_string()),
-        ])
+    /// The display name of the movement, i.e. "Pull Up", the same used by the
+    /// `Display` implementation without allocating a `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::Movement;
+    ///
+    /// assert_eq!(Movement::PullUp.name(), "Pull Up");
+    /// assert_eq!(Movement::PullUp.name(), Movement::PullUp.to_string());
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Movement::AirSquat => "Air Squat",
+            Movement::FrontSquat => "Front Squat",
+            Movement::BackSquat => "Back Squat",
+            Movement::OverheadSquat => "Overhead Squat",
+            Movement::PistolSquat => "Pistol Squat",
+            Movement::GobletSquat => "Goblet Squat",
+            Movement::Deadlift => "Deadlift",
+            Movement::SumoDeadlift => "Sumo Deadlift",
+            Movement::RomanianDeadlift => "Romanian Deadlift",
+            Movement::ShoulderPress => "Shoulder Press",
+            Movement::PushPress => "Push Press",
+            Movement::PushJerk => "Push Jerk",
+            Movement::SplitJerk => "Split Jerk",
+            Movement::BenchPress => "Bench Press",
+            Movement::Clean => "Clean",
+            Movement::PowerClean => "Power Clean",
+            Movement::HangClean => "Hang Clean",
+            Movement::HangPowerClean => "Hang Power Clean",
+            Movement::CleanAndJerk => "Clean And Jerk",
+            Movement::PowerCleanAndJerk => "Power Clean And Jerk",
+            Movement::CleanPull => "Clean Pull",
+            Movement::CleanDeadlift => "Clean Deadlift",
+            Movement::Snatch => "Snatch",
+            Movement::PowerSnatch => "Power Snatch",
+            Movement::HangSnatch => "Hang Snatch",
+            Movement::HangPowerSnatch => "Hang Power Snatch",
+            Movement::SnatchBalance => "Snatch Balance",
+            Movement::SnatchPull => "Snatch Pull",
+            Movement::SnatchDeadlift => "Snatch Deadlift",
+            Movement::MuscleSnatch => "Muscle Snatch",
+            Movement::PushUp => "Push Up",
+            Movement::PullUp => "Pull Up",
+            Movement::ChinUp => "Chin Up",
+            Movement::ChestToBar => "Chest To Bar",
+            Movement::MuscleUp => "Muscle Up",
+            Movement::BarMuscleUp => "Bar Muscle Up",
+            Movement::RingMuscleUp => "Ring Muscle Up",
+            Movement::ToesToBar => "Toes To Bar",
+            Movement::KneesToElbows => "Knees To Elbows",
+            Movement::LSit => "L Sit",
+            Movement::SitUp => "Sit Up",
+            Movement::VUp => "V Up",
+            Movement::GHD => "GHD",
+            Movement::StrictPullUp => "Strict Pull Up",
+            Movement::RingRow => "Ring Row",
+            Movement::StrictHandstandPushUp => "Strict Handstand Push Up",
+            Movement::HandstandPushUp => "Handstand Push Up",
+            Movement::HandstandWalk => "Handstand Walk",
+            Movement::WallWalk => "Wall Walk",
+            Movement::HandstandHold => "Handstand Hold",
+            Movement::Thruster => "Thruster",
+            Movement::FrontRackLunge => "Front Rack Lunge",
+            Movement::BackRackLunge => "Back Rack Lunge",
+            Movement::OverheadWalkingLunge => "Overhead Walking Lunge",
+            Movement::Burpee => "Burpee",
+            Movement::BoxJump => "Box Jump",
+            Movement::BoxJumpOver => "Box Jump Over",
+            Movement::BurpeeBoxJump => "Burpee Box Jump",
+            Movement::BurpeeBoxJumpOver => "Burpee Box Jump Over",
+            Movement::BurpeeOverTheBar => "Burpee Over The Bar",
+            Movement::BurpeeToTarget => "Burpee To Target",
+            Movement::BurpeePullUp => "Burpee Pull Up",
+            Movement::DoubleUnder => "Double Under",
+            Movement::WallBall => "Wall Ball",
+            Movement::KettlebellSwing => "Kettlebell Swing",
+            Movement::TurkishGetUp => "Turkish Get Up",
+            Movement::FarmersCarry => "Farmer's Carry",
+            Movement::SledPush => "Sled Push",
+            Movement::SledPull => "Sled Pull",
+            Movement::SledDrag => "Sled Drag",
+            Movement::RopeClimb => "Rope Climb",
+            Movement::LeglessRopeClimb => "Legless Rope Climb",
+            Movement::SandbagClean => "Sandbag Clean",
+            Movement::DBall => "DBall",
+            Movement::DBallCarry => "DBall Carry",
+            Movement::DBallHold => "DBall Hold",
+            Movement::Row => "Row",
+            Movement::Run => "Run",
+            Movement::Bike => "Bike",
+            Movement::EchoBike => "Echo Bike",
+            Movement::Ski => "Ski",
+            Movement::DumbbellSnatch => "Dumbbell Snatch",
+            Movement::DumbbellClean => "Dumbbell Clean",
+            Movement::DumbbellPowerClean => "Dumbbell Power Clean",
+            Movement::DumbbellHangClean => "Dumbbell Hang Clean",
+            Movement::DumbbellCleanAndJerk => "Dumbbell Clean and Jerk",
+            Movement::DumbbellHangCleanAndJerk => "Dumbbell Hang Clean and Jerk",
+            Movement::DevilPress => "Devil Press",
+            Movement::Rest => "",
+        }
+    }
+
+    /// The name used for the movement in the shorthand, its name in lowercase
+    /// when the parser accepts it, or its first alias otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::Movement;
+    ///
+    /// assert_eq!(Movement::PullUp.shorthand(), "pull up");
+    /// assert_eq!(Movement::CleanDeadlift.shorthand(), "clean deadlift");
+    /// ```
+    pub fn shorthand(&self) -> &'static str {
+        let name = self.name();
+        self.aliases()
+            .find(|m| m.eq_ignore_ascii_case(name))
+            .or_else(|| self.aliases().next())
+            .unwrap_or_default()
+    }
+
+    /// Whether the movement is done in a machine, i.e. a rower or a bike.
+    pub fn is_machine(&self) -> bool {
+        matches!(
+            self,
+            Movement::Row | Movement::Bike | Movement::EchoBike | Movement::Ski
+        )
+    }
+
+    /// The equipment needed for the movement, `None` for the bodyweight movements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::movement::{Equipment, Movement};
+    ///
+    /// assert_eq!(Movement::Thruster.equipment(), Some(Equipment::Barbell));
+    /// assert_eq!(Movement::DoubleUnder.equipment(), Some(Equipment::JumpRope));
+    /// assert_eq!(Movement::Burpee.equipment(), None);
+    /// ```
+    pub fn equipment(&self) -> Option<Equipment> {
+        use Movement::*;
+
+        let equipment = match self {
+            FrontSquat | BackSquat | OverheadSquat | Deadlift | SumoDeadlift | RomanianDeadlift
+            | ShoulderPress | PushPress | PushJerk | SplitJerk | BenchPress | Clean
+            | PowerClean | HangClean | HangPowerClean | CleanAndJerk | PowerCleanAndJerk
+            | CleanPull | CleanDeadlift | Snatch | PowerSnatch | HangSnatch | HangPowerSnatch
+            | SnatchBalance | SnatchPull | SnatchDeadlift | MuscleSnatch | Thruster
+            | FrontRackLunge | BackRackLunge | OverheadWalkingLunge | BurpeeOverTheBar => {
+                Equipment::Barbell
+            }
+            DumbbellSnatch
+            | DumbbellClean
+            | DumbbellPowerClean
+            | DumbbellHangClean
+            | DumbbellCleanAndJerk
+            | DumbbellHangCleanAndJerk
+            | DevilPress
+            | FarmersCarry => Equipment::Dumbbell,
+            GobletSquat | KettlebellSwing | TurkishGetUp => Equipment::Kettlebell,
+            PullUp | ChinUp | ChestToBar | BarMuscleUp | ToesToBar | KneesToElbows
+            | StrictPullUp | BurpeePullUp => Equipment::PullUpBar,
+            MuscleUp | RingMuscleUp | RingRow => Equipment::Rings,
+            BoxJump | BoxJumpOver | BurpeeBoxJump | BurpeeBoxJumpOver => Equipment::Box,
+            DoubleUnder => Equipment::JumpRope,
+            WallBall => Equipment::WallBall,
+            RopeClimb | LeglessRopeClimb => Equipment::Rope,
+            SledPush | SledPull | SledDrag => Equipment::Sled,
+            SandbagClean => Equipment::Sandbag,
+            DBall | DBallCarry | DBallHold => Equipment::DBall,
+            GHD => Equipment::Ghd,
+            Row => Equipment::Rower,
+            Bike | EchoBike => Equipment::Bike,
+            Ski => Equipment::SkiErg,
+            AirSquat
+            | PistolSquat
+            | PushUp
+            | LSit
+            | SitUp
+            | VUp
+            | StrictHandstandPushUp
+            | HandstandPushUp
+            | WallWalk
+            | HandstandWalk
+            | HandstandHold
+            | Burpee
+            | BurpeeToTarget
+            | Run
+            | Rest => return None,
+        };
+        Some(equipment)
+    }
+
+    /// The movement pattern of the movement, `None` for the rest periods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::movement::{Movement, Pattern};
+    ///
+    /// assert_eq!(Movement::Thruster.pattern(), Some(Pattern::Squat));
+    /// assert_eq!(Movement::HangPowerSnatch.pattern(), Some(Pattern::Olympic));
+    /// assert_eq!(Movement::Rest.pattern(), None);
+    /// ```
+    pub fn pattern(&self) -> Option<Pattern> {
+        use Movement::*;
+
+        let pattern = match self {
+            AirSquat | FrontSquat | BackSquat | OverheadSquat | PistolSquat | GobletSquat
+            | Thruster | FrontRackLunge | BackRackLunge | OverheadWalkingLunge | WallBall => {
+                Pattern::Squat
+            }
+            Deadlift | SumoDeadlift | RomanianDeadlift | CleanPull | CleanDeadlift | SnatchPull
+            | SnatchDeadlift | KettlebellSwing | FarmersCarry | SledPush | SledPull | SledDrag
+            | DBall | DBallCarry | DBallHold => Pattern::Hinge,
+            ShoulderPress | PushPress | PushJerk | SplitJerk | BenchPress | PushUp | DevilPress
+            | TurkishGetUp => Pattern::Press,
+            PullUp | ChinUp | ChestToBar | MuscleUp | BarMuscleUp | RingMuscleUp | StrictPullUp
+            | RingRow | RopeClimb | LeglessRopeClimb | BurpeePullUp => Pattern::Pull,
+            Clean
+            | PowerClean
+            | HangClean
+            | HangPowerClean
+            | CleanAndJerk
+            | PowerCleanAndJerk
+            | Snatch
+            | PowerSnatch
+            | HangSnatch
+            | HangPowerSnatch
+            | SnatchBalance
+            | MuscleSnatch
+            | SandbagClean
+            | DumbbellSnatch
+            | DumbbellClean
+            | DumbbellPowerClean
+            | DumbbellHangClean
+            | DumbbellCleanAndJerk
+            | DumbbellHangCleanAndJerk => Pattern::Olympic,
+            StrictHandstandPushUp | HandstandPushUp | WallWalk | HandstandWalk | HandstandHold => {
+                Pattern::Inverted
+            }
+            ToesToBar | KneesToElbows | LSit | SitUp | VUp | GHD => Pattern::Midline,
+            Burpee | BoxJump | BoxJumpOver | BurpeeBoxJump | BurpeeBoxJumpOver
+            | BurpeeOverTheBar | BurpeeToTarget | DoubleUnder => Pattern::Jump,
+            Row | Run | Bike | EchoBike | Ski => Pattern::Monostructural,
+            Rest => return None,
+        };
+        Some(pattern)
+    }
+
+    /// The range of loads in kg that makes sense for the movement, from the lightest
+    /// to the heaviest anyone would program, `None` for the movements done in a
+    /// machine. The bodyweight movements can be done with a vest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::Movement;
+    ///
+    /// assert_eq!(Movement::Deadlift.plausible_load(), Some((20.0, 400.0)));
+    /// assert_eq!(Movement::WallBall.plausible_load(), Some((2.0, 15.0)));
+    /// assert_eq!(Movement::Row.plausible_load(), None);
+    /// ```
+    pub fn plausible_load(&self) -> Option<(f64, f64)> {
+        use Movement::*;
+
+        let range = match self {
+            Deadlift | SumoDeadlift | RomanianDeadlift | CleanDeadlift | SnatchDeadlift
+            | CleanPull | SnatchPull => (20.0, 400.0),
+            BackSquat | FrontSquat | BenchPress => (20.0, 350.0),
+            Snatch | PowerSnatch | HangSnatch | HangPowerSnatch | SnatchBalance | MuscleSnatch
+            | OverheadSquat | Thruster | ShoulderPress | PushPress | FrontRackLunge
+            | BackRackLunge | OverheadWalkingLunge => (10.0, 200.0),
+            Row | Bike | EchoBike | Ski | Rest => return None,
+            _ => match self.equipment() {
+                Some(Equipment::Barbell) => (15.0, 300.0),
+                Some(Equipment::Dumbbell) => (2.5, 70.0),
+                Some(Equipment::Kettlebell) => (4.0, 70.0),
+                Some(Equipment::WallBall) => (2.0, 15.0),
+                Some(Equipment::Sandbag | Equipment::DBall) => (10.0, 150.0),
+                Some(Equipment::Sled) => (0.0, 400.0),
+                _ => (0.0, 60.0),
+            },
+        };
+        Some(range)
+    }
+
+    /// Every movement known by the parser, in the order of the registry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::Movement;
+    ///
+    /// let movements: Vec<Movement> = Movement::all().collect();
+    /// assert_eq!(movements[0], Movement::AirSquat);
+    /// assert!(movements.contains(&Movement::Thruster));
+    /// ```
+    pub fn all() -> impl Iterator<Item = Movement> {
+        MOVEMENTS
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, movement))| !MOVEMENTS[..*i].iter().any(|(_, m)| m == movement))
+            .map(|(_, (_, movement))| movement.clone())
+    }
+
+    /// The names accepted by the parser for the movement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::Movement;
+    ///
+    /// let aliases: Vec<&str> = Movement::OverheadSquat.aliases().collect();
+    /// assert_eq!(aliases, vec!["ohs", "overhead squat"]);
+    /// ```
+    pub fn aliases(&self) -> impl Iterator<Item = &'static str> + '_ {
+        MOVEMENTS
+            .iter()
+            .filter(move |(_, movement)| movement == self)
+            .map(|(name, _)| *name)
+    }
+
+    /// The video explaining the movement, if there's one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::Movement;
+    ///
+    /// assert_eq!(
+    ///     Movement::AirSquat.video(),
+    ///     Some("https://www.crossfit.com/essentials/the-air-squat")
+    /// );
+    /// assert_eq!(Movement::GobletSquat.video(), None);
+    /// ```
+    pub fn video(&self) -> Option<&'static str> {
+        VIDEOS
+            .iter()
+            .find_map(|(movement, url)| (movement == self).then_some(*url))
     }
 }
 
-fn suggest_closest_movement(movement: &str) -> Option<&'static str> {
-    // TODO: This will always return a str, change the output type
-    // to just assume a string will be returned.
-    let mut closest = None;
-    let mut min_distance = usize::MAX;
-    for &m in MOVEMENTS {
-        let distance = levenshtein(movement, m);
-        if distance < min_distance {
-            min_distance = distance;
-            closest = Some(m);
+/// Looks for inconsistencies in the registry of movements: names listed twice, names
+/// the parser doesn't read back as their movement, and video URLs that are not valid
+/// links.
+pub fn registry_problems() -> Vec<String> {
+    let mut problems = Vec::new();
+    for (i, (name, movement)) in MOVEMENTS.iter().enumerate() {
+        if MOVEMENTS[..i].iter().any(|(other, _)| other == name) {
+            problems.push(format!("movement `{}` is listed twice", name));
+        }
+        if !round_trips(name, movement) {
+            problems.push(format!("movement `{}` can't be written in a workout", name));
         }
     }
-    closest
+    for (movement, url) in VIDEOS {
+        if !url.starts_with("https://") {
+            problems.push(format!("invalid video URL for `{}`: {}", movement, url));
+        }
+    }
+    problems
+}
+
+/// Whether a workout with `name` as its only line reads back as `movement`.
+pub(crate) fn round_trips(name: &str, movement: &Movement) -> bool {
+    create_workout(&format!("ft 1 {}", name), None, None)
+        .map(|workout| workout.movements().eq([movement]))
+        .unwrap_or(false)
+}
+
+/// Videos explaining the movements, from the CrossFit essentials.
+static VIDEOS: &[(Movement, &str)] = &[
+    (
+        Movement::AirSquat,
+        "https://www.crossfit.com/essentials/the-air-squat",
+    ),
+    (
+        Movement::FrontSquat,
+        "https://www.crossfit.com/essentials/the-front-squat",
+    ),
+    (
+        Movement::BackSquat,
+        "https://www.crossfit.com/essentials/the-back-squat",
+    ),
+    (
+        Movement::OverheadSquat,
+        "https://www.crossfit.com/essentials/the-overhead-squat",
+    ),
+    (
+        Movement::PistolSquat,
+        "https://www.crossfit.com/essentials/the-single-leg-squat",
+    ),
+    (
+        Movement::Deadlift,
+        "https://www.crossfit.com/essentials/the-deadlift",
+    ),
+    (
+        Movement::SumoDeadlift,
+        "https://www.crossfit.com/essentials/the-sumo-deadlift",
+    ),
+    (
+        Movement::ShoulderPress,
+        "https://www.crossfit.com/essentials/the-shoulder-press",
+    ),
+    (
+        Movement::PushPress,
+        "https://www.crossfit.com/essentials/the-push-press",
+    ),
+    (
+        Movement::PushJerk,
+        "https://www.crossfit.com/essentials/the-push-jerk",
+    ),
+    (
+        Movement::SplitJerk,
+        "https://www.crossfit.com/essentials/the-split-jerk",
+    ),
+    (
+        Movement::BenchPress,
+        "https://www.crossfit.com/essentials/the-bench-press",
+    ),
+    (
+        Movement::Clean,
+        "https://www.crossfit.com/essentials/the-clean-2",
+    ),
+    (
+        Movement::PowerClean,
+        "https://www.crossfit.com/essentials/the-power-clean",
+    ),
+    (
+        Movement::HangClean,
+        "https://www.crossfit.com/essentials/the-hang-squat-clean",
+    ),
+    (
+        Movement::HangPowerClean,
+        "https://www.crossfit.com/essentials/the-hang-power-clean",
+    ),
+    (
+        Movement::CleanAndJerk,
+        "https://www.crossfit.com/essentials/the-clean-and-jerk",
+    ),
+    (
+        Movement::PowerCleanAndJerk,
+        "https://www.crossfit.com/essentials/the-squat-clean-and-push-jerk",
+    ),
+    (
+        Movement::Snatch,
+        "https://www.crossfit.com/essentials/the-snatch",
+    ),
+    (
+        Movement::PowerSnatch,
+        "https://www.crossfit.com/essentials/the-power-snatch",
+    ),
+    (
+        Movement::HangSnatch,
+        "https://www.crossfit.com/essentials/the-hang-snatch",
+    ),
+    (
+        Movement::HangPowerSnatch,
+        "https://www.crossfit.com/essentials/the-hang-power-snatch",
+    ),
+    (
+        Movement::SnatchBalance,
+        "https://www.crossfit.com/essentials/the-snatch-balance",
+    ),
+    (
+        Movement::MuscleSnatch,
+        "https://www.crossfit.com/essentials/the-muscle-snatch",
+    ),
+];
+
+/// The canonical names of the `n` movements closest to `movement`, ranked by their
+/// distance. The canonical name is the one the shorthand writes, so it reads back as
+/// the movement, and the aliases ("ohs") aren't suggested.
+pub(crate) fn suggest_closest_movements(movement: &str, n: usize) -> Vec<&'static str> {
+    let mut ranked: Vec<(usize, &'static str)> = Movement::all()
+        .map(|m| m.shorthand())
+        .map(|name| (levenshtein(movement, name), name))
+        .collect();
+    // Stable, the ties keep the order of the registry
+    ranked.sort_by_key(|(distance, _)| *distance);
+    ranked.into_iter().take(n).map(|(_, name)| name).collect()
 }
 
 #[cfg(test)]
@@ -663,6 +980,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_equipment() {
+        for equipment in Equipment::ALL {
+            assert_eq!(equipment.to_string().parse::<Equipment>(), Ok(equipment));
+        }
+        assert_eq!("Pull up bar".parse::<Equipment>(), Ok(Equipment::PullUpBar));
+        assert!("treadmill".parse::<Equipment>().is_err());
+        assert_eq!(Movement::EchoBike.equipment(), Some(Equipment::Bike));
+        assert_eq!(Movement::Run.equipment(), None);
+    }
+
+    #[test]
+    fn test_all() {
+        let movements: Vec<Movement> = Movement::all().collect();
+        for (m, movement) in MOVEMENTS {
+            assert_eq!(Movement::from_str(m).unwrap(), *movement);
+            assert!(movements.contains(movement));
+        }
+        for movement in &movements {
+            assert_eq!(movements.iter().filter(|m| *m == movement).count(), 1);
+            assert!(movement.aliases().next().is_some());
+        }
+    }
+
+    #[test]
+    fn test_shorthand() {
+        for (_, movement) in MOVEMENTS {
+            assert_eq!(Movement::from_str(movement.shorthand()).unwrap(), *movement);
+        }
+    }
+
     #[test]
     fn test_from_str_invalid() {
         assert!(Movement::from_str("invalid movement").is_err());
@@ -671,11 +1019,27 @@ mod tests {
     }
 
     #[test]
-    fn test_suggest_closest_movement() {
-        assert_eq!(suggest_closest_movement("air squa"), Some("air squat"));
-        assert_eq!(suggest_closest_movement("front s"), Some("front squat"));
-        assert_eq!(suggest_closest_movement("back squ"), Some("back squat"));
-        assert_eq!(suggest_closest_movement("snacth"), Some("snatch"));
+    fn test_suggest_closest_movements() {
+        let closest = |m: &str| suggest_closest_movements(m, 1);
+        assert_eq!(closest("air squa"), vec!["air squat"]);
+        assert_eq!(closest("front s"), vec!["front squat"]);
+        assert_eq!(closest("back squ"), vec!["back squat"]);
+        assert_eq!(closest("snacth"), vec!["snatch"]);
+        assert_eq!(suggest_closest_movements("snacth", 0), Vec::<&str>::new());
+
+        assert_eq!(
+            suggest_closest_movements("clean and jerk press", 3),
+            vec!["clean and jerk", "shoulder press", "bench press"]
+        );
+        // Only the canonical names are suggested, "double under" but not "du"
+        let suggestions = suggest_closest_movements("double unders", 3);
+        assert_eq!(suggestions[0], "double under");
+        assert!(!suggestions.contains(&"du"));
+        assert_eq!(closest("clean-deadlift"), vec!["clean deadlift"]);
+        // And they read back as the movement
+        for name in suggest_closest_movements("squat", Movement::all().count()) {
+            assert_eq!(Movement::from_str(name).unwrap().shorthand(), name);
+        }
     }
 
     #[test]
@@ -683,16 +1047,67 @@ mod tests {
         let err = Movement::from_str("clone").unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Invalid movement: `clone`, did you mean: `clean`?"
+            "Invalid movement: `clone`, did you mean: `clean`, `row` or `run`?"
         );
 
         let err = Movement::from_str("squat").unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Invalid movement: `squat`, did you mean: `air squat`?"
+            "Invalid movement: `squat`, did you mean: `air squat`, `clean` or `snatch`?"
+        );
+
+        let err = Movement::from_str("pulup").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid movement: `pulup`, did you mean: `pull up`, `push up` or `v up`?"
+        );
+
+        let err = Movement::from_str("thrusters").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid movement: `thrusters`, did you mean: `thruster`, `rest` or `air squat`?"
         );
     }
 
+    #[test]
+    fn test_registry_problems() {
+        assert_eq!(registry_problems(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_shorthand_round_trips() {
+        for movement in Movement::all() {
+            assert!(
+                round_trips(movement.shorthand(), &movement),
+                "`{}` doesn't read back as {:?}",
+                movement.shorthand(),
+                movement
+            );
+        }
+    }
+
+    #[test]
+    fn test_aliases_round_trip() {
+        for movement in Movement::all() {
+            for alias in movement.aliases() {
+                assert!(
+                    round_trips(alias, &movement),
+                    "`{}` doesn't read back as {:?}",
+                    alias,
+                    movement
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trips() {
+        assert!(round_trips("clean deadlift", &Movement::CleanDeadlift));
+        assert!(round_trips("l sit", &Movement::LSit));
+        assert!(!round_trips("clean-deadlift", &Movement::CleanDeadlift));
+        assert!(!round_trips("L-sit", &Movement::LSit));
+    }
+
     #[test]
     fn test_movement_display() {
         assert_eq!(format!("{}", Movement::AirSquat), "Air Squat");
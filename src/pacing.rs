@@ -0,0 +1,212 @@
+//! Target paces of the monostructural lines (row, run, bike and ski), computed from
+//! a baseline score of the athlete, i.e. a 2k row in 7:30 or a 5k run in 22:00.
+//!
+//! The time for a distance is extrapolated from the baseline with the formula of
+//! Riegel, `t2 = t1 * (d2 / d1) ^ 1.06`, so shorter pieces are done at a faster
+//! pace. The calories of the rower and the ski erg are converted with the formula
+//! of their monitors, at the pace of the baseline.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::movement::Movement;
+use crate::rep_types::rep_type::RepType;
+use crate::workout::Prescription;
+use crate::Distance;
+
+/// Exponent of the formula of Riegel, the fatigue of going further.
+const RIEGEL_EXPONENT: f64 = 1.06;
+
+/// A score of the athlete used as reference for the paces: the time over a distance.
+///
+/// # Examples
+///
+/// ```
+/// use wod::pacing::Baseline;
+///
+/// let baseline: Baseline = "2k 7:30".parse().unwrap();
+/// assert_eq!(baseline.seconds, 450);
+/// assert_eq!(baseline.time_for(2000.0).round(), 450.0);
+/// assert_eq!(baseline.to_string(), "2k 7:30");
+/// assert!("2k".parse::<Baseline>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    /// The distance of the piece, i.e. 2k.
+    pub distance: Distance,
+    /// The time it was done in.
+    pub seconds: u32,
+}
+
+impl Baseline {
+    /// The time expected for a distance in meters.
+    pub fn time_for(&self, meters: f64) -> f64 {
+        self.seconds as f64 * (meters / self.distance.meters()).powf(RIEGEL_EXPONENT)
+    }
+
+    /// The time expected for the calories of a rower or a ski erg at the pace of the
+    /// baseline. The monitors compute the calories per hour from the watts as
+    /// `watts * 4 * 0.8604 + 300`, with `watts = 2.8 / (seconds per meter) ^ 3`.
+    pub fn time_for_calories(&self, calories: f64) -> f64 {
+        let pace = self.seconds as f64 / self.distance.meters();
+        let watts = 2.8 / pace.powi(3);
+        let per_hour = watts * 4.0 * 0.8604 + 300.0;
+        calories / per_hour * 3600.0
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid baseline: `{}`, expected a distance and a time, i.e. \"2k 7:30\"",
+                s
+            )
+        };
+        let (distance, time) = s.trim().split_once(' ').ok_or_else(invalid)?;
+        let distance: Distance = distance.parse().map_err(|_| invalid())?;
+        let seconds = parse_time(time)
+            .filter(|seconds| *seconds > 0)
+            .ok_or_else(invalid)?;
+        if distance.num == 0 {
+            return Err(invalid());
+        }
+        Ok(Baseline { distance, seconds })
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.distance, clock(self.seconds as f64))
+    }
+}
+
+/// The distance the split of a movement is given for, with its label: 500m for the
+/// rower and the ski erg, a kilometer for the run and the bikes. `None` for the
+/// movements that aren't monostructural.
+///
+/// # Examples
+///
+/// ```
+/// use wod::pacing::split;
+/// use wod::Movement;
+///
+/// assert_eq!(split(&Movement::Row), Some((500.0, "500m")));
+/// assert_eq!(split(&Movement::Run), Some((1000.0, "km")));
+/// assert_eq!(split(&Movement::Thruster), None);
+/// ```
+pub fn split(movement: &Movement) -> Option<(f64, &'static str)> {
+    match movement {
+        Movement::Row | Movement::Ski => Some((500.0, "500m")),
+        Movement::Run | Movement::Bike | Movement::EchoBike => Some((1000.0, "km")),
+        _ => None,
+    }
+}
+
+/// The target of a monostructural line with the baseline of its movement: the time
+/// and the split, i.e. "1:44, 1:44/500m" for "500m row". The lines of calories are
+/// only paced for the rower and the ski erg, and have a time per gender when they
+/// differ, i.e. "1:03/0:47, 1:53/500m".
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use wod::create_workout;
+/// use wod::pacing::target;
+/// use wod::workout::WorkoutStructure;
+///
+/// let baselines = BTreeMap::from([("Row".to_string(), "2k 7:30".parse().unwrap())]);
+/// let workout = create_workout("3rd 500m row, 20/15cal row, 10 burpee", None, None).unwrap();
+/// let WorkoutStructure::Simple(simple) = &workout.structure else { unreachable!() };
+/// let targets: Vec<_> = simple.prescriptions.iter().map(|line| target(line, &baselines)).collect();
+/// assert_eq!(targets[0].as_deref(), Some("1:44, 1:44/500m"));
+/// assert_eq!(targets[1].as_deref(), Some("1:03/0:47, 1:53/500m"));
+/// assert_eq!(targets[2], None);
+/// ```
+pub fn target(line: &Prescription, baselines: &BTreeMap<String, Baseline>) -> Option<String> {
+    let [movement] = line.movements.as_slice() else {
+        return None;
+    };
+    let (split_meters, split_label) = split(movement)?;
+    let baseline = baselines.get(movement.name())?;
+    let (time, pace) = match line.reps.first()? {
+        RepType::Distance(distance) => {
+            let seconds = baseline.time_for(distance.meters());
+            let pace = seconds * split_meters / distance.meters();
+            (clock(seconds), pace)
+        }
+        RepType::Cals(cals) if matches!(movement, Movement::Row | Movement::Ski) => {
+            let man = clock(baseline.time_for_calories(cals.cals_man as f64));
+            let woman = clock(baseline.time_for_calories(cals.cals_woman as f64));
+            let time = if man == woman {
+                man
+            } else {
+                format!("{}/{}", man, woman)
+            };
+            let pace = baseline.seconds as f64 * split_meters / baseline.distance.meters();
+            (time, pace)
+        }
+        _ => return None,
+    };
+    Some(format!("{}, {}/{}", time, clock(pace), split_label))
+}
+
+/// Formats seconds as "m:ss", or "h:mm:ss" from an hour.
+fn clock(seconds: f64) -> String {
+    let seconds = seconds.round() as u32;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// A time in "mm:ss" or "hh:mm:ss" in seconds.
+pub(crate) fn parse_time(score: &str) -> Option<u32> {
+    let parts: Vec<&str> = score.trim().split(':').collect();
+    if !(2..=3).contains(&parts.len()) {
+        return None;
+    }
+    parts.iter().try_fold(0, |seconds, part| {
+        let value: u32 = part.parse().ok()?;
+        Some(seconds * 60 + value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_riegel() {
+        let baseline: Baseline = "5k 22:00".parse().unwrap();
+        // Shorter pieces are faster per meter, longer ones slower
+        assert!(baseline.time_for(1000.0) < 264.0);
+        assert!(baseline.time_for(10000.0) > 2640.0);
+        assert_eq!(clock(baseline.time_for(10000.0)), "45:52");
+    }
+
+    #[test]
+    fn test_clock() {
+        assert_eq!(clock(59.6), "1:00");
+        assert_eq!(clock(450.0), "7:30");
+        assert_eq!(clock(3725.0), "1:02:05");
+    }
+
+    #[test]
+    fn test_baseline_errors() {
+        assert!("2k 0:00".parse::<Baseline>().is_err());
+        assert!("0m 7:30".parse::<Baseline>().is_err());
+        assert!("2 7:30".parse::<Baseline>().is_err());
+        assert!("2k 7m30".parse::<Baseline>().is_err());
+        assert_eq!(
+            "5k 1:05:00".parse::<Baseline>().unwrap().to_string(),
+            "5k 1:05:00"
+        );
+    }
+}
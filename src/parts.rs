@@ -0,0 +1,205 @@
+//! Parts of a class in a single entry, i.e. the strength and the metcon of the day:
+//!
+//! ```text
+//! A) wl 5x3 back squat @80%; B) amrap-12 10 pull up, 15 wall ball @9/6kg
+//! ```
+//!
+//! The parts are separated by `;` (the ones of the weightlifting blocks, inside
+//! brackets, and of the notes in parentheses don't count) and can start with a
+//! label, `A)`. The parts without one are labelled with the letter of their
+//! position. Every part is written as its own workout, named "Part A", "Part B"...
+
+use crate::error::WodError;
+use crate::i18n::{self, Message};
+use crate::workout::{create_workout, Workout};
+
+/// A part of an entry, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part<'a> {
+    /// The label of the part, i.e. "A".
+    pub label: String,
+    /// The workout of the part in the shorthand.
+    pub workout: &'a str,
+}
+
+/// The label written at the start of a part, "A)" or "1)", and the rest of it.
+fn label(part: &str) -> Option<(&str, &str)> {
+    let (label, rest) = part.split_once(')')?;
+    let valid = (1..=2).contains(&label.len()) && label.chars().all(|c| c.is_ascii_alphanumeric());
+    valid.then(|| (label, rest.trim_start()))
+}
+
+/// Splits an entry in its parts, `None` if it's a single workout without label or
+/// there is nothing but `;` in it.
+///
+/// # Examples
+///
+/// ```
+/// use wod::parts::split;
+///
+/// let parts = split("A) wl [1rm snatch; 1rm clean]; amrap-12 10 pull up").unwrap();
+/// assert_eq!(parts[0].label, "A");
+/// assert_eq!(parts[0].workout, "wl [1rm snatch; 1rm clean]");
+/// assert_eq!(parts[1].label, "B");
+/// assert_eq!(parts[1].workout, "amrap-12 10 pull up");
+/// assert!(split("wl [1rm snatch; 1rm clean]").is_none());
+/// ```
+pub fn split(entry: &str) -> Option<Vec<Part<'_>>> {
+    let mut segments = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, c) in entry.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            // The parenthesis of a label closes nothing
+            ']' | ')' => depth = (depth - 1).max(0),
+            ';' if depth == 0 => {
+                segments.push(&entry[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&entry[start..]);
+    let segments: Vec<&str> = segments
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+    if segments.is_empty() || (segments.len() == 1 && label(segments[0]).is_none()) {
+        return None;
+    }
+
+    let parts = segments
+        .into_iter()
+        .enumerate()
+        .map(|(i, segment)| match label(segment) {
+            Some((label, workout)) => Part {
+                label: label.to_uppercase(),
+                workout,
+            },
+            None => Part {
+                label: char::from(b'A' + (i % 26) as u8).to_string(),
+                workout: segment,
+            },
+        })
+        .collect();
+    Some(parts)
+}
+
+/// Creates the workouts of an entry, one per part (see [`split`]), or a single one
+/// if it doesn't have parts.
+///
+/// The parts are named after their label, preceded by the `name` of the entry if
+/// it has one, i.e. "Monday, Part A". The `comments` go to the last part.
+///
+/// # Examples
+///
+/// ```
+/// use wod::parts::create_workouts;
+///
+/// let workouts = create_workouts(
+///     "A) wl 5x3 back squat @80%; B) amrap-12 10 pull up",
+///     Some("Scale the pull ups".to_string()),
+///     None,
+/// )
+/// .unwrap();
+/// assert_eq!(workouts[0].name(), Some("Part A"));
+/// assert_eq!(workouts[0].comments(), None);
+/// assert_eq!(workouts[1].name(), Some("Part B"));
+/// assert!(workouts[1].comments().is_some());
+/// ```
+///
+/// # Errors
+///
+/// Returns `WodError::InvalidWorkout` if the entry or one of its parts is empty, or
+/// the error of the first part that can't be parsed.
+pub fn create_workouts(
+    entry: &str,
+    comments: Option<String>,
+    name: Option<String>,
+) -> Result<Vec<Workout>, WodError> {
+    let Some(parts) = split(entry) else {
+        if entry
+            .trim_matches(|c: char| c == ';' || c.is_whitespace())
+            .is_empty()
+        {
+            return Err(WodError::InvalidWorkout("the workout is empty".to_string()));
+        }
+        return Ok(vec![create_workout(entry, comments, name)?]);
+    };
+    if let Some(part) = parts.iter().find(|p| p.workout.is_empty()) {
+        return Err(WodError::InvalidWorkout(format!(
+            "part {} is empty",
+            part.label
+        )));
+    }
+    let last = parts.len() - 1;
+    parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| {
+            let label = i18n::text(Message::Part, &[&part.label]);
+            let part_name = match &name {
+                Some(name) => format!("{}, {}", name, label),
+                None => label,
+            };
+            let comments = if i == last { comments.clone() } else { None };
+            create_workout(part.workout, comments, Some(part_name))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_labels() {
+        let parts = split("a) ft 21-15-9 pull up, thruster @43/30kg ;C) wl 5x5 deadlift").unwrap();
+        assert_eq!(
+            parts,
+            [
+                Part {
+                    label: "A".to_string(),
+                    workout: "ft 21-15-9 pull up, thruster @43/30kg"
+                },
+                Part {
+                    label: "C".to_string(),
+                    workout: "wl 5x5 deadlift"
+                },
+            ]
+        );
+        // A single part with a label is still a part
+        assert_eq!(split("1) wl 5x5 deadlift").unwrap()[0].label, "1");
+        // The parentheses of a workout aren't labels
+        assert!(split("wl 3x(2+1) clean, split jerk @85%").is_none());
+        // Nor the `;` of a note
+        assert!(split("ft 21 pull up (strict; kipping if needed)").is_none());
+        // Nor an entry without workouts
+        assert!(split("").is_none());
+        assert!(split(" ; ").is_none());
+    }
+
+    #[test]
+    fn test_create_workouts() {
+        let workouts = create_workouts(
+            "A) wl 5x3 back squat @80%; B) ft 21-15-9 pull up, thruster @43/30kg",
+            None,
+            Some("Monday".to_string()),
+        )
+        .unwrap();
+        assert_eq!(workouts.len(), 2);
+        assert_eq!(workouts[1].name(), Some("Monday, Part B"));
+        assert!(create_workouts("A) wl 5x3 back squat; B) ft 21 unknown", None, None).is_err());
+        for entry in ["", ";", "A) wl 5x3 back squat; B)"] {
+            assert!(matches!(
+                create_workouts(entry, None, None),
+                Err(WodError::InvalidWorkout(_))
+            ));
+        }
+        assert_eq!(
+            create_workouts("ft 21 pull up", None, None).unwrap()[0].name(),
+            None
+        );
+    }
+}
@@ -0,0 +1,371 @@
+//! Periodization: a plan of the training across weeks, written in TOML, from which
+//! the skeleton of every day is generated for the coaches to refine.
+//!
+//! A plan has a start date, the days of the week to train and its phases. Every
+//! phase lasts some weeks, with the weekly targets of intensity (percentage of the
+//! 1RM) and volume (reps of the strength work), the lifts to focus on, and the
+//! template with the structure of its days (see [`crate::templates`]):
+//!
+//! ```toml
+//! start = "2025-03-24"
+//! days = ["mon", "wed", "fri"]
+//!
+//! [[phases]]
+//! name = "Accumulation"
+//! weeks = 3
+//! intensity = [70, 72.5, 75]
+//! volume = [25, 25, 20]
+//! focus = ["back squat", "deadlift"]
+//!
+//! [[phases]]
+//! name = "Intensification"
+//! weeks = 2
+//! intensity = [82.5, 87.5]
+//! volume = [15]
+//! focus = ["back squat", "deadlift"]
+//! template = "strength+skill+metcon"
+//! stimulus = "gymnastics"
+//! ```
+//!
+//! The `Strength` section of the template gets the lift of the day at the targets of
+//! the week, and the `Metcon` section a workout of the generator (see
+//! [`crate::generator`]). The rest of the sections are kept as written.
+
+use std::path::Path;
+
+use chrono::{Duration, NaiveDate, Weekday};
+use serde::Deserialize;
+
+use crate::cycles::Session;
+use crate::date::{parse_iso, relative_weekday};
+use crate::error::WodError;
+use crate::generator::{random_workout, Constraints, Stimulus};
+use crate::movement::Movement;
+use crate::templates;
+use crate::weight::format_load;
+
+/// The template of the days when a phase doesn't have one.
+const DEFAULT_TEMPLATE: &str = "strength+metcon";
+
+/// A plan as written in the TOML file, the values are checked when converted.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PlanFile {
+    start: String,
+    days: Vec<String>,
+    seed: Option<u64>,
+    phases: Vec<PhaseFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PhaseFile {
+    name: String,
+    weeks: usize,
+    intensity: Vec<f64>,
+    volume: Vec<u32>,
+    #[serde(default)]
+    focus: Vec<String>,
+    template: Option<String>,
+    stimulus: Option<String>,
+    minutes: Option<u32>,
+}
+
+/// A plan of the training, see the [module documentation](self) for its format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plan {
+    /// The first day of the plan, the phases follow each other from it.
+    pub start: NaiveDate,
+    /// The days of the week to train.
+    pub days: Vec<Weekday>,
+    /// Seed of the generated workouts, the same seed gives the same plan. `None`
+    /// takes one from the clock.
+    pub seed: Option<u64>,
+    /// The phases, in order.
+    pub phases: Vec<Phase>,
+}
+
+/// A phase of a plan, i.e. accumulation or intensification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    /// The name of the phase.
+    pub name: String,
+    /// The number of weeks it lasts.
+    pub weeks: usize,
+    /// The intensity per week, a percentage of the 1RM. The last one is kept for the
+    /// weeks without a value.
+    pub intensity: Vec<f64>,
+    /// The reps of the strength work per week, the last one is kept for the weeks
+    /// without a value.
+    pub volume: Vec<u32>,
+    /// The lifts of the strength work, one per day in turns.
+    pub focus: Vec<Movement>,
+    /// The template of the days.
+    pub template: String,
+    /// The kind of effort of the generated metcons.
+    pub stimulus: Stimulus,
+    /// The length of the generated metcons in minutes.
+    pub minutes: u32,
+}
+
+impl Plan {
+    /// Reads a plan from its TOML content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::planner::Plan;
+    ///
+    /// let plan = Plan::parse(
+    ///     "start = \"2025-03-24\"\ndays = [\"mon\"]\n\n\
+    ///      [[phases]]\nname = \"Base\"\nweeks = 4\nintensity = [70]\nvolume = [25]\n",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(plan.phases[0].template, "strength+metcon");
+    /// assert!(Plan::parse("start = \"2025-03-24\"\ndays = [\"mon\"]\nphases = []\n").is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML isn't valid, or a value of the plan isn't: the
+    /// date, the days, the movements or the stimulus, a phase without weeks or
+    /// targets, or a plan without days or phases.
+    pub fn parse(content: &str) -> Result<Plan, WodError> {
+        let file: PlanFile = toml::from_str(content)?;
+        let start = parse_iso(&file.start)
+            .ok_or_else(|| format!("Invalid start: `{}`, expected YYYY-MM-DD", file.start))?;
+        let days = file
+            .days
+            .iter()
+            .map(|day| {
+                day.parse::<Weekday>()
+                    .map_err(|_| format!("Invalid day: `{}`, expected a weekday, i.e. mon", day))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if days.is_empty() {
+            return Err("The plan must have at least a day of training".into());
+        }
+        if file.phases.is_empty() {
+            return Err("The plan must have at least a phase".into());
+        }
+
+        let phases = file
+            .phases
+            .into_iter()
+            .map(|phase| {
+                if phase.weeks == 0 || phase.intensity.is_empty() || phase.volume.is_empty() {
+                    return Err(WodError::from(format!(
+                        "The phase `{}` must have weeks, intensity and volume",
+                        phase.name
+                    )));
+                }
+                let focus = phase
+                    .focus
+                    .iter()
+                    .map(|movement| movement.trim().parse())
+                    .collect::<Result<Vec<Movement>, _>>()?;
+                Ok(Phase {
+                    name: phase.name,
+                    weeks: phase.weeks,
+                    intensity: phase.intensity,
+                    volume: phase.volume,
+                    focus,
+                    template: phase
+                        .template
+                        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string()),
+                    stimulus: phase.stimulus.as_deref().unwrap_or("mixed").parse()?,
+                    minutes: phase.minutes.unwrap_or(12),
+                })
+            })
+            .collect::<Result<Vec<_>, WodError>>()?;
+
+        Ok(Plan {
+            start,
+            days,
+            seed: file.seed,
+            phases,
+        })
+    }
+}
+
+/// The reps per set of the strength work for an intensity, fewer the heavier.
+fn reps_per_set(intensity: f64) -> u32 {
+    match intensity {
+        i if i >= 90.0 => 1,
+        i if i >= 85.0 => 2,
+        i if i >= 80.0 => 3,
+        i if i >= 75.0 => 4,
+        _ => 5,
+    }
+}
+
+/// The target of a week of a phase, the last one for the weeks without a value.
+fn target<T: Copy>(targets: &[T], week: usize) -> T {
+    targets[week.min(targets.len() - 1)]
+}
+
+/// Generates the skeleton of every day of training of the plan, the lines of its
+/// wod file after a comment with the phase, the week and the targets.
+///
+/// The templates are looked up in `templates_dir` first, see [`templates::load`].
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use wod::planner::{skeleton, Plan};
+///
+/// let plan = Plan::parse(
+///     "start = \"2025-03-24\"\ndays = [\"mon\", \"thu\"]\nseed = 7\n\n\
+///      [[phases]]\nname = \"Base\"\nweeks = 2\nintensity = [70, 80]\nvolume = [25]\n\
+///      focus = [\"front squat\", \"deadlift\"]\n",
+/// )
+/// .unwrap();
+/// let days = skeleton(&plan, Path::new("no-templates")).unwrap();
+/// assert_eq!(days.len(), 4);
+/// assert_eq!(days[1].date.to_string(), "2025-03-27");
+/// assert_eq!(days[1].lines[0], "# Base, week 1: 70% for 25 reps");
+/// assert_eq!(days[1].lines[1], "wl 5x5 deadlift @70%||Strength");
+/// assert_eq!(days[2].lines[1], "wl 8x3 front squat @80%||Strength");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a template doesn't exist or a metcon can't be generated.
+pub fn skeleton(plan: &Plan, templates_dir: &Path) -> Result<Vec<Session>, WodError> {
+    let mut sessions = Vec::new();
+    let mut week_start = plan.start;
+    let mut day_number: u64 = 0;
+    for phase in &plan.phases {
+        let template = templates::load(&phase.template, templates_dir)?;
+        let mut dates: Vec<NaiveDate> = Vec::new();
+        for week in 0..phase.weeks {
+            dates.extend(plan.days.iter().map(|day| {
+                relative_weekday(week_start, *day, None) + Duration::weeks(week as i64)
+            }));
+        }
+        dates.sort();
+
+        for (i, date) in dates.into_iter().enumerate() {
+            let week = i / plan.days.len();
+            let (intensity, volume) = (target(&phase.intensity, week), target(&phase.volume, week));
+            let mut lines = vec![format!(
+                "# {}, week {}: {} for {} reps",
+                phase.name,
+                week + 1,
+                format_load(intensity, "%"),
+                volume
+            )];
+            for line in template.lines().filter(|line| !line.trim().is_empty()) {
+                let (workout, comments, name) = crate::parse_wodfile_line(line)?;
+                let section = name.as_deref().unwrap_or_default().to_lowercase();
+                let workout = match section.as_str() {
+                    "strength" if !phase.focus.is_empty() => {
+                        let lift = &phase.focus[i % phase.focus.len()];
+                        let reps = reps_per_set(intensity);
+                        let sets = ((volume as f64 / reps as f64).round() as u32).max(1);
+                        format!(
+                            "wl {}x{} {} @{}",
+                            sets,
+                            reps,
+                            lift.shorthand(),
+                            format_load(intensity, "%")
+                        )
+                    }
+                    "metcon" => {
+                        let constraints = Constraints {
+                            minutes: phase.minutes,
+                            stimulus: phase.stimulus,
+                            seed: plan.seed.map(|seed| seed.wrapping_add(day_number)),
+                            ..Default::default()
+                        };
+                        random_workout(&constraints)?.to_shorthand()
+                    }
+                    _ => workout.to_string(),
+                };
+                lines.push(format!(
+                    "{}|{}|{}",
+                    workout,
+                    comments.unwrap_or_default(),
+                    name.unwrap_or_default()
+                ));
+            }
+            sessions.push(Session { date, lines });
+            day_number += 1;
+        }
+        week_start += Duration::weeks(phase.weeks as i64);
+    }
+    Ok(sessions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_workout;
+
+    fn plan() -> Plan {
+        Plan::parse(
+            r#"
+            start = "2025-03-26"
+            days = ["mon", "wed", "fri"]
+            seed = 3
+
+            [[phases]]
+            name = "Accumulation"
+            weeks = 2
+            intensity = [70, 75]
+            volume = [25, 20]
+            focus = ["back squat", "shoulder press", "deadlift"]
+
+            [[phases]]
+            name = "Intensification"
+            weeks = 1
+            intensity = [90]
+            volume = [5]
+            focus = ["clean"]
+            template = "strength+skill+metcon"
+            stimulus = "cardio"
+            minutes = 20
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_skeleton() {
+        let days = skeleton(&plan(), Path::new("no-templates")).unwrap();
+        assert_eq!(days.len(), 9);
+        // The plan starts on wednesday, the monday goes to the end of the week
+        assert_eq!(days[0].date.to_string(), "2025-03-26");
+        assert_eq!(days[2].date.to_string(), "2025-03-31");
+        assert_eq!(days[2].lines[1], "wl 5x5 deadlift @70%||Strength");
+        assert_eq!(days[3].lines[0], "# Accumulation, week 2: 75% for 20 reps");
+        assert_eq!(days[3].lines[1], "wl 5x4 back squat @75%||Strength");
+        // The second phase starts after the weeks of the first one
+        assert_eq!(days[6].date.to_string(), "2025-04-09");
+        assert_eq!(days[6].lines[1], "wl 5x1 clean @90%||Strength");
+        assert_eq!(days[6].lines[2], "emom-10-alt 3 bar mu, 5 hspu||Skill");
+        for line in days.iter().flat_map(|day| &day.lines[1..]) {
+            let (workout, _, _) = crate::parse_wodfile_line(line).unwrap();
+            assert!(create_workout(workout, None, None).is_ok(), "{}", line);
+        }
+        // The same seed gives the same plan
+        assert_eq!(days, skeleton(&plan(), Path::new("no-templates")).unwrap());
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let phase = "[[phases]]\nname = \"Base\"\nweeks = 1\nintensity = [70]\nvolume = [25]\n";
+        let plan = |head: &str, phase: &str| Plan::parse(&format!("{}\n{}", head, phase));
+        let head = "start = \"2025-03-24\"\ndays = [\"mon\"]";
+        assert!(plan(head, phase).is_ok());
+        assert!(plan("start = \"24/03/2025\"\ndays = [\"mon\"]", phase).is_err());
+        assert!(plan("start = \"2025-03-24\"\ndays = [\"someday\"]", phase).is_err());
+        assert!(plan("start = \"2025-03-24\"\ndays = []", phase).is_err());
+        assert!(plan(head, &phase.replace("weeks = 1", "weeks = 0")).is_err());
+        assert!(plan(head, &phase.replace("volume = [25]", "volume = []")).is_err());
+        assert!(plan(head, &format!("{}focus = [\"squat\"]\n", phase)).is_err());
+        assert!(plan(head, &format!("{}stimulus = \"hard\"\n", phase)).is_err());
+        assert!(plan(head, &format!("{}level = \"rx\"\n", phase)).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::WodError;
+use crate::movement::Movement;
+use crate::pacing::Baseline;
+use crate::weight::Weight;
+
+/// Local store of personal records (1RM) per movement, saved as a JSON file.
+///
+/// The movements are stored by their display name, so aliases like "ohs" and
+/// "overhead squat" share the same record. The baseline scores used for the paces
+/// of the monostructural movements (see [`crate::pacing`]) are stored next to them.
+///
+/// # Examples
+///
+/// ```
+/// use wod::pr::PrStore;
+/// use wod::{Movement, Weight};
+///
+/// let mut store = PrStore::default();
+/// store.set(&Movement::BackSquat, "140kg".parse::<Weight>().unwrap());
+/// assert_eq!(store.get(&Movement::BackSquat), Some("140kg".parse::<Weight>().unwrap()));
+/// assert_eq!(store.get(&Movement::Snatch), None);
+/// ```
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrStore {
+    records: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    baselines: BTreeMap<String, String>,
+}
+
+impl PrStore {
+    /// Default location of the store, `<wod home>/prs.json`.
+    pub fn default_path() -> PathBuf {
+        crate::wod_home().join("prs.json")
+    }
+
+    /// Loads the store from a file, an empty store is returned if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, WodError> {
+        if !path.exists() {
+            return Ok(PrStore::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Saves the store to a file, creating the parent directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), WodError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Sets the personal record for a movement, replacing the previous one.
+    pub fn set(&mut self, movement: &Movement, weight: Weight) {
+        self.records
+            .insert(movement.to_string(), weight.to_string());
+    }
+
+    /// Gets the personal record for a movement, if any.
+    pub fn get(&self, movement: &Movement) -> Option<Weight> {
+        self.records
+            .get(&movement.to_string())
+            .and_then(|w| w.parse().ok())
+    }
+
+    /// All the personal records as (movement, weight), sorted by movement.
+    pub fn records(&self) -> BTreeMap<String, Weight> {
+        self.records
+            .iter()
+            .filter_map(|(movement, w)| Some((movement.clone(), w.parse().ok()?)))
+            .collect()
+    }
+
+    /// Sets the baseline score for a movement, i.e. a 2k row in 7:30.
+    pub fn set_baseline(&mut self, movement: &Movement, baseline: &Baseline) {
+        self.baselines
+            .insert(movement.to_string(), baseline.to_string());
+    }
+
+    /// All the baseline scores as (movement, baseline), sorted by movement.
+    pub fn baselines(&self) -> BTreeMap<String, Baseline> {
+        self.baselines
+            .iter()
+            .filter_map(|(movement, b)| Some((movement.clone(), b.parse().ok()?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_set_replaces_and_shares_aliases() {
+        let mut store = PrStore::default();
+        store.set(&"ohs".parse().unwrap(), "80kg".parse().unwrap());
+        store.set(&"overhead squat".parse().unwrap(), "85kg".parse().unwrap());
+        assert_eq!(store.records().len(), 1);
+        assert_eq!(
+            store.get(&Movement::OverheadSquat),
+            Some("85kg".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("prs.json");
+        assert_eq!(PrStore::load(&path).unwrap(), PrStore::default());
+
+        let mut store = PrStore::default();
+        store.set(&Movement::Snatch, "90kg".parse().unwrap());
+        store.set_baseline(&Movement::Row, &"2k 7:30".parse().unwrap());
+        store.save(&path).unwrap();
+        assert_eq!(PrStore::load(&path).unwrap(), store);
+
+        // The stores saved before the baselines can still be read
+        let store: PrStore = serde_json::from_str(r#"{"records": {}}"#).unwrap();
+        assert!(store.baselines().is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+//! QR codes of the links to the published pages, as SVG images to print and stick
+//! on the whiteboard of the gym.
+
+use std::fmt::Write;
+
+use qrcodegen::{QrCode, QrCodeEcc};
+
+use crate::error::WodError;
+
+/// Modules of white space around the code, the quiet zone of the standard.
+const BORDER: i32 = 4;
+
+/// The QR code of a text, i.e. the link of a page, as an SVG image.
+///
+/// The image has a module per unit of its `viewBox`, so it can be scaled to any
+/// size without losing quality.
+///
+/// # Examples
+///
+/// ```
+/// use wod::qr::svg;
+///
+/// let image = svg("https://wod.example.com/wod/wod-2025-03-24/").unwrap();
+/// assert!(image.starts_with("<?xml"));
+/// assert!(image.contains("viewBox=\"0 0 41 41\""));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the text is too long for a QR code.
+pub fn svg(text: &str) -> Result<String, WodError> {
+    let code = QrCode::encode_text(text, QrCodeEcc::Medium)
+        .map_err(|_| format!("The text is too long for a QR code: {}", text))?;
+    let size = code.size() + BORDER * 2;
+    let mut path = String::new();
+    for y in 0..code.size() {
+        for x in 0..code.size() {
+            if code.get_module(x, y) {
+                let _ = write!(path, "M{},{}h1v1h-1z", x + BORDER, y + BORDER);
+            }
+        }
+    }
+    Ok(format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" viewBox=\"0 0 {size} {size}\" stroke=\"none\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>\n\
+         <path d=\"{path}\" fill=\"#000000\"/>\n\
+         </svg>\n",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg() {
+        let image = svg("https://wod.example.com/").unwrap();
+        // Version 2 for this length, 25 modules and the border
+        assert!(image.contains("viewBox=\"0 0 33 33\""));
+        // The finder pattern of the top left corner starts after the border
+        assert!(image.contains("<path d=\"M4,4h1v1h-1z"));
+        assert!(svg(&"a".repeat(5000)).is_err());
+    }
+}
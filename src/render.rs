@@ -0,0 +1,684 @@
+//! Output formats for the workouts.
+//!
+//! The markdown of the `Display` implementation of [`Workout`] is the reference output,
+//! the other formats are derived from it so every workout type is supported by all of them.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde_json::json;
+
+use crate::movement::Movement;
+use crate::workout::Workout;
+use crate::Kind;
+
+/// Renders a workout in a given output format.
+pub trait Renderer {
+    fn render(&self, workout: &Workout) -> String;
+}
+
+/// A workout along with the renderer used to display it, see [`Workout::display_with`].
+pub struct Rendered<'a> {
+    pub(crate) workout: &'a Workout,
+    pub(crate) renderer: &'a dyn Renderer,
+}
+
+impl fmt::Display for Rendered<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.renderer.render(self.workout))
+    }
+}
+
+/// How the movements of a workout are laid out in the markdown, chosen per kind of
+/// workout (see [`crate::WorkoutType::kind`]) in the `layouts` of [`Markdown`], i.e.
+/// the weightlifting as a table while the metcons keep their lists.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use wod::create_workout;
+/// use wod::render::{Layout, Markdown, Renderer};
+/// use wod::Kind;
+///
+/// let markdown = Markdown {
+///     layouts: BTreeMap::from([(Kind::Weightlifting, Layout::Table)]),
+///     ..Default::default()
+/// };
+/// let workout = create_workout("wl 5x3 back squat @75%", None, None).unwrap();
+/// assert_eq!(
+///     markdown.render(&workout),
+///     "---\n\n**Weightlifting**\n\n| Reps | Movement | Load |\n| --- | --- | --- |\n\
+///      | 5x3 | Back Squat | 75% |\n\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Layout {
+    /// A line per movement, as items of a list in the metcons.
+    #[default]
+    Lines,
+    /// A table with the repetitions, the movements and the load of every line. The
+    /// weightlifting with parts in brackets keeps its lines.
+    Table,
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lines" => Ok(Layout::Lines),
+            "table" => Ok(Layout::Table),
+            _ => Err(format!(
+                "Invalid layout: `{}`, expected one of: lines, table",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Layout::Lines => write!(f, "lines"),
+            Layout::Table => write!(f, "table"),
+        }
+    }
+}
+
+/// How the comments of the workouts are written in the markdown.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::render::{CommentStyle, Markdown, Renderer};
+///
+/// let comments = Some("See [the standards](https://example.com)".to_string());
+/// let workout = create_workout("wl 5x5 back squat", comments, None).unwrap();
+/// assert!(workout.to_string().ends_with("Comments: *See \\[the standards\\](https://example.com)*\n\n"));
+///
+/// let markdown = Markdown { comments: CommentStyle::Quote, ..Default::default() };
+/// assert!(markdown.render(&workout).ends_with("> Comments: See [the standards](https://example.com)\n\n"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum CommentStyle {
+    /// In italics, with the characters of markdown escaped so they are shown as written.
+    #[default]
+    Emphasis,
+    /// As a blockquote, the markdown of the comments (links, lists, emphasis) is kept.
+    Quote,
+    /// As they are written, the markdown of the comments is kept.
+    Markdown,
+}
+
+impl FromStr for CommentStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "emphasis" => Ok(CommentStyle::Emphasis),
+            "quote" => Ok(CommentStyle::Quote),
+            "markdown" => Ok(CommentStyle::Markdown),
+            _ => Err(format!(
+                "Invalid comment style: `{}`, expected one of: emphasis, quote, markdown",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CommentStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommentStyle::Emphasis => write!(f, "emphasis"),
+            CommentStyle::Quote => write!(f, "quote"),
+            CommentStyle::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+/// Characters with a meaning in the inline markdown, escaped with a backslash.
+const MARKDOWN_CHARS: &str = "\\*_`[]<>";
+
+/// Escapes the characters of markdown, so "2 * 3" is shown as written in italics.
+pub(crate) fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKDOWN_CHARS.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Reverts [`escape_markdown`].
+pub(crate) fn unescape_markdown(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(&next) if c == '\\' && MARKDOWN_CHARS.contains(next) => {
+                unescaped.push(next);
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+/// Markdown for a Hugo blog post, the format used in the generated files. The
+/// `Display` of the workouts is the markdown by default, the files are written with
+/// the one of the configuration, see [`crate::config::Config::markdown`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Markdown {
+    /// The layout of the movements per kind of workout, the lines for the kinds missing.
+    pub layouts: BTreeMap<Kind, Layout>,
+    /// How the comments are written.
+    pub comments: CommentStyle,
+    /// Whether the media are written with the shortcodes of Hugo, or as a link and an
+    /// image in markdown, see [`crate::media::Media::markdown`].
+    pub shortcodes: bool,
+}
+
+impl Default for Markdown {
+    fn default() -> Self {
+        Markdown {
+            layouts: BTreeMap::new(),
+            comments: CommentStyle::default(),
+            shortcodes: true,
+        }
+    }
+}
+
+impl Markdown {
+    /// The layout of a kind of workout, the lines by default.
+    pub fn layout(&self, kind: Kind) -> Layout {
+        self.layouts.get(&kind).copied().unwrap_or_default()
+    }
+}
+
+/// Plain text, without markup, to share in messaging apps.
+pub struct Text;
+
+/// HTML fragment, wrapped in a `<section class="wod">`.
+pub struct Html;
+
+/// JSON object with the type, name, comments and the plain text of the workout,
+/// for scripting.
+#[cfg(feature = "serde")]
+pub struct Json;
+
+/// Markdown for an Obsidian note, with the movements linked to their notes
+/// (`[[Thruster]]`) and without the separator of the sections.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::render::{Obsidian, Renderer};
+///
+/// let workout = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
+/// assert_eq!(
+///     Obsidian.render(&workout),
+///     "**For Time**\n\n21-15-9\n\n- [[Pull Up]]\n\n- [[Thruster]] @ 43/30kg\n"
+/// );
+/// ```
+pub struct Obsidian;
+
+/// Markdown with the movements as task list items (`- [ ] 21 Pull Up`), for athletes
+/// ticking them off in the markdown apps. The items are written without blank lines
+/// between them, so they render as a single list.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::render::{Checklist, Renderer};
+///
+/// let workout = create_workout("5rd 10 pull up, 5 thruster @43/30kg", None, None).unwrap();
+/// assert_eq!(
+///     Checklist.render(&workout),
+///     "---\n\n**5 rounds for time**\n\n- [ ] 10 Pull Up\n- [ ] 5 Thruster @ 43/30kg\n"
+/// );
+/// ```
+pub struct Checklist;
+
+impl Renderer for Markdown {
+    fn render(&self, workout: &Workout) -> String {
+        workout.markdown_with(self)
+    }
+}
+
+impl Renderer for Text {
+    fn render(&self, workout: &Workout) -> String {
+        let mut text = String::new();
+        for line in workout.to_string().lines() {
+            let line = match Line::from(line) {
+                Line::Separator => continue,
+                Line::Blank => {
+                    if text.is_empty() || text.ends_with("\n\n") {
+                        continue;
+                    }
+                    String::new()
+                }
+                Line::Item(item) => format!("- {}", strip_emphasis(item)),
+                Line::Text(t) => strip_emphasis(t),
+            };
+            text.push_str(&line);
+            text.push('\n');
+        }
+        text.trim_end().to_string()
+    }
+}
+
+impl Renderer for Html {
+    fn render(&self, workout: &Workout) -> String {
+        let mut html = String::from("<section class=\"wod\">\n");
+        let mut in_list = false;
+        for line in workout.to_string().lines() {
+            let line = Line::from(line);
+            if in_list && !matches!(line, Line::Item(_) | Line::Blank) {
+                html.push_str("</ul>\n");
+                in_list = false;
+            }
+            match line {
+                Line::Separator | Line::Blank => {}
+                Line::Item(item) => {
+                    if !in_list {
+                        html.push_str("<ul>\n");
+                        in_list = true;
+                    }
+                    html.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+                }
+                Line::Text(t) => html.push_str(&format!("<p>{}</p>\n", inline_html(t))),
+            }
+        }
+        if in_list {
+            html.push_str("</ul>\n");
+        }
+        html.push_str("</section>");
+        html
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Renderer for Json {
+    fn render(&self, workout: &Workout) -> String {
+        let workout_type = workout.workout_type.to_string();
+        let value = json!({
+            "type": workout_type.split("\n\n").next().unwrap_or_default(),
+            "name": workout.name(),
+            "comments": workout.comments(),
+            "text": Text.render(workout),
+        });
+        serde_json::to_string_pretty(&value).unwrap()
+    }
+}
+
+impl Renderer for Obsidian {
+    fn render(&self, workout: &Workout) -> String {
+        // The longest names first, so "Strict Pull Up" is linked instead of "Pull Up"
+        let mut names: Vec<String> = workout
+            .movements()
+            .chain(
+                workout
+                    .scaled_versions()
+                    .iter()
+                    .flat_map(Workout::movements),
+            )
+            .filter(|m| **m != Movement::Rest)
+            .map(|m| m.to_string())
+            .collect();
+        names.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        names.dedup();
+
+        let mut note = String::new();
+        let markdown = workout.to_string();
+        for line in markdown.trim_start_matches("---").trim_start().lines() {
+            match Line::from(line) {
+                // The headers and the name of the workout
+                Line::Text(text) if text.starts_with('*') => note.push_str(text),
+                Line::Separator => {}
+                _ => note.push_str(&wiki_links(line, &names)),
+            }
+            note.push('\n');
+        }
+        format!("{}\n", note.trim_end())
+    }
+}
+
+impl Renderer for Checklist {
+    fn render(&self, workout: &Workout) -> String {
+        let markdown = workout.to_string();
+        let lines: Vec<Line> = markdown.lines().map(Line::from).collect();
+        let mut checklist = String::new();
+        for (i, line) in lines.iter().enumerate() {
+            match line {
+                Line::Item(item) => checklist.push_str(&format!("- [ ] {}", item)),
+                Line::Blank => {
+                    let between_items = matches!(lines.get(i.wrapping_sub(1)), Some(Line::Item(_)))
+                        && matches!(lines.get(i + 1), Some(Line::Item(_)));
+                    if between_items {
+                        continue;
+                    }
+                }
+                Line::Separator => checklist.push_str("---"),
+                Line::Text(text) => checklist.push_str(text),
+            }
+            checklist.push('\n');
+        }
+        format!("{}\n", checklist.trim_end())
+    }
+}
+
+/// Links the names found in a line as `[[name]]`, only whole words are linked.
+fn wiki_links(line: &str, names: &[String]) -> String {
+    let mut linked = String::new();
+    let mut rest = line;
+    let mut previous: Option<char> = None;
+    while let Some(c) = rest.chars().next() {
+        let boundary = !previous.is_some_and(char::is_alphanumeric);
+        let name = names.iter().find(|name| {
+            rest.starts_with(name.as_str())
+                && !rest[name.len()..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphanumeric)
+        });
+        match name {
+            Some(name) if boundary => {
+                linked.push_str(&format!("[[{}]]", name));
+                rest = &rest[name.len()..];
+                previous = name.chars().last();
+            }
+            _ => {
+                linked.push(c);
+                rest = &rest[c.len_utf8()..];
+                previous = Some(c);
+            }
+        }
+    }
+    linked
+}
+
+/// Kinds of line found in the markdown of a workout.
+enum Line<'a> {
+    Separator,
+    Blank,
+    Item(&'a str),
+    Text(&'a str),
+}
+
+impl<'a> From<&'a str> for Line<'a> {
+    fn from(line: &'a str) -> Self {
+        let line = line.trim_end();
+        if line == "---" {
+            Line::Separator
+        } else if line.is_empty() {
+            Line::Blank
+        } else if let Some(item) = line.strip_prefix("- ") {
+            Line::Item(item)
+        } else {
+            Line::Text(line)
+        }
+    }
+}
+
+/// The text of a line without the markers of emphasis, the escaped characters are
+/// kept as written.
+fn strip_emphasis(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.extend(chars.next()),
+            '*' => {}
+            _ => text.push(c),
+        }
+    }
+    text
+}
+
+/// Escapes the text and converts the `**bold**` and `*italic*` markers to HTML tags.
+fn inline_html(line: &str) -> String {
+    let escaped = line
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    let with_strong = replace_pairs(&escaped, "**", "strong");
+    replace_pairs(&with_strong, "*", "em")
+}
+
+fn replace_pairs(line: &str, marker: &str, tag: &str) -> String {
+    let parts: Vec<&str> = line.split(marker).collect();
+    if parts.len() < 3 {
+        return line.to_string();
+    }
+    let mut html = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            // Unpaired markers at the end are kept as they are
+            if i % 2 == 0 {
+                html.push_str(&format!("</{}>", tag));
+            } else if i == parts.len() - 1 {
+                html.push_str(marker);
+            } else {
+                html.push_str(&format!("<{}>", tag));
+            }
+        }
+        html.push_str(part);
+    }
+    html
+}
+
+/// Output formats available.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::render::Format;
+///
+/// let workout = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
+/// let format: Format = "text".parse().unwrap();
+/// assert_eq!(
+///     format.renderer().render(&workout),
+///     "For Time\n\n21-15-9\n\n- Pull Up\n\n- Thruster @ 43/30kg"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    #[default]
+    Md,
+    #[cfg(feature = "serde")]
+    Json,
+    Text,
+    Html,
+    Checklist,
+}
+
+impl Format {
+    /// The renderer for the format.
+    pub fn renderer(&self) -> Box<dyn Renderer> {
+        match self {
+            Format::Md => Box::new(Markdown::default()),
+            #[cfg(feature = "serde")]
+            Format::Json => Box::new(Json),
+            Format::Text => Box::new(Text),
+            Format::Html => Box::new(Html),
+            Format::Checklist => Box::new(Checklist),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "md" | "markdown" => Ok(Format::Md),
+            #[cfg(feature = "serde")]
+            "json" => Ok(Format::Json),
+            "text" | "txt" => Ok(Format::Text),
+            "html" => Ok(Format::Html),
+            "checklist" | "tasks" => Ok(Format::Checklist),
+            _ => Err(format!(
+                "Invalid format: `{}`, expected one of: md, json, text, html, checklist",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Md => write!(f, "md"),
+            #[cfg(feature = "serde")]
+            Format::Json => write!(f, "json"),
+            Format::Text => write!(f, "text"),
+            Format::Html => write!(f, "html"),
+            Format::Checklist => write!(f, "checklist"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    fn fran() -> Workout {
+        create_workout(
+            "ft 21-15-9 pull up, thruster @43/30kg",
+            Some("T.C. 10'".to_string()),
+            Some("Fran".to_string()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(Markdown::default().render(&fran()), fran().to_string());
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            Text.render(&fran()),
+            "Fran\n\nFor Time\n\n21-15-9\n\n- Pull Up\n\n- Thruster @ 43/30kg\n\nComments: T.C. 10'"
+        );
+    }
+
+    #[test]
+    fn test_obsidian() {
+        assert_eq!(
+            Obsidian.render(&fran()),
+            "*Fran*\n\n**For Time**\n\n21-15-9\n\n- [[Pull Up]]\n\n- [[Thruster]] @ 43/30kg\n\nComments: *T.C. 10'*\n"
+        );
+        let names = vec!["Strict Pull Up".to_string(), "Pull Up".to_string()];
+        assert_eq!(
+            wiki_links("10 Strict Pull Up, 5 Pull Up, Pull Ups", &names),
+            "10 [[Strict Pull Up]], 5 [[Pull Up]], Pull Ups"
+        );
+    }
+
+    #[test]
+    fn test_html() {
+        assert_eq!(
+            Html.render(&fran()),
+            "<section class=\"wod\">\n<p><em>Fran</em></p>\n<p><strong>For Time</strong></p>\n<p>21-15-9</p>\n<ul>\n<li>Pull Up</li>\n<li>Thruster @ 43/30kg</li>\n</ul>\n<p>Comments: <em>T.C. 10'</em></p>\n</section>"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json() {
+        let value: serde_json::Value = serde_json::from_str(&Json.render(&fran())).unwrap();
+        assert_eq!(value["type"], "For Time");
+        assert_eq!(value["name"], "Fran");
+        assert_eq!(value["comments"], "T.C. 10'");
+        assert!(value["text"].as_str().unwrap().contains("- Pull Up"));
+    }
+
+    #[test]
+    fn test_checklist() {
+        assert_eq!(
+            Checklist.render(&fran()),
+            "---\n\n*Fran*\n\n**For Time**\n\n21-15-9\n\n- [ ] Pull Up\n- [ ] Thruster @ 43/30kg\n\nComments: *T.C. 10'*\n"
+        );
+        let mut workout =
+            create_workout("ft 21-15-9 bar mu, thruster @43/30kg", None, None).unwrap();
+        workout.add_scaled(crate::scaling::Level::Beginner, &Default::default());
+        // The movements of the scaled versions can be ticked off too
+        let checklist = Checklist.render(&workout);
+        assert!(checklist.contains("- [ ] Bar Muscle Up\n- [ ] Thruster"));
+        assert!(!checklist.contains("\n- Bar"));
+        assert_eq!(checklist.matches("- [ ] ").count(), 4);
+    }
+
+    #[test]
+    fn test_inline_html() {
+        assert_eq!(inline_html("a < b"), "a &lt; b");
+        assert_eq!(
+            inline_html("**bold** and *it*"),
+            "<strong>bold</strong> and <em>it</em>"
+        );
+        assert_eq!(inline_html("2 * 3"), "2 * 3");
+    }
+
+    #[test]
+    fn test_layout_from_str() {
+        assert_eq!("Table".parse::<Layout>().unwrap(), Layout::Table);
+        assert!("grid".parse::<Layout>().is_err());
+        assert_eq!(Layout::Lines.to_string(), "lines");
+        // The lines for the kinds without a layout
+        assert_eq!(
+            Markdown::default().layout(Kind::Weightlifting),
+            Layout::Lines
+        );
+    }
+
+    #[test]
+    fn test_escape_markdown() {
+        for text in [
+            "2 * 3",
+            "[link](url) and `code`",
+            "a\\b",
+            "snake_case <br>",
+            "end\\",
+        ] {
+            assert_eq!(unescape_markdown(&escape_markdown(text)), text);
+        }
+        assert_eq!(escape_markdown("*a* [b]"), "\\*a\\* \\[b\\]");
+        assert_eq!(strip_emphasis("*2 \\* 3*"), "2 * 3");
+        assert_eq!(
+            "quote".parse::<CommentStyle>().unwrap(),
+            CommentStyle::Quote
+        );
+        assert!("bold".parse::<CommentStyle>().is_err());
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("md".parse::<Format>().unwrap(), Format::Md);
+        assert_eq!("html".parse::<Format>().unwrap(), Format::Html);
+        assert!("pdf".parse::<Format>().is_err());
+        assert_eq!(Format::Text.to_string(), "text");
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::i18n::{self, Message};
 use crate::rep_types::split_gender_unit;
 
 /// Represents calories for both men and women.
@@ -37,6 +38,7 @@ use crate::rep_types::split_gender_unit;
 /// assert_eq!(format!("{}", cals_mf), "100/80 calories".to_string());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cals {
     /// Calories for men.
     pub cals_man: u32,
@@ -47,7 +49,7 @@ pub struct Cals {
 impl FromStr for Cals {
     type Err = String;
     fn from_str(w: &str) -> Result<Self, Self::Err> {
-        let (cals_man, cals_woman, _unit) = split_gender_unit(w);
+        let (cals_man, cals_woman, _unit) = split_gender_unit(w)?;
         Ok(Cals {
             cals_man,
             cals_woman,
@@ -57,16 +59,12 @@ impl FromStr for Cals {
 
 impl fmt::Display for Cals {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.cals_woman != self.cals_man {
-            write!(
-                f,
-                "{man}/{woman} calories",
-                man = self.cals_man,
-                woman = self.cals_woman,
-            )
+        let cals = if self.cals_woman != self.cals_man {
+            format!("{}/{}", self.cals_man, self.cals_woman)
         } else {
-            write!(f, "{} calories", self.cals_man)
-        }
+            self.cals_man.to_string()
+        };
+        write!(f, "{}", i18n::text(Message::Calories, &[&cals]))
     }
 }
 
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::units::DistanceUnit;
+
 /// Represents a distance with a numeric value and a unit.
 ///
 /// # Examples
@@ -10,23 +12,24 @@ use std::str::FromStr;
 /// The `Distance` struct can be parsed from a string using the `FromStr` trait. The string should be in the format of a number followed by the unit.
 ///
 /// ```
+/// use wod::units::DistanceUnit;
 /// use wod::Distance;
 ///
 /// let distance1: Distance = "100m".parse().unwrap();
 /// assert_eq!(distance1.num, 100);
-/// assert_eq!(distance1.unit, "m".to_string());
+/// assert_eq!(distance1.unit, DistanceUnit::Meters);
 ///
 /// let distance2: Distance = "5k".parse().unwrap();
 /// assert_eq!(distance2.num, 5);
-/// assert_eq!(distance2.unit, "k".to_string());
+/// assert_eq!(distance2.unit, DistanceUnit::Kilometers);
 ///
 /// let distance3: Distance = "5K".parse().unwrap();
 /// assert_eq!(distance3.num, 5);
-/// assert_eq!(distance3.unit, "K".to_string());
+/// assert_eq!(distance3.unit, DistanceUnit::Kilometers);
 ///
 /// let distance4: Distance = "1mile".parse().unwrap();
 /// assert_eq!(distance4.num, 1);
-/// assert_eq!(distance4.unit, "mile".to_string());
+/// assert_eq!(distance4.unit, DistanceUnit::Miles);
 /// ```
 ///
 /// ## Display
@@ -39,14 +42,15 @@ use std::str::FromStr;
 /// assert_eq!(format!("{}", distance), "100m".to_string());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Distance {
     /// The numeric value of the distance.
     pub num: u32,
-    /// The unit of the distance as a string.(e.g., "m" for meters, "k" for kilometers, "mile" for miles).
-    pub unit: String,
+    /// The unit of the distance.
+    pub unit: DistanceUnit,
 }
 
-fn extract_distance(d: &str) -> (u32, String) {
+fn extract_distance(d: &str) -> Result<(u32, DistanceUnit), String> {
     let mut num = String::new();
     let mut unit = String::new();
 
@@ -58,13 +62,19 @@ fn extract_distance(d: &str) -> (u32, String) {
         }
     }
 
-    (num.parse().unwrap(), unit)
+    let num = num
+        .parse()
+        .map_err(|e| format!("Invalid Distance '{}': {}", d, e))?;
+    let unit = unit
+        .parse()
+        .map_err(|e| format!("Invalid Distance '{}': {}", d, e))?;
+    Ok((num, unit))
 }
 
 impl FromStr for Distance {
     type Err = String;
     fn from_str(d: &str) -> Result<Self, Self::Err> {
-        let (num, unit) = extract_distance(d);
+        let (num, unit) = extract_distance(d)?;
         Ok(Distance { num, unit })
     }
 }
@@ -75,6 +85,20 @@ impl fmt::Display for Distance {
     }
 }
 
+impl Distance {
+    /// The distance in meters.
+    ///
+    /// ```
+    /// use wod::Distance;
+    ///
+    /// assert_eq!("5k".parse::<Distance>().unwrap().meters(), 5000.0);
+    /// assert_eq!("400m".parse::<Distance>().unwrap().meters(), 400.0);
+    /// ```
+    pub fn meters(&self) -> f64 {
+        self.num as f64 * self.unit.meters()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,28 +109,28 @@ mod tests {
             "100m".parse::<Distance>().unwrap(),
             Distance {
                 num: 100,
-                unit: "m".to_string()
+                unit: DistanceUnit::Meters
             }
         );
         assert_eq!(
             "5k".parse::<Distance>().unwrap(),
             Distance {
                 num: 5,
-                unit: "k".to_string()
+                unit: DistanceUnit::Kilometers
             }
         );
         assert_eq!(
             "5K".parse::<Distance>().unwrap(),
             Distance {
                 num: 5,
-                unit: "K".to_string()
+                unit: DistanceUnit::Kilometers
             }
         );
         assert_eq!(
             "1mile".parse::<Distance>().unwrap(),
             Distance {
                 num: 1,
-                unit: "mile".to_string()
+                unit: DistanceUnit::Miles
             }
         );
     }
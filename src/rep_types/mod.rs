@@ -5,7 +5,28 @@ pub mod reps;
 pub mod rest_period;
 pub mod time;
 
-pub fn split_gender_unit(w: &str) -> (u32, u32, String) {
+/// Longest duration accepted by the time based types (times, rest periods, AMRAPs,
+/// EMOMs and Tabatas), a day in seconds.
+pub const MAX_SECONDS: u32 = 24 * 60 * 60;
+
+/// Checks a duration parsed from `s` isn't zero nor longer than [`MAX_SECONDS`],
+/// `unit_seconds` being the seconds in a unit of `value`.
+pub(crate) fn check_duration(
+    kind: &str,
+    s: &str,
+    value: u32,
+    unit_seconds: u32,
+) -> Result<u32, String> {
+    if value == 0 || value.saturating_mul(unit_seconds) > MAX_SECONDS {
+        return Err(format!(
+            "Invalid {}: `{}`, expected a duration between 1 second and 24 hours",
+            kind, s
+        ));
+    }
+    Ok(value)
+}
+
+pub fn split_gender_unit(w: &str) -> Result<(u32, u32, String), String> {
     let mut man = String::new();
     let mut unit = String::new();
     let mut woman = String::new();
@@ -33,7 +54,11 @@ pub fn split_gender_unit(w: &str) -> (u32, u32, String) {
     // copy the value from the man
     let woman = if is_man { man.clone() } else { woman };
 
-    (man.parse().unwrap(), woman.parse().unwrap(), unit)
+    let parse = |n: &str| {
+        n.parse::<u32>()
+            .map_err(|e| format!("Invalid number in '{}': {}", w, e))
+    };
+    Ok((parse(&man)?, parse(&woman)?, unit))
 }
 
 #[cfg(test)]
@@ -42,9 +67,23 @@ mod tests {
 
     #[test]
     fn test_split_gender_unit() {
-        assert_eq!(split_gender_unit("100cal"), (100, 100, "cal".to_string()));
-        assert_eq!(split_gender_unit("100/80cal"), (100, 80, "cal".to_string()));
-        assert_eq!(split_gender_unit("100"), (100, 100, "".to_string()));
-        assert_eq!(split_gender_unit("15/20"), (15, 20, "".to_string()));
+        assert_eq!(
+            split_gender_unit("100cal").unwrap(),
+            (100, 100, "cal".to_string())
+        );
+        assert_eq!(
+            split_gender_unit("100/80cal").unwrap(),
+            (100, 80, "cal".to_string())
+        );
+        assert_eq!(
+            split_gender_unit("100").unwrap(),
+            (100, 100, "".to_string())
+        );
+        assert_eq!(
+            split_gender_unit("15/20").unwrap(),
+            (15, 20, "".to_string())
+        );
+        assert!(split_gender_unit("cal").is_err());
+        assert!(split_gender_unit("99999999999").is_err());
     }
 }
@@ -1,6 +1,7 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::i18n::{self, Message};
 use crate::rep_types::{
     cals::Cals, distance::Distance, reps::Reps, rest_period::RestPeriod, time::Time,
 };
@@ -15,6 +16,7 @@ use crate::rep_types::{
 ///
 /// ```
 /// use wod::{RepType, Distance, Reps, Cals, Time};
+/// use wod::units::TimeUnit;
 ///
 /// let reps = "10".parse::<RepType>().unwrap();
 /// assert_eq!(reps, RepType::Reps(Reps{reps_man: 10, reps_woman: 10}));
@@ -23,11 +25,12 @@ use crate::rep_types::{
 /// let cals = "10cals".parse::<RepType>().unwrap();
 /// assert_eq!(cals, RepType::Cals(Cals{cals_man: 10, cals_woman: 10}));
 /// let secs = "90sec".parse::<RepType>().unwrap();
-/// assert_eq!(secs, RepType::Time(Time{ num: 90, unit: "sec".to_string() }));
+/// assert_eq!(secs, RepType::Time(Time{ num: 90, unit: TimeUnit::Seconds }));
 /// let max = "max".parse::<RepType>().unwrap();
 /// assert_eq!(max, RepType::Max);
 /// ```
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RepType {
     /// Default number of repetitions, e.g. 10 or whatever single number
     Reps(Reps),
@@ -49,24 +52,20 @@ impl FromStr for RepType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Check if is a rest period
         if s.starts_with('r') && (s.ends_with('m') || s.ends_with('s')) {
-            return Ok(RepType::RestPeriod(
-                s.parse::<RestPeriod>().expect("Invalid rest period"),
-            ));
+            return Ok(RepType::RestPeriod(s.parse::<RestPeriod>()?));
         }
         // Check if it's a distance, e.g. 100m, 5K
         if s.ends_with('m') || s.to_lowercase().ends_with('k') {
-            return Ok(RepType::Distance(
-                s.parse::<Distance>().expect("Invalid distance"),
-            ));
+            return Ok(RepType::Distance(s.parse::<Distance>()?));
         }
 
         // Check if it's a number followed by "cal"
         if s.contains("cal") {
-            return Ok(RepType::Cals(s.parse::<Cals>().expect("Invalid calories")));
+            return Ok(RepType::Cals(s.parse::<Cals>()?));
         }
 
         if s.ends_with("sec") || s.ends_with("min") {
-            return Ok(RepType::Time(s.parse::<Time>().expect("Invalid time")));
+            return Ok(RepType::Time(s.parse::<Time>()?));
         }
 
         if s == "max" {
@@ -74,14 +73,43 @@ impl FromStr for RepType {
         }
 
         // Check if it's a number, we haven't
-        Ok(RepType::Reps(
-            s.parse::<Reps>().expect("Invalid repetitions"),
-        ))
+        Ok(RepType::Reps(s.parse::<Reps>()?))
 
         // Err("Invalid rep type".to_string())
     }
 }
 
+impl RepType {
+    /// The shorthand notation of the repetitions, as accepted by `FromStr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::RepType;
+    ///
+    /// for rep in ["21", "21/15", "15/12cal", "400m", "5k", "90sec", "max", "r2m"] {
+    ///     assert_eq!(rep.parse::<RepType>().unwrap().shorthand(), rep);
+    /// }
+    /// ```
+    pub fn shorthand(&self) -> String {
+        fn man_woman(man: u32, woman: u32) -> String {
+            if man == woman {
+                man.to_string()
+            } else {
+                format!("{}/{}", man, woman)
+            }
+        }
+        match self {
+            RepType::Reps(reps) => man_woman(reps.reps_man, reps.reps_woman),
+            RepType::Cals(cals) => format!("{}cal", man_woman(cals.cals_man, cals.cals_woman)),
+            RepType::Distance(distance) => format!("{}{}", distance.num, distance.unit),
+            RepType::Time(time) => format!("{}{}", time.num, time.unit),
+            RepType::Max => "max".to_string(),
+            RepType::RestPeriod(rest) => format!("r{}{}", rest.duration, rest.unit.symbol()),
+        }
+    }
+}
+
 impl fmt::Display for RepType {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -89,7 +117,7 @@ impl fmt::Display for RepType {
             RepType::Cals(cals) => write!(formatter, "{}", cals),
             RepType::Distance(distance) => write!(formatter, "{}", distance),
             RepType::Time(time) => write!(formatter, "{}", time),
-            RepType::Max => write!(formatter, "Max reps of"),
+            RepType::Max => write!(formatter, "{}", i18n::text(Message::MaxRepsOf, &[])),
             RepType::RestPeriod(rest) => write!(formatter, "{}", rest),
         }
     }
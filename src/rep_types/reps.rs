@@ -37,6 +37,7 @@ use crate::rep_types::split_gender_unit;
 /// assert_eq!(format!("{}", cals_mf), "100/80 calories".to_string());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Reps {
     /// Calories for men.
     pub reps_man: u32,
@@ -47,7 +48,7 @@ pub struct Reps {
 impl FromStr for Reps {
     type Err = String;
     fn from_str(w: &str) -> Result<Self, Self::Err> {
-        let (reps_man, reps_woman, _unit) = split_gender_unit(w);
+        let (reps_man, reps_woman, _unit) = split_gender_unit(w)?;
         Ok(Reps {
             reps_man,
             reps_woman,
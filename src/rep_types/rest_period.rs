@@ -1,16 +1,21 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::i18n::{self, Message};
+use crate::rep_types::check_duration;
+use crate::units::TimeUnit;
+
 /// Represents a rest period with a specified duration and unit.
 ///
 /// # Examples
 /// ```
 /// use wod::RestPeriod;
+/// use wod::units::TimeUnit;
 ///
 /// let rest: RestPeriod = "r1m".parse().unwrap();
 ///
 /// assert_eq!(rest.duration, 1);
-/// assert_eq!(rest.unit, "m");
+/// assert_eq!(rest.unit, TimeUnit::Minutes);
 /// ```
 ///
 /// # Display
@@ -23,37 +28,36 @@ use std::str::FromStr;
 /// assert_eq!(format!("{}", rest), "Rest 1 minute");
 /// ```
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RestPeriod {
     /// The length of the rest period.
-    pub duration: u16,
-    /// The unit of measurement for the rest period (e.g., "s" for seconds, "m" for minutes).
-    pub unit: String,
+    pub duration: u32,
+    /// The unit of measurement for the rest period, written as "s" or "m".
+    pub unit: TimeUnit,
 }
 
 impl FromStr for RestPeriod {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut duration = String::new();
-        let mut unit = String::new();
-
-        for c in s.to_string().chars() {
-            if c.is_numeric() {
-                duration.push(c);
-            } else if c == 'r' {
-                continue;
-            } else {
-                unit.push(c);
-            }
-        }
+        // A rest period starts with "r", otherwise movements like "c2b" would be one
+        let Some(rest) = s.strip_prefix('r') else {
+            return Err(format!("Invalid RestPeriod format: '{}'", s));
+        };
+        let (duration, unit) =
+            rest.split_at(rest.find(|c: char| !c.is_numeric()).unwrap_or(rest.len()));
 
-        if duration.is_empty() || unit.is_empty() {
+        if duration.is_empty() || unit.is_empty() || !unit.chars().all(char::is_alphabetic) {
             return Err(format!("Invalid RestPeriod format: '{}'", s));
         }
 
+        let unit: TimeUnit = unit
+            .parse()
+            .map_err(|e| format!("Invalid RestPeriod '{}': {}", s, e))?;
         let duration_parsed = duration
-            .parse::<u16>()
+            .parse::<u32>()
             .map_err(|e| format!("Invalid duration in RestPeriod '{}': {}", s, e))?;
+        let duration_parsed = check_duration("RestPeriod", s, duration_parsed, unit.seconds())?;
 
         Ok(RestPeriod {
             duration: duration_parsed,
@@ -62,20 +66,17 @@ impl FromStr for RestPeriod {
     }
 }
 
+impl RestPeriod {
+    /// Returns the rest period expressed in seconds.
+    pub fn seconds(&self) -> u32 {
+        self.duration * self.unit.seconds()
+    }
+}
+
 impl fmt::Display for RestPeriod {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let unit = match self.unit.as_str() {
-            "m" => {
-                if self.duration != 1 {
-                    "minutes"
-                } else {
-                    "minute"
-                }
-            }
-            "s" => "seconds",
-            _ => "minutes",
-        };
-        write!(formatter, "Rest {} {}", self.duration, unit)
+        let duration = i18n::duration(self.duration, self.unit);
+        write!(formatter, "{}", i18n::text(Message::Rest, &[&duration]))
     }
 }
 
@@ -89,18 +90,29 @@ mod tests {
             RestPeriod::from_str("r1m").unwrap(),
             RestPeriod {
                 duration: 1,
-                unit: "m".to_string(),
+                unit: TimeUnit::Minutes,
             }
         );
         assert_eq!(
             RestPeriod::from_str("r90s").unwrap(),
             RestPeriod {
                 duration: 90,
-                unit: "s".to_string(),
+                unit: TimeUnit::Seconds,
             }
         );
     }
 
+    #[test]
+    fn test_rest_invalid() {
+        assert!(RestPeriod::from_str("c2b").is_err());
+        assert!(RestPeriod::from_str("r").is_err());
+        assert!(RestPeriod::from_str("r30").is_err());
+        assert!(RestPeriod::from_str("rm").is_err());
+        assert!(RestPeriod::from_str("r0s").is_err());
+        assert!(RestPeriod::from_str("r2h").is_err());
+        assert!(RestPeriod::from_str("r1441m").is_err());
+    }
+
     #[test]
     fn test_work_display() {
         assert_eq!(
@@ -108,7 +120,7 @@ mod tests {
                 "{}",
                 RestPeriod {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                 }
             ),
             "Rest 1 minute"
@@ -118,7 +130,7 @@ mod tests {
                 "{}",
                 RestPeriod {
                     duration: 2,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                 }
             ),
             "Rest 2 minutes"
@@ -128,7 +140,7 @@ mod tests {
                 "{}",
                 RestPeriod {
                     duration: 90,
-                    unit: "s".to_string(),
+                    unit: TimeUnit::Seconds,
                 }
             ),
             "Rest 90 seconds"
@@ -142,7 +154,7 @@ mod tests {
                 "{}",
                 RestPeriod {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                 }
             ),
             "Rest 1 minute"
@@ -152,7 +164,7 @@ mod tests {
                 "{}",
                 RestPeriod {
                     duration: 2,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                 }
             ),
             "Rest 2 minutes"
@@ -162,7 +174,7 @@ mod tests {
                 "{}",
                 RestPeriod {
                     duration: 90,
-                    unit: "s".to_string(),
+                    unit: TimeUnit::Seconds,
                 }
             ),
             "Rest 90 seconds"
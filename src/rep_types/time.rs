@@ -1,6 +1,9 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::rep_types::check_duration;
+use crate::units::TimeUnit;
+
 /// Represents a chunk of time for a movement to be held.
 ///
 /// # Examples
@@ -11,14 +14,18 @@ use std::str::FromStr;
 ///
 /// ```
 /// use wod::Time;
+/// use wod::units::TimeUnit;
 ///
 /// let time1: Time = "90sec".parse().unwrap();
 /// assert_eq!(time1.num, 90);
-/// assert_eq!(time1.unit, "sec".to_string());
+/// assert_eq!(time1.unit, TimeUnit::Seconds);
 ///
 /// let time2: Time = "1min".parse().unwrap();
 /// assert_eq!(time2.num, 1);
-/// assert_eq!(time2.unit, "min".to_string());
+/// assert_eq!(time2.unit, TimeUnit::Minutes);
+///
+/// assert_eq!("300sec".parse::<Time>().unwrap().seconds(), 300);
+/// assert!("0sec".parse::<Time>().is_err());
 /// ```
 ///
 /// ## Display
@@ -31,14 +38,15 @@ use std::str::FromStr;
 /// assert_eq!(format!("{}", time1), "90 sec".to_string());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Time {
     /// The numeric value of the time.
-    pub num: u8,
-    /// The unit of the time as a string.(e.g., "sec" for seconds, "min" for minutes).
-    pub unit: String,
+    pub num: u32,
+    /// The unit of the time, written as "sec" or "min".
+    pub unit: TimeUnit,
 }
 
-fn extract_time(d: &str) -> (u8, String) {
+fn extract_time(d: &str) -> Result<(u32, String), String> {
     let mut num = String::new();
     let mut unit = String::new();
 
@@ -50,17 +58,39 @@ fn extract_time(d: &str) -> (u8, String) {
         }
     }
 
-    (num.parse().unwrap(), unit)
+    let num = num
+        .parse()
+        .map_err(|e| format!("Invalid Time '{}': {}", d, e))?;
+    Ok((num, unit))
 }
 
 impl FromStr for Time {
     type Err = String;
     fn from_str(d: &str) -> Result<Self, Self::Err> {
-        let (num, unit) = extract_time(d);
+        let (num, unit) = extract_time(d)?;
+        // "s" and "m" are rest periods and distances
+        let unit = match unit.as_str() {
+            "sec" => TimeUnit::Seconds,
+            "min" => TimeUnit::Minutes,
+            _ => {
+                return Err(format!(
+                    "Invalid Time unit: `{}`, expected one of: sec, min",
+                    d
+                ))
+            }
+        };
+        let num = check_duration("Time", d, num, unit.seconds())?;
         Ok(Time { num, unit })
     }
 }
 
+impl Time {
+    /// Returns the time expressed in seconds.
+    pub fn seconds(&self) -> u32 {
+        self.num * self.unit.seconds()
+    }
+}
+
 impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} {}", self.num, self.unit)
@@ -77,16 +107,21 @@ mod tests {
             "90sec".parse::<Time>().unwrap(),
             Time {
                 num: 90,
-                unit: "sec".to_string()
+                unit: TimeUnit::Seconds
             }
         );
         assert_eq!(
             "1min".parse::<Time>().unwrap(),
             Time {
                 num: 1,
-                unit: "min".to_string()
+                unit: TimeUnit::Minutes
             }
         );
+        assert_eq!("300sec".parse::<Time>().unwrap().num, 300);
+        assert!("sec".parse::<Time>().is_err());
+        assert!("0min".parse::<Time>().is_err());
+        assert!("1441min".parse::<Time>().is_err());
+        assert!("90hours".parse::<Time>().is_err());
     }
 
     #[test]
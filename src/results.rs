@@ -0,0 +1,372 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::WodError;
+use crate::movement::Movement;
+use crate::pacing::parse_time;
+use crate::rm::{estimate_1rm, Formula};
+use crate::units::LoadUnit;
+use crate::weight::Weight;
+
+/// A result recorded for a workout.
+///
+/// # Examples
+///
+/// ```
+/// use wod::results::ResultEntry;
+///
+/// let entry = ResultEntry {
+///     date: "2025-03-21".to_string(),
+///     workout: "Fran".to_string(),
+///     score: "4:32".to_string(),
+///     notes: Some("rx".to_string()),
+/// };
+/// assert_eq!(format!("{}", entry), "2025-03-21  Fran  4:32  (rx)");
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ResultEntry {
+    /// Date of the workout, as found in the front matter of the file ("YYYY-MM-DD").
+    pub date: String,
+    /// Identifier of the workout, its name or the title of the file.
+    pub workout: String,
+    /// The score, i.e. "12:34", "5 rounds + 3", "100kg".
+    pub score: String,
+    /// Optional notes, i.e. "rx", "scaled".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+impl fmt::Display for ResultEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}  {}  {}", self.date, self.workout, self.score)?;
+        if let Some(notes) = &self.notes {
+            write!(f, "  ({})", notes)?;
+        }
+        Ok(())
+    }
+}
+
+impl ResultEntry {
+    /// Whether the result is for a movement: the workout is the movement itself
+    /// (i.e. logged with `--workout "back squat"`), or its name mentions it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::results::ResultEntry;
+    /// use wod::Movement;
+    ///
+    /// let entry = ResultEntry {
+    ///     date: "2025-03-21".to_string(),
+    ///     workout: "Heavy back squat".to_string(),
+    ///     score: "5x120kg".to_string(),
+    ///     notes: None,
+    /// };
+    /// assert!(entry.is_for(&Movement::BackSquat));
+    /// assert!(!entry.is_for(&Movement::FrontSquat));
+    ///
+    /// let entry = ResultEntry { workout: "hspu".to_string(), ..entry };
+    /// assert!(entry.is_for(&Movement::HandstandPushUp));
+    /// ```
+    pub fn is_for(&self, movement: &Movement) -> bool {
+        let workout = self.workout.trim().to_lowercase();
+        workout.parse::<Movement>().ok().as_ref() == Some(movement)
+            || workout.contains(&movement.name().to_lowercase())
+    }
+
+    /// The result as a point of a time series, see [`DataPoint`].
+    pub fn data_point(&self, formula: Formula) -> DataPoint {
+        let mut point = DataPoint {
+            date: self.date.clone(),
+            workout: self.workout.clone(),
+            score: self.score.clone(),
+            value: None,
+            unit: None,
+            one_rm: None,
+        };
+        if let Some(seconds) = parse_time(&self.score) {
+            point.value = Some(seconds as f64);
+            point.unit = Some("s".to_string());
+            return point;
+        }
+        // A load, alone or with the repetitions done with it: "140kg", "5x100kg", "100kg x 5"
+        let parts: Vec<&str> = self
+            .score
+            .split(|c: char| c.is_whitespace() || c == 'x' || c == '×')
+            .filter(|part| !part.is_empty())
+            .collect();
+        let load = parts
+            .iter()
+            .find_map(|part| part.parse::<Weight>().ok())
+            .filter(|weight| weight.unit != LoadUnit::Percent);
+        match load {
+            Some(load) => {
+                let reps = parts
+                    .iter()
+                    .find_map(|part| part.parse::<u32>().ok())
+                    .unwrap_or(1);
+                let one_rm = estimate_1rm(load.weight_man, reps, formula);
+                point.value = Some(load.weight_man);
+                point.unit = Some(load.unit.to_string());
+                point.one_rm = one_rm.is_finite().then(|| (one_rm * 10.0).round() / 10.0);
+            }
+            // The first number of the score otherwise, the rounds of "5 rounds + 3"
+            None => point.value = parts.iter().find_map(|part| part.parse::<f64>().ok()),
+        }
+        point
+    }
+}
+
+/// A result as a point of a time series, with the score as a number to plot it.
+///
+/// # Examples
+///
+/// ```
+/// use wod::results::ResultEntry;
+/// use wod::rm::Formula;
+///
+/// let entry = ResultEntry {
+///     date: "2025-03-21".to_string(),
+///     workout: "Back Squat".to_string(),
+///     score: "5x120kg".to_string(),
+///     notes: None,
+/// };
+/// let point = entry.data_point(Formula::Epley);
+/// assert_eq!(point.value, Some(120.0));
+/// assert_eq!(point.unit.as_deref(), Some("kg"));
+/// assert_eq!(point.one_rm, Some(140.0));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DataPoint {
+    /// Date of the result.
+    pub date: String,
+    /// The workout of the result.
+    pub workout: String,
+    /// The score as it was logged.
+    pub score: String,
+    /// The score as a number: the seconds of a time ("4:32"), the load lifted
+    /// ("5x100kg"), or the first number of the score otherwise ("5 rounds + 3").
+    pub value: Option<f64>,
+    /// The unit of the value, "s" for times or the unit of the load.
+    pub unit: Option<String>,
+    /// The 1RM estimated from a load lifted for a number of repetitions.
+    pub one_rm: Option<f64>,
+}
+
+/// Formats of the exported time series of results.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SeriesFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl FromStr for SeriesFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(SeriesFormat::Json),
+            "csv" => Ok(SeriesFormat::Csv),
+            _ => Err(format!(
+                "Invalid format: `{}`, expected one of: json, csv",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SeriesFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeriesFormat::Json => write!(f, "json"),
+            SeriesFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// Writes a time series of results as a JSON array or as CSV with a header, to plot
+/// it in the site.
+///
+/// # Examples
+///
+/// ```
+/// use wod::results::{export, ResultEntry, SeriesFormat};
+/// use wod::rm::Formula;
+///
+/// let entry = ResultEntry {
+///     date: "2025-03-21".to_string(),
+///     workout: "Fran".to_string(),
+///     score: "4:32".to_string(),
+///     notes: None,
+/// };
+/// let points = [entry.data_point(Formula::Epley)];
+/// assert_eq!(
+///     export(&points, SeriesFormat::Csv),
+///     "date,workout,score,value,unit,one_rm\n2025-03-21,Fran,4:32,272,s,\n"
+/// );
+/// ```
+pub fn export(points: &[DataPoint], format: SeriesFormat) -> String {
+    match format {
+        SeriesFormat::Json => serde_json::to_string_pretty(points).unwrap_or_default(),
+        SeriesFormat::Csv => {
+            let mut csv = String::from("date,workout,score,value,unit,one_rm\n");
+            let number = |n: Option<f64>| n.map(|n| n.to_string()).unwrap_or_default();
+            for point in points {
+                let fields = [
+                    csv_field(&point.date),
+                    csv_field(&point.workout),
+                    csv_field(&point.score),
+                    number(point.value),
+                    point.unit.clone().unwrap_or_default(),
+                    number(point.one_rm),
+                ];
+                csv.push_str(&fields.join(","));
+                csv.push('\n');
+            }
+            csv
+        }
+    }
+}
+
+/// A field of a CSV line, quoted if it contains a separator or quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Local store of results, saved as a JSON file.
+///
+/// # Examples
+///
+/// ```
+/// use wod::results::{ResultEntry, ResultsStore};
+///
+/// let mut store = ResultsStore::default();
+/// store.add(ResultEntry {
+///     date: "2025-03-21".to_string(),
+///     workout: "Fran".to_string(),
+///     score: "4:32".to_string(),
+///     notes: None,
+/// });
+/// assert_eq!(store.entries().len(), 1);
+/// ```
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultsStore {
+    entries: Vec<ResultEntry>,
+}
+
+impl ResultsStore {
+    /// Default location of the store, `<wod home>/results.json`.
+    pub fn default_path() -> PathBuf {
+        crate::wod_home().join("results.json")
+    }
+
+    /// Loads the store from a file, an empty store is returned if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, WodError> {
+        if !path.exists() {
+            return Ok(ResultsStore::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Saves the store to a file, creating the parent directory if needed.
+    pub fn save(&self, path: &Path) -> Result<(), WodError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Adds a new result, keeping the entries sorted by date.
+    /// A result for the same date and workout replaces the previous one.
+    pub fn add(&mut self, entry: ResultEntry) {
+        self.entries
+            .retain(|e| !(e.date == entry.date && e.workout == entry.workout));
+        self.entries.push(entry);
+        self.entries.sort_by(|a, b| a.date.cmp(&b.date));
+    }
+
+    /// All the results, sorted by date.
+    pub fn entries(&self) -> &[ResultEntry] {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(date: &str, workout: &str, score: &str) -> ResultEntry {
+        ResultEntry {
+            date: date.to_string(),
+            workout: workout.to_string(),
+            score: score.to_string(),
+            notes: None,
+        }
+    }
+
+    #[test]
+    fn test_add_sorted_and_replaced() {
+        let mut store = ResultsStore::default();
+        store.add(entry("2025-03-22", "Grace", "3:10"));
+        store.add(entry("2025-03-21", "Fran", "4:32"));
+        store.add(entry("2025-03-21", "Fran", "4:20"));
+        assert_eq!(
+            store.entries(),
+            &[
+                entry("2025-03-21", "Fran", "4:20"),
+                entry("2025-03-22", "Grace", "3:10")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_data_point() {
+        let point =
+            |score: &str| entry("2025-03-21", "Back Squat", score).data_point(Formula::Epley);
+        assert_eq!(point("4:32").value, Some(272.0));
+        assert_eq!(point("1:02:03").value, Some(3723.0));
+        assert_eq!(point("140kg").one_rm, Some(140.0));
+        assert_eq!(point("100kg x 6").one_rm, Some(120.0));
+        assert_eq!(point("3 x 225lb").unit.as_deref(), Some("lb"));
+        let rounds = point("5 rounds + 3");
+        assert_eq!(
+            (rounds.value, rounds.unit, rounds.one_rm),
+            (Some(5.0), None, None)
+        );
+    }
+
+    #[test]
+    fn test_export_csv_quotes() {
+        let mut result = entry("2025-03-21", "Back Squat, heavy", "140kg");
+        result.notes = Some("rx".to_string());
+        let csv = export(&[result.data_point(Formula::Epley)], SeriesFormat::Csv);
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("2025-03-21,\"Back Squat, heavy\",140kg,140,kg,140")
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("nested").join("results.json");
+        assert_eq!(ResultsStore::load(&path).unwrap(), ResultsStore::default());
+
+        let mut store = ResultsStore::default();
+        store.add(entry("2025-03-21", "Fran", "4:32"));
+        store.save(&path).unwrap();
+        assert_eq!(ResultsStore::load(&path).unwrap(), store);
+    }
+}
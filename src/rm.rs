@@ -27,25 +27,29 @@ use std::str::FromStr;
 /// assert_eq!(format!("{}", rm), "1rm".to_string());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RM {
     pub num: u8,
 }
 
-fn extract_rm(m: &str) -> u8 {
+fn extract_rm(m: &str) -> Result<u8, String> {
     let mut num = String::new();
     for c in m.chars() {
         if c.is_numeric() {
             num.push(c);
         }
     }
-    num.parse().unwrap()
+    num.parse()
+        .map_err(|e| format!("Invalid RM '{}': {}", m, e))
 }
 
 impl FromStr for RM {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(RM { num: extract_rm(s) })
+        Ok(RM {
+            num: extract_rm(s)?,
+        })
     }
 }
 
@@ -55,6 +59,112 @@ impl fmt::Display for RM {
     }
 }
 
+/// Formulas to estimate the 1RM from the weight lifted for a number of repetitions.
+///
+/// # Examples
+///
+/// ```
+/// use wod::rm::Formula;
+///
+/// let formula: Formula = "brzycki".parse().unwrap();
+/// assert_eq!(formula, Formula::Brzycki);
+/// assert_eq!(Formula::default(), Formula::Epley);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Formula {
+    /// `weight × (1 + reps / 30)`
+    #[default]
+    Epley,
+    /// `weight × 36 / (37 - reps)`
+    Brzycki,
+}
+
+impl Formula {
+    /// Fraction of the 1RM that can be lifted for a number of repetitions.
+    fn fraction(&self, reps: u32) -> f64 {
+        if reps <= 1 {
+            return 1.0;
+        }
+        let reps = reps as f64;
+        match self {
+            Formula::Epley => 1.0 / (1.0 + reps / 30.0),
+            // Brzycki isn't defined from 37 repetitions on
+            Formula::Brzycki => ((37.0 - reps) / 36.0).max(0.0),
+        }
+    }
+}
+
+impl FromStr for Formula {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "epley" => Ok(Formula::Epley),
+            "brzycki" => Ok(Formula::Brzycki),
+            _ => Err(format!(
+                "Invalid formula: `{}`, expected one of: epley, brzycki",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Formula {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Formula::Epley => write!(f, "epley"),
+            Formula::Brzycki => write!(f, "brzycki"),
+        }
+    }
+}
+
+/// Estimates the 1RM from the weight lifted for a number of repetitions.
+///
+/// # Examples
+///
+/// ```
+/// use wod::rm::{estimate_1rm, Formula};
+///
+/// assert_eq!(estimate_1rm(100.0, 1, Formula::Epley), 100.0);
+/// assert_eq!(estimate_1rm(100.0, 6, Formula::Epley), 120.0);
+/// assert_eq!(estimate_1rm(100.0, 10, Formula::Brzycki).round(), 133.0);
+/// ```
+pub fn estimate_1rm(weight: f64, reps: u32, formula: Formula) -> f64 {
+    let fraction = formula.fraction(reps);
+    if fraction == 0.0 {
+        return f64::INFINITY;
+    }
+    weight / fraction
+}
+
+/// The weight that can be lifted for a number of repetitions, the inverse of
+/// [`estimate_1rm`].
+///
+/// # Examples
+///
+/// ```
+/// use wod::rm::{weight_for_reps, Formula};
+///
+/// assert_eq!(weight_for_reps(120.0, 6, Formula::Epley), 100.0);
+/// ```
+pub fn weight_for_reps(one_rm: f64, reps: u32, formula: Formula) -> f64 {
+    one_rm * formula.fraction(reps)
+}
+
+/// The percentage of the 1RM that can be lifted for a number of repetitions.
+///
+/// # Examples
+///
+/// ```
+/// use wod::rm::{percent_for_reps, Formula};
+///
+/// assert_eq!(percent_for_reps(1, Formula::Brzycki), 100.0);
+/// assert_eq!(percent_for_reps(5, Formula::Brzycki).round(), 89.0);
+/// ```
+pub fn percent_for_reps(reps: u32, formula: Formula) -> f64 {
+    formula.fraction(reps) * 100.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,6 +174,19 @@ mod tests {
         assert_eq!(RM::from_str("1rm").unwrap(), RM { num: 1 });
         assert_eq!(RM::from_str("3rm").unwrap(), RM { num: 3 });
         assert_eq!(RM::from_str("5rm").unwrap(), RM { num: 5 });
+        assert!(RM::from_str("rm").is_err());
+    }
+
+    #[test]
+    fn test_estimate_1rm() {
+        for formula in [Formula::Epley, Formula::Brzycki] {
+            let one_rm = estimate_1rm(100.0, 5, formula);
+            assert!(one_rm > 110.0 && one_rm < 120.0, "{}: {}", formula, one_rm);
+            assert!((weight_for_reps(one_rm, 5, formula) - 100.0).abs() < 1e-9);
+        }
+        assert_eq!(estimate_1rm(100.0, 0, Formula::Epley), 100.0);
+        assert!(estimate_1rm(20.0, 40, Formula::Brzycki).is_infinite());
+        assert!("wendler".parse::<Formula>().is_err());
     }
 
     #[test]
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents a "Rate of Perceived Exertion", the intensity of a set prescribed by how
+/// hard it feels instead of a load, from 1 to 10. It can be a range, "rpe7-8".
+///
+/// # Examples
+///
+/// ## Creating a "Rpe" instance
+///
+/// It's parsed from the shorthand, "rpe8", or as it's rendered, "RPE 8".
+///
+/// ```
+/// use wod::rpe::Rpe;
+///
+/// let rpe: Rpe = "rpe8".parse().unwrap();
+/// assert_eq!(rpe, Rpe { low: 8.0, high: 8.0 });
+/// assert_eq!("RPE 7-8".parse::<Rpe>().unwrap(), Rpe { low: 7.0, high: 8.0 });
+/// assert!("rpe11".parse::<Rpe>().is_err());
+/// ```
+///
+/// ## Displaying a "Rpe" instance
+///
+/// ```
+/// use wod::rpe::Rpe;
+///
+/// let rpe: Rpe = "rpe7.5-8".parse().unwrap();
+/// assert_eq!(rpe.to_string(), "RPE 7.5-8");
+/// assert_eq!(rpe.shorthand(), "rpe7.5-8");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rpe {
+    /// Lower end of the range, the same as `high` for a single value.
+    pub low: f64,
+    /// Upper end of the range.
+    pub high: f64,
+}
+
+fn extract_rpe(s: &str) -> Result<(f64, f64), String> {
+    let error = |reason: &str| format!("Invalid RPE '{}': {}", s, reason);
+    let value = s
+        .get(..3)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("rpe"))
+        .map(|_| s[3..].trim_start())
+        .ok_or_else(|| error("expected it to start with `rpe`"))?;
+    let (low, high) = value.split_once('-').unwrap_or((value, value));
+    let parse = |n: &str| match n.parse::<f64>() {
+        Ok(n) if (1.0..=10.0).contains(&n) => Ok(n),
+        _ => Err(error("expected a value from 1 to 10")),
+    };
+    let (low, high) = (parse(low)?, parse(high)?);
+    if low > high {
+        return Err(error("the range goes from the lowest to the highest"));
+    }
+    Ok((low, high))
+}
+
+impl FromStr for Rpe {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (low, high) = extract_rpe(s)?;
+        Ok(Rpe { low, high })
+    }
+}
+
+impl fmt::Display for Rpe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RPE {}", self.range())
+    }
+}
+
+impl Rpe {
+    /// The value or range, "8" or "7-8".
+    fn range(&self) -> String {
+        if self.low == self.high {
+            self.low.to_string()
+        } else {
+            format!("{}-{}", self.low, self.high)
+        }
+    }
+
+    /// The RPE as written in the shorthand, "rpe8".
+    pub fn shorthand(&self) -> String {
+        format!("rpe{}", self.range())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpe() {
+        assert_eq!(
+            "RPE8".parse::<Rpe>().unwrap(),
+            Rpe {
+                low: 8.0,
+                high: 8.0
+            }
+        );
+        assert_eq!("rpe7-8".parse::<Rpe>().unwrap().shorthand(), "rpe7-8");
+        for invalid in ["8", "rpe", "rpe0", "rpe8-7", "rpe 7-11", "rm8"] {
+            assert!(invalid.parse::<Rpe>().is_err(), "{}", invalid);
+        }
+    }
+}
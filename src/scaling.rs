@@ -0,0 +1,477 @@
+//! Scaled versions of the workouts, for the athletes that can't do them as prescribed.
+//!
+//! A scaled workout keeps the structure of the original one, with the loads reduced
+//! to a percentage of the Rx load (see [`Scaling`]) and the harder movements replaced
+//! following the substitution table of [`substitute`].
+//!
+//! The workouts can also be personalized for an athlete with restrictions, i.e. an
+//! injured shoulder, replacing the movements they rule out (see [`AthleteProfile`]).
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::generator;
+use crate::movement::Movement;
+use crate::units::LoadUnit;
+use crate::weight::Weight;
+
+/// Levels a workout can be scaled to.
+///
+/// # Examples
+///
+/// ```
+/// use wod::scaling::Level;
+///
+/// let level: Level = "beginner".parse().unwrap();
+/// assert_eq!(level, Level::Beginner);
+/// assert_eq!(Level::Intermediate.to_string(), "intermediate");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Level {
+    Intermediate,
+    Beginner,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "intermediate" => Ok(Level::Intermediate),
+            "beginner" => Ok(Level::Beginner),
+            _ => Err(format!(
+                "Invalid level: `{}`, expected one of: intermediate, beginner",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Intermediate => write!(f, "intermediate"),
+            Level::Beginner => write!(f, "beginner"),
+        }
+    }
+}
+
+/// A kind of movement an athlete can't do, i.e. because of an injury.
+///
+/// # Examples
+///
+/// ```
+/// use wod::scaling::Restriction;
+///
+/// assert_eq!("no overhead".parse::<Restriction>().unwrap(), Restriction::Overhead);
+/// assert_eq!("impact".parse::<Restriction>().unwrap(), Restriction::Impact);
+/// assert_eq!(Restriction::Impact.to_string(), "no impact");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum Restriction {
+    /// Nothing over the head: jerks, snatches, presses, wall balls or handstands.
+    #[cfg_attr(feature = "serde", serde(rename = "no overhead"))]
+    Overhead,
+    /// No jumping or running.
+    #[cfg_attr(feature = "serde", serde(rename = "no impact"))]
+    Impact,
+}
+
+impl FromStr for Restriction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('-', " ");
+        match name.strip_prefix("no ").unwrap_or(&name) {
+            "overhead" => Ok(Restriction::Overhead),
+            "impact" => Ok(Restriction::Impact),
+            _ => Err(format!(
+                "Invalid restriction: `{}`, expected one of: no overhead, no impact",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Restriction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Restriction::Overhead => write!(f, "no overhead"),
+            Restriction::Impact => write!(f, "no impact"),
+        }
+    }
+}
+
+/// The restrictions of an athlete, the `[athlete]` table of the configuration file.
+///
+/// # Examples
+///
+/// ```
+/// use wod::config::Config;
+/// use wod::scaling::Restriction;
+/// use wod::Movement;
+///
+/// let config: Config = toml::from_str("[athlete]\nrestrictions = [\"no overhead\"]").unwrap();
+/// assert_eq!(config.athlete.restrictions, vec![Restriction::Overhead]);
+/// assert_eq!(config.athlete.substitute(&Movement::Thruster), Movement::FrontSquat);
+/// assert_eq!(config.athlete.substitute(&Movement::Run), Movement::Run);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct AthleteProfile {
+    /// The kinds of movements the athlete can't do.
+    pub restrictions: Vec<Restriction>,
+}
+
+impl AthleteProfile {
+    /// The movement the athlete does instead of another one, the same movement if
+    /// none of the restrictions rule it out. See [`substitute_restricted`].
+    pub fn substitute(&self, movement: &Movement) -> Movement {
+        self.restrictions
+            .iter()
+            .fold(movement.clone(), |movement, restriction| {
+                substitute_restricted(&movement, *restriction)
+            })
+    }
+}
+
+/// How hard a day of programming is, written as the `level` of the front matter so
+/// the site can be filtered by it.
+///
+/// # Examples
+///
+/// ```
+/// use wod::scaling::Difficulty;
+///
+/// let difficulty: Difficulty = "rx".parse().unwrap();
+/// assert!(difficulty > Difficulty::Intermediate);
+/// assert_eq!(Difficulty::Beginner.to_string(), "beginner");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Rx,
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "beginner" => Ok(Difficulty::Beginner),
+            "intermediate" => Ok(Difficulty::Intermediate),
+            "rx" => Ok(Difficulty::Rx),
+            _ => Err(format!(
+                "Invalid difficulty: `{}`, expected one of: beginner, intermediate, rx",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difficulty::Beginner => write!(f, "beginner"),
+            Difficulty::Intermediate => write!(f, "intermediate"),
+            Difficulty::Rx => write!(f, "rx"),
+        }
+    }
+}
+
+/// How the workouts are scaled, the `[scaling]` table of the configuration file.
+///
+/// # Examples
+///
+/// ```
+/// use wod::config::Config;
+/// use wod::scaling::Level;
+///
+/// let config: Config = toml::from_str("[scaling]\nbeginner = 40\nlevels = [\"beginner\"]").unwrap();
+/// assert_eq!(config.scaling.percent(Level::Beginner), 40.0);
+/// assert_eq!(config.scaling.percent(Level::Intermediate), 70.0);
+/// assert_eq!(config.scaling.levels, vec![Level::Beginner]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Scaling {
+    /// Percentage of the Rx load for the intermediate level.
+    pub intermediate: f64,
+    /// Percentage of the Rx load for the beginner level.
+    pub beginner: f64,
+    /// Levels rendered under every workout added to a file, none by default.
+    pub levels: Vec<Level>,
+}
+
+impl Default for Scaling {
+    fn default() -> Self {
+        Scaling {
+            intermediate: 70.0,
+            beginner: 50.0,
+            levels: Vec::new(),
+        }
+    }
+}
+
+impl Scaling {
+    /// Percentage of the Rx load used for a level.
+    pub fn percent(&self, level: Level) -> f64 {
+        match level {
+            Level::Intermediate => self.intermediate,
+            Level::Beginner => self.beginner,
+        }
+    }
+
+    /// The difficulty of a movement done with a load: Rx from the usual Rx load of its
+    /// equipment, intermediate from the percentage of the intermediate level, and
+    /// beginner below it. Movements without a load are rated by the skill they need,
+    /// they are Rx when they are replaced at the intermediate level (see [`substitute`])
+    /// and intermediate when they are replaced for beginners.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::scaling::{Difficulty, Scaling};
+    /// use wod::Movement;
+    ///
+    /// let scaling = Scaling::default();
+    /// let load = "61/43kg".parse().ok();
+    /// assert_eq!(scaling.difficulty(&Movement::Thruster, load.as_ref()), Difficulty::Rx);
+    /// let load = "35/25kg".parse().ok();
+    /// assert_eq!(scaling.difficulty(&Movement::Thruster, load.as_ref()), Difficulty::Intermediate);
+    /// assert_eq!(scaling.difficulty(&Movement::BarMuscleUp, None), Difficulty::Rx);
+    /// assert_eq!(scaling.difficulty(&Movement::BoxJumpOver, None), Difficulty::Intermediate);
+    /// ```
+    pub fn difficulty(&self, movement: &Movement, load: Option<&Weight>) -> Difficulty {
+        let skill = if substitute(movement, Level::Intermediate) != *movement {
+            Difficulty::Rx
+        } else if substitute(movement, Level::Beginner) != *movement {
+            Difficulty::Intermediate
+        } else {
+            Difficulty::Beginner
+        };
+
+        let kilograms = |weight: &Weight| Some(weight.to_unit(LoadUnit::Kilograms)?.weight_man);
+        let rx = movement
+            .equipment()
+            .and_then(generator::load)
+            .and_then(|rx| kilograms(&rx.parse().ok()?));
+        let load = match (load.and_then(kilograms), rx) {
+            (Some(load), Some(rx)) if load >= rx => Difficulty::Rx,
+            (Some(load), Some(rx)) if load >= rx * self.intermediate / 100.0 => {
+                Difficulty::Intermediate
+            }
+            _ => Difficulty::Beginner,
+        };
+        skill.max(load)
+    }
+
+    /// Reduces a load to the percentage of a level, rounded to the closest whole
+    /// number. Percentages of the 1RM are kept, they already depend on the athlete.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::scaling::{Level, Scaling};
+    ///
+    /// let scaling = Scaling::default();
+    /// let load = scaling.scale_load(&"43/30kg".parse().unwrap(), Level::Intermediate);
+    /// assert_eq!(load.to_string(), "30/21kg");
+    /// let load = scaling.scale_load(&"80%".parse().unwrap(), Level::Beginner);
+    /// assert_eq!(load.to_string(), "80%");
+    /// ```
+    pub fn scale_load(&self, weight: &Weight, level: Level) -> Weight {
+        if weight.unit == LoadUnit::Percent {
+            return weight.clone();
+        }
+        let scale = |w: f64| (w * self.percent(level) / 100.0).round();
+        Weight {
+            weight_man: scale(weight.weight_man),
+            weight_woman: scale(weight.weight_woman),
+            unit: weight.unit,
+        }
+    }
+}
+
+/// The movement done instead of another one at a level, the same movement if it
+/// doesn't need to be replaced.
+///
+/// # Examples
+///
+/// ```
+/// use wod::scaling::{substitute, Level};
+/// use wod::Movement;
+///
+/// assert_eq!(substitute(&Movement::BarMuscleUp, Level::Intermediate), Movement::ChestToBar);
+/// assert_eq!(substitute(&Movement::BarMuscleUp, Level::Beginner), Movement::PullUp);
+/// assert_eq!(substitute(&Movement::Thruster, Level::Beginner), Movement::Thruster);
+/// ```
+pub fn substitute(movement: &Movement, level: Level) -> Movement {
+    use Movement::*;
+
+    match (level, movement) {
+        (Level::Intermediate, RingMuscleUp | MuscleUp) => BarMuscleUp,
+        (Level::Intermediate, BarMuscleUp) => ChestToBar,
+        (Level::Intermediate, LeglessRopeClimb) => RopeClimb,
+        (Level::Intermediate, StrictHandstandPushUp) => HandstandPushUp,
+        (Level::Intermediate, HandstandWalk) => WallWalk,
+        (Level::Intermediate, ToesToBar) => KneesToElbows,
+        (Level::Beginner, RingMuscleUp | MuscleUp | BarMuscleUp | ChestToBar) => PullUp,
+        (Level::Beginner, RopeClimb | LeglessRopeClimb) => PullUp,
+        (Level::Beginner, HandstandPushUp | StrictHandstandPushUp) => PushUp,
+        (Level::Beginner, HandstandWalk | HandstandHold) => WallWalk,
+        (Level::Beginner, ToesToBar | KneesToElbows | GHD | VUp) => SitUp,
+        (Level::Beginner, PistolSquat) => AirSquat,
+        (Level::Beginner, BoxJumpOver) => BoxJump,
+        (Level::Beginner, BurpeeBoxJumpOver) => BurpeeBoxJump,
+        (_, movement) => movement.clone(),
+    }
+}
+
+/// The movement done instead of another one that a restriction rules out, keeping
+/// the load and the repetitions, the same movement if it's allowed.
+///
+/// # Examples
+///
+/// ```
+/// use wod::scaling::{substitute_restricted, Restriction};
+/// use wod::Movement;
+///
+/// assert_eq!(substitute_restricted(&Movement::PushJerk, Restriction::Overhead), Movement::BenchPress);
+/// assert_eq!(substitute_restricted(&Movement::Run, Restriction::Impact), Movement::Row);
+/// assert_eq!(substitute_restricted(&Movement::Run, Restriction::Overhead), Movement::Run);
+/// ```
+pub fn substitute_restricted(movement: &Movement, restriction: Restriction) -> Movement {
+    use Movement::*;
+
+    match (restriction, movement) {
+        (Restriction::Overhead, Thruster | OverheadSquat | SnatchBalance) => FrontSquat,
+        (Restriction::Overhead, ShoulderPress | PushPress | PushJerk | SplitJerk) => BenchPress,
+        (Restriction::Overhead, Snatch | CleanAndJerk) => Clean,
+        (Restriction::Overhead, PowerSnatch | MuscleSnatch | PowerCleanAndJerk) => PowerClean,
+        (Restriction::Overhead, HangSnatch) => HangClean,
+        (Restriction::Overhead, HangPowerSnatch) => HangPowerClean,
+        (Restriction::Overhead, OverheadWalkingLunge) => FrontRackLunge,
+        (Restriction::Overhead, WallBall | TurkishGetUp) => GobletSquat,
+        (Restriction::Overhead, DumbbellSnatch | DumbbellCleanAndJerk | DevilPress) => {
+            DumbbellClean
+        }
+        (Restriction::Overhead, DumbbellHangCleanAndJerk) => DumbbellHangClean,
+        (
+            Restriction::Overhead,
+            HandstandPushUp | StrictHandstandPushUp | HandstandWalk | HandstandHold | WallWalk,
+        ) => PushUp,
+        (Restriction::Impact, Run) => Row,
+        (Restriction::Impact, BoxJump | BoxJumpOver | DoubleUnder) => AirSquat,
+        (
+            Restriction::Impact,
+            Burpee | BurpeeBoxJump | BurpeeBoxJumpOver | BurpeeOverTheBar | BurpeeToTarget,
+        ) => PushUp,
+        (Restriction::Impact, BurpeePullUp) => PullUp,
+        (_, movement) => movement.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty() {
+        let scaling = Scaling::default();
+        let weight = |s: &str| s.parse::<Weight>().unwrap();
+        assert_eq!(
+            scaling.difficulty(&Movement::Thruster, Some(&weight("95/65lb"))),
+            Difficulty::Rx
+        );
+        assert_eq!(
+            scaling.difficulty(&Movement::Thruster, Some(&weight("20/15kg"))),
+            Difficulty::Beginner
+        );
+        // Percentages can't be compared with the Rx load
+        assert_eq!(
+            scaling.difficulty(&Movement::Thruster, Some(&weight("85%"))),
+            Difficulty::Beginner
+        );
+        assert_eq!(
+            scaling.difficulty(&Movement::AirSquat, None),
+            Difficulty::Beginner
+        );
+        assert!("elite".parse::<Difficulty>().is_err());
+    }
+
+    #[test]
+    fn test_level_from_str() {
+        assert_eq!(
+            "Intermediate".parse::<Level>().unwrap(),
+            Level::Intermediate
+        );
+        assert!("rx".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn test_scale_load() {
+        let scaling = Scaling {
+            beginner: 40.0,
+            ..Default::default()
+        };
+        let load = scaling.scale_load(&"24/16kg".parse().unwrap(), Level::Beginner);
+        assert_eq!(load.to_string(), "10/6kg");
+    }
+
+    #[test]
+    fn test_substitute() {
+        assert_eq!(
+            substitute(&Movement::ToesToBar, Level::Intermediate),
+            Movement::KneesToElbows
+        );
+        assert_eq!(
+            substitute(&Movement::ToesToBar, Level::Beginner),
+            Movement::SitUp
+        );
+        assert_eq!(
+            substitute(&Movement::PullUp, Level::Intermediate),
+            Movement::PullUp
+        );
+    }
+
+    #[test]
+    fn test_athlete_profile() {
+        let profile = AthleteProfile {
+            restrictions: vec![Restriction::Overhead, Restriction::Impact],
+        };
+        assert_eq!(profile.substitute(&Movement::Snatch), Movement::Clean);
+        assert_eq!(
+            profile.substitute(&Movement::BurpeeBoxJump),
+            Movement::PushUp
+        );
+        assert_eq!(profile.substitute(&Movement::PullUp), Movement::PullUp);
+        assert_eq!(
+            AthleteProfile::default().substitute(&Movement::Thruster),
+            Movement::Thruster
+        );
+        assert!("no running".parse::<Restriction>().is_err());
+        #[cfg(feature = "cli")]
+        assert!(toml::from_str::<AthleteProfile>("restrictions = [\"overhead\"]").is_err());
+    }
+}
@@ -0,0 +1,319 @@
+//! A small HTTP server to use the parser as a service, i.e. from the internal tools
+//! of a gym, without shelling out to the binary.
+//!
+//! The endpoints available:
+//!
+//! - `POST /parse`: the workout in the body, returns the JSON of the parsed workout,
+//!   or the error with the position of the offending token and status 400.
+//! - `POST /render?format=html`: the workout in the body, returns it rendered in the
+//!   format (`md` by default, see [`Format`]).
+//! - `GET /movements`: the JSON list of movements, with their aliases and videos.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::movement::Movement;
+use crate::render::Format;
+use crate::workout::create_workout;
+
+/// Largest body accepted, the workouts are a line of text.
+const MAX_BODY: usize = 64 * 1024;
+/// Largest request line and headers accepted, altogether.
+const MAX_HEAD: usize = 8 * 1024;
+/// Time a connection waits for the client to send or receive, so a client that
+/// stalls doesn't keep its thread forever.
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// Connections handled at the same time, the rest wait to be accepted.
+const WORKERS: usize = 8;
+
+/// Response to a request.
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: impl Into<String>) -> Self {
+        Response {
+            status,
+            content_type,
+            body: body.into(),
+        }
+    }
+
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Response::new(status, "text/plain; charset=utf-8", body)
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            431 => "Request Header Fields Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+fn content_type(format: Format) -> &'static str {
+    match format {
+        Format::Md | Format::Checklist => "text/markdown; charset=utf-8",
+        Format::Json => "application/json",
+        Format::Text => "text/plain; charset=utf-8",
+        Format::Html => "text/html; charset=utf-8",
+    }
+}
+
+/// Answers a request, `target` is the path along with the query string.
+///
+/// # Examples
+///
+/// ```
+/// use wod::serve::handle;
+///
+/// let response = handle("POST", "/render?format=text", "ft 21-15-9 pull up, thruster @43/30kg");
+/// assert_eq!(response.status, 200);
+/// assert!(response.body.ends_with("- Thruster @ 43/30kg"));
+/// assert_eq!(handle("GET", "/render", "").status, 405);
+/// ```
+pub fn handle(method: &str, target: &str, body: &str) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    match (method, path) {
+        ("POST", "/parse") => match crate::parse_workout_value(body.trim()) {
+            Ok(value) => Response::new(200, "application/json", value.to_string()),
+            Err(value) => Response::new(400, "application/json", value.to_string()),
+        },
+        ("POST", "/render") => {
+            let format = query
+                .split('&')
+                .find_map(|param| param.strip_prefix("format="))
+                .unwrap_or("md");
+            let format: Format = match format.parse() {
+                Ok(format) => format,
+                Err(e) => return Response::text(400, e),
+            };
+            match create_workout(body.trim(), None, None) {
+                Ok(workout) => Response::new(
+                    200,
+                    content_type(format),
+                    format.renderer().render(&workout),
+                ),
+                Err(e) => Response::text(400, e.to_string()),
+            }
+        }
+        ("GET", "/movements") => {
+            let movements: Vec<_> = Movement::all()
+                .map(|m| {
+                    json!({
+                        "name": m.to_string(),
+                        "aliases": m.aliases().collect::<Vec<_>>(),
+                        "url": m.video(),
+                    })
+                })
+                .collect();
+            Response::new(200, "application/json", json!(movements).to_string())
+        }
+        (_, "/parse" | "/render" | "/movements") => Response::text(405, "Method not allowed"),
+        _ => Response::text(404, "Not found"),
+    }
+}
+
+/// Reads a line of the request line or the headers into `line`, without reading
+/// more than the `left` bytes of [`MAX_HEAD`]. `false` if the line doesn't fit.
+fn read_head_line(
+    reader: &mut impl BufRead,
+    line: &mut String,
+    left: &mut usize,
+) -> io::Result<bool> {
+    let read = reader.take(*left as u64).read_line(line)?;
+    *left -= read;
+    Ok(read == 0 || line.ends_with('\n'))
+}
+
+/// Reads a request and answers it.
+fn respond(reader: &mut impl BufRead) -> io::Result<Response> {
+    let too_large = || Response::text(431, "The request line or the headers are too large");
+    let mut left = MAX_HEAD;
+    let mut request_line = String::new();
+    if !read_head_line(reader, &mut request_line, &mut left)? {
+        return Ok(too_large());
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or("/"));
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if !read_head_line(reader, &mut header, &mut left)? {
+            return Ok(too_large());
+        }
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = match value.trim().parse() {
+                    Ok(length) => length,
+                    Err(_) => return Ok(Response::text(400, "The Content-Length is not a number")),
+                };
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Ok(Response::text(413, "The body is too large"));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(match String::from_utf8(body) {
+        Ok(body) => handle(method, target, &body),
+        Err(_) => Response::text(400, "The body is not valid UTF-8"),
+    })
+}
+
+/// Reads a request from the connection and writes the response.
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    let response = respond(&mut BufReader::new(stream.try_clone()?))?;
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Handles the connections accepted by `listener`, one after the other.
+fn accept(listener: &TcpListener) {
+    for stream in listener.incoming().flatten() {
+        // The client is gone or stalled, there's no one to tell
+        let _ = handle_connection(stream);
+    }
+}
+
+/// The server, bound to an address.
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+}
+
+impl Server {
+    /// Binds the server to `addr`, it doesn't accept connections until [`Server::run`].
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+        })
+    }
+
+    /// The address bound, i.e. the port picked when binding to port 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves the endpoints with [`WORKERS`] threads, each of them handles a connection
+    /// at a time and gives up on the clients that stall for more than [`TIMEOUT`]. It
+    /// only returns if the listener can't be shared with the threads.
+    pub fn run(self) -> io::Result<()> {
+        for _ in 1..WORKERS {
+            let listener = self.listener.try_clone()?;
+            thread::spawn(move || accept(&listener));
+        }
+        accept(&self.listener);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let response = handle("POST", "/parse", "ft 21-15-9 pull up, thruster @43/30kg\n");
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with("{\"workout\":"));
+
+        let response = handle("POST", "/parse", "ft 21-15-9 pulup");
+        assert_eq!(response.status, 400);
+        assert!(response.body.contains("\"start\":11"));
+    }
+
+    #[test]
+    fn test_render() {
+        let response = handle("POST", "/render?format=html", "wl 5x5 back squat");
+        assert_eq!(response.content_type, "text/html; charset=utf-8");
+        assert!(response.body.starts_with("<section class=\"wod\">"));
+        assert_eq!(
+            handle("POST", "/render?format=pdf", "wl 5x5 back squat").status,
+            400
+        );
+        assert_eq!(handle("POST", "/render", "wl 5x5 bak squat").status, 400);
+    }
+
+    #[test]
+    fn test_movements() {
+        let response = handle("GET", "/movements", "");
+        let movements: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(movements[0]["name"], "Air Squat");
+        assert!(movements[0]["url"].is_string());
+        assert_eq!(handle("GET", "/workouts", "").status, 404);
+    }
+
+    #[test]
+    fn test_respond() {
+        let request = "POST /parse HTTP/1.1\r\nContent-Length: 13\r\n\r\nft 21 pull up";
+        assert_eq!(respond(&mut request.as_bytes()).unwrap().status, 200);
+
+        let header = format!("X-Padding: {}\r\n", "a".repeat(1000));
+        let request = format!("GET /movements HTTP/1.1\r\n{}\r\n", header.repeat(10));
+        assert_eq!(respond(&mut request.as_bytes()).unwrap().status, 431);
+        let request = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_HEAD));
+        assert_eq!(respond(&mut request.as_bytes()).unwrap().status, 431);
+
+        let request = "POST /parse HTTP/1.1\r\nContent-Length: 100000\r\n\r\n";
+        assert_eq!(respond(&mut request.as_bytes()).unwrap().status, 413);
+        let request = "POST /parse HTTP/1.1\r\nContent-Length: 13 bytes\r\n\r\nft 21 pull up";
+        assert_eq!(respond(&mut request.as_bytes()).unwrap().status, 400);
+        let request = "POST /parse HTTP/1.1\r\nContent-Length: -1\r\n\r\n";
+        assert_eq!(respond(&mut request.as_bytes()).unwrap().status, 400);
+    }
+
+    #[test]
+    fn test_serve() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+
+        // A client that stalls doesn't keep the others waiting
+        let _stalled = TcpStream::connect(addr).unwrap();
+        let mut stream = TcpStream::connect(addr).unwrap();
+        let body = "ft 21 pull up";
+        write!(
+            stream,
+            "POST /render?format=text HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\nFor Time\n\n- 21 Pull Up"));
+    }
+}
@@ -0,0 +1,140 @@
+//! Templates for the days that follow a fixed structure, i.e. strength and metcon.
+//!
+//! A template is written as a wod file, a workout per line, where the name of each
+//! workout is the section it represents:
+//!
+//! ```text
+//! wl 5x5 back squat @75%||Strength
+//! 3rd 400m run, 21 kettlebell swing @24/16kg, 12 pull up||Metcon
+//! ```
+//!
+//! Besides the templates shipped with `wod`, new ones can be added as `<name>.wod`
+//! files to the templates directory, a user template replaces the shipped one with
+//! the same name.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::WodError;
+
+/// Templates shipped with `wod`.
+const BUILTIN: &[(&str, &str)] = &[
+    (
+        "strength+metcon",
+        "wl 5x5 back squat @75%||Strength\n\
+         3rd 400m run, 21 kettlebell swing @24/16kg, 12 pull up||Metcon\n",
+    ),
+    (
+        "skill+metcon",
+        "emom-10-alt 3 bar mu, 5 hspu||Skill\n\
+         ft 50cal row, 40 wall ball @9/6kg, 30 toes to bar||Metcon\n",
+    ),
+    (
+        "strength+skill+metcon",
+        "wl 5x3 deadlift @80%||Strength\n\
+         emom-10-alt 3 bar mu, 5 hspu||Skill\n\
+         3rd 400m run, 21 kettlebell swing @24/16kg, 12 pull up||Metcon\n",
+    ),
+];
+
+/// Default directory for the user templates, `<wod home>/templates`.
+pub fn default_dir() -> PathBuf {
+    crate::wod_home().join("templates")
+}
+
+/// Gets the content of a template, looking first for `<name>.wod` in `dir`
+/// and then in the templates shipped.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use wod::templates::load;
+///
+/// let template = load("strength+metcon", Path::new("no-templates")).unwrap();
+/// assert!(template.starts_with("wl 5x5 back squat"));
+/// assert!(load("rest day", Path::new("no-templates")).is_err());
+/// ```
+pub fn load(name: &str, dir: &Path) -> Result<String, WodError> {
+    let path = dir.join(format!("{}.wod", name));
+    if path.is_file() {
+        return fs::read_to_string(&path).map_err(|e| {
+            let message = format!("Can't read template {}: {}", path.display(), e);
+            io::Error::new(e.kind(), message).into()
+        });
+    }
+    BUILTIN
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, content)| content.to_string())
+        .ok_or_else(|| {
+            WodError::Invalid(format!(
+                "Template `{}` not found, the available ones are: {}",
+                name,
+                list(dir).join(", ")
+            ))
+        })
+}
+
+/// Names of the templates available, the shipped ones and those in `dir`, sorted.
+pub fn list(dir: &Path) -> Vec<String> {
+    let mut names: BTreeSet<String> = BUILTIN.iter().map(|(n, _)| n.to_string()).collect();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().is_some_and(|ext| ext == "wod") {
+                if let Some(stem) = path.file_stem() {
+                    names.insert(stem.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    names.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_templates_parse() {
+        for (name, content) in BUILTIN {
+            for line in content.lines() {
+                let (workout, _, section) = crate::parse_wodfile_line(line).unwrap();
+                assert!(section.is_some(), "{}: section without name", name);
+                assert!(create_workout(workout, None, None).is_ok(), "{}", line);
+            }
+        }
+    }
+
+    #[test]
+    fn test_user_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("oly.wod"), "wl 5x2 snatch @70%||Snatch\n").unwrap();
+        fs::write(
+            dir.join("strength+metcon.wod"),
+            "wl 5x5 front squat||Strength\n",
+        )
+        .unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        assert_eq!(load("oly", dir).unwrap(), "wl 5x2 snatch @70%||Snatch\n");
+        assert_eq!(
+            load("strength+metcon", dir).unwrap(),
+            "wl 5x5 front squat||Strength\n"
+        );
+        assert_eq!(
+            list(dir),
+            vec![
+                "oly",
+                "skill+metcon",
+                "strength+metcon",
+                "strength+skill+metcon"
+            ]
+        );
+    }
+}
@@ -10,7 +10,7 @@ mod testing {
     fn test_for_time_0() {
         let workout = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
         let expected = "---\n\n**For Time**\n\n21-15-9\n\n- Pull Up\n\n- Thruster @ 43/30kg\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
@@ -18,7 +18,7 @@ mod testing {
         let workout = create_workout("4rd 21 box jump over, 15 bar mu", None, None).unwrap();
         let expected =
             "---\n\n**4 rounds for time**\n\n- 21 Box Jump Over\n\n- 15 Bar Muscle Up\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
@@ -30,14 +30,14 @@ mod testing {
         )
         .unwrap();
         let expected = "---\n\n**3 rounds for time**\n\n- 15 Chest To Bar\n\n- 15 calories Echo Bike\n\n- 15 Thruster @ 40kg\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
     fn test_for_time_3() {
         let workout = create_workout("ft 50cal row, r2m, 50cal row", None, None).unwrap();
         let expected = "---\n\n**For Time**\n\n- 50 calories Row\n\n- Rest 2 minutes \n\n- 50 calories Row\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     // For weightlifting a small hint should be placed for what (4x2) means (Low priority)
@@ -45,7 +45,7 @@ mod testing {
     fn test_weightlifting_0() {
         let workout = create_workout("wl 4x2 snatch @ 85%", None, None).unwrap();
         let expected = "---\n\n**Weightlifting**\n\n4x2 Snatch @ 85%\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
@@ -58,28 +58,28 @@ mod testing {
         .unwrap();
         let expected =
             "---\n\n**Weightlifting**\n\n3x(1+1+1) Clean + Front Squat + Split Jerk @ 80kg\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
     fn test_weightlifting_2() {
         let workout = create_workout("wl 3x(1+1) clean,split jerk @ 80kg", None, None).unwrap();
         let expected = "---\n\n**Weightlifting**\n\n3x(1+1) Clean + Split Jerk @ 80kg\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
     fn test_weightlifting_block_0() {
         let workout = create_workout("wl [1rm snatch; 1rm clean and jerk]", None, None).unwrap();
-        let expected = "---\n\n**Weightlifting**\n\n- 1rm Snatch\n\n- 1rm Clean And Jerk\n\n";
-        assert_eq!(workout.write(), expected);
+        let expected = "---\n\n**Weightlifting**\n\n- Build to a 1RM Snatch\n\n- Build to a 1RM Clean And Jerk\n\n";
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
     fn test_weightlifting_block_1() {
         let workout = create_workout("wl [3x2 snatch @80%; 3x2 clean @80%]", None, None).unwrap();
         let expected = "---\n\n**Weightlifting**\n\n- 3x2 Snatch @ 80%\n\n- 3x2 Clean @ 80%\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
@@ -91,7 +91,7 @@ mod testing {
         )
         .unwrap();
         let expected = "---\n\n**EMOM 12 minutes**\n\n- 15 calories Row\n\n- 12 Toes To Bar\n\n- Max reps of Dumbbell Clean and Jerk @ 22/15kg\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
     }
 
     #[test]
@@ -103,6 +103,126 @@ mod testing {
         )
         .unwrap();
         let expected = "---\n\n**EMOM 12 minutes**\n\nwork every 3 minutes, rest 1 minute\n\n- 15 calories Row\n\n- 12 Toes To Bar\n\n- Max reps of Dumbbell Clean and Jerk @ 22/15kg\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_notes_0() {
+        let workout =
+            create_workout("ft 21-15-9 pull up (strict), thruster @43/30kg", None, None).unwrap();
+        let expected =
+            "---\n\n**For Time**\n\n21-15-9\n\n- Pull Up (strict)\n\n- Thruster @ 43/30kg\n\n";
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_loads_per_set_0() {
+        let workout = create_workout(
+            "wl 3x5 back squat @60,70,80kg, 3x3 snatch @70-75-80%",
+            None,
+            None,
+        )
+        .unwrap();
+        let expected = "---\n\n**Weightlifting**\n\n3x5 Back Squat\n\n| Set | Load |\n| --- | --- |\n| 1 | 60kg |\n| 2 | 70kg |\n| 3 | 80kg |\n\n3x3 Snatch\n\n| Set | Load |\n| --- | --- |\n| 1 | 70% |\n| 2 | 75% |\n| 3 | 80% |\n\n";
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_progression_0() {
+        let workout = create_workout("wl 4x2 snatch @70%+2%", None, None).unwrap();
+        let expected = "---\n\n**Weightlifting**\n\n4x2 Snatch\n\n| Set | Load |\n| --- | --- |\n| 1 | 70% |\n| 2 | 72% |\n| 3 | 74% |\n| 4 | 76% |\n\n";
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_rpe_0() {
+        let workout = create_workout(
+            "wl 3x5 back squat @rpe8, 1rm snatch, 5x2 clean @rpe7-8",
+            None,
+            None,
+        )
+        .unwrap();
+        let expected = "---\n\n**Weightlifting**\n\n3x5 Back Squat @ RPE 8\n\nBuild to a 1RM Snatch\n\n5x2 Clean @ RPE 7-8\n\n";
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_wave_0() {
+        let workout = create_workout("wl 3x(3-2-1) power clean @80-85-90%", None, None).unwrap();
+        let expected = "---\n\n**Weightlifting**\n\n3x(3-2-1) Power Clean\n\n| Reps | Load |\n| --- | --- |\n| 3 | 80% |\n| 2 | 85% |\n| 1 | 90% |\n\n";
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_wave_progression() {
+        let workout = create_workout("wl 2x(3-2-1) power clean @80%+5%", None, None).unwrap();
+        let expected = "---\n\n**Weightlifting**\n\n2x(3-2-1) Power Clean\n\n| Reps | Load |\n| --- | --- |\n| 3 | 80% |\n| 2 | 85% |\n| 1 | 90% |\n\n";
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_superset_0() {
+        let workout = create_workout(
+            "wl 5x5 back squat @75% / A1 4x8 bench press @70% / A2 4x10 ring row",
+            None,
+            None,
+        )
+        .unwrap();
+        let expected = "---\n\n**Weightlifting**\n\n5x5 Back Squat @ 75%\n\n**A1/A2 superset**\n\nA1) 4x8 Bench Press @ 70%\n\nA2) 4x10 Ring Row\n\n";
+        assert_eq!(workout.to_string(), expected);
+    }
+
+    #[test]
+    fn test_for_load_0() {
+        let workout = create_workout("5rd-load 3 deadlift, 6 box jump", None, None).unwrap();
+        let expected = "---\n\n**5 rounds for load**\n\nScore: heaviest load across rounds\n\n- 3 Deadlift\n\n- 6 Box Jump\n\n";
+        assert_eq!(workout.to_string(), expected);
+        assert_eq!(workout.to_shorthand(), "5rd-load 3 deadlift, 6 box jump");
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_find_date() {
+        use crate::find_date;
+        let find = |name| find_date(name).map(|date| date.to_string());
+        assert_eq!(find("wod-2025-03-21"), Some("2025-03-21".to_string()));
+        assert_eq!(find("2025-03-21"), Some("2025-03-21".to_string()));
+        assert_eq!(find("monday-2025-3-21"), None);
+        assert_eq!(find("wod-2025-02-29"), None);
+        assert_eq!(
+            find("día-2025-13-21-2025-03-22"),
+            Some("2025-03-22".to_string())
+        );
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_workout_filename() {
+        use crate::{slugify, workout_filename};
+        use std::path::{Path, PathBuf};
+        assert_eq!(slugify("Open 25.2"), "open-25-2");
+        assert_eq!(slugify("  Día  de piernas! "), "día-de-piernas");
+        assert_eq!(
+            workout_filename(Path::new("content/wod-2025-03-21"), "fran"),
+            PathBuf::from("content/wod-2025-03-21-fran")
+        );
+        assert_eq!(
+            workout_filename(Path::new("wod-2025-03-21.md"), "2"),
+            PathBuf::from("wod-2025-03-21-2")
+        );
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_in_threads() {
+        use crate::in_threads;
+        let squares = in_threads((0..20).collect(), 4, |i: u32| Ok(i * i)).unwrap();
+        assert_eq!(squares, (0..20).map(|i| i * i).collect::<Vec<_>>());
+
+        let result = in_threads((0..20).collect(), 4, |i: u32| match i {
+            7 | 12 => Err(format!("failed {}", i).into()),
+            i => Ok(i),
+        });
+        assert_eq!(result.unwrap_err().to_string(), "failed 7");
     }
 }
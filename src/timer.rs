@@ -0,0 +1,224 @@
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::WodError;
+use crate::workout::Workout;
+use crate::{Movement, WorkoutType};
+
+/// A single countdown period of a timed workout.
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::timer::{schedule, Interval, IntervalKind};
+///
+/// let workout = create_workout("amrap-12 10 pull up, 15 push up", None, None).unwrap();
+/// assert_eq!(
+///     schedule(&workout).unwrap(),
+///     vec![Interval {
+///         label: "AMRAP".to_string(),
+///         seconds: 720,
+///         beep: true,
+///         kind: IntervalKind::Work(None),
+///     }]
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Interval {
+    /// Text shown while the interval is running, i.e. "Round 1/12" or "Rest".
+    pub label: String,
+    /// Length of the interval in seconds.
+    pub seconds: u32,
+    /// Whether the terminal bell should ring when the interval starts.
+    pub beep: bool,
+    /// Whether the interval is of work or of rest.
+    pub kind: IntervalKind,
+}
+
+/// What is done during an interval.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum IntervalKind {
+    /// Work on every line of the workout, or only on the one at the index given (in
+    /// the order of [`Workout::iter_prescriptions`]) in the alternating EMOMs.
+    Work(Option<usize>),
+    /// Rest until the next interval of work.
+    Rest,
+}
+
+impl Interval {
+    fn work(label: String, seconds: u32, line: Option<usize>) -> Self {
+        Interval {
+            label,
+            seconds,
+            beep: true,
+            kind: IntervalKind::Work(line),
+        }
+    }
+
+    fn rest(seconds: u32) -> Self {
+        Interval {
+            label: "Rest".to_string(),
+            seconds,
+            beep: true,
+            kind: IntervalKind::Rest,
+        }
+    }
+
+    /// The label followed by the movements of the line of the round, when it's
+    /// only done on one, i.e. "Round 1/4 - Pull Up".
+    pub(crate) fn title(&self, workout: &Workout) -> String {
+        let line = match self.kind {
+            IntervalKind::Work(Some(line)) => workout.iter_prescriptions().nth(line),
+            _ => None,
+        };
+        match line {
+            Some(line) => {
+                let movements: Vec<&str> = line.movements.iter().map(Movement::name).collect();
+                format!("{} - {}", self.label, movements.join(" + "))
+            }
+            None => self.label.clone(),
+        }
+    }
+}
+
+/// Builds the list of intervals to count down from the structure of the workout.
+///
+/// - EMOM: one interval per "every" period during the whole workout, followed
+///   by the rest period if any. Alternating EMOMs work on a line every round.
+/// - AMRAP: a single countdown with the total time.
+/// - Tabata: work and rest intervals for each round.
+///
+/// For Time and Weightlifting workouts don't have a time structure, an error is returned.
+pub fn schedule(workout: &Workout) -> Result<Vec<Interval>, WodError> {
+    let lines = workout.iter_prescriptions().count();
+
+    match &workout.workout_type {
+        WorkoutType::EMOM(emom) => {
+            let work = emom.every.seconds();
+            let rest = emom.rest.seconds();
+            if work == 0 {
+                return Err(WodError::Invalid(
+                    "The EMOM interval must be greater than 0".to_string(),
+                ));
+            }
+            let total = emom.rounds * 60;
+            let rounds = (total / (work + rest)).max(1);
+            let mut intervals = Vec::new();
+            for round in 0..rounds {
+                let label = format!("Round {}/{}", round + 1, rounds);
+                let line = (emom.alternating && lines > 0).then_some(round as usize % lines);
+                intervals.push(Interval::work(label, work, line));
+                if rest > 0 {
+                    intervals.push(Interval::rest(rest));
+                }
+            }
+            Ok(intervals)
+        }
+        WorkoutType::AMRAP(amrap) => Ok(vec![Interval::work(
+            "AMRAP".to_string(),
+            amrap.seconds,
+            None,
+        )]),
+        WorkoutType::Tabata(tabata) => {
+            let mut intervals = Vec::new();
+            for round in 0..tabata.rounds {
+                intervals.push(Interval::work(
+                    format!("Work {}/{}", round + 1, tabata.rounds),
+                    tabata.work,
+                    None,
+                ));
+                intervals.push(Interval::rest(tabata.rest));
+            }
+            Ok(intervals)
+        }
+        other => Err(WodError::Invalid(format!(
+            "No timer available for workout type: {}",
+            other
+        ))),
+    }
+}
+
+fn format_clock(seconds: u32) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Runs the intervals of the workout in the terminal, refreshing the remaining time
+/// every second and ringing the terminal bell at the start of each interval.
+pub fn countdown(workout: &Workout, intervals: &[Interval]) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    for interval in intervals {
+        let title = interval.title(workout);
+        if interval.beep {
+            write!(stdout, "\x07")?;
+        }
+        for remaining in (1..=interval.seconds).rev() {
+            write!(stdout, "\r{} {}   ", title, format_clock(remaining))?;
+            stdout.flush()?;
+            thread::sleep(Duration::from_secs(1));
+        }
+        writeln!(stdout, "\r{} {}   ", title, format_clock(0))?;
+    }
+    writeln!(stdout, "\x07Time!")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    #[test]
+    fn test_schedule_emom() {
+        let workout = create_workout("emom-3 10 pull up", None, None).unwrap();
+        let intervals = schedule(&workout).unwrap();
+        assert_eq!(intervals.len(), 3);
+        assert_eq!(intervals[0].label, "Round 1/3");
+        assert!(intervals.iter().all(|i| i.seconds == 60));
+    }
+
+    #[test]
+    fn test_schedule_emom_with_rest() {
+        let workout = create_workout("emom-12-3m-r1m 15cal row", None, None).unwrap();
+        let intervals = schedule(&workout).unwrap();
+        assert_eq!(intervals.len(), 6);
+        assert_eq!(intervals[0].seconds, 180);
+        assert_eq!(intervals[1], Interval::rest(60));
+    }
+
+    #[test]
+    fn test_schedule_emom_alternating() {
+        let workout = create_workout("emom-4-alt 10 pull up, 5 push up", None, None).unwrap();
+        let intervals = schedule(&workout).unwrap();
+        assert_eq!(intervals[0].kind, IntervalKind::Work(Some(0)));
+        assert_eq!(intervals[0].title(&workout), "Round 1/4 - Pull Up");
+        assert_eq!(intervals[1].title(&workout), "Round 2/4 - Push Up");
+        assert_eq!(intervals[2].title(&workout), "Round 3/4 - Pull Up");
+    }
+
+    #[test]
+    fn test_schedule_tabata() {
+        let workout = create_workout("tabata 20 air squat", None, None).unwrap();
+        let intervals = schedule(&workout).unwrap();
+        assert_eq!(intervals.len(), 16);
+        assert_eq!(
+            intervals[0],
+            Interval::work("Work 1/8".to_string(), 20, None)
+        );
+        assert_eq!(intervals[1], Interval::rest(10));
+    }
+
+    #[test]
+    fn test_schedule_unsupported() {
+        let workout = create_workout("wl 5x5 back squat @70%", None, None).unwrap();
+        assert!(schedule(&workout).is_err());
+    }
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(0), "00:00");
+        assert_eq!(format_clock(75), "01:15");
+        assert_eq!(format_clock(720), "12:00");
+    }
+}
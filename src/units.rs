@@ -0,0 +1,335 @@
+//! Conversions between the units used in the workouts.
+//!
+//! The units are given as written in the shorthand ("kg", "lb", "m", "k", "mile"),
+//! the conversions return `None` for units they don't know about, i.e. a percentage.
+//! The types of the workouts keep them as [`TimeUnit`], [`DistanceUnit`] and
+//! [`LoadUnit`], so a typo like "70kgs" is an error instead of a new unit.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Kilograms in a pound.
+pub const KG_PER_LB: f64 = 0.45359237;
+/// Kilograms in a pood, the unit of the kettlebells.
+pub const KG_PER_POOD: f64 = 16.38;
+/// Meters in a mile.
+pub const M_PER_MILE: f64 = 1609.344;
+/// Meters in a foot.
+pub const M_PER_FT: f64 = 0.3048;
+/// Kilojoules in a (kilo)calorie, as shown by the rowers and bikes.
+pub const KJ_PER_CAL: f64 = 4.184;
+
+/// Converts kilograms to pounds.
+pub fn kg_to_lb(kg: f64) -> f64 {
+    kg / KG_PER_LB
+}
+
+/// Converts pounds to kilograms.
+pub fn lb_to_kg(lb: f64) -> f64 {
+    lb * KG_PER_LB
+}
+
+/// Converts meters to miles.
+pub fn m_to_mile(m: f64) -> f64 {
+    m / M_PER_MILE
+}
+
+/// Converts miles to meters.
+pub fn mile_to_m(mile: f64) -> f64 {
+    mile * M_PER_MILE
+}
+
+/// Converts meters to feet.
+pub fn m_to_ft(m: f64) -> f64 {
+    m / M_PER_FT
+}
+
+/// Converts feet to meters.
+pub fn ft_to_m(ft: f64) -> f64 {
+    ft * M_PER_FT
+}
+
+/// Converts calories to kilojoules.
+pub fn cal_to_kj(cal: f64) -> f64 {
+    cal * KJ_PER_CAL
+}
+
+/// Converts kilojoules to calories.
+pub fn kj_to_cal(kj: f64) -> f64 {
+    kj / KJ_PER_CAL
+}
+
+/// Kilograms in a unit of weight.
+fn kg_per(unit: &str) -> Option<f64> {
+    match unit.to_lowercase().as_str() {
+        "kg" | "kgs" => Some(1.0),
+        "lb" | "lbs" => Some(KG_PER_LB),
+        "pood" | "poods" => Some(KG_PER_POOD),
+        _ => None,
+    }
+}
+
+/// Meters in a unit of distance.
+fn m_per(unit: &str) -> Option<f64> {
+    match unit.to_lowercase().as_str() {
+        "m" => Some(1.0),
+        "k" | "km" => Some(1000.0),
+        "mi" | "mile" | "miles" => Some(M_PER_MILE),
+        "ft" => Some(M_PER_FT),
+        _ => None,
+    }
+}
+
+/// Converts a weight between units, kilograms, pounds or poods.
+///
+/// # Examples
+///
+/// ```
+/// use wod::units::convert_weight;
+///
+/// assert_eq!(convert_weight(100.0, "kg", "kg"), Some(100.0));
+/// assert_eq!(convert_weight(135.0, "lb", "kg").map(f64::round), Some(61.0));
+/// assert_eq!(convert_weight(1.5, "pood", "kg").map(f64::round), Some(25.0));
+/// assert_eq!(convert_weight(85.0, "%", "kg"), None);
+/// ```
+pub fn convert_weight(value: f64, from: &str, to: &str) -> Option<f64> {
+    Some(value * kg_per(from)? / kg_per(to)?)
+}
+
+/// Converts a distance between units: meters, kilometers ("k"), miles or feet.
+///
+/// # Examples
+///
+/// ```
+/// use wod::units::convert_distance;
+///
+/// assert_eq!(convert_distance(5.0, "k", "m"), Some(5000.0));
+/// assert_eq!(convert_distance(1.0, "mile", "m"), Some(1609.344));
+/// assert_eq!(convert_distance(400.0, "m", "cal"), None);
+/// ```
+pub fn convert_distance(value: f64, from: &str, to: &str) -> Option<f64> {
+    Some(value * m_per(from)? / m_per(to)?)
+}
+
+/// Units of the times, intervals and rest periods.
+///
+/// # Examples
+///
+/// ```
+/// use wod::units::TimeUnit;
+///
+/// assert_eq!("sec".parse::<TimeUnit>().unwrap(), TimeUnit::Seconds);
+/// assert_eq!("m".parse::<TimeUnit>().unwrap(), TimeUnit::Minutes);
+/// assert_eq!(TimeUnit::Minutes.to_string(), "min");
+/// assert_eq!(TimeUnit::Minutes.symbol(), "m");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeUnit {
+    #[cfg_attr(feature = "serde", serde(rename = "s"))]
+    Seconds,
+    #[cfg_attr(feature = "serde", serde(rename = "m"))]
+    Minutes,
+}
+
+impl TimeUnit {
+    /// Seconds in a unit.
+    pub fn seconds(&self) -> u32 {
+        match self {
+            TimeUnit::Seconds => 1,
+            TimeUnit::Minutes => 60,
+        }
+    }
+
+    /// The unit as written in the intervals and rest periods, "s" or "m".
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TimeUnit::Seconds => "s",
+            TimeUnit::Minutes => "m",
+        }
+    }
+}
+
+impl FromStr for TimeUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "s" | "sec" => Ok(TimeUnit::Seconds),
+            "m" | "min" => Ok(TimeUnit::Minutes),
+            _ => Err(format!(
+                "Invalid time unit: `{}`, expected one of: s, sec, m, min",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TimeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TimeUnit::Seconds => "sec",
+            TimeUnit::Minutes => "min",
+        })
+    }
+}
+
+/// Units of the distances.
+///
+/// # Examples
+///
+/// ```
+/// use wod::units::DistanceUnit;
+///
+/// assert_eq!("K".parse::<DistanceUnit>().unwrap(), DistanceUnit::Kilometers);
+/// assert_eq!(DistanceUnit::Miles.to_string(), "mile");
+/// assert!("kms".parse::<DistanceUnit>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DistanceUnit {
+    #[cfg_attr(feature = "serde", serde(rename = "m"))]
+    Meters,
+    #[cfg_attr(feature = "serde", serde(rename = "k"))]
+    Kilometers,
+    #[cfg_attr(feature = "serde", serde(rename = "mile"))]
+    Miles,
+    #[cfg_attr(feature = "serde", serde(rename = "ft"))]
+    Feet,
+}
+
+impl DistanceUnit {
+    /// Meters in a unit.
+    pub fn meters(&self) -> f64 {
+        match self {
+            DistanceUnit::Meters => 1.0,
+            DistanceUnit::Kilometers => 1000.0,
+            DistanceUnit::Miles => M_PER_MILE,
+            DistanceUnit::Feet => M_PER_FT,
+        }
+    }
+}
+
+impl FromStr for DistanceUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "m" => Ok(DistanceUnit::Meters),
+            "k" | "K" | "km" => Ok(DistanceUnit::Kilometers),
+            "mile" | "miles" | "mi" => Ok(DistanceUnit::Miles),
+            "ft" => Ok(DistanceUnit::Feet),
+            _ => Err(format!(
+                "Invalid distance unit: `{}`, expected one of: m, k, km, mile, mi, ft",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for DistanceUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DistanceUnit::Meters => "m",
+            DistanceUnit::Kilometers => "k",
+            DistanceUnit::Miles => "mile",
+            DistanceUnit::Feet => "ft",
+        })
+    }
+}
+
+/// Units of the loads, a weight or a percentage of the 1RM.
+///
+/// # Examples
+///
+/// ```
+/// use wod::units::LoadUnit;
+///
+/// assert_eq!("lbs".parse::<LoadUnit>().unwrap(), LoadUnit::Pounds);
+/// assert_eq!(LoadUnit::Percent.to_string(), "%");
+/// assert!("kgs".parse::<LoadUnit>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoadUnit {
+    #[cfg_attr(feature = "serde", serde(rename = "kg"))]
+    Kilograms,
+    #[cfg_attr(feature = "serde", serde(rename = "lb"))]
+    Pounds,
+    #[cfg_attr(feature = "serde", serde(rename = "pood"))]
+    Poods,
+    #[cfg_attr(feature = "serde", serde(rename = "%"))]
+    Percent,
+}
+
+impl FromStr for LoadUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "kg" => Ok(LoadUnit::Kilograms),
+            "lb" | "lbs" => Ok(LoadUnit::Pounds),
+            "pood" | "poods" => Ok(LoadUnit::Poods),
+            "%" => Ok(LoadUnit::Percent),
+            _ => Err(format!(
+                "Invalid load unit: `{}`, expected one of: kg, lb, lbs, pood, poods, %",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LoadUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LoadUnit::Kilograms => "kg",
+            LoadUnit::Pounds => "lb",
+            LoadUnit::Poods => "pood",
+            LoadUnit::Percent => "%",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_round_trips() {
+        assert!(approx(lb_to_kg(kg_to_lb(60.0)), 60.0));
+        assert!(approx(mile_to_m(m_to_mile(400.0)), 400.0));
+        assert!(approx(ft_to_m(m_to_ft(15.0)), 15.0));
+        assert!(approx(kj_to_cal(cal_to_kj(20.0)), 20.0));
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(kg_to_lb(100.0).round(), 220.0);
+        assert_eq!(
+            convert_weight(225.0, "LBS", "kg").map(f64::round),
+            Some(102.0)
+        );
+        assert_eq!(convert_distance(1.0, "m", "%"), None);
+        assert_eq!(
+            convert_distance(100.0, "ft", "m").map(f64::round),
+            Some(30.0)
+        );
+    }
+
+    #[test]
+    fn test_unit_from_str() {
+        assert!("kgs".parse::<LoadUnit>().is_err());
+        assert!("".parse::<LoadUnit>().is_err());
+        assert!("hours".parse::<TimeUnit>().is_err());
+        assert_eq!("mi".parse::<DistanceUnit>().unwrap(), DistanceUnit::Miles);
+        // The units are written back in the form the conversions take
+        for unit in [LoadUnit::Kilograms, LoadUnit::Pounds, LoadUnit::Poods] {
+            assert!(kg_per(&unit.to_string()).is_some());
+        }
+        assert!(convert_distance(1.0, &DistanceUnit::Feet.to_string(), "m").is_some());
+    }
+}
@@ -0,0 +1,183 @@
+//! Weekly volume load of the archive: the load lifted (see [`Workout::tonnage`]) per
+//! week and movement pattern, to plot the training load over a cycle, see `wod volume`.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+
+use crate::movement::Pattern;
+use crate::results::SeriesFormat;
+use crate::units::LoadUnit;
+use crate::weight::Weight;
+use crate::workout::Workout;
+
+/// The load lifted in a week for a movement pattern.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct WeekLoad {
+    /// The Monday of the week, "YYYY-MM-DD".
+    pub week: String,
+    /// The movement pattern, i.e. "squat".
+    pub pattern: String,
+    /// Load lifted with the Rx loads for men, in kg.
+    pub tonnage_man: f64,
+    /// Load lifted with the Rx loads for women, in kg.
+    pub tonnage_woman: f64,
+}
+
+/// The Monday of the week of a day.
+fn week_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// The load lifted per week and movement pattern in the workouts of some days, in kg.
+///
+/// Every week from the first to the last one with workouts is included, with every
+/// pattern lifted in any of them, so the series can be plotted as they are. The
+/// percentages are resolved from the 1RMs, as in [`Workout::tonnage_by_pattern`].
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use chrono::NaiveDate;
+/// use wod::create_workout;
+/// use wod::volume::weekly_tonnage;
+///
+/// let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+/// let workout = |w| create_workout(w, None, None).unwrap();
+/// let days = vec![
+///     (day(18), vec![workout("wl 5x5 back squat @100kg")]),
+///     (day(20), vec![workout("wl 5x5 back squat @70%")]),
+/// ];
+/// let one_rms = BTreeMap::from([("Back Squat".to_string(), "140kg".parse().unwrap())]);
+/// let weeks = weekly_tonnage(&days, &one_rms);
+/// assert_eq!(weeks.len(), 1);
+/// assert_eq!(weeks[0].week, "2025-03-17");
+/// assert_eq!(weeks[0].pattern, "squat");
+/// assert_eq!(weeks[0].tonnage_man, 4950.0);
+/// ```
+pub fn weekly_tonnage(
+    days: &[(NaiveDate, Vec<Workout>)],
+    one_rms: &BTreeMap<String, Weight>,
+) -> Vec<WeekLoad> {
+    let mut weeks: BTreeMap<NaiveDate, BTreeMap<Pattern, (f64, f64)>> = BTreeMap::new();
+    let mut patterns: Vec<Pattern> = Vec::new();
+    for (date, workouts) in days {
+        let week = weeks.entry(week_of(*date)).or_default();
+        for workout in workouts {
+            for (pattern, loads) in workout.tonnage_by_pattern(one_rms) {
+                let total = week.entry(pattern).or_default();
+                for load in loads.iter().filter_map(|l| l.to_unit(LoadUnit::Kilograms)) {
+                    total.0 += load.weight_man;
+                    total.1 += load.weight_woman;
+                }
+                if !patterns.contains(&pattern) {
+                    patterns.push(pattern);
+                }
+            }
+        }
+    }
+    patterns.sort();
+
+    let (Some(first), Some(last)) = (weeks.keys().next(), weeks.keys().next_back()) else {
+        return Vec::new();
+    };
+    let mut rows = Vec::new();
+    let mut week = *first;
+    while week <= *last {
+        for pattern in &patterns {
+            let (man, woman) = weeks
+                .get(&week)
+                .and_then(|w| w.get(pattern))
+                .copied()
+                .unwrap_or_default();
+            rows.push(WeekLoad {
+                week: week.to_string(),
+                pattern: pattern.to_string(),
+                tonnage_man: man,
+                tonnage_woman: woman,
+            });
+        }
+        week += Duration::days(7);
+    }
+    rows
+}
+
+/// Writes the weekly loads as a JSON array or as CSV with a header.
+///
+/// # Examples
+///
+/// ```
+/// use wod::results::SeriesFormat;
+/// use wod::volume::{export, WeekLoad};
+///
+/// let week = WeekLoad {
+///     week: "2025-03-17".to_string(),
+///     pattern: "olympic lifting".to_string(),
+///     tonnage_man: 1830.0,
+///     tonnage_woman: 1290.0,
+/// };
+/// assert_eq!(
+///     export(&[week], SeriesFormat::Csv),
+///     "week,pattern,tonnage_man,tonnage_woman,unit\n2025-03-17,olympic lifting,1830,1290,kg\n"
+/// );
+/// ```
+pub fn export(weeks: &[WeekLoad], format: SeriesFormat) -> String {
+    match format {
+        SeriesFormat::Json => serde_json::to_string_pretty(weeks).unwrap_or_default(),
+        SeriesFormat::Csv => {
+            let mut csv = String::from("week,pattern,tonnage_man,tonnage_woman,unit\n");
+            for week in weeks {
+                csv.push_str(&format!(
+                    "{},{},{},{},kg\n",
+                    week.week, week.pattern, week.tonnage_man, week.tonnage_woman
+                ));
+            }
+            csv
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workout::create_workout;
+
+    #[test]
+    fn test_weekly_tonnage() {
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let workout = |w| create_workout(w, None, None).unwrap();
+        let days = vec![
+            (day(3), vec![workout("wl 5x5 deadlift @100kg")]),
+            (
+                day(19),
+                vec![
+                    workout("wl 3x(2+1) clean, split jerk @80kg"),
+                    workout("ft 21-15-9 pull up, thruster @95/65lb"),
+                ],
+            ),
+        ];
+        let weeks = weekly_tonnage(&days, &BTreeMap::new());
+        let rows: Vec<(&str, &str, f64)> = weeks
+            .iter()
+            .map(|w| (w.week.as_str(), w.pattern.as_str(), w.tonnage_man))
+            .collect();
+        // The week without workouts is written with zeros, the pounds in kg
+        assert_eq!(
+            rows,
+            vec![
+                ("2025-03-03", "squat", 0.0),
+                ("2025-03-03", "hinge", 2500.0),
+                ("2025-03-03", "olympic lifting", 0.0),
+                ("2025-03-10", "squat", 0.0),
+                ("2025-03-10", "hinge", 0.0),
+                ("2025-03-10", "olympic lifting", 0.0),
+                ("2025-03-17", "squat", 1939.0),
+                ("2025-03-17", "hinge", 0.0),
+                ("2025-03-17", "olympic lifting", 720.0),
+            ]
+        );
+        assert!(weekly_tonnage(&[], &BTreeMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,192 @@
+//! Warm-ups for the workouts of a day, from the movements they use.
+//!
+//! A warm-up has a general part, to raise the temperature on the machine of the day
+//! (or a jog), and a specific one with drills for the patterns of the movements
+//! (squats, hinges, presses, pulls...) and building sets for the loaded ones.
+//!
+//! The warm-up is a block of the file of the day placed before the workouts, marked
+//! with a comment (see [`MARKER`]) so it's told apart from them in any language.
+
+use std::fmt::Write;
+
+use crate::i18n::{self, Message};
+use crate::movement::{Equipment, Movement, Pattern};
+use crate::workout::Workout;
+
+/// The comment that marks the block of a warm-up.
+pub const MARKER: &str = "<!-- warmup -->";
+
+impl Pattern {
+    /// The drills that prepare the pattern, the monostructural movements are
+    /// covered by the general warm-up.
+    fn drills(self) -> &'static [&'static str] {
+        match self {
+            Pattern::Squat => &["10 cossack squats", "5 air squats with a 3 second descent"],
+            Pattern::Hinge => &["10 good mornings", "10 glute bridges"],
+            Pattern::Press => &["10 PVC pass-throughs", "10 scap push ups"],
+            Pattern::Pull => &["10 scap pull ups", "10 kip swings"],
+            Pattern::Olympic => &[
+                "10 PVC pass-throughs",
+                "5 muscle cleans, 5 front squats, 5 push presses with an empty barbell",
+            ],
+            Pattern::Inverted => &["3x20 seconds wall facing hold", "10 plank shoulder taps"],
+            Pattern::Midline => &["3x20 seconds hollow hold", "10 arch rocks"],
+            Pattern::Jump => &["30 seconds of single unders", "5 broad jumps"],
+            Pattern::Monostructural => &[],
+        }
+    }
+}
+
+/// A warm-up for the workouts of a day, see [`WarmUp::for_workouts`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmUp {
+    /// The general part, the same for every movement but the machine.
+    pub general: Vec<String>,
+    /// The specific part, the drills for the patterns of the movements and the
+    /// building sets of the loaded ones.
+    pub specific: Vec<String>,
+}
+
+impl WarmUp {
+    /// The warm-up for the movements of the workouts, in the order they appear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    /// use wod::warmup::WarmUp;
+    ///
+    /// let workout = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
+    /// let warmup = WarmUp::for_workouts(&[workout]);
+    /// assert_eq!(warmup.general[0], "3 minutes easy jog");
+    /// assert_eq!(
+    ///     warmup.specific,
+    ///     [
+    ///         "10 scap pull ups",
+    ///         "10 kip swings",
+    ///         "10 cossack squats",
+    ///         "5 air squats with a 3 second descent",
+    ///         "3 building sets of Thruster",
+    ///     ]
+    /// );
+    /// ```
+    pub fn for_workouts(workouts: &[Workout]) -> WarmUp {
+        let movements: Vec<&Movement> = workouts.iter().flat_map(|w| w.movements()).collect();
+
+        let machine = movements.iter().find(|m| m.is_machine());
+        let general = vec![
+            match machine {
+                Some(machine) => format!("3 minutes easy {}", machine.name().to_lowercase()),
+                None => "3 minutes easy jog".to_string(),
+            },
+            "2 rounds of 10 jumping jacks, 5 inchworms, 10 lunges".to_string(),
+        ];
+
+        let mut specific: Vec<String> = Vec::new();
+        let mut patterns = Vec::new();
+        for pattern in movements.iter().filter_map(|m| m.pattern()) {
+            if patterns.contains(&pattern) {
+                continue;
+            }
+            patterns.push(pattern);
+            for drill in pattern.drills() {
+                if !specific.iter().any(|d| d == drill) {
+                    specific.push(drill.to_string());
+                }
+            }
+        }
+
+        let loaded = [
+            Equipment::Barbell,
+            Equipment::Dumbbell,
+            Equipment::Kettlebell,
+        ];
+        for movement in &movements {
+            let building = format!("3 building sets of {}", movement);
+            if movement.equipment().is_some_and(|e| loaded.contains(&e))
+                && !specific.contains(&building)
+            {
+                specific.push(building);
+            }
+        }
+
+        WarmUp { general, specific }
+    }
+
+    /// Renders the warm-up as a markdown block of the file of the day.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::warmup::WarmUp;
+    ///
+    /// let warmup = WarmUp {
+    ///     general: vec!["3 minutes easy row".to_string()],
+    ///     specific: vec!["10 good mornings".to_string()],
+    /// };
+    /// assert_eq!(
+    ///     warmup.render(),
+    ///     "---\n\n<!-- warmup -->\n**Warm-up**\n\n*General*\n\n- 3 minutes easy row\n\n*Specific*\n\n- 10 good mornings\n\n"
+    /// );
+    /// ```
+    pub fn render(&self) -> String {
+        let heading = i18n::text(Message::WarmUp, &[]);
+        let mut block = format!("---\n\n{}\n**{}**\n\n", MARKER, heading);
+        for (part, lines) in [("General", &self.general), ("Specific", &self.specific)] {
+            if lines.is_empty() {
+                continue;
+            }
+            let _ = write!(block, "*{}*\n\n", part);
+            for line in lines {
+                let _ = writeln!(block, "- {}", line);
+            }
+            block.push('\n');
+        }
+        block
+    }
+}
+
+/// Whether a block of a file is a warm-up instead of a workout.
+pub fn is_warmup(block: &str) -> bool {
+    block.lines().any(|line| line.trim_end() == MARKER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_workout;
+
+    #[test]
+    fn test_for_workouts() {
+        let workouts = [
+            create_workout("wl 5x3 power clean @75%", None, None).unwrap(),
+            create_workout("ft 50cal row, 30 wall ball @9/6kg, 20 hspu", None, None).unwrap(),
+        ];
+        let warmup = WarmUp::for_workouts(&workouts);
+        assert_eq!(warmup.general[0], "3 minutes easy row");
+        // The drills shared by the patterns are done once
+        assert_eq!(
+            warmup
+                .specific
+                .iter()
+                .filter(|d| *d == "10 PVC pass-throughs")
+                .count(),
+            1
+        );
+        assert!(warmup
+            .specific
+            .contains(&"3x20 seconds wall facing hold".to_string()));
+        assert_eq!(
+            warmup.specific.last().unwrap(),
+            "3 building sets of Power Clean"
+        );
+    }
+
+    #[test]
+    fn test_is_warmup() {
+        let workout = create_workout("ft 30 burpee", None, None).unwrap();
+        let block = WarmUp::for_workouts(&[workout]).render();
+        assert!(is_warmup(&block));
+        assert!(!is_warmup("---\n\n**For Time**\n\n- 30 Burpee\n\n"));
+    }
+}
@@ -0,0 +1,58 @@
+//! Bindings to use the parser from JavaScript, i.e. to validate and preview the
+//! shorthand in a web form with the same grammar used by `wod`.
+//!
+//! Built with the `wasm` feature, as a `cdylib` with the JavaScript glue of
+//! `wasm-bindgen` in `pkg`:
+//!
+//! ```text
+//! make wasm
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::workout::create_workout;
+
+/// Parses a workout and returns it as JSON, `{"workout": {...}}` with the fields of
+/// [`crate::Workout`], or `{"error": {"message": ..., "start": ..., "end": ...}}` when
+/// it isn't valid.
+///
+/// The `start` and `end` of the error are the positions of the offending token in the
+/// JavaScript string (UTF-16 code units), `null` if the error isn't in a single token.
+#[wasm_bindgen]
+pub fn parse_workout(source: &str) -> String {
+    crate::parse_workout_json(source)
+}
+
+/// Renders a workout as the markdown of the generated files, throws an error with
+/// the message if it isn't valid.
+#[wasm_bindgen]
+pub fn render_markdown(source: &str) -> Result<String, JsError> {
+    create_workout(source, None, None)
+        .map(|workout| workout.to_string())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_workout() {
+        let value: serde_json::Value =
+            serde_json::from_str(&parse_workout("ft 21-15-9 pull up, thruster @43/30kg")).unwrap();
+        assert!(value["workout"]["workout_type"]["ForTime"].is_object());
+
+        let value: serde_json::Value =
+            serde_json::from_str(&parse_workout("ft 21-15-9 pulup, thruster")).unwrap();
+        assert!(value["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("did you mean: `pull up`"));
+        assert_eq!(value["error"]["start"], 11);
+        assert_eq!(value["error"]["end"], 16);
+
+        let value: serde_json::Value =
+            serde_json::from_str(&parse_workout("amrap-10 [1rm snatch]")).unwrap();
+        assert!(value["error"]["start"].is_null());
+    }
+}
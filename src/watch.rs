@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::diagnostics::Diagnostic;
+use crate::workout::create_workout;
+
+/// Renders the content of a wod file as it would be written to the markdown file.
+///
+/// The lines that can't be parsed are replaced by their diagnostic, so every
+/// error of the file is shown at once.
+///
+/// # Examples
+///
+/// ```
+/// use wod::watch::render;
+///
+/// let rendered = render("wl 5x5 back squat\nft 10 pulup", false);
+/// assert!(rendered.contains("5x5 Back Squat"));
+/// assert!(rendered.contains("line 2: error: Invalid Movement"));
+/// ```
+pub fn render(content: &str, color: bool) -> String {
+    let mut rendered = String::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty()
+            || crate::is_wodfile_comment(line)
+            || crate::is_day_separator(line)
+        {
+            continue;
+        }
+        let (workout, comments, name) = match crate::parse_wodfile_line(line) {
+            Ok(parts) => parts,
+            Err(err) => {
+                rendered.push_str(&format!("line {}: {}\n\n", i + 1, err));
+                continue;
+            }
+        };
+        if let Some(diagnostic) = Diagnostic::from_source(workout) {
+            rendered.push_str(&format!("line {}: {}\n", i + 1, diagnostic.render(color)));
+            continue;
+        }
+        match create_workout(workout, comments, name) {
+            Ok(wkt) => rendered.push_str(&wkt.to_string()),
+            Err(err) => rendered.push_str(&format!("line {}: {}\n\n", i + 1, err)),
+        }
+    }
+    rendered
+}
+
+/// Calls `on_change` with the content of the file every time it's modified, starting
+/// with the current content. The file is checked every `poll` interval, and it runs
+/// until there's an error reading the file.
+pub fn watch(path: &Path, poll: Duration, mut on_change: impl FnMut(&str)) -> io::Result<()> {
+    let mut last_modified: Option<SystemTime> = None;
+    loop {
+        let modified = fs::metadata(path)?.modified()?;
+        if last_modified != Some(modified) {
+            last_modified = Some(modified);
+            on_change(&fs::read_to_string(path)?);
+        }
+        thread::sleep(poll);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let rendered = render("ft 21-15-9 pull up, thruster|T.C. 10'|Fran\n\n", false);
+        assert_eq!(
+            rendered,
+            create_workout(
+                "ft 21-15-9 pull up, thruster",
+                Some("T.C. 10'".to_string()),
+                Some("Fran".to_string())
+            )
+            .unwrap()
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let rendered = render("wl 5x5 back squat|a|b|c\nfr 10 pull up", false);
+        assert!(rendered.starts_with("line 1: Invalid wodfile"));
+        assert!(rendered.contains("line 2: error: "));
+    }
+
+    #[test]
+    fn test_watch_missing_file() {
+        let result = watch(Path::new("missing.wod"), Duration::from_millis(1), |_| {});
+        assert!(result.is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::units::{self, LoadUnit};
+
 /// Represents weight information for both men and women, along with the unit of measurement.
 ///
 /// # Examples
@@ -11,95 +13,93 @@ use std::str::FromStr;
 ///
 /// ```
 /// use std::str::FromStr;
+/// use wod::units::LoadUnit;
 /// use wod::weight::Weight;
 ///
 /// let weight = Weight::from_str("70kg").unwrap();
 /// assert_eq!(weight, Weight {
-///     weight_man: 70,
-///     weight_woman: 70,
-///     unit: "kg".to_string(),
+///     weight_man: 70.0,
+///     weight_woman: 70.0,
+///     unit: LoadUnit::Kilograms,
 /// });
 /// ```
 ///
 /// Or indirectly by parsing the string.
 ///
 /// ```
+/// use wod::units::LoadUnit;
 /// use wod::weight::Weight;
 /// let weight: Weight = "70kg".parse().unwrap();
 /// assert_eq!(
 ///     weight,
 ///     Weight {
-///        weight_man: 70,
-///        weight_woman: 70,
-///        unit: "kg".to_string()
+///        weight_man: 70.0,
+///        weight_woman: 70.0,
+///        unit: LoadUnit::Kilograms
 ///     }
 /// );
 /// ```
 ///
+/// The weights can have decimals, and the unit can be separated by a space.
+///
+/// ```
+/// use wod::weight::Weight;
+///
+/// let weight: Weight = "52.5/35kg".parse().unwrap();
+/// assert_eq!(weight.weight_man, 52.5);
+/// assert_eq!(weight.weight_woman, 35.0);
+/// assert_eq!("1.5 pood".parse::<Weight>().unwrap().to_string(), "1.5pood");
+/// assert!("70kgs".parse::<Weight>().is_err());
+/// ```
+///
 /// ## Displaying a "Weight" instance
 ///
-/// The "Weight" will be displayed as is.
+/// The "Weight" will be displayed as is, whole numbers without decimals.
 ///
 /// ```
 /// use wod::weight::Weight;
 ///
 /// let weight: Weight = "70kg".parse().unwrap();
 /// assert_eq!(format!("{}", weight), "70kg".to_string());
+/// let weight: Weight = "102.5kg".parse().unwrap();
+/// assert_eq!(format!("{}", weight), "102.5kg".to_string());
 /// ```
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Weight {
     /// Weight for men.
-    pub weight_man: u32,
+    pub weight_man: f64,
     /// Weight for women.
-    pub weight_woman: u32,
-    /// Unit of measurement (e.g., "kg", "lbs").
-    pub unit: String,
+    pub weight_woman: f64,
+    /// Unit of measurement, a weight or a percentage of the 1RM.
+    pub unit: LoadUnit,
 }
 
 // If a woman's weight is not informed, it will be the same
-fn extract_unit(w: &str) -> (u32, u32, String) {
-    let mut weight_man = String::new();
-    let mut unit = String::new();
-    let mut weight_woman = String::new();
-
-    // To deal with one/two weights
-    let mut is_man = true;
-
-    for c in w.chars() {
-        // Assume the first number is the weight for man
-        if c == '/' {
-            is_man = false;
-            continue;
-        }
-        if c.is_numeric() {
-            match is_man {
-                true => weight_man.push(c),
-                false => weight_woman.push(c),
-            }
-        } else {
-            unit.push(c);
-        }
-    }
+fn extract_unit(w: &str) -> Result<(f64, f64, LoadUnit), String> {
+    let (weights, unit) = w.split_at(
+        w.find(|c: char| !(c.is_numeric() || c == '.' || c == '/'))
+            .unwrap_or(w.len()),
+    );
 
-    // If is_man is true, it means only one weight is informed, then
-    // copy the value from the man
-    let weight_woman = if is_man {
-        weight_man.clone()
-    } else {
-        weight_woman
-    };
+    // Assume the first number is the weight for man
+    let (weight_man, weight_woman) = weights.split_once('/').unwrap_or((weights, weights));
 
-    (
-        weight_man.parse().unwrap(),
-        weight_woman.parse().unwrap(),
-        unit,
-    )
+    let parse = |n: &str| {
+        n.parse::<f64>()
+            .map_err(|e| format!("Invalid Weight '{}': {}", w, e))
+    };
+    let unit = unit
+        .trim_start()
+        .parse()
+        .map_err(|e| format!("Invalid Weight '{}': {}", w, e))?;
+    Ok((parse(weight_man)?, parse(weight_woman)?, unit))
 }
 
 impl FromStr for Weight {
     type Err = String;
     fn from_str(w: &str) -> Result<Self, Self::Err> {
-        let (weight_man, weight_woman, unit) = extract_unit(w);
+        let (weight_man, weight_woman, unit) = extract_unit(w)?;
         Ok(Weight {
             weight_man,
             weight_woman,
@@ -110,48 +110,180 @@ impl FromStr for Weight {
 
 impl fmt::Display for Weight {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount(), self.unit)
+    }
+}
+
+impl Weight {
+    /// The numbers of the weight without the unit, "43/30" for "43/30kg".
+    fn amount(&self) -> String {
         if self.weight_woman != self.weight_man {
-            write!(
-                f,
-                "{weight_man}/{weight_woman}{unit}",
-                weight_man = self.weight_man,
-                weight_woman = self.weight_woman,
-                unit = self.unit
-            )
+            format!("{}/{}", self.weight_man, self.weight_woman)
         } else {
-            write!(f, "{}{}", self.weight_man, self.unit)
+            self.weight_man.to_string()
         }
     }
+
+    /// The weight in a different unit (kilograms, pounds or poods), rounded to the
+    /// closest whole number. `None` if either unit isn't a weight, i.e. a percentage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::units::LoadUnit;
+    /// use wod::weight::Weight;
+    ///
+    /// let weight: Weight = "43/30kg".parse().unwrap();
+    /// assert_eq!(weight.to_unit(LoadUnit::Pounds).unwrap().to_string(), "95/66lb");
+    /// assert!("85%".parse::<Weight>().unwrap().to_unit(LoadUnit::Kilograms).is_none());
+    /// ```
+    pub fn to_unit(&self, unit: LoadUnit) -> Option<Weight> {
+        let (from, to) = (self.unit.to_string(), unit.to_string());
+        let convert = |w: f64| units::convert_weight(w, &from, &to).map(f64::round);
+        Some(Weight {
+            weight_man: convert(self.weight_man)?,
+            weight_woman: convert(self.weight_woman)?,
+            unit,
+        })
+    }
+}
+
+/// The loads of a number of sets starting from `start` and adding `step` every set, i.e.
+/// "70%" adding "2%" for 4 sets is "70,72,74,76%".
+///
+/// # Examples
+///
+/// ```
+/// use wod::weight::{format_loads, progression};
+///
+/// let loads = progression(&"60/40kg".parse().unwrap(), &"2.5kg".parse().unwrap(), 3);
+/// assert_eq!(format_loads(&loads, ","), "60/40,62.5/42.5,65/45kg");
+/// ```
+pub fn progression(start: &Weight, step: &Weight, sets: usize) -> Vec<Weight> {
+    // Rounded to the hundredths, adding decimals accumulates errors
+    let add =
+        |load: f64, step: f64, set: usize| ((load + step * set as f64) * 100.0).round() / 100.0;
+    (0..sets)
+        .map(|set| Weight {
+            weight_man: add(start.weight_man, step.weight_man, set),
+            weight_woman: add(start.weight_woman, step.weight_woman, set),
+            unit: start.unit,
+        })
+        .collect()
+}
+
+/// Parses the loads of every set of a line, separated by commas or hyphens and with
+/// the unit written once after the last one, i.e. "60,65,70kg" or "70-75-80%".
+///
+/// # Examples
+///
+/// ```
+/// use wod::weight::parse_loads;
+///
+/// let loads = parse_loads("60,65/45,70kg").unwrap();
+/// assert_eq!(loads.len(), 3);
+/// assert_eq!(loads[1].to_string(), "65/45kg");
+/// assert_eq!(parse_loads("70-75-80%").unwrap()[2].to_string(), "80%");
+/// assert!(parse_loads("60,,70kg").is_err());
+/// ```
+pub fn parse_loads(loads: &str) -> Result<Vec<Weight>, String> {
+    let (numbers, unit) = loads.split_at(
+        loads
+            .find(|c: char| !(c.is_numeric() || matches!(c, '.' | '/' | ',' | '-')))
+            .unwrap_or(loads.len()),
+    );
+    numbers
+        .split([',', '-'])
+        .map(|number| format!("{}{}", number, unit).parse())
+        .collect()
+}
+
+/// Writes the loads of every set with the unit once after the last one, the inverse
+/// of [`parse_loads`].
+///
+/// ```
+/// use wod::weight::{format_loads, parse_loads};
+///
+/// let loads = parse_loads("70-75-80%").unwrap();
+/// assert_eq!(format_loads(&loads, ","), "70,75,80%");
+/// assert_eq!(format_loads(&loads, ", "), "70, 75, 80%");
+/// ```
+pub fn format_loads(loads: &[Weight], separator: &str) -> String {
+    let amounts: Vec<String> = loads.iter().map(Weight::amount).collect();
+    let unit = loads.last().map(|w| w.unit.to_string()).unwrap_or_default();
+    format!("{}{}", amounts.join(separator), unit)
+}
+
+/// Rounds a load to the closest multiple of the increment available with the plates.
+///
+/// # Examples
+///
+/// ```
+/// use wod::weight::round_to_increment;
+///
+/// assert_eq!(round_to_increment(82.0, 2.5), 82.5);
+/// assert_eq!(round_to_increment(81.0, 2.5), 80.0);
+/// assert_eq!(round_to_increment(81.2, 0.0), 81.2);
+/// ```
+pub fn round_to_increment(load: f64, increment: f64) -> f64 {
+    if increment <= 0.0 {
+        return load;
+    }
+    (load / increment).round() * increment
+}
+
+/// Formats a load with up to two decimals, without the trailing zeros, i.e. "82.5kg",
+/// "81.25kg" or "80kg".
+///
+/// ```
+/// use wod::weight::format_load;
+///
+/// assert_eq!(format_load(82.5, "kg"), "82.5kg");
+/// assert_eq!(format_load(81.25, "kg"), "81.25kg");
+/// assert_eq!(format_load(80.0, "kg"), "80kg");
+/// ```
+pub fn format_load(load: f64, unit: &str) -> String {
+    let amount = format!("{:.2}", load);
+    let amount = amount.trim_end_matches('0').trim_end_matches('.');
+    format!("{}{}", amount, unit)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_load_increment() {
+        // 65% of 125kg is 81.25kg, already a multiple of the increment
+        let load = round_to_increment(125.0 * 0.65, 1.25);
+        assert_eq!(format_load(load, "kg"), "81.25kg");
+        assert_eq!(format_load(round_to_increment(83.0, 1.25), "kg"), "82.5kg");
+    }
+
     #[test]
     fn test_weight() {
         assert_eq!(
             Weight::from_str("70kg").unwrap(),
             Weight {
-                weight_man: 70,
-                weight_woman: 70,
-                unit: "kg".to_string()
+                weight_man: 70.0,
+                weight_woman: 70.0,
+                unit: LoadUnit::Kilograms
             }
         );
         assert_eq!(
             Weight::from_str("70%").unwrap(),
             Weight {
-                weight_man: 70,
-                weight_woman: 70,
-                unit: "%".to_string()
+                weight_man: 70.0,
+                weight_woman: 70.0,
+                unit: LoadUnit::Percent
             }
         );
         assert_eq!(
             Weight::from_str("60/40kg").unwrap(),
             Weight {
-                weight_man: 60,
-                weight_woman: 40,
-                unit: "kg".to_string()
+                weight_man: 60.0,
+                weight_woman: 40.0,
+                unit: LoadUnit::Kilograms
             }
         );
     }
@@ -173,16 +305,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_round_to_increment() {
+        assert_eq!(round_to_increment(101.0, 5.0), 100.0);
+        assert_eq!(round_to_increment(103.0, 5.0), 105.0);
+        assert_eq!(round_to_increment(101.4, 1.0), 101.0);
+    }
+
     #[test]
     fn test_parse() {
         let weight: Weight = "70kg".parse().unwrap();
         assert_eq!(
             weight,
             Weight {
-                weight_man: 70,
-                weight_woman: 70,
-                unit: "kg".to_string()
+                weight_man: 70.0,
+                weight_woman: 70.0,
+                unit: LoadUnit::Kilograms
             }
         );
     }
+
+    #[test]
+    fn test_parse_decimal() {
+        let weight: Weight = "102.5kg".parse().unwrap();
+        assert_eq!(weight.weight_man, 102.5);
+        assert_eq!(weight.to_string(), "102.5kg");
+        let weight: Weight = "52.5/35kg".parse().unwrap();
+        assert_eq!(weight.to_string(), "52.5/35kg");
+        let weight: Weight = "1.5 pood".parse().unwrap();
+        assert_eq!(
+            weight,
+            Weight {
+                weight_man: 1.5,
+                weight_woman: 1.5,
+                unit: LoadUnit::Poods
+            }
+        );
+        assert_eq!(weight.to_string(), "1.5pood");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("kg".parse::<Weight>().is_err());
+        assert!("60/kg".parse::<Weight>().is_err());
+        assert!("1.2.5kg".parse::<Weight>().is_err());
+        assert!("60/40/20kg".parse::<Weight>().is_err());
+        assert!("70kgs".parse::<Weight>().is_err());
+        assert!("70".parse::<Weight>().is_err());
+    }
 }
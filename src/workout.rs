@@ -1,10 +1,22 @@
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
 use std::str::FromStr;
 
-use crate::lexer::{Lexer, LexerError, Token};
-use crate::movement::Movement;
+use crate::diff::{self, Change};
+use crate::error::WodError;
+use crate::generator;
+use crate::i18n::{self, Message};
+use crate::lexer::{Lexer, Token};
+use crate::media::Media;
+use crate::movement::{Equipment, Movement, Pattern};
+use crate::pacing::{self, Baseline};
+use crate::render::{self, CommentStyle, Layout, Markdown, Rendered, Renderer};
 use crate::rep_types::rep_type::RepType;
 use crate::rm::RM;
-use crate::weight::Weight;
+use crate::rpe::Rpe;
+use crate::scaling::{self, AthleteProfile, Difficulty, Level, Scaling};
+use crate::units::LoadUnit;
+use crate::weight::{self, Weight};
 use crate::WorkoutType;
 
 /// Represents a structured workout with various components such as movements, repetitions,
@@ -35,9 +47,10 @@ use crate::WorkoutType;
 /// let mut workout = Workout::new(tokens, comments, name);
 /// workout.parse();
 ///
-/// println!("{}", workout.write());
+/// println!("{}", workout.to_string());
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Workout {
     /// The type of workout (ForTime, EMOM, Weightlifting, etc.)
     pub workout_type: WorkoutType,
@@ -49,9 +62,21 @@ pub struct Workout {
     comments: Option<String>,
     /// Optional name of the workout. Some workouts are given a name, i.e. "Fran".
     name: Option<String>,
+    /// 1RM per movement display name, used to render percentages as absolute loads.
+    one_rms: BTreeMap<String, Weight>,
+    /// Baseline scores per movement display name, used to render the target paces.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    baselines: BTreeMap<String, Baseline>,
+    /// Scaled versions rendered under the workout, see [`Workout::add_scaled`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scaled: Vec<Workout>,
+    /// Videos and images shown under the workout, see [`Workout::add_media`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    media: Vec<Media>,
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WorkoutStructure {
     /// Simple workout with direct movements/reps/weights
     Simple(SimpleWorkout),
@@ -59,82 +84,225 @@ pub enum WorkoutStructure {
     Block(Vec<SimpleWorkout>),
 }
 
-#[derive(Debug, PartialEq)]
-pub struct SimpleWorkout {
-    /// List of movements included in the workout
+/// A line of a workout: the repetitions, the movements they are done with and the load.
+///
+/// For time, EMOM and Tabata workouts have a movement per line ("21 pull up"), while
+/// a weightlifting line can have sets and a complex of movements ("3x(2+1) clean,
+/// split jerk @85%").
+///
+/// # Examples
+///
+/// ```
+/// use wod::create_workout;
+/// use wod::workout::WorkoutStructure;
+///
+/// let workout = create_workout("wl 3x(2+1) clean, split jerk @85%", None, None).unwrap();
+/// let WorkoutStructure::Simple(simple) = &workout.structure else { unreachable!() };
+/// let line = &simple.prescriptions[0];
+/// assert_eq!(line.sets.as_ref().unwrap().to_string(), "3");
+/// assert_eq!(line.reps.len(), 2);
+/// assert_eq!(line.movements.len(), 2);
+/// assert_eq!(line.weight.as_ref().unwrap().to_string(), "85%");
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Prescription {
+    /// Number of sets of a weightlifting line, the first 5 in "5x5".
+    pub sets: Option<RepType>,
+    /// Repetitions, more than one for a complex like "2+1".
+    pub reps: Vec<RepType>,
+    /// Repetitions of the sets of a wave, "3-2-1" in "3x(3-2-1)", paired in order with
+    /// the loads of every set, see [`Prescription::wave_loads`].
+    pub wave: Vec<RepType>,
+    /// Repetition maximum to build up to, i.e. "1rm".
+    pub rm: Option<RM>,
+    /// Movements, more than one for a complex like "clean, split jerk".
     pub movements: Vec<Movement>,
-    /// List of repetition types
-    pub rep_types: Vec<RepType>,
-    /// List of weights used in the workout
-    pub weights: Vec<Weight>,
-    /// Optional collection of "x" tokens that may appear in the workout description
-    /// (e.g., "3x5" repetition scheme)
-    pub x: Option<Vec<Token>>,
-    /// Optional collection of "@" tokens that may appear in the workout description
-    /// (e.g., "back squat @100kg")
-    pub at: Option<Vec<Token>>,
-    /// Optional collection of "+" tokens that may appear in the workout description
-    /// (e.g., "2+2+2" repetition scheme)
-    pub plus: Option<Vec<Token>>,
-    /// Optional collection of "RM" (repetition maximum) tokens
-    /// (e.g., "5RM" for 5 repetition maximum)
-    pub rm: Option<Vec<RM>>,
+    /// Load of the line, the first one when it changes across the sets.
+    pub weight: Option<Weight>,
+    /// Load of every set when it changes across them, "@60,65,70kg", empty otherwise.
+    pub loads: Vec<Weight>,
+    /// Intensity as a rate of perceived exertion, "@rpe8", instead of a load.
+    pub rpe: Option<Rpe>,
+    /// Note written after the movements in parentheses, i.e. "strict".
+    pub note: Option<String>,
+    /// Label of the line in a superset, i.e. "A1". The lines that follow each other
+    /// with the same letter are grouped, see [`Prescription::superset_letter`].
+    pub superset: Option<String>,
+}
+
+impl Prescription {
+    fn is_empty(&self) -> bool {
+        self.sets.is_none()
+            && self.reps.is_empty()
+            && self.wave.is_empty()
+            && self.rm.is_none()
+            && self.movements.is_empty()
+    }
+
+    /// The load of every set, a single load is the same for all of them. When there
+    /// are fewer loads per set than sets, the last one is kept for the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let workout = create_workout("wl 3x3 back squat @60,70,80kg, 2x3 snatch @50kg", None, None).unwrap();
+    /// let loads: Vec<Vec<String>> = workout
+    ///     .iter_prescriptions()
+    ///     .map(|line| line.set_loads().iter().map(|w| w.to_string()).collect())
+    ///     .collect();
+    /// assert_eq!(loads, vec![vec!["60kg", "70kg", "80kg"], vec!["50kg", "50kg"]]);
+    /// ```
+    pub fn set_loads(&self) -> Vec<&Weight> {
+        let sets = self.num_sets();
+        match self.loads.last() {
+            Some(last) => (0..sets.max(self.loads.len()))
+                .map(|i| self.loads.get(i).unwrap_or(last))
+                .collect(),
+            None => self.weight.iter().cycle().take(sets).collect(),
+        }
+    }
+
+    /// The repetitions of every set of a wave paired with their load, the same one for
+    /// all of them when a single load is written. Empty for the lines that aren't a wave.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let workout = create_workout("wl 3x(3-2-1) power clean @80-85-90%", None, None).unwrap();
+    /// let line = workout.iter_prescriptions().next().unwrap();
+    /// let pairs: Vec<String> = line
+    ///     .wave_loads()
+    ///     .iter()
+    ///     .map(|(reps, load)| format!("{} @ {}", reps, load.unwrap()))
+    ///     .collect();
+    /// assert_eq!(pairs, vec!["3 @ 80%", "2 @ 85%", "1 @ 90%"]);
+    /// ```
+    pub fn wave_loads(&self) -> Vec<(&RepType, Option<&Weight>)> {
+        self.wave
+            .iter()
+            .enumerate()
+            .map(|(i, reps)| (reps, self.loads.get(i).or(self.weight.as_ref())))
+            .collect()
+    }
+
+    /// The number of sets of the line, 1 if they aren't written.
+    fn num_sets(&self) -> usize {
+        match &self.sets {
+            Some(RepType::Reps(sets)) => sets.reps_man as usize,
+            _ => 1,
+        }
+    }
+
+    /// The letter of the superset the line is part of, "A" for "A1".
+    pub fn superset_letter(&self) -> Option<char> {
+        self.superset.as_deref()?.chars().next()
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimpleWorkout {
+    /// Repetitions per round shared by all the lines, i.e. "21-15-9".
+    pub rounds: Vec<RepType>,
+    /// The lines of the workout, in order.
+    pub prescriptions: Vec<Prescription>,
 }
 
 impl SimpleWorkout {
     pub fn new() -> Self {
-        SimpleWorkout {
-            movements: Vec::new(),
-            rep_types: Vec::new(),
-            weights: Vec::new(),
-            x: None,
-            at: None,
-            plus: None,
-            rm: None,
-        }
+        Self::default()
     }
 
-    /// Parse tokens into this SimpleWorkout - this is your existing parse logic
-    pub fn parse_from_tokens(&mut self, tokens: &[Token]) {
+    /// Builds the lines of the workout from its tokens. In weightlifting workouts the
+    /// movements that follow each other are a complex, otherwise each movement starts
+    /// a new line.
+    pub fn from_tokens(tokens: &[Token], workout_type: &WorkoutType) -> Self {
+        let mut simple_workout = SimpleWorkout::new();
+        let weightlifting = *workout_type == WorkoutType::Weightlifting;
+
+        // Contiguous repetitions at the start are the reps per round, "21-15-9"
+        let mut tokens = tokens.iter().peekable();
+        while let Some(Token::RepType(rep_type)) = tokens.peek() {
+            simple_workout.rounds.push(rep_type.clone());
+            tokens.next();
+        }
+        if simple_workout.rounds.len() == 1 {
+            let reps = simple_workout.rounds.pop();
+            simple_workout.prescriptions.push(Prescription {
+                reps: reps.into_iter().collect(),
+                ..Default::default()
+            });
+        }
+
+        let mut prev: Option<&Token> = None;
+        let mut line = simple_workout.prescriptions.pop().unwrap_or_default();
         for token in tokens {
             match token {
-                Token::Movement(movement) => {
-                    self.movements.push(movement.clone());
-                }
                 Token::RepType(rep_type) => {
-                    self.rep_types.push(rep_type.clone());
-                }
-                Token::Weight(weight) => {
-                    self.weights.push(weight.clone());
+                    if !matches!(prev, Some(Token::X | Token::Plus)) && !line.is_empty() {
+                        simple_workout.prescriptions.push(std::mem::take(&mut line));
+                    }
+                    line.reps.push(rep_type.clone());
                 }
-                Token::X => {
-                    if self.x.is_none() {
-                        self.x = Some(Vec::new());
+                Token::X => line.sets = line.reps.pop(),
+                Token::Wave(wave) => line.wave = wave.clone(),
+                Token::RM(rm) => {
+                    if !line.is_empty() {
+                        simple_workout.prescriptions.push(std::mem::take(&mut line));
                     }
-                    self.x.as_mut().unwrap().push(Token::X);
+                    line.rm = Some(rm.clone());
                 }
-                Token::At => {
-                    if self.at.is_none() {
-                        self.at = Some(Vec::new());
+                Token::Movement(movement) => {
+                    let intensity = line.weight.is_some() || line.rpe.is_some();
+                    if !line.movements.is_empty() && (!weightlifting || intensity) {
+                        simple_workout.prescriptions.push(std::mem::take(&mut line));
                     }
-                    self.at.as_mut().unwrap().push(Token::At);
+                    line.movements.push(movement.clone());
                 }
-                Token::Plus => {
-                    if self.plus.is_none() {
-                        self.plus = Some(Vec::new());
+                Token::Weight(weight) => line.weight = Some(weight.clone()),
+                Token::Loads(loads) => {
+                    line.weight = loads.first().cloned();
+                    line.loads = loads.clone();
+                }
+                Token::Progression(start, step) => {
+                    line.weight = Some(start.clone());
+                    // The sets come before the load, "6x2 snatch @70%+2%", the load
+                    // of a wave increases every set of it
+                    let sets = match line.wave.len() {
+                        0 => line.num_sets(),
+                        sets => sets,
+                    };
+                    if sets > 1 {
+                        line.loads = weight::progression(start, step, sets);
                     }
-                    self.plus.as_mut().unwrap().push(Token::Plus);
                 }
-                Token::RM(rm) => {
-                    if self.rm.is_none() {
-                        self.rm = Some(Vec::new());
+                Token::Rpe(rpe) => line.rpe = Some(rpe.clone()),
+                Token::Note(note) => line.note = Some(note.clone()),
+                Token::Superset(label) => {
+                    if !line.is_empty() {
+                        simple_workout.prescriptions.push(std::mem::take(&mut line));
                     }
-                    self.rm.as_mut().unwrap().push(rm.clone());
+                    line.superset = Some(label.clone());
                 }
-                // Ignore structural tokens (brackets, semicolons) and workout type
+                // The rest of the tokens only give structure to the text
                 _ => {}
             }
+            prev = Some(token);
+        }
+        if !line.is_empty() {
+            simple_workout.prescriptions.push(line);
         }
+        simple_workout
+    }
+
+    /// The movements of the workout, in order.
+    pub fn movements(&self) -> impl Iterator<Item = &Movement> {
+        self.prescriptions.iter().flat_map(|p| p.movements.iter())
     }
 }
 
@@ -147,10 +315,31 @@ impl Default for Workout {
             tokens: Vec::new(),
             comments: None,
             name: None,
+            one_rms: BTreeMap::new(),
+            baselines: BTreeMap::new(),
+            scaled: Vec::new(),
+            media: Vec::new(),
         }
     }
 }
 
+/// Two workouts are equal when they only differ in the way they were typed: the
+/// spacing, the aliases used for the movements ("c2b" or "chest to bar"), or the
+/// 1RMs and baselines set to render the loads and paces. See [`Workout::normalized`].
+impl PartialEq for Workout {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
+
+/// Displays the workout as the markdown of the generated files, use
+/// [`Workout::display_with`] for other formats.
+impl fmt::Display for Workout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.markdown())
+    }
+}
+
 impl Workout {
     /// Creates a new Workout from a vector of tokens and optional comments.
     ///
@@ -172,6 +361,425 @@ impl Workout {
         }
     }
 
+    /// The name of the workout, if any, i.e. "Fran".
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The comments of the workout, if any.
+    pub fn comments(&self) -> Option<&str> {
+        self.comments.as_deref()
+    }
+
+    /// The lines of the workout, including those of every part of a block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let workout = create_workout("wl [5x5 back squat @75%; 1rm snatch]", None, None).unwrap();
+    /// assert_eq!(workout.iter_prescriptions().count(), 2);
+    /// ```
+    pub fn iter_prescriptions(&self) -> impl Iterator<Item = &Prescription> {
+        let simple_workouts = match &self.structure {
+            WorkoutStructure::Simple(simple) => std::slice::from_ref(simple),
+            WorkoutStructure::Block(sub_workouts) => sub_workouts.as_slice(),
+        };
+        simple_workouts.iter().flat_map(|s| s.prescriptions.iter())
+    }
+
+    /// The movements of the workout in order, a movement appears as many times as it's
+    /// prescribed.
+    pub fn movements(&self) -> impl Iterator<Item = &Movement> {
+        self.iter_prescriptions().flat_map(|p| p.movements.iter())
+    }
+
+    /// The movements done with a load, along with it. Every movement of a complex
+    /// is paired with the load of the line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::{create_workout, Movement};
+    ///
+    /// let workout = create_workout(
+    ///     "3rd 400m run, 21 kettlebell swing @24/16kg, 12 pull up", None, None
+    /// ).unwrap();
+    /// let loaded: Vec<(&Movement, String)> = workout
+    ///     .movements_with_load()
+    ///     .map(|(m, w)| (m, w.to_string()))
+    ///     .collect();
+    /// assert_eq!(loaded, vec![(&Movement::KettlebellSwing, "24/16kg".to_string())]);
+    /// ```
+    pub fn movements_with_load(&self) -> impl Iterator<Item = (&Movement, &Weight)> {
+        self.iter_prescriptions().flat_map(|p| {
+            p.weight
+                .iter()
+                .flat_map(move |w| p.movements.iter().map(move |m| (m, w)))
+        })
+    }
+
+    /// The machines used in the workout (rower, bikes, ski erg), without repetitions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::{create_workout, Movement};
+    ///
+    /// let workout = create_workout("ft 50cal row, 30 burpee, 50cal row", None, None).unwrap();
+    /// assert_eq!(workout.machines(), vec![&Movement::Row]);
+    /// ```
+    pub fn machines(&self) -> Vec<&Movement> {
+        let mut machines: Vec<&Movement> = Vec::new();
+        for movement in self.movements().filter(|m| m.is_machine()) {
+            if !machines.contains(&movement) {
+                machines.push(movement);
+            }
+        }
+        machines
+    }
+
+    /// The equipment needed for the workout (see [`Movement::equipment`]), in the
+    /// order it's first used and without repetitions, to plan the logistics of a class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    /// use wod::movement::Equipment;
+    ///
+    /// let workout = create_workout("ft 30 clean @60kg, 30 pull up, 30 burpee, 30 snatch @40kg", None, None).unwrap();
+    /// assert_eq!(workout.equipment(), vec![Equipment::Barbell, Equipment::PullUpBar]);
+    /// ```
+    pub fn equipment(&self) -> Vec<Equipment> {
+        let mut equipment = Vec::new();
+        for item in self.movements().filter_map(Movement::equipment) {
+            if !equipment.contains(&item) {
+                equipment.push(item);
+            }
+        }
+        equipment
+    }
+
+    /// Taxonomy tags for the workout: the movements, the type of workout and the
+    /// modalities of the movements, lowercase and without repetitions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let workout = create_workout("emom-10 3 snatch @60/40kg, 5 pull up", None, None).unwrap();
+    /// assert_eq!(
+    ///     workout.tags(),
+    ///     vec!["snatch", "pull up", "emom", "weightlifting", "gymnastics"]
+    /// );
+    /// ```
+    pub fn tags(&self) -> Vec<String> {
+        let workout_type = self.workout_type.kind();
+        let movements: Vec<&Movement> =
+            self.movements().filter(|m| **m != Movement::Rest).collect();
+        let tags = movements
+            .iter()
+            .map(|m| m.name().to_lowercase())
+            .chain(std::iter::once(workout_type.to_string()))
+            .chain(movements.iter().map(|m| generator::modality(m).to_string()));
+
+        let mut unique: Vec<String> = Vec::new();
+        for tag in tags {
+            if !unique.contains(&tag) {
+                unique.push(tag);
+            }
+        }
+        unique
+    }
+
+    /// The difficulty of the workout, the hardest of its movements as rated by
+    /// [`Scaling::difficulty`] from their loads and the skill they need.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    /// use wod::scaling::{Difficulty, Scaling};
+    ///
+    /// let scaling = Scaling::default();
+    /// let workout = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
+    /// assert_eq!(workout.level(&scaling), Difficulty::Rx);
+    /// let workout = create_workout("amrap-12 10 air squat, 10 push up", None, None).unwrap();
+    /// assert_eq!(workout.level(&scaling), Difficulty::Beginner);
+    /// ```
+    pub fn level(&self, scaling: &Scaling) -> Difficulty {
+        self.iter_prescriptions()
+            .flat_map(|p| {
+                p.movements
+                    .iter()
+                    .map(move |m| scaling.difficulty(m, p.weight.as_ref()))
+            })
+            .max()
+            .unwrap_or(Difficulty::Beginner)
+    }
+
+    /// Total load lifted in the workout, the sets times the repetitions times the
+    /// load of every line, with a total per unit (usually a single one).
+    ///
+    /// Percentage based lines are resolved from the 1RM of the athlete, keyed by the
+    /// display name of the movement (see [`crate::pr::PrStore::records`]), and are
+    /// skipped when there isn't one. Lines measured in calories, distance or time,
+    /// and the repetitions of an AMRAP beyond the first round, are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use wod::{create_workout, Weight};
+    ///
+    /// let workout = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
+    /// let tonnage = workout.tonnage(&BTreeMap::new());
+    /// assert_eq!(tonnage[0].to_string(), "1935/1350kg");
+    ///
+    /// let workout = create_workout("wl 5x5 back squat @70%", None, None).unwrap();
+    /// let one_rms = BTreeMap::from([("Back Squat".to_string(), "140kg".parse().unwrap())]);
+    /// assert_eq!(workout.tonnage(&one_rms)[0].to_string(), "2450kg");
+    /// ```
+    pub fn tonnage(&self, one_rms: &BTreeMap<String, Weight>) -> Vec<Weight> {
+        let mut totals = Vec::new();
+        for (_, load) in self.tonnage_per_set(one_rms) {
+            add_load(&mut totals, &load);
+        }
+        totals
+    }
+
+    /// Load lifted in the workout per movement pattern (see [`Movement::pattern`]),
+    /// as [`Workout::tonnage`]. The load of a complex counts for the pattern of its
+    /// first movement, "clean, split jerk" is olympic lifting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use wod::create_workout;
+    /// use wod::movement::Pattern;
+    ///
+    /// let workout = create_workout("ft 21-15-9 thruster @43/30kg, deadlift @100/70kg", None, None).unwrap();
+    /// let tonnage = workout.tonnage_by_pattern(&BTreeMap::new());
+    /// assert_eq!(tonnage[0].0, Pattern::Squat);
+    /// assert_eq!(tonnage[0].1[0].to_string(), "1935/1350kg");
+    /// assert_eq!(tonnage[1].0, Pattern::Hinge);
+    /// ```
+    pub fn tonnage_by_pattern(
+        &self,
+        one_rms: &BTreeMap<String, Weight>,
+    ) -> Vec<(Pattern, Vec<Weight>)> {
+        let mut patterns: Vec<(Pattern, Vec<Weight>)> = Vec::new();
+        for (line, load) in self.tonnage_per_set(one_rms) {
+            let Some(pattern) = line.movements.first().and_then(Movement::pattern) else {
+                continue;
+            };
+            match patterns.iter_mut().find(|(p, _)| *p == pattern) {
+                Some((_, totals)) => add_load(totals, &load),
+                None => patterns.push((pattern, vec![load])),
+            }
+        }
+        patterns
+    }
+
+    /// Load lifted in every set of the workout, the repetitions times the load, along
+    /// with its line. See [`Workout::tonnage`] for what's counted.
+    fn tonnage_per_set(&self, one_rms: &BTreeMap<String, Weight>) -> Vec<(&Prescription, Weight)> {
+        let simple_workouts = match &self.structure {
+            WorkoutStructure::Simple(simple) => std::slice::from_ref(simple),
+            WorkoutStructure::Block(sub_workouts) => sub_workouts.as_slice(),
+        };
+        let rounds = match &self.workout_type {
+            WorkoutType::ForTime(ft) => ft.rounds,
+            WorkoutType::EMOM(emom) => emom.rounds,
+            WorkoutType::Tabata(tabata) => tabata.rounds,
+            WorkoutType::ForLoad(for_load) => for_load.rounds,
+            WorkoutType::AMRAP(_) | WorkoutType::Weightlifting => 1,
+        };
+        // Only the repetitions count towards the load lifted
+        let reps = |rep_types: &[RepType]| {
+            rep_types.iter().fold((0, 0), |(man, woman), r| match r {
+                RepType::Reps(reps) => (man + reps.reps_man, woman + reps.reps_woman),
+                _ => (man, woman),
+            })
+        };
+
+        let mut loads = Vec::new();
+        for simple in simple_workouts {
+            // Alternating EMOMs do a single line every interval
+            let rounds = match &self.workout_type {
+                WorkoutType::EMOM(emom) if emom.alternating => {
+                    rounds / simple.prescriptions.len().max(1) as u32
+                }
+                _ => rounds,
+            };
+            for line in &simple.prescriptions {
+                let line_reps = if line.reps.is_empty() {
+                    reps(&simple.rounds)
+                } else {
+                    reps(&line.reps)
+                };
+                // Every set is added with its own load, the sets of a wave with their
+                // repetitions and once per wave
+                let sets: Vec<((u32, u32), &Weight)> = if line.wave.is_empty() {
+                    line.set_loads()
+                        .into_iter()
+                        .map(|w| (line_reps, w))
+                        .collect()
+                } else {
+                    line.wave_loads()
+                        .into_iter()
+                        .filter_map(|(r, w)| Some((reps(std::slice::from_ref(r)), w?)))
+                        .flat_map(|set| std::iter::repeat_n(set, line.num_sets()))
+                        .collect()
+                };
+                for ((reps_man, reps_woman), weight) in sets {
+                    let Some(load) = resolve_load(weight, line, one_rms) else {
+                        continue;
+                    };
+                    let reps = |reps: u32| (rounds * reps) as f64;
+                    let lifted = Weight {
+                        weight_man: reps(reps_man) * load.weight_man,
+                        weight_woman: reps(reps_woman) * load.weight_woman,
+                        unit: load.unit,
+                    };
+                    loads.push((line, lifted));
+                }
+            }
+        }
+        loads
+    }
+
+    /// Sets the 1RM of the athlete per movement (by display name, i.e. "Back Squat"),
+    /// so percentage based weightlifting lines are rendered with the absolute load,
+    /// i.e. "5x5 Back Squat @ 70% (98kg)".
+    pub fn set_one_rms(&mut self, one_rms: BTreeMap<String, Weight>) {
+        self.one_rms = one_rms;
+    }
+
+    /// Sets the baseline scores of the athlete per movement (by display name, i.e.
+    /// "Row"), so the monostructural lines are rendered with their target time and
+    /// split, i.e. "500m Row (1:44, 1:44/500m)". See [`pacing::target`].
+    pub fn set_baselines(&mut self, baselines: BTreeMap<String, Baseline>) {
+        self.baselines = baselines;
+    }
+
+    /// Adds a video or an image, shown under the workout after the comments, i.e. the
+    /// demo of the complex of the day.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    /// use wod::media::Media;
+    ///
+    /// let mut workout = create_workout("wl 5x(1+1) clean, split jerk @75%", None, None).unwrap();
+    /// workout.add_media(Media::video("https://youtu.be/9FGilxCbdz8").unwrap());
+    /// assert!(workout.to_string().ends_with("{{< youtube 9FGilxCbdz8 >}}\n\n"));
+    /// ```
+    pub fn add_media(&mut self, media: Media) {
+        self.media.push(media);
+    }
+
+    /// The videos and images of the workout, see [`Workout::add_media`].
+    pub fn media(&self) -> &[Media] {
+        &self.media
+    }
+
+    /// The scaled versions rendered under the workout, see [`Workout::add_scaled`].
+    pub(crate) fn scaled_versions(&self) -> &[Workout] {
+        &self.scaled
+    }
+
+    /// The workout scaled to a level, with the default loads of [`Scaling`]: the loads
+    /// reduced and the harder movements replaced (see [`scaling::substitute`]). The
+    /// name and comments are not kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    /// use wod::scaling::Level;
+    ///
+    /// let workout = create_workout("ft 21-15-9 bar mu, thruster @43/30kg", None, None).unwrap();
+    /// assert_eq!(
+    ///     workout.scaled(Level::Intermediate).to_shorthand(),
+    ///     "ft 21-15-9 chest to bar, thruster @30/21kg"
+    /// );
+    /// ```
+    pub fn scaled(&self, level: Level) -> Workout {
+        self.scaled_with(level, &Scaling::default())
+    }
+
+    /// The workout scaled to a level with the loads of `scaling`, see [`Workout::scaled`].
+    pub fn scaled_with(&self, level: Level, scaling: &Scaling) -> Workout {
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|token| match token {
+                Token::Movement(movement) => Token::Movement(scaling::substitute(movement, level)),
+                Token::Weight(weight) => Token::Weight(scaling.scale_load(weight, level)),
+                Token::Loads(loads) => Token::Loads(
+                    loads
+                        .iter()
+                        .map(|weight| scaling.scale_load(weight, level))
+                        .collect(),
+                ),
+                Token::Progression(start, step) => Token::Progression(
+                    scaling.scale_load(start, level),
+                    scaling.scale_load(step, level),
+                ),
+                token => token.clone(),
+            })
+            .collect();
+        let mut workout = Workout::new(tokens, None, None);
+        workout.parse();
+        workout
+    }
+
+    /// The workout personalized for an athlete, with the movements ruled out by their
+    /// restrictions replaced (see [`AthleteProfile::substitute`]). The loads, the
+    /// name, the comments and the media are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    /// use wod::scaling::{AthleteProfile, Restriction};
+    ///
+    /// let profile = AthleteProfile { restrictions: vec![Restriction::Overhead] };
+    /// let workout = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
+    /// assert_eq!(
+    ///     workout.substitute(&profile).to_shorthand(),
+    ///     "ft 21-15-9 pull up, front squat @43/30kg"
+    /// );
+    /// ```
+    pub fn substitute(&self, profile: &AthleteProfile) -> Workout {
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|token| match token {
+                Token::Movement(movement) => Token::Movement(profile.substitute(movement)),
+                token => token.clone(),
+            })
+            .collect();
+        let mut workout = Workout::new(tokens, self.comments.clone(), self.name.clone());
+        workout.parse();
+        workout.media = self.media.clone();
+        workout
+    }
+
+    /// Adds the scaled version of a level, rendered under the workout in a block
+    /// starting with "*Scaled: intermediate*".
+    pub fn add_scaled(&mut self, level: Level, scaling: &Scaling) {
+        let mut scaled = self.scaled_with(level, scaling);
+        scaled.name = Some(format!("Scaled: {}", level));
+        self.scaled.push(scaled);
+    }
+
     /// This method analyzes the tokens vector and extracts specific workout components
     /// like workout type, movements, repetition types, weights, etc. into their respective
     /// fields for easier access and manipulation.
@@ -197,48 +805,207 @@ impl Workout {
     }
 
     fn parse_simple_workout(&self, start_index: usize) -> SimpleWorkout {
-        let mut simple_workout = SimpleWorkout::new();
+        SimpleWorkout::from_tokens(&self.tokens[start_index..], &self.workout_type)
+    }
 
-        // Reuse the shared parsing logic
-        simple_workout.parse_from_tokens(&self.tokens[start_index..]);
+    fn parse_block(&self, start_index: usize) -> Vec<SimpleWorkout> {
+        // The sub-workouts are the slices of tokens between the semicolons, up to the
+        // end of the block
+        let tokens = &self.tokens[start_index..];
+        let end = tokens
+            .iter()
+            .position(|token| *token == Token::RightBracket)
+            .unwrap_or(tokens.len());
+        tokens[..end]
+            .split(|token| *token == Token::Semicolon)
+            .filter(|tokens| !tokens.is_empty())
+            .map(|tokens| SimpleWorkout::from_tokens(tokens, &self.workout_type))
+            .collect()
+    }
 
-        // TODO: This should cover possible errors, not done yet
-        simple_workout
+    /// Formats the workout as the markdown of the generated files.
+    #[deprecated(
+        since = "0.5.0",
+        note = "use the `Display` implementation instead, i.e. `workout.to_string()`"
+    )]
+    pub fn write(&self) -> String {
+        self.to_string()
     }
 
-    fn parse_block(&self, start_index: usize) -> Vec<SimpleWorkout> {
-        let mut sub_workouts = Vec::new();
-        let mut current_tokens = Vec::new();
-        let mut i = start_index;
-
-        while i < self.tokens.len() {
-            match &self.tokens[i] {
-                Token::RightBracket => {
-                    // End of block - parse the last sub-workout
-                    if !current_tokens.is_empty() {
-                        let mut sub_workout = SimpleWorkout::new();
-                        sub_workout.parse_from_tokens(&current_tokens);
-                        sub_workouts.push(sub_workout);
+    /// Displays the workout in the format of a renderer, to use it with `format!`
+    /// and `println!` in formats other than markdown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    /// use wod::render::Text;
+    ///
+    /// let workout = create_workout("wl 5x5 back squat", None, None).unwrap();
+    /// assert_eq!(
+    ///     format!("{}", workout.display_with(&Text)),
+    ///     "Weightlifting\n\n5x5 Back Squat"
+    /// );
+    /// ```
+    pub fn display_with<'a>(&'a self, renderer: &'a dyn Renderer) -> Rendered<'a> {
+        Rendered {
+            workout: self,
+            renderer,
+        }
+    }
+
+    /// The workout as a line of a wod file in a canonical form, `workout|comments|name`,
+    /// with the workout written as in [`Workout::to_shorthand`] and the comments and
+    /// name trimmed. Useful to find duplicated workouts, or to search for one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let workout = create_workout(
+    ///     "ft 21-15-9  c2b,thruster @ 43/30kg",
+    ///     Some(" T.C. 10' ".to_string()),
+    ///     None,
+    /// ).unwrap();
+    /// assert_eq!(
+    ///     workout.normalized(),
+    ///     "ft 21-15-9 chest to bar, thruster @43/30kg|T.C. 10'"
+    /// );
+    /// let same = create_workout(
+    ///     "ft 21-15-9 chest to bar, thruster @43/30kg",
+    ///     Some("T.C. 10'".to_string()),
+    ///     None,
+    /// ).unwrap();
+    /// assert_eq!(workout, same);
+    /// ```
+    pub fn normalized(&self) -> String {
+        let part = |p: Option<&str>| p.map(str::trim).unwrap_or_default().to_string();
+        let mut parts = vec![
+            self.to_shorthand(),
+            part(self.comments()),
+            part(self.name()),
+        ];
+        while parts.len() > 1 && parts.last().is_some_and(|p| p.is_empty()) {
+            parts.pop();
+        }
+        parts.join("|")
+    }
+
+    /// The differences with another workout: the type, the repetitions per round, and
+    /// the movements, repetitions and load of every line. The name and comments are
+    /// not compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let rx = create_workout("ft 21-15-9 pull up, thruster @43/30kg", None, None).unwrap();
+    /// let scaled = create_workout("ft 21-15-9 strict pull up, thruster @30/20kg", None, None);
+    /// let changes: Vec<String> = rx.diff(&scaled.unwrap()).iter().map(|c| c.to_string()).collect();
+    /// assert_eq!(
+    ///     changes,
+    ///     vec!["Line 1 movements: Pull Up -> Strict Pull Up", "Line 2 load: 43/30kg -> 30/20kg"]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Workout) -> Vec<Change> {
+        diff::changes(self, other)
+    }
+
+    /// Writes the workout back in the shorthand, in a canonical form: the first alias of
+    /// the movements, lowercase, and a single space between the parts. Two workouts that
+    /// only differ in the way they were typed have the same shorthand.
+    ///
+    /// The comments and name are not part of it, see [`Workout::comments`] and
+    /// [`Workout::name`] to write a line of a wod file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let workout = create_workout("ft 21-15-9 pull up,thruster  @ 43/30kg", None, None).unwrap();
+    /// assert_eq!(workout.to_shorthand(), "ft 21-15-9 pull up, thruster @43/30kg");
+    /// ```
+    pub fn to_shorthand(&self) -> String {
+        let mut shorthand = self.workout_type.shorthand();
+        let mut prev: Option<&Token> = None;
+        let mut in_complex = false;
+
+        for (i, token) in self.tokens.iter().enumerate() {
+            let separator = match (prev, token) {
+                (_, Token::WorkoutType(_)) => continue,
+                // The rest is written as a single token, "r2m"
+                (Some(Token::RepType(RepType::RestPeriod(_))), Token::Movement(Movement::Rest)) => {
+                    prev = Some(token);
+                    continue;
+                }
+                (None, _) => " ",
+                (Some(Token::RepType(_)), Token::RepType(_)) => "-",
+                (Some(Token::X | Token::Plus | Token::At | Token::LeftBracket), _) => "",
+                (Some(Token::Semicolon), Token::Superset(_)) => " ",
+                (_, Token::Superset(_)) => " / ",
+                (Some(Token::RepType(_)), Token::X | Token::Plus) => "",
+                (_, Token::Semicolon | Token::RightBracket) => "",
+                (_, Token::At | Token::Note(_)) => " ",
+                (
+                    Some(
+                        Token::Movement(_)
+                        | Token::Weight(_)
+                        | Token::Loads(_)
+                        | Token::Progression(..)
+                        | Token::Rpe(_)
+                        | Token::Note(_),
+                    ),
+                    _,
+                ) => ", ",
+                _ => " ",
+            };
+            shorthand.push_str(separator);
+
+            match token {
+                Token::RepType(rep_type) => {
+                    shorthand.push_str(&rep_type.shorthand());
+                    if in_complex && !matches!(self.tokens.get(i + 1), Some(Token::Plus)) {
+                        shorthand.push(')');
+                        in_complex = false;
                     }
-                    break;
                 }
-                Token::Semicolon => {
-                    // End of current sub-workout
-                    if !current_tokens.is_empty() {
-                        let mut sub_workout = SimpleWorkout::new();
-                        sub_workout.parse_from_tokens(&current_tokens);
-                        sub_workouts.push(sub_workout);
-                        current_tokens.clear();
+                Token::Movement(movement) => shorthand.push_str(movement.shorthand()),
+                Token::Wave(wave) => {
+                    let wave: Vec<String> = wave.iter().map(|r| r.shorthand()).collect();
+                    let _ = write!(shorthand, "({})", wave.join("-"));
+                }
+                Token::X => {
+                    shorthand.push('x');
+                    // Complexes are written as 3x(2+1)
+                    if matches!(self.tokens.get(i + 2), Some(Token::Plus)) {
+                        shorthand.push('(');
+                        in_complex = true;
                     }
                 }
-                token => {
-                    current_tokens.push(token.clone());
+                Token::At => shorthand.push('@'),
+                Token::Plus => shorthand.push('+'),
+                Token::RM(rm) => shorthand.push_str(&rm.to_string()),
+                Token::Weight(weight) => shorthand.push_str(&weight.to_string()),
+                Token::Loads(loads) => shorthand.push_str(&weight::format_loads(loads, ",")),
+                Token::Progression(start, step) => {
+                    let _ = write!(shorthand, "{}+{}", start, step);
                 }
+                Token::Rpe(rpe) => shorthand.push_str(&rpe.shorthand()),
+                Token::LeftBracket => shorthand.push('['),
+                Token::RightBracket => shorthand.push(']'),
+                Token::Semicolon => shorthand.push(';'),
+                Token::Note(note) => {
+                    let _ = write!(shorthand, "({})", note);
+                }
+                Token::Superset(label) => shorthand.push_str(label),
+                Token::WorkoutType(_) => {}
             }
-            i += 1;
+            prev = Some(token);
         }
-
-        sub_workouts
+        shorthand
     }
 
     /// Formats the workout into a human-readable string representation.
@@ -249,37 +1016,58 @@ impl Workout {
     /// # Returns
     ///
     /// A formatted string representation of the workout.
-    pub fn write(&self) -> String {
+    fn markdown(&self) -> String {
+        self.markdown_with(&Markdown::default())
+    }
+
+    /// The markdown of the workout with the layout of its kind and the style of the
+    /// comments and the media of `markdown`, see [`Markdown`].
+    pub(crate) fn markdown_with(&self, markdown: &Markdown) -> String {
+        let layout = markdown.layout(self.workout_type.kind());
         // Start from a markdown section separator
         let mut workout = String::from("---");
 
-        if self.name.is_some() {
-            workout.push_str(&format!("\n\n*{}*", self.name.as_ref().unwrap()));
+        if let Some(name) = &self.name {
+            let _ = write!(workout, "\n\n*{}*", name);
         }
 
         match &self.workout_type {
             WorkoutType::ForTime(_ft) => {
                 workout.push_str(&self.get_header("ft"));
-                workout.push_str(self.write_for_time().as_str());
+                workout.push_str(self.write_lines(layout).as_str());
             }
             WorkoutType::Weightlifting => {
                 workout.push_str(&self.get_header("wl"));
-                workout.push_str(self.write_weightlifting().as_str());
+                workout.push_str(self.write_weightlifting(layout).as_str());
             }
             WorkoutType::EMOM(_emom) => {
                 workout.push_str(&self.get_header("emom"));
-                workout.push_str(self.write_emom().as_str());
+                workout.push_str(self.write_lines(layout).as_str());
+            }
+            WorkoutType::Tabata(_tabata) => {
+                workout.push_str(&self.get_header("tabata"));
+                workout.push_str(self.write_lines(layout).as_str());
+            }
+            WorkoutType::ForLoad(_for_load) => {
+                workout.push_str(&self.get_header("for load"));
+                workout.push_str(self.write_lines(layout).as_str());
             }
-            // WorkoutType::Amrap(_amrap) => {
-            //     workout.push_str(self.write_amrap().as_str());
-            // }
-            _ => {
-                eprintln!("Workout type not implemented {:?}", self.workout_type);
+            WorkoutType::AMRAP(_amrap) => {
+                workout.push_str(&self.get_header("amrap"));
+                workout.push_str(self.write_lines(layout).as_str());
             }
         }
 
-        if self.comments.is_some() {
-            workout.push_str(&self.get_comments());
+        if let Some(comments) = &self.comments {
+            workout.push_str(&Self::get_comments(comments, markdown.comments));
+        }
+        for media in &self.media {
+            let _ = write!(workout, "{}\n\n", media.markdown(markdown.shortcodes));
+        }
+
+        // The scaled versions go in the same section, without the separator
+        for scaled in &self.scaled {
+            workout.push_str(scaled.markdown_with(markdown).trim_start_matches("---\n\n"));
         }
 
         workout
@@ -295,7 +1083,7 @@ impl Workout {
     ///
     /// A formatted header string with appropriate markdown formatting.
     fn get_header(&self, workout_type: &str) -> String {
-        if workout_type == "emom" {
+        if ["emom", "tabata", "for load"].contains(&workout_type) {
             let header = format!("{}", self.workout_type);
             let separator = "\n\n";
             let formatted_header = header
@@ -316,105 +1104,83 @@ impl Workout {
         }
     }
 
-    /// Formats a "For Time" workout into a human-readable string.
-    ///
-    /// Handles special cases like "21-15-9" rep schemes and combines movements
-    /// with their associated weights and repetitions.
+    /// Formats the lines of For Time, EMOM and Tabata workouts, a movement per line
+    /// preceded by the reps per round if any, i.e. "21-15-9".
     ///
     /// # Returns
     ///
-    /// A formatted string representation of the "For Time" workout.
-    fn write_for_time(&self) -> String {
-        // TODO: We need some kind of identifier for workouts that have reps informed as 21-15-9
-        // Check this behaviour within the tokens
-        fn check_contiguous_reps(tokens: &[Token]) -> bool {
-            // Helper function to check whether there are two contiguous movements/rep types,
-            // e.g. 21-15-9 pull up, thruster, to help writing in such a format.
-            tokens.windows(2).any(|window| {
-                matches!(window[0], Token::RepType(_)) && matches!(window[1], Token::RepType(_))
-            })
-        }
-
+    /// A formatted string representation of the lines of the workout.
+    fn write_lines(&self, layout: Layout) -> String {
         let mut workout = String::new();
+        let WorkoutStructure::Simple(simple) = &self.structure else {
+            // Blocks are rejected by `create_workout` for these workouts
+            return workout;
+        };
 
-        if check_contiguous_reps(&self.tokens) {
-            // Handle this properly with blocks inside "ft" workouts
-            let WorkoutStructure::Simple(simple) = &self.structure else {
-                panic!("For Time formatting only supported for simple workouts");
-            };
-
-            let reps_formatted = simple
-                .rep_types
-                .iter()
-                .map(|r| r.to_string())
-                .collect::<Vec<_>>()
-                .join("-");
-
-            // Format the Reps -> TO REMOVE
-            // let reps_formatted = self
-            //     .rep_types
-            //     .iter()
-            //     .map(|r| r.to_string())
-            //     .collect::<Vec<_>>()
-            //     .join("-");
-            workout.push_str(&format!("{}\n\n", reps_formatted));
-            // Format the Movements
-            // In this case, the weights can be placed after the movements, and to associate
-            // them we need to check the tokens order.
-
-            // Iterate over the vector of tokens until we find the first Movement, from that point
-            // we can start adding the movements and the weights.
-            // To determine when to place the \n\n, a movement can have an associated weight.
-            // If we are in a movement, and the previous movement was one, we add the \n\n
-            let mut was_movement = false;
-            for token in self.tokens.iter() {
-                match token {
-                    Token::Movement(movement) => {
-                        if was_movement {
-                            workout.push_str("\n\n");
-                        }
-                        workout.push_str(&format!("- {}", movement));
-                        was_movement = true;
-                    }
-                    Token::Weight(weight) => {
-                        was_movement = false;
-                        workout.push_str(&format!(" @ {}\n\n", weight));
-                    }
-                    _ => {}
-                }
-            }
-            // Add the last \n\n if the last token was a movement
-            if was_movement {
-                workout.push_str("\n\n");
-            }
-        } else {
-            let mut first_rep = true;
-            // Format the Movements
-            for token in self.tokens.iter().skip(1) {
-                match token {
-                    Token::RepType(rep_type) => {
-                        if first_rep {
-                            workout.push_str(&format!("- {} ", rep_type));
-                            first_rep = false;
-                        } else {
-                            workout.push_str(&format!("\n\n- {} ", rep_type));
-                        }
-                    }
-                    // We need to determine whether to add \n\n
-                    Token::Movement(movement) => {
-                        workout.push_str(&format!("{}", movement));
-                    }
-                    Token::Weight(weight) => {
-                        workout.push_str(&format!(" @ {}", weight));
-                    }
-                    _ => {}
-                }
+        if !simple.rounds.is_empty() {
+            for (i, rep_type) in simple.rounds.iter().enumerate() {
+                let separator = if i == 0 { "" } else { "-" };
+                let _ = write!(workout, "{}{}", separator, rep_type);
             }
             workout.push_str("\n\n");
         }
+
+        if layout == Layout::Table {
+            let rows = simple.prescriptions.iter().map(|line| {
+                let reps: Vec<String> = line.reps.iter().map(|r| r.to_string()).collect();
+                let mut load: Vec<String> = line.weight.iter().map(|w| w.to_string()).collect();
+                load.extend(line.rpe.iter().map(|rpe| rpe.to_string()));
+                let mut load = load.join(" @ ");
+                if let Some(target) = pacing::target(line, &self.baselines) {
+                    let _ = write!(load, " ({})", target);
+                }
+                [
+                    reps.join(" "),
+                    movements_cell(line),
+                    load.trim().to_string(),
+                ]
+            });
+            workout.push_str(&movements_table(rows));
+            return workout;
+        }
+
+        for line in simple.prescriptions.iter() {
+            let _ = write!(workout, "- {}\n\n", self.line_text(line));
+        }
         workout
     }
 
+    /// The text of a line of a For Time, AMRAP, EMOM or Tabata workout, as written
+    /// in the list of its movements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::create_workout;
+    ///
+    /// let workout = create_workout("emom-10 5 thruster @43/30kg", None, None).unwrap();
+    /// let line = workout.iter_prescriptions().next().unwrap();
+    /// assert_eq!(workout.line_text(line), "5 Thruster @ 43/30kg");
+    /// ```
+    pub fn line_text(&self, line: &Prescription) -> String {
+        let mut text = String::new();
+        for rep_type in line.reps.iter() {
+            let _ = write!(text, "{} ", rep_type);
+        }
+        write_movements(&mut text, &line.movements);
+        write_note(&mut text, line);
+        if let Some(weight) = &line.weight {
+            let _ = write!(text, " @ {}", weight);
+        }
+        if let Some(rpe) = &line.rpe {
+            let _ = write!(text, " @ {}", rpe);
+        }
+        if let Some(target) = pacing::target(line, &self.baselines) {
+            let _ = write!(text, " ({})", target);
+        }
+        text
+    }
+
     /// Formats a "Weightlifting" workout into a human-readable string.
     ///
     /// Handles repetition schemes like "3x3", "2+2", or "2x(2+2)" and combines
@@ -423,18 +1189,19 @@ impl Workout {
     /// # Returns
     ///
     /// A formatted string representation of the "Weightlifting" workout.
-    fn write_weightlifting(&self) -> String {
+    fn write_weightlifting(&self, layout: Layout) -> String {
         match &self.structure {
+            WorkoutStructure::Simple(simple_workout) if layout == Layout::Table => {
+                self.write_weightlifting_table(simple_workout)
+            }
             WorkoutStructure::Simple(simple_workout) => {
                 self.write_simple_weightlifting(simple_workout)
             }
             WorkoutStructure::Block(sub_workouts) => {
                 let mut workout = String::new();
                 for sub_workout in sub_workouts.iter() {
-                    workout.push_str(&format!(
-                        "- {}",
-                        &self.write_simple_weightlifting(sub_workout)
-                    ));
+                    workout.push_str("- ");
+                    workout.push_str(&self.write_simple_weightlifting(sub_workout));
                 }
                 workout
             }
@@ -444,115 +1211,156 @@ impl Workout {
     fn write_simple_weightlifting(&self, simple_workout: &SimpleWorkout) -> String {
         let mut workout = String::new();
 
-        fn prepare_reps(
-            rep_types: &[RepType],
-            x: &Option<Vec<Token>>,
-            plus: &Option<Vec<Token>>,
-        ) -> String {
-            // The simpler case: 3x3
-            if plus.is_none() {
-                let mut formatted = rep_types
+        let lines = &simple_workout.prescriptions;
+        for (i, line) in lines.iter().enumerate() {
+            // The first line of a superset is preceded by the labels of all of them
+            let letter = line.superset_letter();
+            if letter.is_some() && (i == 0 || lines[i - 1].superset_letter() != letter) {
+                let labels: Vec<&str> = lines[i..]
                     .iter()
-                    .map(|m| m.to_string())
-                    .collect::<Vec<_>>()
-                    .join("x");
-                formatted.push(' ');
-                return formatted;
+                    .take_while(|l| l.superset_letter() == letter)
+                    .filter_map(|l| l.superset.as_deref())
+                    .collect();
+                let _ = write!(workout, "**{} superset**\n\n", labels.join("/"));
             }
-            // Case of 2+2 (not sure I would write this way, but anyway)
-            if x.is_none() {
-                let mut formatted = rep_types
-                    .iter()
-                    .map(|m| m.to_string())
-                    .collect::<Vec<_>>()
-                    .join("+");
-                formatted.push(' ');
-                return formatted;
-            }
-            // Case of 2x(2+2)
-            let mut reps_str = String::new();
-            for (i, rep) in rep_types.iter().enumerate() {
-                reps_str.push_str(&format!("{}", rep));
-                if i == 0 {
-                    reps_str.push_str("x(");
-                    continue;
-                }
-                if i != rep_types.len() - 1 {
-                    reps_str.push('+');
-                }
+            if let Some(label) = &line.superset {
+                let _ = write!(workout, "{}) ", label);
             }
-            reps_str.push_str(") ");
-            reps_str
-        }
 
-        // If the workout contains "...rm", it will go on its own
-        if let Some(rms) = &simple_workout.rm {
-            for (rm, movement) in rms.iter().zip(simple_workout.movements.iter()) {
-                workout.push_str(&format!("{} {}\n", rm, movement));
+            match &line.rm {
+                Some(rm) => {
+                    let _ = write!(workout, "Build to a {}RM ", rm.num);
+                }
+                None => workout.push_str(&prepare_reps(line)),
             }
-            workout.push('\n');
-            return workout;
-        }
-        workout.push_str(&prepare_reps(
-            &simple_workout.rep_types,
-            &simple_workout.x,
-            &simple_workout.plus,
-        ));
 
-        // Format the Movements as a + separated list
-        let movements = simple_workout
-            .movements
-            .iter()
-            .map(|m| m.to_string())
-            .collect::<Vec<_>>()
-            .join(" + ");
-
-        workout.push_str(&movements.to_string());
+            // Format the Movements as a + separated list
+            write_movements(&mut workout, &line.movements);
+            write_note(&mut workout, line);
 
-        // NOTE: Could there be more than one weight?
-        if !simple_workout.weights.is_empty() {
-            workout.push_str(&format!(" @ {}\n\n", simple_workout.weights[0]));
-        } else {
+            if !line.loads.is_empty() {
+                workout.push_str(&self.write_loads(line));
+            } else if let Some(weight) = &line.weight {
+                let _ = write!(workout, " @ {}", weight);
+                // The target of a rep max is a percentage of the 1RM, "5RM @ 85% of 1RM"
+                if line.rm.is_some() && weight.unit == LoadUnit::Percent {
+                    workout.push_str(" of 1RM");
+                }
+                if let Some(load) = self.absolute_load(line) {
+                    let _ = write!(workout, " ({})", load);
+                }
+            }
+            if let Some(rpe) = &line.rpe {
+                let _ = write!(workout, " @ {}", rpe);
+            }
             workout.push_str("\n\n");
         }
 
         workout
     }
 
-    /// Formats an "EMOM" (Every Minute On the Minute) workout into a human-readable string.
-    ///
-    /// # Returns
-    ///
-    /// A formatted string representation of the "EMOM" workout.
-    fn write_emom(&self) -> String {
-        let mut workout = String::new();
-        // Format the Rounds
-        let mut first_rep = true;
-        // TODO: This is the same function used in the For Time (general case),
-        // Refactor to avoid code duplication
-        for token in self.tokens.iter().skip(1) {
-            match token {
-                Token::RepType(rep_type) => {
-                    if first_rep {
-                        workout.push_str(&format!("- {} ", rep_type));
-                        first_rep = false;
-                    } else {
-                        workout.push_str(&format!("\n\n- {} ", rep_type));
-                    }
+    /// Formats a "Weightlifting" workout as a table, with a row per line: the label of
+    /// the superset along with the repetitions, the movements and the load. The loads
+    /// per set are written as a list, as a table can't be nested in a cell.
+    fn write_weightlifting_table(&self, simple_workout: &SimpleWorkout) -> String {
+        let rows = simple_workout.prescriptions.iter().map(|line| {
+            let mut reps = String::new();
+            if let Some(label) = &line.superset {
+                let _ = write!(reps, "{}) ", label);
+            }
+            match &line.rm {
+                Some(rm) => {
+                    let _ = write!(reps, "Build to a {}RM", rm.num);
                 }
-                // We need to determine whether to add \n\n
-                Token::Movement(movement) => {
-                    workout.push_str(&format!("{}", movement));
+                None => reps.push_str(&prepare_reps(line)),
+            }
+
+            let mut load = Vec::new();
+            if !line.loads.is_empty() {
+                let mut loads = weight::format_loads(&line.loads, ", ");
+                let resolved: Option<Vec<Weight>> = line
+                    .loads
+                    .iter()
+                    .map(|w| match w.unit {
+                        LoadUnit::Percent => resolve_load(w, line, &self.one_rms),
+                        _ => None,
+                    })
+                    .collect();
+                if let Some(resolved) = resolved {
+                    let _ = write!(loads, " ({})", weight::format_loads(&resolved, ", "));
                 }
-                Token::Weight(weight) => {
-                    workout.push_str(&format!(" @ {}", weight));
+                load.push(loads);
+            } else if let Some(weight) = &line.weight {
+                let mut text = weight.to_string();
+                if line.rm.is_some() && weight.unit == LoadUnit::Percent {
+                    text.push_str(" of 1RM");
                 }
-                _ => {}
+                if let Some(absolute) = self.absolute_load(line) {
+                    let _ = write!(text, " ({})", absolute);
+                }
+                load.push(text);
             }
+            load.extend(line.rpe.iter().map(|rpe| rpe.to_string()));
+            [
+                reps.trim().to_string(),
+                movements_cell(line),
+                load.join(" @ "),
+            ]
+        });
+        movements_table(rows)
+    }
+
+    /// Formats the loads of every set of a line: a table with a row per set when
+    /// there's a load for each of them, otherwise a comma separated list, i.e.
+    /// " @ 70, 75, 80%". The rows of a wave are its repetitions paired with the
+    /// loads. Percentages are followed by the absolute loads if the 1RM is known.
+    fn write_loads(&self, line: &Prescription) -> String {
+        let absolute = |weight: &Weight| match weight.unit {
+            LoadUnit::Percent => resolve_load(weight, line, &self.one_rms),
+            _ => None,
+        };
+        let rows = if line.wave.is_empty() {
+            line.set_loads().len()
+        } else {
+            line.wave.len()
+        };
+        if rows != line.loads.len() {
+            let mut loads = format!(" @ {}", weight::format_loads(&line.loads, ", "));
+            let resolved: Option<Vec<Weight>> = line.loads.iter().map(absolute).collect();
+            if let Some(resolved) = resolved {
+                let _ = write!(loads, " ({})", weight::format_loads(&resolved, ", "));
+            }
+            return loads;
         }
-        workout.push_str("\n\n");
 
-        workout
+        let header = if line.wave.is_empty() { "Set" } else { "Reps" };
+        let mut table = format!("\n\n| {} | Load |\n| --- | --- |", header);
+        for (set, weight) in line.loads.iter().enumerate() {
+            match line.wave.get(set) {
+                Some(reps) => {
+                    let _ = write!(table, "\n| {} | {}", reps, weight);
+                }
+                None => {
+                    let _ = write!(table, "\n| {} | {}", set + 1, weight);
+                }
+            }
+            if let Some(load) = absolute(weight) {
+                let _ = write!(table, " ({})", load);
+            }
+            table.push_str(" |");
+        }
+        table
+    }
+
+    /// Computes the absolute load of a percentage based line, from the 1RM of the
+    /// first movement that has one.
+    fn absolute_load(&self, line: &Prescription) -> Option<Weight> {
+        match &line.weight {
+            Some(weight) if weight.unit == LoadUnit::Percent => {
+                resolve_load(weight, line, &self.one_rms)
+            }
+            _ => None,
+        }
     }
 
     /// Formats the workout comments into a human-readable string.
@@ -560,22 +1368,119 @@ impl Workout {
     /// # Returns
     ///
     /// A formatted string representation of the workout comments.
-    fn get_comments(&self) -> String {
-        let prepared_contents = self.comments.as_ref().unwrap();
-        let comments: String = if prepared_contents.contains("\n") {
-            prepared_contents
-                .split("\n")
-                .map(|part| format!("*{}*", part))
+    fn get_comments(prepared_contents: &str, style: CommentStyle) -> String {
+        let comments = match style {
+            // Every line in italics, the markdown of the comments would break them
+            CommentStyle::Emphasis => prepared_contents
+                .split('\n')
+                .map(|part| format!("*{}*", render::escape_markdown(part)))
                 .collect::<Vec<_>>()
-                .join("\n")
-        } else {
-            format!("*{}*", prepared_contents)
+                .join("\n"),
+            CommentStyle::Quote | CommentStyle::Markdown => prepared_contents.to_string(),
         };
+        let comments = i18n::text(Message::Comments, &[&comments]);
+        let comments = match style {
+            CommentStyle::Quote => comments
+                .split('\n')
+                .map(|line| format!("> {}", line).trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => comments,
+        };
+
+        format!("{}\n\n", comments)
+    }
+}
 
-        format!("Comments: {}\n\n", comments)
+/// The repetitions of a weightlifting line followed by a space, i.e. "5x5 " or
+/// "3x(2+1) ", the sets of a wave are written as "3x(3-2-1) ".
+fn prepare_reps(line: &Prescription) -> String {
+    let (reps, separator) = if line.wave.is_empty() {
+        (&line.reps, "+")
+    } else {
+        (&line.wave, "-")
+    };
+    let reps = reps
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(separator);
+    match &line.sets {
+        // The simpler case: 3x3
+        Some(sets) if line.reps.len() == 1 => format!("{}x{} ", sets, reps),
+        // Case of 2x(2+2) or 3x(3-2-1)
+        Some(sets) => format!("{}x({}) ", sets, reps),
+        // Case of 2+2 (not sure I would write this way, but anyway)
+        None => format!("{} ", reps),
     }
 }
 
+/// The movements of a line along with its note, the middle cell of the table layout.
+fn movements_cell(line: &Prescription) -> String {
+    let mut cell = String::new();
+    write_movements(&mut cell, &line.movements);
+    write_note(&mut cell, line);
+    cell
+}
+
+/// The table of the [`Layout::Table`], a row per line with its repetitions, movements
+/// and load, see `markdown::table_row` for the way it's read back.
+fn movements_table(rows: impl Iterator<Item = [String; 3]>) -> String {
+    let mut table = String::from("| Reps | Movement | Load |\n| --- | --- | --- |\n");
+    for [reps, movements, load] in rows {
+        let _ = writeln!(table, "| {} | {} | {} |", reps, movements, load);
+    }
+    table.push('\n');
+    table
+}
+
+/// Writes the names of the movements of a line separated by " + ", i.e. "Clean + Split Jerk".
+fn write_movements(out: &mut String, movements: &[Movement]) {
+    for (i, movement) in movements.iter().enumerate() {
+        if i > 0 {
+            out.push_str(" + ");
+        }
+        out.push_str(movement.name());
+    }
+}
+
+/// Writes the note of a line after its movements, i.e. "Pull Up (strict)".
+fn write_note(out: &mut String, line: &Prescription) {
+    if let Some(note) = &line.note {
+        let _ = write!(out, " ({})", note);
+    }
+}
+
+/// A load of a line, resolving a percentage from the 1RM of the first movement
+/// that has one in `one_rms`.
+/// Adds a load to the totals, the one of its unit.
+fn add_load(totals: &mut Vec<Weight>, load: &Weight) {
+    match totals.iter_mut().find(|t| t.unit == load.unit) {
+        Some(total) => {
+            total.weight_man += load.weight_man;
+            total.weight_woman += load.weight_woman;
+        }
+        None => totals.push(load.clone()),
+    }
+}
+
+fn resolve_load(
+    weight: &Weight,
+    line: &Prescription,
+    one_rms: &BTreeMap<String, Weight>,
+) -> Option<Weight> {
+    if weight.unit != LoadUnit::Percent {
+        return Some(weight.clone());
+    }
+    let one_rm = line.movements.iter().find_map(|m| one_rms.get(m.name()))?;
+    let load = |rm: f64| (rm * weight.weight_man / 100.0).round();
+    Some(Weight {
+        weight_man: load(one_rm.weight_man),
+        weight_woman: load(one_rm.weight_woman),
+        unit: one_rm.unit,
+    })
+}
+
 /// Creates a `Workout` object from a workout string and optional comments.
 ///
 /// This function parses a workout string into a structured `Workout` object by:
@@ -588,7 +1493,7 @@ impl Workout {
 /// * `comments` - Optional comments to be associated with the workout
 ///
 /// # Returns
-/// * `Result<Workout, LexerError>` - A `Workout` object if parsing succeeds, or a `LexerError` if tokenization fails
+/// * `Result<Workout, WodError>` - A `Workout` object if parsing succeeds, or a `WodError` if it fails
 ///
 /// # Examples
 ///
@@ -620,16 +1525,32 @@ impl Workout {
 ///
 /// # Errors
 ///
-/// This function will return a `LexerError` if the `Lexer` fails to tokenize the input string.
+/// This function will return a `WodError::Lexer` if the `Lexer` fails to tokenize the input string,
+/// or `WodError::InvalidWorkout` if it uses a block or a superset in a workout other than
+/// weightlifting.
 pub fn create_workout(
     workout: &str,
     comments: Option<String>,
     name: Option<String>,
-) -> Result<Workout, LexerError> {
+) -> Result<Workout, WodError> {
     let mut lexer = Lexer::new(workout);
     let tokens = lexer.tokenize()?;
     let mut workout = Workout::new(tokens, comments, name);
     workout.parse();
+    if matches!(workout.structure, WorkoutStructure::Block(_))
+        && workout.workout_type != WorkoutType::Weightlifting
+    {
+        return Err(WodError::InvalidWorkout(
+            "blocks `[...]` are only supported in weightlifting workouts".to_string(),
+        ));
+    }
+    if workout.workout_type != WorkoutType::Weightlifting
+        && workout.iter_prescriptions().any(|p| p.superset.is_some())
+    {
+        return Err(WodError::InvalidWorkout(
+            "supersets `A1 ... / A2 ...` are only supported in weightlifting workouts".to_string(),
+        ));
+    }
     Ok(workout)
 }
 
@@ -640,6 +1561,86 @@ mod tests {
     use crate::workout_types::for_time::ForTime;
     use crate::workout_types::workout_type::WorkoutType;
 
+    #[test]
+    fn test_to_shorthand() {
+        let cases = [
+            ("ft 21-15-9 pull up, thruster @43/30kg", None),
+            (
+                "4rd 21 box jump over, 15 bar mu",
+                Some("4rd 21 box jump over, 15 bar muscle up"),
+            ),
+            ("emom-12-3m-r1m 15cal row, 12 toes to bar", None),
+            ("wl 3x(2+1) clean, split jerk @85%", None),
+            ("wl [1rm snatch; 1rm clean and jerk]", None),
+            (
+                "ft 400m run, r2m, 15/12cal bike, max du",
+                Some("ft 400m run, r2m, 15/12cal bike, max double under"),
+            ),
+            ("tabata-6-30s-15s 10 air squat", None),
+            ("wl 5x5  back squat @ 75%", Some("wl 5x5 back squat @75%")),
+            (
+                "ft 21-15-9 pull up ( strict ),thruster @43/30kg",
+                Some("ft 21-15-9 pull up (strict), thruster @43/30kg"),
+            ),
+            (
+                "wl 3x3 back squat @ 70-75-80%",
+                Some("wl 3x3 back squat @70,75,80%"),
+            ),
+            ("wl 3x(3-2-1) power clean @80,85,90%", None),
+            (
+                "wl 6x2 snatch @ start 70% add 2% per set",
+                Some("wl 6x2 snatch @70%+2%"),
+            ),
+            ("wl 5x3 back squat @60/40kg+2.5kg, 1rm snatch", None),
+            ("amrap-75s 10 burpee", None),
+            ("amrap-90s 10 burpee", Some("amrap-1.5 10 burpee")),
+            ("amrap-7.5 10 burpee", None),
+            (
+                "wl 3x5 back squat @ RPE8, 3x3 deadlift @rpe7.5-8",
+                Some("wl 3x5 back squat @rpe8, 3x3 deadlift @rpe7.5-8"),
+            ),
+            (
+                "wl a1 4x8 bench press @70%/A2 4x10 ring row",
+                Some("wl A1 4x8 bench press @70% / A2 4x10 ring row"),
+            ),
+        ];
+        for (workout, expected) in cases {
+            let shorthand = create_workout(workout, None, None).unwrap().to_shorthand();
+            assert_eq!(shorthand, expected.unwrap_or(workout));
+            // The shorthand is parsed back to the same workout
+            assert_eq!(
+                create_workout(&shorthand, None, None).unwrap(),
+                create_workout(workout, None, None).unwrap()
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let workout = create_workout(
+            "wl [3x(2+1) clean, split jerk @85%; 1rm snatch]",
+            Some("T.C. 20'".to_string()),
+            None,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&workout).unwrap();
+        assert_eq!(serde_json::from_str::<Workout>(&json).unwrap(), workout);
+    }
+
+    #[test]
+    fn test_create_workout_block_not_weightlifting() {
+        let err = create_workout("ft [21 pull up; 10 thruster]", None, None).unwrap_err();
+        assert!(matches!(err, WodError::InvalidWorkout(_)));
+        assert!(create_workout("wl [1rm snatch; 1rm clean and jerk]", None, None).is_ok());
+    }
+
+    #[test]
+    fn test_create_workout_superset_not_weightlifting() {
+        let err = create_workout("ft A1 21 pull up / A2 21 push up", None, None).unwrap_err();
+        assert!(matches!(err, WodError::InvalidWorkout(_)));
+    }
+
     #[test]
     fn test_workout_parse() {
         let tokens = vec![
@@ -658,8 +1659,9 @@ mod tests {
             panic!("For Time formatting only supported for simple workouts");
         };
 
-        assert_eq!(simple.movements.len(), 2);
-        assert_eq!(simple.rep_types.len(), 3);
+        assert_eq!(simple.movements().count(), 2);
+        assert_eq!(simple.rounds.len(), 3);
+        assert_eq!(simple.prescriptions.len(), 2);
         assert_eq!(
             workout.workout_type,
             WorkoutType::ForTime(ForTime {
@@ -669,6 +1671,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_prescriptions() {
+        let workout = create_workout(
+            "3rd 400m run, 21 kettlebell swing @24/16kg, 12 pull up",
+            None,
+            None,
+        )
+        .unwrap();
+        let WorkoutStructure::Simple(simple) = &workout.structure else {
+            panic!("expected a simple workout");
+        };
+        assert!(simple.rounds.is_empty());
+        let lines: Vec<(String, String, Option<String>)> = simple
+            .prescriptions
+            .iter()
+            .map(|p| {
+                (
+                    p.reps[0].to_string(),
+                    p.movements[0].to_string(),
+                    p.weight.as_ref().map(|w| w.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("400m".to_string(), "Run".to_string(), None),
+                (
+                    "21".to_string(),
+                    "Kettlebell Swing".to_string(),
+                    Some("24/16kg".to_string())
+                ),
+                ("12".to_string(), "Pull Up".to_string(), None),
+            ]
+        );
+
+        // Each movement is a line, even without repetitions
+        let workout = create_workout("ft 21 pull up, thruster", None, None).unwrap();
+        assert!(workout
+            .to_string()
+            .ends_with("- 21 Pull Up\n\n- Thruster\n\n"));
+    }
+
+    #[test]
+    fn test_movements_with_load() {
+        let workout = create_workout(
+            "wl [3x(2+1) clean, split jerk @85%; 1rm snatch]",
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(workout.movements().count(), 3);
+        let loaded: Vec<&Movement> = workout.movements_with_load().map(|(m, _)| m).collect();
+        assert_eq!(loaded, vec![&Movement::Clean, &Movement::SplitJerk]);
+        assert!(workout.machines().is_empty());
+    }
+
+    #[test]
+    fn test_normalized_equality() {
+        let fran = |w: &str, name: &str| create_workout(w, None, Some(name.to_string())).unwrap();
+        let mut workout = fran("ft 21-15-9 c2b, thruster @43/30kg", "Fran");
+        assert_eq!(
+            workout,
+            fran("ft 21-15-9 chest to bar,thruster  @ 43/30kg", " Fran")
+        );
+        assert_ne!(workout, fran("ft 21-15-9 c2b, thruster @43/30kg", "Grace"));
+        assert_ne!(workout, fran("ft 21-15-9 c2b, thruster @40/30kg", "Fran"));
+
+        workout.set_one_rms(BTreeMap::from([(
+            "Thruster".to_string(),
+            "80kg".parse().unwrap(),
+        )]));
+        assert_eq!(workout, fran("ft 21-15-9 c2b, thruster @43/30kg", "Fran"));
+        assert_eq!(
+            workout.normalized(),
+            "ft 21-15-9 chest to bar, thruster @43/30kg||Fran"
+        );
+    }
+
+    #[test]
+    fn test_rm_lines() {
+        let mut workout = create_workout(
+            "wl 1rm snatch, 5rm back squat @85%, 3x3 deadlift",
+            None,
+            None,
+        )
+        .unwrap();
+        workout.set_one_rms(BTreeMap::from([(
+            "Back Squat".to_string(),
+            "140kg".parse().unwrap(),
+        )]));
+        assert_eq!(
+            workout.to_string(),
+            "---\n\n**Weightlifting**\n\nBuild to a 1RM Snatch\n\nBuild to a 5RM Back Squat @ 85% of 1RM (119kg)\n\n3x3 Deadlift\n\n"
+        );
+    }
+
+    #[test]
+    fn test_tonnage() {
+        let one_rms = BTreeMap::from([("Clean".to_string(), "100kg".parse().unwrap())]);
+        let tonnage = |w: &str| {
+            create_workout(w, None, None)
+                .unwrap()
+                .tonnage(&one_rms)
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<_>>()
+        };
+        // 3 sets of 3 repetitions at 85kg
+        assert_eq!(tonnage("wl 3x(2+1) clean, split jerk @85%"), vec!["765kg"]);
+        // Without a 1RM the percentage can't be resolved
+        assert!(tonnage("wl 5x5 back squat @75%").is_empty());
+        assert_eq!(
+            tonnage("3rd 400m run, 21 kettlebell swing @24/16kg, 12 pull up"),
+            vec!["1512/1008kg"]
+        );
+        assert_eq!(
+            tonnage("emom-10-alt 10 deadlift @100kg, 10 burpee"),
+            vec!["5000kg"]
+        );
+        assert_eq!(
+            tonnage("wl [5x3 deadlift @140kg; 3x10 bench press @50kg]"),
+            vec!["3600kg"]
+        );
+        // Every set with its own load, and the waves with the repetitions paired
+        assert_eq!(tonnage("wl 3x5 deadlift @100,110,120kg"), vec!["1650kg"]);
+        assert_eq!(tonnage("wl 3x(3-2-1) clean @80-85-90%"), vec!["1500kg"]);
+        assert!(tonnage("amrap-12 10 burpee, 20cal row").is_empty());
+    }
+
     #[test]
     fn test_workout_write() {
         let tokens = vec![
@@ -684,7 +1816,11 @@ mod tests {
         workout.parse();
 
         let expected = "---\n\n**For Time**\n\n21-15-9\n\n- Pull Up\n\n- Thruster\n\n";
-        assert_eq!(workout.write(), expected);
+        assert_eq!(workout.to_string(), expected);
+        assert_eq!(format!("{}", workout), expected);
+        #[allow(deprecated)]
+        let written = workout.write();
+        assert_eq!(written, expected);
     }
 
     #[test]
@@ -693,20 +1829,22 @@ mod tests {
         let expected = Workout {
             workout_type: WorkoutType::from_str("ft").unwrap(),
             structure: WorkoutStructure::Simple(SimpleWorkout {
-                movements: vec![
-                    Movement::from_str("pull up").unwrap(),
-                    Movement::from_str("thruster").unwrap(),
-                ],
-                rep_types: vec![
+                rounds: vec![
                     RepType::from_str("21").unwrap(),
                     RepType::from_str("15").unwrap(),
                     RepType::from_str("9").unwrap(),
                 ],
-                weights: vec![Weight::from_str("43/30kg").unwrap()],
-                x: None,
-                at: vec![Token::At].into(),
-                plus: None,
-                rm: None,
+                prescriptions: vec![
+                    Prescription {
+                        movements: vec![Movement::from_str("pull up").unwrap()],
+                        ..Default::default()
+                    },
+                    Prescription {
+                        movements: vec![Movement::from_str("thruster").unwrap()],
+                        weight: Some(Weight::from_str("43/30kg").unwrap()),
+                        ..Default::default()
+                    },
+                ],
             }),
             tokens: vec![
                 Token::WorkoutType(WorkoutType::from_str("ft").unwrap()),
@@ -720,16 +1858,43 @@ mod tests {
             ],
             comments: None,
             name: None,
+            one_rms: BTreeMap::new(),
+            baselines: BTreeMap::new(),
+            scaled: Vec::new(),
+            media: Vec::new(),
         };
 
         assert_eq!(create_workout(workout, None, None).unwrap(), expected);
     }
 
+    #[test]
+    fn test_add_scaled() {
+        let mut workout = create_workout(
+            "ft 21-15-9 bar mu, thruster @43/30kg",
+            Some("T.C. 10'".to_string()),
+            Some("Fran".to_string()),
+        )
+        .unwrap();
+        workout.add_scaled(Level::Beginner, &Scaling::default());
+        assert_eq!(
+            workout.to_string(),
+            "---\n\n*Fran*\n\n**For Time**\n\n21-15-9\n\n- Bar Muscle Up\n\n- Thruster @ 43/30kg\n\n\
+             Comments: *T.C. 10'*\n\n\
+             *Scaled: beginner*\n\n**For Time**\n\n21-15-9\n\n- Pull Up\n\n- Thruster @ 22/15kg\n\n"
+        );
+        // The scaled versions are not part of the workout
+        assert_eq!(
+            workout.to_shorthand(),
+            "ft 21-15-9 bar muscle up, thruster @43/30kg"
+        );
+    }
+
     #[test]
     fn test_create_workout_error() {
         let workout = "ft 21-15-9 pulup, thruster @ 43/30kg";
         let expected =
-            "Invalid Movement: Invalid movement: `pulup`, did you mean: `pull up`?".to_string();
+            "Invalid Movement: Invalid movement: `pulup`, did you mean: `pull up`, `push up` or `v up`?"
+                .to_string();
         let workout = create_workout(workout, None, None);
         assert_eq!(workout.unwrap_err().to_string(), expected);
     }
@@ -741,7 +1906,7 @@ mod tests {
         "---\n\n**For Time**\n\n21-15-9\n\n- Pull Up\n\n- Thruster @ 43/30kg\n\nComments: *blabla*\n\n".to_string();
         let comments = Some("blabla".to_string());
         let workout = create_workout(workout_str, comments, None).unwrap();
-        let content = workout.write();
+        let content = workout.to_string();
         assert_eq!(content, expected);
 
         // Test the case of \n in the comments.
@@ -749,10 +1914,34 @@ mod tests {
         "---\n\n**For Time**\n\n21-15-9\n\n- Pull Up\n\n- Thruster @ 43/30kg\n\nComments: *blabla*\n*other line*\n\n".to_string();
         let comments = Some("blabla\nother line".to_string());
         let workout = create_workout(workout_str, comments, None).unwrap();
-        let content = workout.write();
+        let content = workout.to_string();
         assert_eq!(content, expected);
     }
 
+    #[test]
+    fn test_write_with_one_rms() {
+        let mut workout = create_workout("wl 5x5 back squat @70%", None, None).unwrap();
+        workout.set_one_rms(BTreeMap::from([(
+            "Back Squat".to_string(),
+            Weight::from_str("140kg").unwrap(),
+        )]));
+        assert_eq!(
+            workout.to_string(),
+            "---\n\n**Weightlifting**\n\n5x5 Back Squat @ 70% (98kg)\n\n"
+        );
+
+        // Without a 1RM for the movement the line is left untouched
+        let mut workout = create_workout("wl 5x5 snatch @70%", None, None).unwrap();
+        workout.set_one_rms(BTreeMap::from([(
+            "Back Squat".to_string(),
+            Weight::from_str("140kg").unwrap(),
+        )]));
+        assert_eq!(
+            workout.to_string(),
+            "---\n\n**Weightlifting**\n\n5x5 Snatch @ 70%\n\n"
+        );
+    }
+
     #[test]
     fn test_create_workout_with_name() {
         let workout_str = "ft 21-15-9 pull up, thruster @ 43/30kg";
@@ -761,7 +1950,48 @@ mod tests {
                 .to_string();
         let name = Some("Fran".to_string());
         let workout = create_workout(workout_str, None, name).unwrap();
-        let content = workout.write();
+        let content = workout.to_string();
         assert_eq!(content, expected);
     }
+
+    #[test]
+    fn test_markdown_table() {
+        let table = |workout: &Workout| {
+            workout.markdown_with(&Markdown {
+                layouts: BTreeMap::from([(workout.workout_type.kind(), Layout::Table)]),
+                ..Default::default()
+            })
+        };
+        let mut workout = create_workout(
+            "wl A1 4x8 bench press (paused) @60% / A2 4x10 ring row, 3x(3-2-1) snatch @70,75,80%",
+            None,
+            None,
+        )
+        .unwrap();
+        workout.set_one_rms(BTreeMap::from([(
+            "Bench Press".to_string(),
+            Weight::from_str("100kg").unwrap(),
+        )]));
+        assert_eq!(
+            table(&workout),
+            "---\n\n**Weightlifting**\n\n| Reps | Movement | Load |\n| --- | --- | --- |\n\
+             | A1) 4x8 | Bench Press (paused) | 60% (60kg) |\n\
+             | A2) 4x10 | Ring Row |  |\n\
+             | 3x(3-2-1) | Snatch | 70, 75, 80% |\n\n"
+        );
+
+        // The metcons keep the repetitions per round and the details of the intervals
+        let workout =
+            create_workout("emom-12-3m-r1m 15cal row, 12 toes to bar", None, None).unwrap();
+        assert_eq!(
+            table(&workout),
+            "---\n\n**EMOM 12 minutes**\n\nwork every 3 minutes, rest 1 minute\n\n\
+             | Reps | Movement | Load |\n| --- | --- | --- |\n\
+             | 15 calories | Row |  |\n| 12 | Toes To Bar |  |\n\n"
+        );
+        assert_eq!(
+            workout.markdown_with(&Markdown::default()),
+            workout.to_string()
+        );
+    }
 }
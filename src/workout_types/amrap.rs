@@ -1,43 +1,56 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::i18n::{self, Message};
+use crate::rep_types::check_duration;
+
 /// Represents an As Many Reps As Possible (AMRAP) workout.
 ///
 /// This struct is used to define a workout session where the goal is to perform as many repetitions
-/// as possible within a specified number of minutes.
+/// as possible within a specified time.
 ///
 /// # Examples
 ///
 /// ## Parsing
 ///
-/// The format should be `amrap-<minutes>`, where `<minutes>` is the number of minutes for the AMRAP.
-/// If the minutes part is missing or invalid, it defaults to 1 minute.
+/// The format should be `amrap-<minutes>`, where `<minutes>` is the number of minutes for the AMRAP,
+/// with decimals if needed, or `amrap-<seconds>s` for the shorter ones.
+/// If the minutes part is missing, it defaults to 1 minute.
 ///
 /// ```
 /// use wod::AMRAP;
 ///
 /// let amrap: AMRAP = "amrap-10".parse().unwrap();
-/// assert_eq!(amrap, AMRAP { minutes: 10 });
+/// assert_eq!(amrap, AMRAP { seconds: 600 });
+///
+/// assert_eq!("amrap-90s".parse::<AMRAP>().unwrap(), AMRAP { seconds: 90 });
+/// assert_eq!("amrap-7.5".parse::<AMRAP>().unwrap(), AMRAP { seconds: 450 });
 ///
 /// let amrap_default: AMRAP = "amrap-".parse().unwrap();
-/// assert_eq!(amrap_default, AMRAP { minutes: 1 });
+/// assert_eq!(amrap_default, AMRAP { seconds: 60 });
 /// ```
 ///
 /// ## Display
 ///
 /// Formats the `AMRAP` for display.
 ///
-/// The output format is `AMRAP <minutes> minutes`.
+/// The output format is `AMRAP <minutes> minutes`, with a decimal for the tenths of a
+/// minute, or `AMRAP <seconds> seconds` below a minute or when the minutes would need
+/// more decimals.
 ///
 /// ```
 /// use wod::AMRAP;
-/// let amrap = AMRAP { minutes: 10 };
+/// let amrap = AMRAP { seconds: 600 };
 /// assert_eq!(format!("{}", amrap), "AMRAP 10 minutes".to_string());
+/// assert_eq!(AMRAP { seconds: 450 }.to_string(), "AMRAP 7.5 minutes");
+/// assert_eq!(AMRAP { seconds: 45 }.to_string(), "AMRAP 45 seconds");
+/// assert_eq!(AMRAP { seconds: 75 }.to_string(), "AMRAP 75 seconds");
 /// ```
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AMRAP {
-    /// The number of minutes allocated for the workout.
-    pub minutes: u8,
+    /// The duration of the workout, in seconds.
+    pub seconds: u32,
     // TODO: For more complex AMRAPs
     // // The number of sets to perform
     // sets: u32,
@@ -59,22 +72,73 @@ impl FromStr for AMRAP {
         }
         let number_part = parts[1];
 
-        // Parse the time domain
-        let minutes = if number_part.is_empty() {
-            1 // Default to 1 if no number is present
+        // Parse the time domain, in minutes or seconds with "90s"
+        let seconds = if number_part.is_empty() {
+            60 // Default to 1 minute if no number is present
+        } else if let Some(seconds) = number_part.strip_suffix('s') {
+            let seconds = seconds
+                .parse::<u32>()
+                .map_err(|_| "Invalid number format".to_string())?;
+            check_duration("AMRAP", s, seconds, 1)?
+        } else if number_part.contains('.') {
+            let minutes = number_part
+                .parse::<f64>()
+                .map_err(|_| "Invalid number format".to_string())?;
+            // Only whole seconds, "7.5" minutes are 450 seconds
+            let seconds = minutes * 60.0;
+            if seconds.fract() != 0.0 || seconds > u32::MAX as f64 {
+                return Err(format!(
+                    "Invalid AMRAP: `{}`, expected a whole number of seconds",
+                    s
+                ));
+            }
+            check_duration("AMRAP", s, seconds as u32, 1)?
         } else {
-            number_part
-                .parse::<u8>()
-                .map_err(|_| "Invalid number format".to_string())?
+            let minutes = number_part
+                .parse::<u32>()
+                .map_err(|_| "Invalid number format".to_string())?;
+            check_duration("AMRAP", s, minutes, 60)? * 60
         };
 
-        Ok(AMRAP { minutes })
+        Ok(AMRAP { seconds })
     }
 }
 
 impl fmt::Display for AMRAP {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(formatter, "AMRAP {} minutes", self.minutes)
+        let text = match self.decimal_minutes() {
+            Some(minutes) => i18n::text(Message::Amrap, &[&minutes]),
+            None => i18n::text(Message::AmrapSeconds, &[&self.seconds]),
+        };
+        write!(formatter, "{}", text)
+    }
+}
+
+impl AMRAP {
+    /// The duration in minutes, `None` if it isn't a whole number of them.
+    pub fn minutes(&self) -> Option<u32> {
+        self.seconds.is_multiple_of(60).then_some(self.seconds / 60)
+    }
+
+    /// The duration as it's shown, in minutes with a decimal for the tenths of a minute,
+    /// "7.5". `None` below a minute, or when it would need more decimals.
+    fn decimal_minutes(&self) -> Option<String> {
+        if self.seconds < 60 || !self.seconds.is_multiple_of(6) {
+            return None;
+        }
+        Some(match self.minutes() {
+            Some(minutes) => minutes.to_string(),
+            None => format!("{}.{}", self.seconds / 60, self.seconds % 60 / 6),
+        })
+    }
+
+    /// The prefix of the workout in the shorthand, in the same unit it's shown:
+    /// "amrap-12" and "amrap-7.5", or "amrap-45s" for the seconds.
+    pub(crate) fn shorthand(&self) -> String {
+        match self.decimal_minutes() {
+            Some(minutes) => format!("amrap-{}", minutes),
+            None => format!("amrap-{}s", self.seconds),
+        }
     }
 }
 
@@ -84,21 +148,82 @@ mod tests {
 
     #[test]
     fn test_amrap() {
-        assert_eq!(AMRAP::from_str("amrap-10").unwrap(), AMRAP { minutes: 10 });
-        assert_eq!(AMRAP::from_str("amrap-").unwrap(), AMRAP { minutes: 1 });
+        assert_eq!(AMRAP::from_str("amrap-10").unwrap(), AMRAP { seconds: 600 });
+        assert_eq!(AMRAP::from_str("amrap-").unwrap(), AMRAP { seconds: 60 });
+        assert_eq!(AMRAP::from_str("amrap-90s").unwrap(), AMRAP { seconds: 90 });
+        assert_eq!(
+            AMRAP::from_str("amrap-7.5").unwrap(),
+            AMRAP { seconds: 450 }
+        );
+        assert_eq!(
+            AMRAP::from_str("amrap-7.5").unwrap().shorthand(),
+            "amrap-7.5"
+        );
+        assert_eq!(
+            AMRAP::from_str("amrap-450s").unwrap().shorthand(),
+            "amrap-7.5"
+        );
+        assert_eq!(
+            AMRAP::from_str("amrap-90s").unwrap().shorthand(),
+            "amrap-1.5"
+        );
+        assert_eq!(
+            AMRAP::from_str("amrap-0.1").unwrap().shorthand(),
+            "amrap-6s"
+        );
+        assert_eq!(
+            AMRAP::from_str("amrap-75s").unwrap().shorthand(),
+            "amrap-75s"
+        );
+        assert_eq!(
+            AMRAP::from_str("amrap-120s").unwrap().shorthand(),
+            "amrap-2"
+        );
     }
 
     #[test]
     fn test_amrap_invalid() {
         assert!(AMRAP::from_str("amrap").is_err());
         assert!(AMRAP::from_str("other-10").is_err());
+        assert!(AMRAP::from_str("amrap-0").is_err());
+        assert!(AMRAP::from_str("amrap-1441").is_err());
+        assert!(AMRAP::from_str("amrap-0s").is_err());
+        assert!(AMRAP::from_str("amrap-7.51").is_err());
+        assert!(AMRAP::from_str("amrap-1e9").is_err());
+        assert_eq!(
+            AMRAP::from_str("amrap-300").unwrap(),
+            AMRAP { seconds: 18000 }
+        );
     }
 
     #[test]
     fn test_display() {
         assert_eq!(
-            format!("{}", AMRAP { minutes: 10 }),
+            format!("{}", AMRAP { seconds: 600 }),
             "AMRAP 10 minutes".to_string()
         );
+        assert_eq!(
+            format!("{}", AMRAP { seconds: 450 }),
+            "AMRAP 7.5 minutes".to_string()
+        );
+        assert_eq!(
+            format!("{}", AMRAP { seconds: 90 }),
+            "AMRAP 1.5 minutes".to_string()
+        );
+        // Below a minute, or with more than a decimal, in seconds
+        assert_eq!(
+            format!("{}", AMRAP { seconds: 6 }),
+            "AMRAP 6 seconds".to_string()
+        );
+        assert_eq!(
+            format!("{}", AMRAP { seconds: 75 }),
+            "AMRAP 75 seconds".to_string()
+        );
+        // The shorthand is in the same unit
+        for seconds in [6, 75, 90, 450, 600] {
+            let amrap = AMRAP { seconds };
+            let shown = amrap.to_string().contains("seconds");
+            assert_eq!(amrap.shorthand().ends_with('s'), shown);
+        }
     }
 }
@@ -1,6 +1,9 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::i18n::{self, Message};
+use crate::rep_types::check_duration;
+use crate::units::TimeUnit;
 use crate::workout_types::every::Every;
 
 /// Represents an Every Minute On the Minute (EMOM) workout.
@@ -12,6 +15,7 @@ use crate::workout_types::every::Every;
 /// # Examples
 ///
 /// ```
+/// use wod::units::TimeUnit;
 /// use wod::EMOM;
 ///
 /// let emom1: EMOM = "emom-10".parse().unwrap();
@@ -19,40 +23,41 @@ use crate::workout_types::every::Every;
 /// assert_eq!(emom1.every.duration, 1);
 /// assert_eq!(emom1.alternating, false);
 /// assert_eq!(emom1.rest.duration, 0);
-/// assert_eq!(emom1.rest.unit, "");
+/// assert_eq!(emom1.rest.unit, TimeUnit::Minutes);
 ///
 /// let emom2: EMOM = "emom-10-2m".parse().unwrap();
 /// assert_eq!(emom2.rounds, 10);
 /// assert_eq!(emom2.every.duration, 2);
 /// assert_eq!(emom2.alternating, false);
 /// assert_eq!(emom2.rest.duration, 0);
-/// assert_eq!(emom2.rest.unit, "");
+/// assert_eq!(emom2.rest.unit, TimeUnit::Minutes);
 ///
 /// let emom3: EMOM = "emom-10-r30s".parse().unwrap();
 /// assert_eq!(emom3.rounds, 10);
 /// assert_eq!(emom3.every.duration, 1);
 /// assert_eq!(emom3.alternating, false);
 /// assert_eq!(emom3.rest.duration, 30);
-/// assert_eq!(emom3.rest.unit, "s");
+/// assert_eq!(emom3.rest.unit, TimeUnit::Seconds);
 ///
 /// let emom4: EMOM = "emom-10-2m-alt".parse().unwrap();
 /// assert_eq!(emom4.rounds, 10);
 /// assert_eq!(emom4.every.duration, 2);
 /// assert_eq!(emom4.alternating, true);
 /// assert_eq!(emom4.rest.duration, 0);
-/// assert_eq!(emom4.rest.unit, "");
+/// assert_eq!(emom4.rest.unit, TimeUnit::Minutes);
 ///
 /// let emom5: EMOM = "emom-10-r30s-alt".parse().unwrap();
 /// assert_eq!(emom5.rounds, 10);
 /// assert_eq!(emom5.every.duration, 1);
 /// assert_eq!(emom5.alternating, true);
 /// assert_eq!(emom5.rest.duration, 30);
-/// assert_eq!(emom5.rest.unit, "s");
+/// assert_eq!(emom5.rest.unit, TimeUnit::Seconds);
 /// ```
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EMOM {
     /// The number of rounds to perform the workout.
-    pub rounds: u16,
+    pub rounds: u32,
     /// The interval at which the exercise is performed. Defaults to 1 minute.
     pub every: Every,
     /// A boolean indicating whether the workout is alternating between exercises.
@@ -71,13 +76,13 @@ impl FromStr for EMOM {
         let mut alternating = false;
         let mut every = Every {
             duration: 1,
-            unit: "m".to_string(),
+            unit: TimeUnit::Minutes,
             rest: false,
         };
         let mut rounds = 1;
         let mut rest = Every {
             duration: 0,
-            unit: "".to_string(),
+            unit: TimeUnit::Minutes,
             rest: false,
         };
 
@@ -92,24 +97,25 @@ impl FromStr for EMOM {
                 }
                 _ => {
                     if part.starts_with('r') && (part.contains('m') | part.contains('s')) {
-                        rest = Every::from_str(part).expect("Invalid Rest format");
+                        rest = Every::from_str(part)?;
                         continue;
                     }
 
                     if counter == 0 {
                         rounds = part
-                            .parse::<u16>()
+                            .parse::<u32>()
                             .map_err(|_| "Invalid number format".to_string())?;
                     } else if counter == 1 {
                         every = part
                             .parse::<Every>()
-                            // .parse::<u16>()
-                            .map_err(|_| "Invalid 'Every' format".to_string())?;
+                            .map_err(|e| format!("Invalid 'Every' format: {}", e))?;
                     }
                     counter += 1;
                 }
             }
         }
+        // The workout as a whole has to fit in a day too
+        check_duration("EMOM", s, rounds, every.seconds())?;
         Ok(EMOM {
             rounds,
             every,
@@ -121,7 +127,7 @@ impl FromStr for EMOM {
 
 impl fmt::Display for EMOM {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut workout = format!("EMOM {} minutes", self.rounds);
+        let mut workout = i18n::text(Message::Emom, &[&self.rounds]);
         if self.every.duration != 1 {
             workout.push_str(&format!("\n\n{}", self.every));
             // workout.push_str(&format!("\n\nEvery {} minutes", self.every));
@@ -136,7 +142,7 @@ impl fmt::Display for EMOM {
             }
         }
         if self.alternating {
-            workout.push_str(", alternating");
+            workout.push_str(&format!(", {}", i18n::text(Message::Alternating, &[])));
         }
 
         write!(formatter, "{}", workout)
@@ -155,13 +161,13 @@ mod tests {
                 rounds: 10,
                 every: Every {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 },
                 alternating: false,
                 rest: Every {
                     duration: 0,
-                    unit: "".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 }
             }
@@ -172,13 +178,13 @@ mod tests {
                 rounds: 10,
                 every: Every {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 },
                 alternating: true,
                 rest: Every {
                     duration: 0,
-                    unit: "".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 }
             }
@@ -189,13 +195,13 @@ mod tests {
                 rounds: 20,
                 every: Every {
                     duration: 2,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 },
                 alternating: false,
                 rest: Every {
                     duration: 0,
-                    unit: "".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 }
             }
@@ -206,13 +212,13 @@ mod tests {
                 rounds: 10,
                 every: Every {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 },
                 alternating: false,
                 rest: Every {
                     duration: 2,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: true
                 }
             }
@@ -223,13 +229,13 @@ mod tests {
                 rounds: 10,
                 every: Every {
                     duration: 2,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 },
                 alternating: true,
                 rest: Every {
                     duration: 0,
-                    unit: "".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 }
             }
@@ -240,13 +246,13 @@ mod tests {
                 rounds: 10,
                 every: Every {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 },
                 alternating: false,
                 rest: Every {
                     duration: 30,
-                    unit: "s".to_string(),
+                    unit: TimeUnit::Seconds,
                     rest: true
                 }
             }
@@ -257,13 +263,13 @@ mod tests {
                 rounds: 10,
                 every: Every {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 },
                 alternating: true,
                 rest: Every {
                     duration: 30,
-                    unit: "s".to_string(),
+                    unit: TimeUnit::Seconds,
                     rest: true
                 }
             }
@@ -274,13 +280,13 @@ mod tests {
                 rounds: 10,
                 every: Every {
                     duration: 30,
-                    unit: "s".to_string(),
+                    unit: TimeUnit::Seconds,
                     rest: false
                 },
                 alternating: true,
                 rest: Every {
                     duration: 0,
-                    unit: "".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 }
             }
@@ -301,13 +307,13 @@ mod tests {
                     rounds: 10,
                     every: Every {
                         duration: 1,
-                        unit: "m".to_string(),
+                        unit: TimeUnit::Minutes,
                         rest: false
                     },
                     alternating: false,
                     rest: Every {
                         duration: 0,
-                        unit: "".to_string(),
+                        unit: TimeUnit::Minutes,
                         rest: true
                     }
                 }
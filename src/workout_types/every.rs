@@ -1,16 +1,21 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::i18n::{self, Message};
+use crate::rep_types::check_duration;
+use crate::units::TimeUnit;
+
 /// Represents a rest period with a specified duration and unit.
 ///
 /// # Examples
 /// ```
 /// use wod::Every;
+/// use wod::units::TimeUnit;
 ///
 /// let rest: Every = "1m".parse().unwrap();
 ///
 /// assert_eq!(rest.duration, 1);
-/// assert_eq!(rest.unit, "m");
+/// assert_eq!(rest.unit, TimeUnit::Minutes);
 /// assert_eq!(rest.rest, false);
 /// ```
 ///
@@ -24,11 +29,12 @@ use std::str::FromStr;
 /// assert_eq!(format!("{}", rest), "rest 1 minute");
 /// ```
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Every {
     /// The length of the rest period.
-    pub duration: u16,
-    /// The unit of measurement for the rest period (e.g., "s" for seconds, "m" for minutes).
-    pub unit: String,
+    pub duration: u32,
+    /// The unit of measurement for the rest period, written as "s" or "m".
+    pub unit: TimeUnit,
     /// Whether Is used for resting
     pub rest: bool,
 }
@@ -55,29 +61,48 @@ impl FromStr for Every {
                 unit.push(c);
             }
         }
+        // Without a unit the duration is in minutes, "emom-12-3"
+        let unit = match unit.as_str() {
+            "" => TimeUnit::Minutes,
+            unit => unit
+                .parse()
+                .map_err(|e| format!("Invalid Every '{}': {}", s, e))?,
+        };
+        let duration = duration
+            .parse()
+            .map_err(|e| format!("Invalid duration in '{}': {}", s, e))?;
+        let duration = check_duration("Every", s, duration, unit.seconds())?;
         Ok(Every {
-            duration: duration.parse().unwrap(),
+            duration,
             unit,
             rest,
         })
     }
 }
 
+impl Every {
+    /// Returns the duration expressed in seconds.
+    ///
+    /// ```
+    /// use wod::Every;
+    ///
+    /// assert_eq!("2m".parse::<Every>().unwrap().seconds(), 120);
+    /// assert_eq!("r30s".parse::<Every>().unwrap().seconds(), 30);
+    /// ```
+    pub fn seconds(&self) -> u32 {
+        self.duration * self.unit.seconds()
+    }
+}
+
 impl fmt::Display for Every {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let unit = match self.unit.as_str() {
-            "m" => {
-                if self.duration != 1 {
-                    "minutes"
-                } else {
-                    "minute"
-                }
-            }
-            "s" => "seconds",
-            _ => "minutes",
+        let duration = i18n::duration(self.duration, self.unit);
+        let message = if self.rest {
+            Message::RestEvery
+        } else {
+            Message::WorkEvery
         };
-        let maybe_rest = if self.rest { "rest " } else { "work every " };
-        write!(formatter, "{}{} {}", maybe_rest, self.duration, unit)
+        write!(formatter, "{}", i18n::text(message, &[&duration]))
     }
 }
 
@@ -91,7 +116,7 @@ mod tests {
             Every::from_str("1m").unwrap(),
             Every {
                 duration: 1,
-                unit: "m".to_string(),
+                unit: TimeUnit::Minutes,
                 rest: false
             }
         );
@@ -99,7 +124,7 @@ mod tests {
             Every::from_str("90s").unwrap(),
             Every {
                 duration: 90,
-                unit: "s".to_string(),
+                unit: TimeUnit::Seconds,
                 rest: false
             }
         );
@@ -107,7 +132,7 @@ mod tests {
             Every::from_str("r1m").unwrap(),
             Every {
                 duration: 1,
-                unit: "m".to_string(),
+                unit: TimeUnit::Minutes,
                 rest: true
             }
         );
@@ -115,7 +140,7 @@ mod tests {
             Every::from_str("r90s").unwrap(),
             Every {
                 duration: 90,
-                unit: "s".to_string(),
+                unit: TimeUnit::Seconds,
                 rest: true
             }
         );
@@ -128,7 +153,7 @@ mod tests {
                 "{}",
                 Every {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 }
             ),
@@ -139,7 +164,7 @@ mod tests {
                 "{}",
                 Every {
                     duration: 2,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: false
                 }
             ),
@@ -150,7 +175,7 @@ mod tests {
                 "{}",
                 Every {
                     duration: 90,
-                    unit: "s".to_string(),
+                    unit: TimeUnit::Seconds,
                     rest: false
                 }
             ),
@@ -165,7 +190,7 @@ mod tests {
                 "{}",
                 Every {
                     duration: 1,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: true
                 }
             ),
@@ -176,7 +201,7 @@ mod tests {
                 "{}",
                 Every {
                     duration: 2,
-                    unit: "m".to_string(),
+                    unit: TimeUnit::Minutes,
                     rest: true
                 }
             ),
@@ -187,7 +212,7 @@ mod tests {
                 "{}",
                 Every {
                     duration: 90,
-                    unit: "s".to_string(),
+                    unit: TimeUnit::Seconds,
                     rest: true
                 }
             ),
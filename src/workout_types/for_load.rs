@@ -0,0 +1,149 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::i18n::{self, Message};
+
+/// How the load of a [`ForLoad`] workout is scored.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LoadScore {
+    /// The heaviest load lifted across the rounds.
+    #[default]
+    Max,
+    /// The sum of the loads lifted in every round.
+    Total,
+}
+
+/// Represents a workout for load: a number of rounds where the load of the barbell
+/// can go up every round, scored by the heaviest load or by the total.
+///
+/// # Examples
+///
+/// ## Parsing
+///
+/// The format is `<rounds>rd-load`, scored by the heaviest load, or
+/// `<rounds>rd-load-total` to add the loads of every round.
+///
+/// ```
+/// use wod::{ForLoad, LoadScore};
+///
+/// let for_load: ForLoad = "5rd-load".parse().unwrap();
+/// assert_eq!(for_load, ForLoad { rounds: 5, score: LoadScore::Max });
+///
+/// let for_load: ForLoad = "3rd-load-total".parse().unwrap();
+/// assert_eq!(for_load.score, LoadScore::Total);
+/// ```
+///
+/// ## Display
+///
+/// The header of the workout is followed by the way it's scored.
+///
+/// ```
+/// use wod::{ForLoad, LoadScore};
+///
+/// let for_load = ForLoad { rounds: 5, score: LoadScore::Max };
+/// assert_eq!(
+///     for_load.to_string(),
+///     "5 rounds for load\n\nScore: heaviest load across rounds"
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForLoad {
+    /// The number of rounds.
+    pub rounds: u32,
+    /// How the load is scored.
+    pub score: LoadScore,
+}
+
+impl FromStr for ForLoad {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid workout for load: `{}`, expected one of: <rounds>rd-load, <rounds>rd-load-total",
+                s
+            )
+        };
+        let (rounds, rest) = s.split_once("rd-load").ok_or_else(invalid)?;
+        let score = match rest {
+            "" => LoadScore::Max,
+            "-total" => LoadScore::Total,
+            _ => return Err(invalid()),
+        };
+        let rounds = if rounds.is_empty() {
+            1
+        } else {
+            rounds.parse::<u32>().map_err(|_| invalid())?
+        };
+        if rounds == 0 {
+            return Err(invalid());
+        }
+        Ok(ForLoad { rounds, score })
+    }
+}
+
+impl ForLoad {
+    /// The prefix of the workout in the shorthand, as accepted by `FromStr`.
+    pub fn shorthand(&self) -> String {
+        match self.score {
+            LoadScore::Max => format!("{}rd-load", self.rounds),
+            LoadScore::Total => format!("{}rd-load-total", self.rounds),
+        }
+    }
+}
+
+impl fmt::Display for ForLoad {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let score = match self.score {
+            LoadScore::Max => Message::MaxLoad,
+            LoadScore::Total => Message::TotalLoad,
+        };
+        write!(
+            formatter,
+            "{}\n\n{}",
+            i18n::text(Message::RoundsForLoad, &[&self.rounds]),
+            i18n::text(score, &[])
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_for_load() {
+        assert_eq!(
+            "rd-load".parse::<ForLoad>().unwrap(),
+            ForLoad {
+                rounds: 1,
+                score: LoadScore::Max
+            }
+        );
+        assert_eq!(
+            "10rd-load-total".parse::<ForLoad>().unwrap(),
+            ForLoad {
+                rounds: 10,
+                score: LoadScore::Total
+            }
+        );
+        assert!("5rd-load-min".parse::<ForLoad>().is_err());
+        assert!("0rd-load".parse::<ForLoad>().is_err());
+        assert!("5rd".parse::<ForLoad>().is_err());
+    }
+
+    #[test]
+    fn test_for_load_display() {
+        let for_load = ForLoad {
+            rounds: 3,
+            score: LoadScore::Total,
+        };
+        assert_eq!(
+            for_load.to_string(),
+            "3 rounds for load\n\nScore: total load"
+        );
+        assert_eq!(for_load.shorthand(), "3rd-load-total");
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::str::FromStr;
 
+use crate::i18n::{self, Message};
+
 /// Represents a time-based exercise or workout configuration.
 ///
 /// The "ForTime" struct is used to denote exercises or workouts that are performed
@@ -67,6 +69,7 @@ use std::str::FromStr;
 /// assert_eq!(rd.to_string(), "5 rounds for time");
 /// ```
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ForTime {
     pub rounds: u32,
     pub name: String,
@@ -101,9 +104,10 @@ impl FromStr for ForTime {
 impl fmt::Display for ForTime {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.rounds > 1 {
-            return write!(formatter, "{} rounds for time", self.rounds);
+            let rounds = i18n::text(Message::RoundsForTime, &[&self.rounds]);
+            return write!(formatter, "{}", rounds);
         }
-        write!(formatter, "For Time")
+        write!(formatter, "{}", i18n::text(Message::ForTime, &[]))
     }
 }
 
@@ -1,5 +1,7 @@
 pub mod amrap;
 pub mod emom;
 pub mod every;
+pub mod for_load;
 pub mod for_time;
+pub mod tabata;
 pub mod workout_type;
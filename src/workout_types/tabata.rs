@@ -0,0 +1,150 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::i18n::{self, Message};
+use crate::rep_types::check_duration;
+
+/// Represents a Tabata workout, intervals of work followed by a short rest.
+///
+/// # Examples
+///
+/// ## Parsing
+///
+/// The format should be `tabata[-<rounds>[-<work>s-<rest>s]]`. By default a Tabata
+/// is 8 rounds of 20 seconds of work followed by 10 seconds of rest.
+///
+/// ```
+/// use wod::Tabata;
+///
+/// let tabata: Tabata = "tabata".parse().unwrap();
+/// assert_eq!(tabata, Tabata { rounds: 8, work: 20, rest: 10 });
+///
+/// let tabata: Tabata = "tabata-10-30s-15s".parse().unwrap();
+/// assert_eq!(tabata, Tabata { rounds: 10, work: 30, rest: 15 });
+/// ```
+///
+/// ## Display
+///
+/// ```
+/// use wod::Tabata;
+///
+/// let tabata: Tabata = "tabata".parse().unwrap();
+/// assert_eq!(
+///     format!("{}", tabata),
+///     "Tabata 8 rounds\n\n20 seconds work, 10 seconds rest".to_string()
+/// );
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tabata {
+    /// The number of work/rest intervals.
+    pub rounds: u32,
+    /// Seconds of work per interval.
+    pub work: u32,
+    /// Seconds of rest per interval.
+    pub rest: u32,
+}
+
+impl Default for Tabata {
+    fn default() -> Self {
+        Tabata {
+            rounds: 8,
+            work: 20,
+            rest: 10,
+        }
+    }
+}
+
+fn parse_seconds(s: &str) -> Result<u32, String> {
+    s.trim_end_matches('s')
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid Tabata interval: '{}'", s))
+}
+
+impl FromStr for Tabata {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('-').collect();
+        if parts[0] != "tabata" {
+            return Err("Invalid Tabata format".to_string());
+        }
+        let mut tabata = Tabata::default();
+        match parts.len() {
+            1 => {}
+            2 => {
+                tabata.rounds = parts[1]
+                    .parse::<u32>()
+                    .map_err(|_| "Invalid number format".to_string())?;
+            }
+            4 => {
+                tabata.rounds = parts[1]
+                    .parse::<u32>()
+                    .map_err(|_| "Invalid number format".to_string())?;
+                tabata.work = check_duration("Tabata", s, parse_seconds(parts[2])?, 1)?;
+                tabata.rest = parse_seconds(parts[3])?;
+            }
+            _ => return Err("Invalid Tabata format".to_string()),
+        }
+        // The rest can be zero, but every round needs work and they have to fit in a day
+        check_duration(
+            "Tabata",
+            s,
+            tabata.rounds,
+            tabata.work.saturating_add(tabata.rest),
+        )?;
+        Ok(tabata)
+    }
+}
+
+impl fmt::Display for Tabata {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "{}\n\n{}",
+            i18n::text(Message::Tabata, &[&self.rounds]),
+            i18n::text(Message::TabataIntervals, &[&self.work, &self.rest])
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tabata() {
+        assert_eq!(Tabata::from_str("tabata").unwrap(), Tabata::default());
+        assert_eq!(
+            Tabata::from_str("tabata-6").unwrap(),
+            Tabata {
+                rounds: 6,
+                work: 20,
+                rest: 10
+            }
+        );
+        assert_eq!(
+            Tabata::from_str("tabata-10-30s-15s").unwrap(),
+            Tabata {
+                rounds: 10,
+                work: 30,
+                rest: 15
+            }
+        );
+    }
+
+    #[test]
+    fn test_tabata_invalid() {
+        assert!(Tabata::from_str("tabata-8-20s").is_err());
+        assert!(Tabata::from_str("other-8").is_err());
+        assert!(Tabata::from_str("tabata-x").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", Tabata::default()),
+            "Tabata 8 rounds\n\n20 seconds work, 10 seconds rest"
+        );
+    }
+}
@@ -1,15 +1,22 @@
 use std::fmt;
 use std::str::FromStr;
 
-use crate::workout_types::{amrap::AMRAP, emom::EMOM, for_time::ForTime};
+use crate::grammar::{Example, WORKOUT_TYPES};
+use crate::i18n::{self, Message};
+use crate::units::TimeUnit;
+use crate::workout_types::{
+    amrap::AMRAP, emom::EMOM, for_load::ForLoad, for_time::ForTime, tabata::Tabata,
+};
 
 /// Represents different types of workouts.
 ///
-/// This enum categorizes workouts into four main types:
+/// This enum categorizes workouts into six main types:
 /// - `ForTime`: A workout that is completed as fast as possible.
 /// - `AMRAP`: As Many Rounds As Possible within a set time.
 /// - `EMOM`: Every Minute On the Minute, typically involving a specific exercise or set of exercises.
 /// - `Weightlifting`: Focused on weightlifting exercises.
+/// - `Tabata`: Intervals of work and rest, 8 rounds of 20/10 seconds by default.
+/// - `ForLoad`: Rounds scored by the heaviest load lifted, or the total.
 ///
 /// # Examples
 ///
@@ -17,11 +24,12 @@ use crate::workout_types::{amrap::AMRAP, emom::EMOM, for_time::ForTime};
 /// use wod::{WorkoutType, ForTime, AMRAP, EMOM};
 ///
 /// let for_time_workout = WorkoutType::ForTime(ForTime { rounds: 1, name: "ft".to_string() });
-/// let amrap_workout = WorkoutType::AMRAP(AMRAP { minutes: 10 });
+/// let amrap_workout = WorkoutType::AMRAP(AMRAP { seconds: 600 });
 /// let emom_workout = WorkoutType::EMOM("emom-10".parse::<EMOM>().unwrap());
 /// let weightlifting_workout = WorkoutType::Weightlifting;
 /// ```
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WorkoutType {
     /// Represents a `ForTime` workout.
     ForTime(ForTime),
@@ -31,6 +39,10 @@ pub enum WorkoutType {
     EMOM(EMOM),
     /// Represents a Weightlifting workout.
     Weightlifting,
+    /// Represents a `Tabata` workout.
+    Tabata(Tabata),
+    /// Represents a workout for load.
+    ForLoad(ForLoad),
 }
 
 impl FromStr for WorkoutType {
@@ -43,9 +55,15 @@ impl FromStr for WorkoutType {
         if s.starts_with("emom") {
             return EMOM::from_str(s).map(WorkoutType::EMOM);
         }
+        if s.starts_with("tabata") {
+            return Tabata::from_str(s).map(WorkoutType::Tabata);
+        }
         if s == "wl" {
             return Ok(WorkoutType::Weightlifting);
         }
+        if s.contains("rd-load") {
+            return ForLoad::from_str(s).map(WorkoutType::ForLoad);
+        }
         if s == "ft" || s.contains("rd") {
             // ft, 5rd, 2rd, etc.
             return ForTime::from_str(s).map(WorkoutType::ForTime);
@@ -54,13 +72,148 @@ impl FromStr for WorkoutType {
     }
 }
 
+impl WorkoutType {
+    /// The prefixes accepted for the workout types, with an example of each variation
+    /// and its description, i.e. `amrap-15`, "As many rounds as possible in 15 minutes".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::WorkoutType;
+    ///
+    /// for prefix in WorkoutType::prefixes() {
+    ///     assert!(prefix.syntax.parse::<WorkoutType>().is_ok());
+    /// }
+    /// ```
+    pub fn prefixes() -> impl Iterator<Item = &'static Example> {
+        WORKOUT_TYPES.iter()
+    }
+
+    /// The kind of workout, without its parameters, see [`Kind`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::{Kind, WorkoutType};
+    ///
+    /// assert_eq!("5rd".parse::<WorkoutType>().unwrap().kind(), Kind::ForTime);
+    /// assert_eq!("emom-12-3m".parse::<WorkoutType>().unwrap().kind().as_str(), "emom");
+    /// ```
+    pub fn kind(&self) -> Kind {
+        match self {
+            WorkoutType::ForTime(_) => Kind::ForTime,
+            WorkoutType::AMRAP(_) => Kind::Amrap,
+            WorkoutType::EMOM(_) => Kind::Emom,
+            WorkoutType::Weightlifting => Kind::Weightlifting,
+            WorkoutType::Tabata(_) => Kind::Tabata,
+            WorkoutType::ForLoad(_) => Kind::ForLoad,
+        }
+    }
+
+    /// The prefix of the workout type in the shorthand, as accepted by `FromStr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wod::WorkoutType;
+    ///
+    /// for prefix in ["ft", "5rd", "amrap-15", "amrap-7.5", "amrap-75s", "emom-12-3m-r1m", "emom-10-alt", "wl", "tabata-6", "5rd-load"] {
+    ///     assert_eq!(prefix.parse::<WorkoutType>().unwrap().shorthand(), prefix);
+    /// }
+    /// ```
+    pub fn shorthand(&self) -> String {
+        match self {
+            WorkoutType::ForTime(ft) if ft.rounds == 1 && ft.name == "ft" => "ft".to_string(),
+            WorkoutType::ForTime(ft) => format!("{}rd", ft.rounds),
+            WorkoutType::AMRAP(amrap) => amrap.shorthand(),
+            WorkoutType::EMOM(emom) => {
+                let mut prefix = format!("emom-{}", emom.rounds);
+                if emom.every.duration != 1 || emom.every.unit != TimeUnit::Minutes {
+                    prefix.push_str(&format!(
+                        "-{}{}",
+                        emom.every.duration,
+                        emom.every.unit.symbol()
+                    ));
+                }
+                if emom.rest.duration != 0 {
+                    prefix.push_str(&format!(
+                        "-r{}{}",
+                        emom.rest.duration,
+                        emom.rest.unit.symbol()
+                    ));
+                }
+                if emom.alternating {
+                    prefix.push_str("-alt");
+                }
+                prefix
+            }
+            WorkoutType::Weightlifting => "wl".to_string(),
+            WorkoutType::Tabata(tabata) => {
+                let default = Tabata::default();
+                if (tabata.work, tabata.rest) != (default.work, default.rest) {
+                    format!("tabata-{}-{}s-{}s", tabata.rounds, tabata.work, tabata.rest)
+                } else if tabata.rounds != default.rounds {
+                    format!("tabata-{}", tabata.rounds)
+                } else {
+                    "tabata".to_string()
+                }
+            }
+            WorkoutType::ForLoad(for_load) => for_load.shorthand(),
+        }
+    }
+}
+
+/// The kind of a workout, a [`WorkoutType`] without its parameters: "for time",
+/// "amrap", "emom", "weightlifting", "tabata" or "for load". The kinds are the keys
+/// of the tables of the configuration set per kind of workout, i.e. the `layouts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub enum Kind {
+    #[cfg_attr(feature = "serde", serde(rename = "for time"))]
+    ForTime,
+    #[cfg_attr(feature = "serde", serde(rename = "amrap"))]
+    Amrap,
+    #[cfg_attr(feature = "serde", serde(rename = "emom"))]
+    Emom,
+    #[cfg_attr(feature = "serde", serde(rename = "weightlifting"))]
+    Weightlifting,
+    #[cfg_attr(feature = "serde", serde(rename = "tabata"))]
+    Tabata,
+    #[cfg_attr(feature = "serde", serde(rename = "for load"))]
+    ForLoad,
+}
+
+impl Kind {
+    /// The name of the kind, as written in the tags of the files and the configuration.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Kind::ForTime => "for time",
+            Kind::Amrap => "amrap",
+            Kind::Emom => "emom",
+            Kind::Weightlifting => "weightlifting",
+            Kind::Tabata => "tabata",
+            Kind::ForLoad => "for load",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl fmt::Display for WorkoutType {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             WorkoutType::ForTime(ft) => write!(formatter, "{}", ft),
             WorkoutType::AMRAP(amrap) => write!(formatter, "{}", amrap),
             WorkoutType::EMOM(emom) => write!(formatter, "{}", emom),
-            WorkoutType::Weightlifting => write!(formatter, "Weightlifting"),
+            WorkoutType::Weightlifting => {
+                write!(formatter, "{}", i18n::text(Message::Weightlifting, &[]))
+            }
+            WorkoutType::Tabata(tabata) => write!(formatter, "{}", tabata),
+            WorkoutType::ForLoad(for_load) => write!(formatter, "{}", for_load),
         }
     }
 }
@@ -80,7 +233,7 @@ mod tests {
         );
         assert_eq!(
             WorkoutType::from_str("amrap-10").unwrap(),
-            WorkoutType::AMRAP(AMRAP { minutes: 10 })
+            WorkoutType::AMRAP(AMRAP { seconds: 600 })
         );
         assert_eq!(
             WorkoutType::from_str("emom-10").unwrap(),
@@ -90,6 +243,15 @@ mod tests {
             "wl".parse::<WorkoutType>().unwrap(),
             WorkoutType::Weightlifting
         );
+        assert_eq!(
+            "tabata".parse::<WorkoutType>().unwrap(),
+            WorkoutType::Tabata(Tabata::default())
+        );
+        assert_eq!(
+            "5rd-load".parse::<WorkoutType>().unwrap(),
+            WorkoutType::ForLoad("5rd-load".parse().unwrap())
+        );
+        assert!("5rd-loads".parse::<WorkoutType>().is_err());
     }
 
     #[test]
@@ -105,7 +267,7 @@ mod tests {
             "For Time"
         );
         assert_eq!(
-            format!("{}", WorkoutType::AMRAP(AMRAP { minutes: 10 })),
+            format!("{}", WorkoutType::AMRAP(AMRAP { seconds: 600 })),
             "AMRAP 10 minutes"
         );
         assert_eq!(
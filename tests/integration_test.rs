@@ -1,8 +1,34 @@
+// The commands of the `wod` command line, which need the `cli` feature.
+#![cfg(feature = "cli")]
+
+use chrono::NaiveDate;
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::PathBuf;
 use tempfile::TempDir;
-use wod::run_add_wod_from_file;
+use wod::config::Config;
+use wod::cycles::{self, Cycle};
+use wod::document::{Document, Position};
+use wod::frequency::ReportFormat;
+use wod::front_matter::{self, Profile};
+use wod::i18n::{self, Catalog, Message};
+use wod::journal::Journal;
+use wod::leaderboard;
+use wod::markdown::Post;
+use wod::media::Media;
+use wod::pr::PrStore;
+use wod::render::Markdown;
+use wod::results::SeriesFormat;
+use wod::rm::Formula;
+use wod::scaling::{AthleteProfile, Restriction, Scaling};
+use wod::{
+    create_workout, page_url, resolve_output_dir, run_add_daily_note, run_add_sessions,
+    run_add_wod_from_file, run_add_workout, run_base, run_build, run_check_wod, run_check_wodfile,
+    run_cycle, run_frequency, run_heatmap, run_import_url, run_init, run_init_hugo,
+    run_leaderboard, run_lint_files, run_log, run_log_export, run_log_show, run_merge, run_new,
+    run_percent, run_personalize, run_plan, run_pr_baseline, run_pr_list, run_pr_set, run_publish,
+    run_qr, run_regen, run_split, run_stats, run_undo, run_volume, run_warmup,
+};
 
 // Base tests to ensure the program runs correctly
 
@@ -23,8 +49,10 @@ fn test_weightlifting_workout() -> Result<(), Box<dyn std::error::Error>> {
     run_add_wod_from_file(
         output_path.clone(),
         wod_file_path.clone(),
-        "2025-03-21".to_string(),
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
         None,
+        &Config::default(),
+        &false,
     )?;
 
     // Verify the file was created
@@ -39,6 +67,8 @@ fn test_weightlifting_workout() -> Result<(), Box<dyn std::error::Error>> {
 title: "workouts"
 date: 2025-03-21
 draft: false
+tags: ["clean", "split jerk", "weightlifting"]
+level: "beginner"
 ---
 "#
     ));
@@ -55,6 +85,33 @@ draft: false
     Ok(())
 }
 
+#[test]
+fn test_layouts_of_the_config() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let wod_file_path = temp_dir.path().join("test.wod");
+    fs::write(&wod_file_path, "wl 5x3 back squat @75%\nemom-10 5 burpee\n")?;
+    let output_path = temp_dir.path().join("workouts.md");
+    let config: Config = toml::from_str("[layouts]\nweightlifting = \"table\"")?;
+
+    run_add_wod_from_file(
+        output_path.clone(),
+        wod_file_path,
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+        None,
+        &config,
+        &false,
+    )?;
+
+    let content = fs::read_to_string(&output_path)?;
+    assert!(content.contains("| 5x3 | Back Squat | 75% |"));
+    assert!(content.contains("- 5 Burpee"));
+    // The layouts only apply to the files written with the configuration
+    let workout = create_workout("wl 5x3 back squat @75%", None, None)?;
+    assert!(workout.to_string().contains("5x3 Back Squat @ 75%"));
+
+    Ok(())
+}
+
 #[test]
 fn test_for_time_workout() -> Result<(), Box<dyn std::error::Error>> {
     // Create a temporary directory for our test files
@@ -72,8 +129,10 @@ fn test_for_time_workout() -> Result<(), Box<dyn std::error::Error>> {
     run_add_wod_from_file(
         output_path.clone(),
         wod_file_path.clone(),
-        "2025-03-21".to_string(),
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
         None,
+        &Config::default(),
+        &false,
     )?;
 
     // Verify the file was created
@@ -127,8 +186,10 @@ fn test_run_add_wod_from_file_with_languages() -> Result<(), Box<dyn std::error:
     run_add_wod_from_file(
         output_path_base.clone(),
         wod_file_path.clone(),
-        "2025-03-21".to_string(),
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
         Some("en,es".to_string()),
+        &Config::default(),
+        &false,
     )?;
 
     // Verify both language files were created
@@ -169,8 +230,10 @@ fn test_run_add_wod_from_file_with_comments_and_names() -> Result<(), Box<dyn st
     run_add_wod_from_file(
         output_path.clone(),
         wod_file_path.clone(),
-        "2025-03-21".to_string(),
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
         None,
+        &Config::default(),
+        &false,
     )?;
 
     // Verify the file was created
@@ -216,8 +279,10 @@ fn test_run_add_wod_from_file_invalid_format() -> Result<(), Box<dyn std::error:
     run_add_wod_from_file(
         output_path.clone(),
         wod_file_path.clone(),
-        "2025-03-21".to_string(),
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
         None,
+        &Config::default(),
+        &false,
     )?;
     let content = fs::read_to_string(&output_path)?;
     // Count the number of jump of line, should be 9 if no wod was added
@@ -229,6 +294,32 @@ fn test_run_add_wod_from_file_invalid_format() -> Result<(), Box<dyn std::error:
     Ok(())
 }
 
+#[test]
+fn test_run_add_wod_from_file_unreadable_line() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let wod_file_path = temp_dir.path().join("test.wod");
+    // The second line is not valid UTF-8, the workouts around it are added
+    fs::write(
+        &wod_file_path,
+        b"wl 5x5 back squat\nft 21-15-9 pull up, thruster \xff\nwl 4x2 front squat @85%\n",
+    )?;
+
+    let output_path = temp_dir.path().join("workouts.md");
+    run_add_wod_from_file(
+        output_path.clone(),
+        wod_file_path,
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+        None,
+        &Config::default(),
+        &false,
+    )?;
+    let content = fs::read_to_string(&output_path)?;
+    assert!(content.contains("5x5 Back Squat"));
+    assert!(content.contains("4x2 Front Squat @ 85%"));
+    assert!(!content.contains("Thruster"));
+    Ok(())
+}
+
 #[test]
 fn test_nonexistent_wodfile() {
     // Create a temporary directory for our test
@@ -241,8 +332,10 @@ fn test_nonexistent_wodfile() {
     let result = run_add_wod_from_file(
         output_path.clone(),
         PathBuf::from("nonexistent_file.wod"),
-        "2025-03-21".to_string(),
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
         None,
+        &Config::default(),
+        &false,
     );
 
     // Verify it returns an error
@@ -253,3 +346,1820 @@ fn test_nonexistent_wodfile() {
 }
 
 // TODO: Tests for the format of different WODS
+
+#[test]
+fn test_run_log() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output_path = temp_dir.path().join("wod-2025-03-21");
+    let store = temp_dir.path().join("results.json");
+
+    let filenames = run_base(
+        output_path,
+        &false,
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+        None,
+        &Config::default(),
+    )?;
+    let entry = run_log(
+        filenames[0].clone(),
+        "12:34".to_string(),
+        Some("rx".to_string()),
+        None,
+        &store,
+    )?;
+    assert_eq!(entry.date, "2025-03-21");
+    assert_eq!(entry.workout, "2025-03-21");
+
+    let history = run_log_show(&store)?;
+    assert_eq!(history, "2025-03-21  2025-03-21  12:34  (rx)");
+
+    Ok(())
+}
+
+#[test]
+fn test_run_leaderboard() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output_path = temp_dir.path().join("wod-2025-03-21.md");
+    run_add_workout(
+        output_path.clone(),
+        "ft 21-15-9 pull up, thruster @43/30kg",
+        None,
+        Some("Fran".to_string()),
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+
+    let entries = leaderboard::parse_csv("athlete,score,division\nAna,4:10\nBob,3:45\n")?;
+    run_leaderboard(output_path.clone(), "Fran", &entries)?;
+    let content = fs::read_to_string(&output_path)?;
+    assert!(content.ends_with(
+        "---\n\n<!-- leaderboard: Fran -->\n**Leaderboard: Fran**\n\n*Rx*\n\n1. Bob: 3:45\n2. Ana: 4:10\n\n"
+    ));
+
+    // Running it again with more scores replaces the leaderboard
+    let entries = leaderboard::parse_csv("Ana,4:10\nBob,3:45\nCarla,5:02,scaled")?;
+    run_leaderboard(output_path.clone(), "Fran", &entries)?;
+    let content = fs::read_to_string(&output_path)?;
+    assert_eq!(content.matches("Leaderboard: Fran").count(), 1);
+    assert!(content.ends_with("*Scaled*\n\n1. Carla: 5:02\n\n"));
+    assert_eq!(Document::parse(&content).blocks().len(), 2);
+
+    // The workouts of the file can still be read back
+    assert_eq!(Post::read(&output_path)?.workouts.len(), 1);
+
+    assert!(run_leaderboard(output_path, "Fran", &[]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_warmup() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output_path = temp_dir.path().join("wod-2025-03-21.md");
+    fs::write(&output_path, "---\ntitle: \"2025-03-21\"\n---\n\n")?;
+    assert!(run_warmup(output_path.clone()).is_err());
+
+    for workout in ["wl 5x3 back squat @75%", "ft 50cal row, 30 toes to bar"] {
+        run_add_workout(
+            output_path.clone(),
+            workout,
+            None,
+            None,
+            &[],
+            &Scaling::default(),
+            &Markdown::default(),
+            &Position::End,
+            false,
+        )?;
+    }
+    let block = run_warmup(output_path.clone())?;
+    assert!(block.contains("- 3 minutes easy row\n"));
+    assert!(block.contains("- 3 building sets of Back Squat\n"));
+
+    // The warm-up goes before the workouts, and is replaced when run again
+    run_warmup(output_path.clone())?;
+    let content = fs::read_to_string(&output_path)?;
+    let doc = Document::parse(&content);
+    assert_eq!(doc.blocks().len(), 3);
+    assert!(doc.blocks()[0].starts_with("---\n\n<!-- warmup -->\n**Warm-up**\n\n*General*\n\n"));
+    assert_eq!(Post::read(&output_path)?.workouts.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_run_personalize() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let output_path = temp_dir.path().join("wod-2025-03-21.md");
+    run_add_workout(
+        output_path.clone(),
+        "ft 21-15-9 pull up, thruster @43/30kg",
+        None,
+        Some("Fran".to_string()),
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    let original = fs::read_to_string(&output_path)?;
+
+    let profile = AthleteProfile {
+        restrictions: vec![Restriction::Overhead, Restriction::Impact],
+    };
+    assert_eq!(
+        run_personalize(&output_path, &profile)?,
+        "---\n\n*Fran*\n\n**For Time**\n\n21-15-9\n\n- Pull Up\n\n- Front Squat @ 43/30kg\n\n"
+    );
+    assert_eq!(fs::read_to_string(&output_path)?, original);
+    assert!(run_personalize(&output_path, &AthleteProfile::default()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_log_export() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let store = temp_dir.path().join("results.json");
+    let file = temp_dir.path().join("wod-2025-03-21.md");
+    fs::write(&file, "---\ndate: 2025-03-21\n---\n")?;
+    let log = |workout: &str, score: &str| {
+        run_log(
+            file.clone(),
+            score.to_string(),
+            None,
+            Some(workout.to_string()),
+            &store,
+        )
+    };
+    log("back squat", "5x120kg")?;
+    log("Fran", "4:32")?;
+
+    let csv = run_log_export(
+        &store,
+        Some("back squat"),
+        Formula::Epley,
+        SeriesFormat::Csv,
+    )?;
+    assert_eq!(
+        csv,
+        "date,workout,score,value,unit,one_rm\n2025-03-21,back squat,5x120kg,120,kg,140\n"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&run_log_export(
+        &store,
+        None,
+        Formula::Epley,
+        SeriesFormat::Json,
+    )?)?;
+    assert_eq!(json.as_array().unwrap().len(), 2);
+    assert_eq!(json[1]["value"], 272.0);
+    assert_eq!(json[1]["unit"], "s");
+
+    assert!(run_log_export(&store, Some("back sqat"), Formula::Epley, SeriesFormat::Csv).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_percent() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let store = temp_dir.path().join("prs.json");
+    let config = Config {
+        plate_increment: 2.5,
+        ..Config::default()
+    };
+
+    run_pr_set("clean", "100kg", 1, Formula::Epley, &store)?;
+    assert_eq!(
+        run_percent(
+            "82%",
+            Some("clean"),
+            None,
+            1,
+            Formula::Epley,
+            &store,
+            &config
+        )?,
+        "82.5kg"
+    );
+    assert_eq!(
+        run_percent(
+            "80%",
+            None,
+            Some("120kg"),
+            1,
+            Formula::Epley,
+            &store,
+            &config
+        )?,
+        "95kg"
+    );
+    assert!(run_percent(
+        "80%",
+        Some("snatch"),
+        None,
+        1,
+        Formula::Epley,
+        &store,
+        &config
+    )
+    .is_err());
+    assert!(run_percent(
+        "80kg",
+        Some("clean"),
+        None,
+        1,
+        Formula::Epley,
+        &store,
+        &config
+    )
+    .is_err());
+
+    // 100kg for 6 repetitions is a 120kg 1RM
+    assert_eq!(
+        run_percent(
+            "80%",
+            None,
+            Some("100kg"),
+            6,
+            Formula::Epley,
+            &store,
+            &config
+        )?,
+        "95kg"
+    );
+    let one_rm = run_pr_set("snatch", "70kg", 3, Formula::Brzycki, &store)?;
+    assert_eq!(one_rm.to_string(), "74kg");
+
+    Ok(())
+}
+
+#[test]
+fn test_run_stats() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let store = temp_dir.path().join("prs.json");
+
+    let workout = "wl 5x5 back squat @70%";
+    assert_eq!(
+        run_stats(workout, &store)?,
+        "Movements: 1\nMachines: -\nTonnage: -"
+    );
+    run_pr_set("back squat", "140kg", 1, Formula::Epley, &store)?;
+    assert_eq!(
+        run_stats(workout, &store)?,
+        "Movements: 1\nMachines: -\nTonnage: 2450kg"
+    );
+    assert!(run_stats("ft 50cal row, 30 wall ball @9/6kg", &store)?.contains("Machines: Row\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_run_check_wod_paces() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let store = temp_dir.path().join("prs.json");
+
+    assert!(run_pr_baseline("thruster", "2k 7:30", &store).is_err());
+    assert!(run_pr_baseline("row", "7:30", &store).is_err());
+    run_pr_baseline("row", "2k 7:30", &store)?;
+    run_pr_baseline("run", "5k 22:00", &store)?;
+    run_pr_set("clean", "100kg", 1, Formula::Epley, &store)?;
+    assert_eq!(
+        run_pr_list(&store)?,
+        "Clean: 100kg\nRow: 2k 7:30\nRun: 5k 22:00"
+    );
+
+    let prs = PrStore::load(&store)?;
+    let workout = "3rd 1k run, 500m row, 15 burpee";
+    let paced = run_check_wod(workout, None, Some(&prs))?;
+    assert!(paced.contains("- 1k Run (4:00, 4:00/km)\n"));
+    assert!(paced.contains("- 500m Row (1:44, 1:44/500m)\n"));
+    assert!(paced.contains("- 15 Burpee\n"));
+    assert!(!run_check_wod(workout, Some(&prs), None)?.contains("/km"));
+
+    Ok(())
+}
+
+#[test]
+fn test_run_cycle() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let store = temp_dir.path().join("prs.json");
+    run_pr_set("back squat", "140kg", 1, Formula::Epley, &store)?;
+
+    let start = NaiveDate::from_ymd_opt(2025, 3, 24).unwrap();
+    let movements = ["back squat".to_string(), "deadlift".to_string()];
+    let config = Config {
+        plate_increment: 2.5,
+        ..Default::default()
+    };
+    let sessions = run_cycle(
+        Cycle::FiveThreeOne,
+        &movements,
+        start,
+        4,
+        &[],
+        &store,
+        &config,
+    )?;
+    assert_eq!(sessions.len(), 4);
+    let wodfile = cycles::wodfile(&sessions);
+    assert!(wodfile.starts_with(
+        "# 2025-03-24\nwl [1x5 back squat @82.5kg; 1x5 back squat @95kg; 1x5 back squat @107.5kg]|"
+    ));
+    // Without a personal record the percentages are kept
+    assert!(wodfile.contains("wl [1x5 deadlift @58.5%;"));
+    assert!(run_cycle(
+        Cycle::Linear,
+        &["squat".to_string()],
+        start,
+        4,
+        &[],
+        &store,
+        &config
+    )
+    .is_err());
+
+    let output_dir = temp_dir.path().join("wods");
+    let filenames = run_add_sessions(&sessions, &output_dir, None, &config, &false)?;
+    assert_eq!(filenames.len(), 4);
+    assert_eq!(filenames[3], output_dir.join("wod-2025-04-14.md"));
+    let content = fs::read_to_string(&filenames[3])?;
+    assert!(content.contains("date: 2025-04-14"));
+    assert!(content.contains("*Back Squat 5/3/1, week 4*"));
+    assert!(content.contains("*Deadlift 5/3/1, week 4*"));
+
+    Ok(())
+}
+
+#[test]
+fn test_run_plan() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let plan = temp_dir.path().join("plan.toml");
+    fs::write(
+        &plan,
+        r#"
+start = "2025-03-24"
+days = ["mon", "thu"]
+seed = 11
+
+[[phases]]
+name = "Accumulation"
+weeks = 2
+intensity = [70, 72.5]
+volume = [25]
+focus = ["back squat", "deadlift"]
+"#,
+    )?;
+
+    let dir = temp_dir.path().join("programming");
+    let templates_dir = temp_dir.path().join("templates");
+    let written = run_plan(&plan, &dir, &templates_dir, &false)?;
+    assert_eq!(written.len(), 4);
+    assert_eq!(written[3], dir.join("2025-04-03.wod"));
+    let content = fs::read_to_string(&written[2])?;
+    assert!(content.starts_with(
+        "# Accumulation, week 2: 72.5% for 25 reps\nwl 5x5 back squat @72.5%||Strength\n"
+    ));
+
+    // The files refined by the coach are kept
+    fs::write(&written[0], "wl 5x5 back squat @70%||Strength\n")?;
+    assert!(run_plan(&plan, &dir, &templates_dir, &false)?.is_empty());
+    assert_eq!(
+        fs::read_to_string(&written[0])?,
+        "wl 5x5 back squat @70%||Strength\n"
+    );
+
+    // The skeleton is built as any other directory of wod files
+    let output_dir = temp_dir.path().join("content");
+    run_build(&dir, &output_dir, &Config::default(), None, &false)?;
+    let page = fs::read_to_string(output_dir.join("wod-2025-04-03.md"))?;
+    assert!(page.contains("date: 2025-04-03"));
+    assert!(page.contains("*Metcon*"));
+
+    fs::write(
+        &plan,
+        "start = \"2025-03-24\"\ndays = [\"mon\"]\nphases = []\n",
+    )?;
+    assert!(run_plan(&plan, &dir, &templates_dir, &false).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_import_url_invalid_link() {
+    let err = run_import_url("ftp://example.com/wod").unwrap_err();
+    assert!(err.to_string().contains("expected an http(s) one"));
+    assert!(run_import_url("wod-2025-03-26.html").is_err());
+}
+
+#[test]
+fn test_run_qr() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let date = NaiveDate::from_ymd_opt(2025, 3, 24).unwrap();
+    let mut config = Config::default();
+    let section = temp_dir.path().join("content").join("wod");
+    let filenames = run_base(section.join("wod-2025-03-24"), &false, date, None, &config)?;
+    assert!(run_qr(&filenames[0], &config).is_err());
+
+    config.base_url = Some("https://wod.example.com/".to_string());
+    assert_eq!(
+        page_url(&filenames[0], &config)?,
+        "https://wod.example.com/wod/wod-2025-03-24/"
+    );
+    config.slug = Some("Day-{date}".to_string());
+    let filenames = run_base(section.join("wod-2025-03-25"), &false, date, None, &config)?;
+    assert_eq!(
+        page_url(&filenames[0], &config)?,
+        "https://wod.example.com/wod/day-2025-03-24/"
+    );
+    config.url = Some("/{year}/{month}/{day}/".to_string());
+    let filenames = run_base(section.join("wod-2025-03-26"), &false, date, None, &config)?;
+    assert_eq!(
+        page_url(&filenames[0], &config)?,
+        "https://wod.example.com/2025/03/24/"
+    );
+
+    let image = run_qr(&filenames[0], &config)?;
+    assert!(image.starts_with("<?xml"));
+    assert!(image.trim_end().ends_with("</svg>"));
+    Ok(())
+}
+
+#[test]
+fn test_output_dir_hugo_site() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    File::create(root.join("config.toml"))?;
+    fs::create_dir(root.join("content"))?;
+
+    let output_dir = resolve_output_dir(None, &Config::default(), root);
+    assert_eq!(output_dir, root.join("content").join("wod"));
+
+    let filenames = run_base(
+        output_dir.join("wod-2025-03-21"),
+        &false,
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+        None,
+        &Config::default(),
+    )?;
+    assert_eq!(
+        filenames,
+        vec![root.join("content").join("wod").join("wod-2025-03-21.md")]
+    );
+    assert!(filenames[0].exists());
+
+    // The config takes precedence over the Hugo site
+    let config = Config {
+        output_dir: Some(PathBuf::from("wods")),
+        ..Config::default()
+    };
+    assert_eq!(
+        resolve_output_dir(None, &config, root),
+        PathBuf::from("wods")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_workout_before() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let filenames = run_base(
+        temp_dir.path().join("wod-2025-03-21"),
+        &false,
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+        None,
+        &Config::default(),
+    )?;
+    let filename = filenames[0].clone();
+
+    run_add_workout(
+        filename.clone(),
+        "ft 21-15-9 pull up, thruster",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    run_add_workout(
+        filename.clone(),
+        "wl 5x5 back squat",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::Before("for time".to_string()),
+        false,
+    )?;
+
+    let content = fs::read_to_string(&filename)?;
+    let strength = content.find("**Weightlifting**").unwrap();
+    let metcon = content.find("**For Time**").unwrap();
+    assert!(strength < metcon);
+    assert!(content.starts_with("---\ntitle: \"2025-03-21\""));
+
+    assert!(run_add_workout(
+        filename,
+        "wl 5x5 deadlift",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::At(4),
+        false
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_undo_inserted_workout() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let journal_path = temp_dir.path().join("journal.json");
+    let filename = temp_dir.path().join("wod.md");
+
+    run_add_workout(
+        filename.clone(),
+        "wl 5x5 back squat",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    let before = fs::read_to_string(&filename)?;
+    let block = run_add_workout(
+        filename.clone(),
+        "ft 21-15-9 pull up, thruster",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::At(1),
+        false,
+    )?
+    .unwrap();
+    let mut journal = Journal::default();
+    journal.record(&fs::canonicalize(&filename)?, block.clone());
+    journal.save(&journal_path)?;
+
+    assert_eq!(run_undo(filename.clone(), &journal_path)?, block);
+    assert_eq!(fs::read_to_string(&filename)?, before);
+
+    // Without a record in the journal the last workout is removed
+    run_undo(filename.clone(), &journal_path)?;
+    assert_eq!(fs::read_to_string(&filename)?, "");
+    assert!(run_undo(filename, &journal_path).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_wodfile_rerun_skips_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let wod_file_path = temp_dir.path().join("test.wod");
+    let mut wod_file = File::create(&wod_file_path)?;
+    writeln!(wod_file, "wl 5x5 back squat")?;
+    writeln!(wod_file, "ft 21-15-9 pull up, thruster")?;
+
+    let output_path = temp_dir.path().join("workouts.md");
+    let run = |force: bool| {
+        run_add_wod_from_file(
+            output_path.clone(),
+            wod_file_path.clone(),
+            NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+            None,
+            &Config::default(),
+            &force,
+        )
+    };
+    run(false)?;
+    let content = fs::read_to_string(&output_path)?;
+
+    // Running it again doesn't duplicate the workouts
+    run(false)?;
+    assert_eq!(fs::read_to_string(&output_path)?, content);
+
+    // With force the file is overwritten
+    fs::write(&output_path, "edited")?;
+    run(true)?;
+    assert_eq!(fs::read_to_string(&output_path)?, content);
+
+    // A single workout can be added again with force
+    let added = run_add_workout(
+        output_path.clone(),
+        "wl 5x5 back squat",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    assert_eq!(added, None);
+    let added = run_add_workout(
+        output_path.clone(),
+        "wl 5x5 back squat",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        true,
+    )?;
+    assert!(added.is_some());
+    Ok(())
+}
+
+#[test]
+fn test_run_add_workout_new_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    // The section of a fresh site doesn't exist until a file is added to it
+    let filename = temp_dir.path().join("content").join("wod").join("wod.md");
+    let add = |position: Position| {
+        run_add_workout(
+            filename.clone(),
+            "ft 21 pull up",
+            None,
+            None,
+            &[],
+            &Scaling::default(),
+            &Markdown::default(),
+            &position,
+            false,
+        )
+    };
+    let err = add(Position::At(1)).unwrap_err();
+    assert!(err.to_string().contains("wod.md"));
+    add(Position::End)?;
+    assert!(fs::read_to_string(&filename)?.contains("21 Pull Up"));
+    Ok(())
+}
+
+#[test]
+fn test_run_add_workout_media() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let filename = temp_dir.path().join("wod.md");
+    let media = vec![
+        Media::video("https://youtu.be/9FGilxCbdz8")?,
+        Media::image("https://example.com/complex.png")?,
+    ];
+    let scaling: Scaling = toml::from_str("levels = [\"beginner\"]")?;
+
+    run_add_workout(
+        filename.clone(),
+        "wl 5x(1+1) clean, split jerk @75%",
+        Some("Hold the catch".to_string()),
+        None,
+        &media,
+        &scaling,
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    let content = fs::read_to_string(&filename)?;
+    // Under the comments, before the scaled versions
+    assert!(content.contains(
+        "Comments: *Hold the catch*\n\n{{< youtube 9FGilxCbdz8 >}}\n\n\
+         {{< figure src=\"https://example.com/complex.png\" >}}\n\n*Scaled: beginner*"
+    ));
+    let post = Post::read(&filename)?;
+    assert_eq!(post.workouts.len(), 1);
+    assert_eq!(post.workouts[0].media(), media.as_slice());
+    Ok(())
+}
+
+#[test]
+fn test_run_add_workout_scaled() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let filename = temp_dir.path().join("wod.md");
+    let scaling: Scaling =
+        toml::from_str("intermediate = 60\nlevels = [\"intermediate\", \"beginner\"]")?;
+
+    run_add_workout(
+        filename.clone(),
+        "4rd 10 ring mu, 15 wall ball @9/6kg",
+        None,
+        None,
+        &[],
+        &scaling,
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    let content = fs::read_to_string(&filename)?;
+    assert!(content.contains("*Scaled: intermediate*\n\n**4 rounds for time**\n\n- 10 Bar Muscle Up\n\n- 15 Wall Ball @ 5/4kg"));
+    assert!(content.contains(
+        "*Scaled: beginner*\n\n**4 rounds for time**\n\n- 10 Pull Up\n\n- 15 Wall Ball @ 5/3kg"
+    ));
+
+    // The scaled versions are removed along with the workout
+    run_undo(filename.clone(), &temp_dir.path().join("journal.json"))?;
+    assert!(!fs::read_to_string(&filename)?.contains("Scaled"));
+    Ok(())
+}
+
+#[test]
+fn test_run_add_workout_parts() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let filename = temp_dir.path().join("wod.md");
+
+    run_add_workout(
+        filename.clone(),
+        "A) wl 5x3 back squat @80%; B) ft 21-15-9 pull up, thruster @43/30kg",
+        Some("Time cap 10'".to_string()),
+        Some("Monday".to_string()),
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    let content = fs::read_to_string(&filename)?;
+    assert!(content.contains("---\n\n*Monday, Part A*\n\n**Weightlifting**"));
+    assert!(content.contains("---\n\n*Monday, Part B*\n\n**For Time**"));
+    // The comments go under the last part
+    assert_eq!(content.matches("Time cap 10'").count(), 1);
+    assert!(content.find("Time cap").unwrap() > content.find("Part B").unwrap());
+    Ok(())
+}
+
+#[test]
+fn test_run_undo_parts() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let filename = temp_dir.path().join("wod.md");
+    let journal_path = temp_dir.path().join("journal.json");
+    run_add_workout(
+        filename.clone(),
+        "ft 21-15-9 pull up, thruster",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    let before = fs::read_to_string(&filename)?;
+    let block = run_add_workout(
+        filename.clone(),
+        "A) wl 5x3 back squat; B) amrap-12 10 pull up",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::At(1),
+        false,
+    )?
+    .unwrap();
+    let mut journal = Journal::default();
+    journal.record(&fs::canonicalize(&filename)?, block.clone());
+    journal.save(&journal_path)?;
+
+    // Every part is removed, the file is written as a block per part
+    assert_eq!(run_undo(filename.clone(), &journal_path)?, block);
+    assert_eq!(fs::read_to_string(&filename)?, before);
+    Ok(())
+}
+
+#[test]
+fn test_run_build() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let plan = temp_dir.path().join("plan");
+    fs::create_dir_all(plan.join("week-2"))?;
+    fs::write(plan.join("2025-03-21.wod"), "wl 5x5 back squat\n")?;
+    fs::write(
+        plan.join("week-2").join("wod-2025-03-28.wod"),
+        "ft 21-15-9 pull up, thruster\n",
+    )?;
+    fs::write(plan.join("notes.txt"), "not a wod")?;
+
+    let output_dir = temp_dir.path().join("content");
+    let wodfiles = run_build(&plan, &output_dir, &Config::default(), None, &false)?;
+    assert_eq!(wodfiles.len(), 2);
+
+    let content = fs::read_to_string(output_dir.join("wod-2025-03-21.md"))?;
+    assert!(content.contains("date: 2025-03-21"));
+    assert!(content.contains("5x5 Back Squat"));
+    let content = fs::read_to_string(output_dir.join("wod-2025-03-28.md"))?;
+    assert!(content.contains("**For Time**"));
+    Ok(())
+}
+
+#[test]
+fn test_run_build_sections() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let plan = temp_dir.path().join("plan");
+    fs::create_dir_all(&plan)?;
+    fs::write(
+        plan.join("2025-03-21.wod"),
+        "wl 5x5 back squat\nwl 5x2 snatch @70%\n",
+    )?;
+    fs::write(
+        plan.join("2025-03-22.wod"),
+        "wl 5x5 back squat\nft 21-15-9 pull up, thruster\n",
+    )?;
+    fs::write(
+        plan.join("2025-03-23.wod"),
+        "# section: competition\nft 21-15-9 pull up, thruster\n",
+    )?;
+
+    let output_dir = temp_dir.path().join("content").join("wod");
+    let config = Config {
+        sections: [("weightlifting".to_string(), "weightlifting".to_string())].into(),
+        ..Config::default()
+    };
+    run_build(&plan, &output_dir, &config, None, &false)?;
+
+    let content_dir = temp_dir.path().join("content");
+    assert!(content_dir
+        .join("weightlifting")
+        .join("wod-2025-03-21.md")
+        .exists());
+    // Not every workout is routed to the same section
+    assert!(output_dir.join("wod-2025-03-22.md").exists());
+    let content = fs::read_to_string(content_dir.join("competition").join("wod-2025-03-23.md"))?;
+    assert!(content.contains("**For Time**"));
+    assert!(!content.contains("section"));
+    Ok(())
+}
+
+#[test]
+fn test_run_new_from_template() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let templates_dir = temp_dir.path().join("templates");
+    fs::create_dir_all(&templates_dir)?;
+    fs::write(
+        templates_dir.join("oly.wod"),
+        "wl 5x2 snatch @70%||Snatch\nft 21-15-9 pull up, thruster||Metcon\n",
+    )?;
+
+    let filename = temp_dir.path().join("wod-2025-03-21");
+    let filenames = run_new(
+        filename.clone(),
+        "oly",
+        &templates_dir,
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+        None,
+        &Config::default(),
+        &false,
+    )?;
+    assert_eq!(filenames, vec![filename.with_extension("md")]);
+    let content = fs::read_to_string(&filenames[0])?;
+    assert!(content.contains("date: 2025-03-21"));
+    assert!(content.contains("*Snatch*"));
+    assert!(content.find("*Snatch*") < content.find("*Metcon*"));
+
+    let missing = run_new(
+        filename,
+        "rest",
+        &templates_dir,
+        NaiveDate::from_ymd_opt(2025, 3, 21).unwrap(),
+        None,
+        &Config::default(),
+        &true,
+    );
+    assert!(missing.is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_lint_files() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let templates_dir = temp_dir.path().join("templates");
+    fs::create_dir_all(&templates_dir)?;
+    fs::write(templates_dir.join("pull.wod"), "wl 5x3 deadlift @80%\n")?;
+    fs::write(
+        templates_dir.join("metcon.wod"),
+        "ft 21-15-9 romanian deadlift @100/70kg, pull up, toes to bar\n",
+    )?;
+
+    let mut files = Vec::new();
+    for (day, template) in [("2025-03-20", "pull"), ("2025-03-21", "metcon")] {
+        files.extend(run_new(
+            temp_dir.path().join(format!("wod-{}", day)),
+            template,
+            &templates_dir,
+            day.parse()?,
+            None,
+            &Config::default(),
+            &false,
+        )?);
+    }
+    let lints = run_lint_files(&files)?;
+    assert_eq!(
+        lints.lines().collect::<Vec<_>>(),
+        vec![
+            "warning[back-to-back-hinge]: wod-2025-03-21.md: heavy hinge two days in a row: Romanian Deadlift after Deadlift the day before",
+            "warning[grip-interference]: wod-2025-03-21.md, workout 1: 3 grip intensive movements in the same workout: Romanian Deadlift, Pull Up, Toes To Bar",
+        ]
+    );
+    assert_eq!(run_lint_files(&files[..1])?, "No problems found");
+    Ok(())
+}
+
+#[test]
+fn test_run_base_front_matter_template() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let template = temp_dir.path().join("archetype.md");
+    fs::write(
+        &template,
+        "+++\ntitle = \"{{title}}\"\ndate = {{date}}\nlanguages = \"{{languages}}\"\nweight = 1\n+++\n",
+    )?;
+    let config = Config {
+        front_matter_template: Some(template),
+        ..Config::default()
+    };
+
+    let filenames = run_base(
+        temp_dir.path().join("wod-2025-03-21"),
+        &false,
+        "2025-03-21".parse()?,
+        Some("en,es".to_string()),
+        &config,
+    )?;
+    assert_eq!(
+        fs::read_to_string(&filenames[1])?,
+        "+++\ntitle = \"2025-03-21\"\ndate = 2025-03-21\nlanguages = \"en,es\"\nweight = 1\n+++\n\n"
+    );
+
+    let missing = Config {
+        front_matter_template: Some(temp_dir.path().join("missing.md")),
+        ..Config::default()
+    };
+    assert!(run_base(
+        temp_dir.path().join("other"),
+        &false,
+        "2025-03-21".parse()?,
+        None,
+        &missing,
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_publish() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let config = Config {
+        draft: true,
+        ..Config::default()
+    };
+    let filenames = run_base(
+        temp_dir.path().join("wod-2025-03-21"),
+        &false,
+        "2025-03-21".parse()?,
+        None,
+        &config,
+    )?;
+    let filename = filenames[0].clone();
+    assert_eq!(
+        front_matter::get_field(&fs::read_to_string(&filename)?, "draft"),
+        Some("true".to_string())
+    );
+
+    run_publish(filename.clone(), false)?;
+    let content = fs::read_to_string(&filename)?;
+    assert!(
+        content.starts_with("---\ntitle: \"2025-03-21\"\ndate: 2025-03-21\ndraft: false\n---\n")
+    );
+    run_publish(filename.clone(), true)?;
+    assert_eq!(
+        front_matter::get_field(&fs::read_to_string(&filename)?, "draft"),
+        Some("true".to_string())
+    );
+
+    let plain = temp_dir.path().join("plain.md");
+    fs::write(&plain, "---\n\n**For Time**\n\n")?;
+    assert!(run_publish(plain, false).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_level() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let filenames = run_base(
+        temp_dir.path().join("wod-2025-03-21"),
+        &false,
+        "2025-03-21".parse()?,
+        None,
+        &Config::default(),
+    )?;
+    let filename = filenames[0].clone();
+    let level = |filename: &PathBuf| -> Result<Option<String>, std::io::Error> {
+        Ok(front_matter::get_field(
+            &fs::read_to_string(filename)?,
+            "level",
+        ))
+    };
+
+    let scaling = Scaling::default();
+    let add = |workout: &str| {
+        run_add_workout(
+            filename.clone(),
+            workout,
+            None,
+            None,
+            &[],
+            &scaling,
+            &Markdown::default(),
+            &Position::End,
+            false,
+        )
+    };
+    add("amrap-12 10 air squat, 10 push up")?;
+    assert_eq!(level(&filename)?, Some("beginner".to_string()));
+    add("ft 21-15-9 pull up, thruster @43/30kg")?;
+    assert_eq!(level(&filename)?, Some("rx".to_string()));
+    // The level of the day is the one of its hardest workout
+    add("emom-10 10 box jump over")?;
+    assert_eq!(level(&filename)?, Some("rx".to_string()));
+
+    // A directive of the wod file overrides it
+    let wodfile = temp_dir.path().join("plan.wod");
+    fs::write(
+        &wodfile,
+        "# level: intermediate\nft 21-15-9 bar mu, thruster @43/30kg\n",
+    )?;
+    let output = temp_dir.path().join("wod-2025-03-22");
+    run_add_wod_from_file(
+        output.clone(),
+        wodfile,
+        "2025-03-22".parse()?,
+        None,
+        &Config::default(),
+        &false,
+    )?;
+    assert_eq!(
+        level(&output.with_extension("md"))?,
+        Some("intermediate".to_string())
+    );
+    Ok(())
+}
+
+#[cfg(feature = "date")]
+#[test]
+fn test_lastmod() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let wodfile = temp_dir.path().join("plan.wod");
+    fs::write(
+        &wodfile,
+        "wl 5x5 back squat\nft 21-15-9 pull up, thruster\n",
+    )?;
+    let output = temp_dir.path().join("wod-2025-03-21");
+    run_add_wod_from_file(
+        output.clone(),
+        wodfile,
+        "2025-03-21".parse()?,
+        None,
+        &Config::default(),
+        &false,
+    )?;
+    // A file written from scratch has no lastmod
+    let filename = output.with_extension("md");
+    assert_eq!(
+        front_matter::get_field(&fs::read_to_string(&filename)?, "lastmod"),
+        None
+    );
+
+    run_add_workout(
+        filename.clone(),
+        "amrap-10 10 push up",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    assert_eq!(
+        front_matter::get_field(&fs::read_to_string(&filename)?, "lastmod"),
+        Some(wod::today().to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_profiles() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let zola = Config {
+        profile: Profile::Zola,
+        url: Some("wod/{year}/{month}/{day}".to_string()),
+        ..Config::default()
+    };
+    let filename = run_base(
+        temp_dir.path().join("wod-2025-03-21"),
+        &false,
+        "2025-03-21".parse()?,
+        None,
+        &zola,
+    )?[0]
+        .clone();
+    run_add_workout(
+        filename.clone(),
+        "wl 5x5 back squat",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    assert_eq!(
+        fs::read_to_string(&filename)?,
+        "+++\ntitle = \"2025-03-21\"\ndate = 2025-03-21\ndraft = false\npath = \"wod/2025/03/21\"\n\
+         [taxonomies]\ntags = [\"back squat\", \"weightlifting\"]\n[extra]\nlevel = \"beginner\"\n\
+         +++\n\nWorkout for the day, 2025-03-21.\n\n---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n"
+    );
+
+    let jekyll = Config {
+        profile: Profile::Jekyll,
+        url: Some("/wod/{date}/".to_string()),
+        draft: true,
+        ..Config::default()
+    };
+    let filename = run_base(
+        temp_dir.path().join("wod-2025-03-22"),
+        &false,
+        "2025-03-22".parse()?,
+        None,
+        &jekyll,
+    )?[0]
+        .clone();
+    assert_eq!(
+        fs::read_to_string(&filename)?,
+        "---\nlayout: post\ntitle: \"2025-03-22\"\ndate: 2025-03-22\npublished: false\n\
+         permalink: \"/wod/2025-03-22/\"\n---\n\nWorkout for the day, 2025-03-22.\n\n"
+    );
+    run_publish(filename.clone(), false)?;
+    assert_eq!(
+        front_matter::get_field(&fs::read_to_string(&filename)?, "published"),
+        Some("true".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_run_add_daily_note() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let note = temp_dir.path().join("daily").join("2025-03-21.md");
+    let scaling = Scaling::default();
+
+    // The heading is added to a new note
+    run_add_daily_note(
+        note.clone(),
+        "## Workout",
+        "wl 5x5 back squat",
+        None,
+        None,
+        &scaling,
+    )?;
+    assert_eq!(
+        fs::read_to_string(&note)?,
+        "## Workout\n\n**Weightlifting**\n\n5x5 [[Back Squat]]\n"
+    );
+
+    // And the workouts are added at the end of its section
+    fs::write(
+        &note,
+        "# Friday\n\n## Workout\n\n**Weightlifting**\n\n5x5 [[Back Squat]]\n\n## Notes\n\nSlept well\n",
+    )?;
+    let added = run_add_daily_note(
+        note.clone(),
+        "## Workout",
+        "ft 21-15-9 pull up, thruster @43/30kg",
+        None,
+        None,
+        &scaling,
+    )?;
+    assert!(added.is_some());
+    assert_eq!(
+        fs::read_to_string(&note)?,
+        "# Friday\n\n## Workout\n\n**Weightlifting**\n\n5x5 [[Back Squat]]\n\n\
+         **For Time**\n\n21-15-9\n\n- [[Pull Up]]\n\n- [[Thruster]] @ 43/30kg\n\n\
+         ## Notes\n\nSlept well\n"
+    );
+    let added = run_add_daily_note(
+        note.clone(),
+        "## Workout",
+        "wl 5x5 back squat",
+        None,
+        None,
+        &scaling,
+    )?;
+    assert_eq!(added, None);
+    Ok(())
+}
+
+#[test]
+fn test_per_workout() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let wodfile = temp_dir.path().join("plan.wod");
+    fs::write(
+        &wodfile,
+        "# level: rx\nwl 5x5 back squat\n\nft 21-15-9 pull up, thruster @43/30kg||Fran\n",
+    )?;
+    let config = Config {
+        per_workout: true,
+        ..Config::default()
+    };
+    run_add_wod_from_file(
+        temp_dir.path().join("wod-2025-03-21"),
+        wodfile,
+        "2025-03-21".parse()?,
+        None,
+        &config,
+        &false,
+    )?;
+
+    assert!(!temp_dir.path().join("wod-2025-03-21.md").exists());
+    let first = fs::read_to_string(temp_dir.path().join("wod-2025-03-21-1.md"))?;
+    assert!(first.starts_with("---\ntitle: \"2025-03-21-1\"\n"));
+    assert!(first.ends_with("---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n"));
+    assert_eq!(
+        front_matter::get_field(&first, "level"),
+        Some("rx".to_string())
+    );
+    let fran = fs::read_to_string(temp_dir.path().join("wod-2025-03-21-fran.md"))?;
+    assert!(fran.contains("*Fran*"));
+    assert!(!fran.contains("Back Squat"));
+    Ok(())
+}
+
+#[test]
+fn test_run_regen() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let config = Config::default();
+
+    // A file generated from the wod file next to it
+    let wodfile = temp_dir.path().join("wod-2025-03-21.wod");
+    fs::write(
+        &wodfile,
+        "ft 21-15-9 pull up, thruster @43/30kg||Fran\nwl 5x5 back squat\n",
+    )?;
+    run_add_wod_from_file(
+        temp_dir.path().join("wod-2025-03-21"),
+        wodfile,
+        "2025-03-21".parse()?,
+        None,
+        &config,
+        &false,
+    )?;
+    let sidecar = temp_dir.path().join("wod-2025-03-21.md");
+    let generated = fs::read_to_string(&sidecar)?;
+    fs::write(&sidecar, generated.replace("Back Squat", "Back squat"))?;
+
+    // A file with the source embedded, in a subdirectory
+    fs::create_dir(temp_dir.path().join("week-13"))?;
+    let embedded = temp_dir.path().join("week-13").join("wod-2025-03-24.md");
+    fs::write(
+        &embedded,
+        "---\ntitle: \"monday\"\n---\n\nIntro.\n\n<!-- wod\nwl 5x5 back squat\n-->\n",
+    )?;
+
+    // And a file without source
+    let plain = temp_dir.path().join("about.md");
+    fs::write(
+        &plain,
+        "---\ntitle: \"about\"\n---\n\n---\n\nNothing to see\n",
+    )?;
+
+    let regenerated = run_regen(temp_dir.path(), &config)?;
+    assert_eq!(regenerated, vec![embedded.clone(), sidecar.clone()]);
+    assert_eq!(fs::read_to_string(&sidecar)?, generated);
+    assert_eq!(
+        fs::read_to_string(&embedded)?,
+        "---\ntitle: \"monday\"\ntags: [\"back squat\", \"weightlifting\"]\nlevel: \"beginner\"\n---\n\n\
+         Intro.\n\n<!-- wod\nwl 5x5 back squat\n-->\n---\n\n**Weightlifting**\n\n5x5 Back Squat\n\n"
+    );
+    assert_eq!(
+        fs::read_to_string(&plain)?,
+        "---\ntitle: \"about\"\n---\n\n---\n\nNothing to see\n"
+    );
+
+    // Running it again leaves the files as they are
+    run_regen(temp_dir.path(), &config)?;
+    assert_eq!(fs::read_to_string(&sidecar)?, generated);
+    Ok(())
+}
+
+#[test]
+fn test_run_base_title_format() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let mut catalog = Catalog::default();
+    catalog.insert(Message::Friday, "Venerdì");
+    i18n::register("it", catalog);
+    let config = Config {
+        title_format: Some("WOD – {weekday} {date}".to_string()),
+        ..Config::default()
+    };
+
+    let filenames = run_base(
+        temp_dir.path().join("wod-2025-03-21"),
+        &false,
+        "2025-03-21".parse()?,
+        Some("en,it".to_string()),
+        &config,
+    )?;
+    let titles = filenames
+        .iter()
+        .map(|filename| {
+            Ok(front_matter::get_field(
+                &fs::read_to_string(filename)?,
+                "title",
+            ))
+        })
+        .collect::<Result<Vec<_>, std::io::Error>>()?;
+    assert_eq!(
+        titles,
+        vec![
+            Some("WOD – Friday 2025-03-21".to_string()),
+            Some("WOD – Venerdì 2025-03-21".to_string())
+        ]
+    );
+
+    let config = Config {
+        title_format: Some("WOD {week}".to_string()),
+        ..Config::default()
+    };
+    assert!(run_base(
+        temp_dir.path().join("other"),
+        &false,
+        "2025-03-21".parse()?,
+        None,
+        &config
+    )
+    .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_base_slug_and_url() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let config = Config {
+        slug: Some("wod-{date}".to_string()),
+        url: Some("/wod/{year}/{month}/{day}/".to_string()),
+        ..Config::default()
+    };
+
+    let filenames = run_base(
+        temp_dir.path().join("monday"),
+        &false,
+        "2025-03-24".parse()?,
+        None,
+        &config,
+    )?;
+    assert_eq!(
+        fs::read_to_string(&filenames[0])?,
+        "---\ntitle: \"monday\"\ndate: 2025-03-24\ndraft: false\nslug: \"wod-2025-03-24\"\nurl: \"/wod/2025/03/24/\"\n---\n\nWorkout for the day, monday.\n\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_run_base_equipment() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let config = Config {
+        equipment: true,
+        ..Config::default()
+    };
+    let filenames = run_base(
+        temp_dir.path().join("wod-2025-03-24"),
+        &false,
+        "2025-03-24".parse()?,
+        None,
+        &config,
+    )?;
+    let filename = filenames[0].clone();
+    assert!(fs::read_to_string(&filename)?.ends_with("2025-03-24.\n\n<!-- equipment -->\n\n"));
+
+    for workout in [
+        "ft 21-15-9 pull up, thruster @43/30kg",
+        "ft 30 burpee",
+        "ft 30 box jump, 30 clean @60kg",
+    ] {
+        run_add_workout(
+            filename.clone(),
+            workout,
+            None,
+            None,
+            &[],
+            &Scaling::default(),
+            &Markdown::default(),
+            &Position::End,
+            false,
+        )?;
+    }
+    let content = fs::read_to_string(&filename)?;
+    assert!(content.contains(
+        "\n\nEquipment: pull-up bar, barbell, box <!-- equipment -->\n\n---\n\n**For Time**"
+    ));
+    assert_eq!(Post::read(&filename)?.workouts.len(), 3);
+
+    // Without the option the pages have no line of equipment
+    let filename = temp_dir.path().join("wod-2025-03-25.md");
+    run_add_workout(
+        filename.clone(),
+        "ft 30 box jump",
+        None,
+        None,
+        &[],
+        &Scaling::default(),
+        &Markdown::default(),
+        &Position::End,
+        false,
+    )?;
+    assert!(!fs::read_to_string(&filename)?.contains("Equipment"));
+    Ok(())
+}
+
+#[test]
+fn test_run_merge() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let config = Config::default();
+    let date = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+    let mut files = Vec::new();
+    for (session, workouts) in [
+        (
+            "pm",
+            ["ft 21-15-9 pull up, thruster @43/30kg", "wl 5x3 back squat"],
+        ),
+        ("am", ["wl 5x3 back squat", "emom-10 5 burpee"]),
+    ] {
+        let file = run_base(
+            temp_dir.path().join(format!("wod-2025-03-21-{}", session)),
+            &false,
+            date,
+            None,
+            &config,
+        )?
+        .remove(0);
+        for workout in workouts {
+            run_add_workout(
+                file.clone(),
+                workout,
+                None,
+                None,
+                &[],
+                &Scaling::default(),
+                &Markdown::default(),
+                &Position::End,
+                false,
+            )?;
+        }
+        files.push(file);
+    }
+    run_warmup(files[1].clone())?;
+
+    let output = temp_dir.path().join("wod-2025-03-21.md");
+    assert_eq!(run_merge(&files, &output, false)?, 4);
+    let content = fs::read_to_string(&output)?;
+    assert_eq!(content.matches("title:").count(), 1);
+    let tags = front_matter::get_list(&content, "tags");
+    for file in &files {
+        for tag in front_matter::get_list(&fs::read_to_string(file)?, "tags") {
+            assert_eq!(tags.iter().filter(|t| **t == tag).count(), 1);
+        }
+    }
+    let doc = Document::parse(&content);
+    assert!(doc.blocks()[0].contains("**Warm-up**"));
+    assert!(doc.blocks()[1].contains("**For Time**"));
+    assert!(doc.blocks()[2].contains("5x3 Back Squat"));
+    assert!(doc.blocks()[3].contains("**EMOM 10 minutes**"));
+
+    // The output isn't overwritten without --force, unless it's one of the files
+    assert!(run_merge(&files, &output, false).is_err());
+    assert_eq!(run_merge(&files, &files[0], false)?, 4);
+    Ok(())
+}
+
+#[test]
+fn test_run_merge_equipment() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    // The workout of the second file was edited by hand and can't be read back
+    let files = [
+        (
+            "wod-2025-03-21-am.md",
+            "barbell",
+            "**AMRAP 12 minutes**\n\n- 10 Thruster @ 43/30kg\n",
+        ),
+        ("wod-2025-03-21-pm.md", "rower", "**For Time**\n\n- 1000m Row\n- 21 Thruster in between\n"),
+    ]
+    .into_iter()
+    .map(|(name, equipment, block)| {
+        let path = temp_dir.path().join(name);
+        let content = format!(
+            "---\ntitle: \"2025-03-21\"\ndate: 2025-03-21\n---\n\nEquipment: {} <!-- equipment -->\n\n---\n\n{}\n",
+            equipment, block
+        );
+        fs::write(&path, content).map(|_| path)
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+    let output = temp_dir.path().join("wod-2025-03-21.md");
+    assert_eq!(run_merge(&files, &output, false)?, 2);
+    let content = fs::read_to_string(&output)?;
+    assert!(content.contains("Equipment: barbell, rower <!-- equipment -->"));
+    Ok(())
+}
+
+#[test]
+fn test_run_split() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let wodfile = temp_dir.path().join("april.wod");
+    fs::write(
+        &wodfile,
+        "# April\nwl 5x5 back squat @75%\nft 21-15-9 pull up, thruster @43/30kg||Fran\n---\n\
+         # level: rx\nft 50cal row, 30 toes to bar\n---\n# Rest\n---\nwl 5x3 deadlift @80%\n",
+    )?;
+    let output_dir = temp_dir.path().join("content");
+    let start = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+    let files = run_split(
+        &wodfile,
+        start,
+        &output_dir,
+        None,
+        &Config::default(),
+        &false,
+    )?;
+    let names: Vec<_> = files
+        .iter()
+        .map(|f| f.file_name().unwrap().to_string_lossy().to_string())
+        .collect();
+    // The third day is a rest day
+    assert_eq!(
+        names,
+        vec![
+            "wod-2025-04-01.md",
+            "wod-2025-04-02.md",
+            "wod-2025-04-04.md"
+        ]
+    );
+
+    let first = fs::read_to_string(&files[0])?;
+    assert_eq!(
+        front_matter::get_field(&first, "date").as_deref(),
+        Some("2025-04-01")
+    );
+    assert_eq!(Post::parse(&first)?.workouts.len(), 2);
+    let second = fs::read_to_string(&files[1])?;
+    assert_eq!(
+        front_matter::get_field(&second, "level").as_deref(),
+        Some("rx")
+    );
+    assert_eq!(
+        Post::parse(&fs::read_to_string(&files[2])?)?.workouts[0].to_shorthand(),
+        "wl 5x3 deadlift @80%"
+    );
+
+    // Run again, the files already have their workouts
+    let files = run_split(
+        &wodfile,
+        start,
+        &output_dir,
+        None,
+        &Config::default(),
+        &false,
+    )?;
+    assert!(files.is_empty());
+    // A new workout is only written to its day
+    fs::write(&wodfile, fs::read_to_string(&wodfile)? + "ft 50 burpee\n")?;
+    let files = run_split(
+        &wodfile,
+        start,
+        &output_dir,
+        None,
+        &Config::default(),
+        &false,
+    )?;
+    assert_eq!(files, vec![output_dir.join("wod-2025-04-04.md")]);
+    Ok(())
+}
+
+#[test]
+fn test_run_frequency() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let config = Config::default();
+    for (day, workout) in [
+        (1, "wl 5x3 snatch @70%"),
+        (18, "ft 50 du, 21 pull up"),
+        (19, "emom-10 20 du, 5 burpee"),
+        (20, "ft 50 du, 30 box jump"),
+        (21, "ft 50 du, 30 air squat"),
+    ] {
+        let date = NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let files = run_base(
+            temp_dir.path().join(format!("wod-{}", date)),
+            &false,
+            date,
+            Some("en,es".to_string()),
+            &config,
+        )?;
+        for file in files {
+            run_add_workout(
+                file,
+                workout,
+                None,
+                None,
+                &[],
+                &Scaling::default(),
+                &Markdown::default(),
+                &Position::End,
+                false,
+            )?;
+        }
+    }
+    let today = NaiveDate::from_ymd_opt(2025, 3, 21).unwrap();
+    let frequency = run_frequency(
+        temp_dir.path(),
+        today,
+        &config.frequency,
+        ReportFormat::Markdown,
+    )?;
+    // The files of the other languages aren't counted twice
+    let report = &frequency.report;
+    assert!(report.contains("| Double Under | 2025-03-21 | 0 | 4 | 4 |\n"));
+    assert!(report.contains("- No olympic lifting in 20 days\n"));
+    assert!(report.contains("- Double Under 4× this week\n"));
+    assert!(frequency.unreadable.is_empty());
+
+    let json: serde_json::Value = serde_json::from_str(
+        &run_frequency(
+            temp_dir.path(),
+            today,
+            &config.frequency,
+            ReportFormat::Json,
+        )?
+        .report,
+    )?;
+    assert_eq!(json["from"], "2025-03-01");
+
+    // The workouts edited by hand that can't be read back are reported
+    let file = temp_dir.path().join("wod-2025-03-21.md");
+    let content = fs::read_to_string(&file)?
+        + "---\n\n**For Time**\n\n- 50 Double Under\n- 30 Air Squat in between\n";
+    fs::write(&file, content)?;
+    let frequency = run_frequency(
+        temp_dir.path(),
+        today,
+        &config.frequency,
+        ReportFormat::Markdown,
+    )?;
+    assert_eq!(frequency.unreadable.len(), 1);
+    assert!(frequency.unreadable[0].contains("wod-2025-03-21.md (workout 2): "));
+
+    let mut rules = config.frequency.clone();
+    rules.windows.insert("pulup".to_string(), 7);
+    assert!(run_frequency(temp_dir.path(), today, &rules, ReportFormat::Json).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_run_volume() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let store = temp_dir.path().join("prs.json");
+    run_pr_set("back squat", "140kg", 1, Formula::Epley, &store)?;
+    let config = Config::default();
+    for (day, workout) in [
+        (18, "wl 5x5 back squat @70%"),
+        (27, "wl 5x3 deadlift @100kg"),
+    ] {
+        let date = NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let files = run_base(
+            temp_dir.path().join(format!("wod-{}", date)),
+            &false,
+            date,
+            Some("en,es".to_string()),
+            &config,
+        )?;
+        for file in files {
+            run_add_workout(
+                file,
+                workout,
+                None,
+                None,
+                &[],
+                &Scaling::default(),
+                &Markdown::default(),
+                &Position::End,
+                false,
+            )?;
+        }
+    }
+    let volume = run_volume(temp_dir.path(), &store, SeriesFormat::Csv)?;
+    // The percentages come from the PR table, the other languages aren't counted twice
+    assert_eq!(
+        volume.report,
+        "week,pattern,tonnage_man,tonnage_woman,unit\n\
+         2025-03-17,squat,2450,2450,kg\n\
+         2025-03-17,hinge,0,0,kg\n\
+         2025-03-24,squat,0,0,kg\n\
+         2025-03-24,hinge,1500,1500,kg\n"
+    );
+    assert!(volume.unreadable.is_empty());
+    let json: serde_json::Value =
+        serde_json::from_str(&run_volume(temp_dir.path(), &store, SeriesFormat::Json)?.report)?;
+    assert_eq!(json.as_array().unwrap().len(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_run_heatmap() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let config = Config::default();
+    for (day, workouts) in [
+        (
+            18,
+            vec!["wl 5x5 back squat @70%", "ft 21-15-9 pull up, burpee"],
+        ),
+        (20, vec!["emom-10 20 du, 5 burpee"]),
+    ] {
+        let date = NaiveDate::from_ymd_opt(2025, 3, day).unwrap();
+        let files = run_base(
+            temp_dir.path().join(format!("wod-{}", date)),
+            &false,
+            date,
+            Some("en,es".to_string()),
+            &config,
+        )?;
+        for file in files {
+            for workout in &workouts {
+                run_add_workout(
+                    file.clone(),
+                    workout,
+                    None,
+                    None,
+                    &[],
+                    &Scaling::default(),
+                    &Markdown::default(),
+                    &Position::End,
+                    false,
+                )?;
+            }
+        }
+    }
+    let heatmap = run_heatmap(temp_dir.path(), SeriesFormat::Csv)?;
+    assert_eq!(
+        heatmap.report,
+        "date,count,types\n2025-03-18,2,weightlifting;for time\n2025-03-20,1,emom\n"
+    );
+    assert!(heatmap.unreadable.is_empty());
+    let json: serde_json::Value =
+        serde_json::from_str(&run_heatmap(temp_dir.path(), SeriesFormat::Json)?.report)?;
+    assert_eq!(json[0]["count"], 2);
+    Ok(())
+}
+
+#[test]
+fn test_run_init_hugo() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path();
+    assert!(run_init_hugo(root, false).is_err());
+
+    fs::write(root.join("hugo.toml"), "baseURL = '/'")?;
+    fs::create_dir(root.join("content"))?;
+    let (written, kept) = run_init_hugo(root, false)?;
+    assert_eq!(written.len(), 3);
+    assert!(kept.is_empty());
+    let archetype = fs::read_to_string(root.join("archetypes").join("wod.md"))?;
+    // The archetype has the fields of the files generated
+    for field in ["title:", "date:", "draft:", "tags:", "level:"] {
+        assert!(archetype.contains(field));
+    }
+    let (written, kept) = run_init_hugo(root, false)?;
+    assert!(written.is_empty());
+    assert_eq!(kept.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_run_init() -> Result<(), Box<dyn std::error::Error>> {
+    let temp_dir = TempDir::new()?;
+    let root = temp_dir.path().join("myblog");
+    let files = run_init(&root)?;
+    assert!(files.contains(&root.join("programming").join("sample.wod")));
+    assert_eq!(
+        resolve_output_dir(None, &Config::default(), &root),
+        root.join("content").join("wod")
+    );
+    let config = fs::read_to_string(root.join("hugo.toml"))?;
+    assert!(config.contains("title = \"myblog\"\n"));
+
+    // The sample wod file is valid
+    let sample = fs::read_to_string(root.join("programming").join("sample.wod"))?;
+    let (report, valid) = run_check_wodfile(&sample, "sample.wod", true, false);
+    assert!(valid, "{}", report);
+
+    assert!(run_init(&root).is_err());
+    Ok(())
+}